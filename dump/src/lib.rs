@@ -2,10 +2,14 @@
 #![allow(clippy::wrong_self_convention)]
 
 use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid_pattern::IndexUidPattern;
 use meilisearch_types::keys::Key;
 use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::milli::Object;
 use meilisearch_types::settings::Unchecked;
-use meilisearch_types::tasks::{Details, IndexSwap, KindWithContent, Status, Task, TaskId};
+use meilisearch_types::tasks::{
+    Details, DumpImportIndex, IndexSwap, KindWithContent, Status, Task, TaskId,
+};
 use meilisearch_types::InstanceUid;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
@@ -104,6 +108,11 @@ pub enum KindDump {
     DocumentDeletionByFilter {
         filter: serde_json::Value,
     },
+    DocumentEdition {
+        filter: Option<serde_json::Value>,
+        context: Option<Object>,
+        function: String,
+    },
     Settings {
         settings: Box<meilisearch_types::settings::Settings<Unchecked>>,
         is_deletion: bool,
@@ -130,10 +139,30 @@ pub enum KindDump {
     DumpCreation {
         keys: Vec<Key>,
         instance_uid: Option<InstanceUid>,
+        // The options of the partial dumps are only written when they differ from a full dump.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        index_uids: Option<Vec<IndexUidPattern>>,
+        #[serde(default = "default_true", skip_serializing_if = "is_true")]
+        include_tasks: bool,
+        #[serde(default = "default_true", skip_serializing_if = "is_true")]
+        include_keys: bool,
+    },
+    DumpImport {
+        dump_uid: String,
+        indexes: Vec<DumpImportIndex>,
+        overwrite: bool,
     },
     SnapshotCreation,
 }
 
+fn default_true() -> bool {
+    true
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
+}
+
 impl From<Task> for TaskDump {
     fn from(task: Task) -> Self {
         TaskDump {
@@ -172,6 +201,9 @@ impl From<KindWithContent> for KindDump {
             KindWithContent::DocumentDeletionByFilter { filter_expr, .. } => {
                 KindDump::DocumentDeletionByFilter { filter: filter_expr }
             }
+            KindWithContent::DocumentEdition { filter_expr, context, function, .. } => {
+                KindDump::DocumentEdition { filter: filter_expr, context, function }
+            }
             KindWithContent::DocumentClear { .. } => KindDump::DocumentClear,
             KindWithContent::SettingsUpdate {
                 new_settings,
@@ -193,8 +225,21 @@ impl From<KindWithContent> for KindDump {
             KindWithContent::TaskDeletion { query, tasks } => {
                 KindDump::TasksDeletion { query, tasks }
             }
-            KindWithContent::DumpCreation { keys, instance_uid } => {
-                KindDump::DumpCreation { keys, instance_uid }
+            KindWithContent::DumpCreation {
+                keys,
+                instance_uid,
+                index_uids,
+                include_tasks,
+                include_keys,
+            } => KindDump::DumpCreation {
+                keys,
+                instance_uid,
+                index_uids,
+                include_tasks,
+                include_keys,
+            },
+            KindWithContent::DumpImport { dump_uid, indexes, overwrite } => {
+                KindDump::DumpImport { dump_uid, indexes, overwrite }
             }
             KindWithContent::SnapshotCreation => KindDump::SnapshotCreation,
         }
@@ -278,6 +323,9 @@ pub(crate) mod test {
             pagination: Setting::NotSet,
             embedders: Setting::NotSet,
             search_cutoff_ms: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            allow_ranking_rules_override: Setting::NotSet,
+            merchandising_rules: Setting::NotSet,
             _kind: std::marker::PhantomData,
         };
         settings.check()
@@ -444,8 +492,8 @@ pub(crate) mod test {
 
     #[test]
     fn test_creating_and_read_dump() {
-        let mut file = create_test_dump();
-        let mut dump = DumpReader::open(&mut file).unwrap();
+        let file = create_test_dump();
+        let mut dump = DumpReader::open(file).unwrap();
 
         // ==== checking the top level infos
         assert_eq!(dump.version(), Version::V6);
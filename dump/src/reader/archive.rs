@@ -0,0 +1,457 @@
+//! Lazy extraction of the dump archives.
+//!
+//! A [`DumpArchive`] doesn't unpack the whole `.dump` before reading it. The entries of the
+//! tarball are extracted by a dedicated thread, only up to the file or directory a reader asks for.
+//! The entries of a directory are contiguous in the archives, whether they were written by the
+//! [`DumpWriter`](crate::DumpWriter) or by walking the whole dump directory like the previous
+//! versions did, so a directory is complete as soon as an entry outside of it is read.
+//! The index directories are removed as soon as the next one is requested, which bounds the disk
+//! footprint of an import to a single index when the top level files come first in the archive,
+//! as the [`DumpWriter`](crate::DumpWriter) writes them.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, BufReader, Read};
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use std::thread::{self, JoinHandle};
+
+use flate2::bufread::GzDecoder;
+use tempfile::TempDir;
+
+use crate::Result;
+
+pub struct DumpArchive {
+    dir: TempDir,
+    state: Mutex<State>,
+}
+
+struct State {
+    /// The thread extracting the archive, `None` once the whole archive has been extracted.
+    stream: Option<Stream>,
+    /// The directory returned by the last call to `dir` or `next_dir`.
+    current_dir: Option<PathBuf>,
+    /// All the directories already returned by `next_dir`.
+    yielded: HashSet<PathBuf>,
+}
+
+struct Stream {
+    requests: Sender<Request>,
+    replies: Receiver<io::Result<Reply>>,
+    handle: JoinHandle<()>,
+}
+
+/// All the paths are relative to the root of the dump.
+enum Request {
+    /// Extract the entries until the file has been extracted.
+    File(PathBuf),
+    /// Extract the entries until all the files of the directory have been extracted.
+    Dir(PathBuf),
+    /// Extract the entries until a directory under `parent` that isn't excluded has been
+    /// entirely extracted, and return it.
+    NextDir { parent: PathBuf, exclude: HashSet<PathBuf> },
+    /// Extract all the remaining entries.
+    All,
+}
+
+struct Reply {
+    path: Option<PathBuf>,
+    /// Whether all the entries of the archive have been extracted.
+    finished: bool,
+}
+
+impl DumpArchive {
+    pub fn open(dump: impl Read + Send + 'static) -> Result<Self> {
+        let dir = TempDir::new()?;
+        let root = dir.path().to_path_buf();
+        let (requests, receiver) = mpsc::channel();
+        let (sender, replies) = mpsc::channel();
+        let handle = thread::Builder::new()
+            .name(String::from("dump-extractor"))
+            .spawn(move || extract(root, dump, receiver, sender))?;
+
+        let stream = Stream { requests, replies, handle };
+        Ok(DumpArchive {
+            dir,
+            state: Mutex::new(State {
+                stream: Some(stream),
+                current_dir: None,
+                yielded: HashSet::new(),
+            }),
+        })
+    }
+
+    /// The directory in which the archive is extracted.
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Extracts the file if it wasn't already and returns its path.
+    ///
+    /// The file may not exist if it isn't part of the dump.
+    pub fn file(&self, path: impl AsRef<Path>) -> Result<PathBuf> {
+        let path = path.as_ref();
+        let mut state = self.state.lock().unwrap();
+        if state.stream.is_some() {
+            state.request(Request::File(path.to_path_buf()))?;
+        }
+        Ok(self.dir.path().join(path))
+    }
+
+    /// Extracts all the files of the directory and returns its path.
+    ///
+    /// The directory previously returned by this method or [`Self::next_dir`] is removed.
+    pub fn dir(&self, path: impl AsRef<Path>) -> Result<PathBuf> {
+        let path = path.as_ref();
+        let mut state = self.state.lock().unwrap();
+        state.release(self.dir.path());
+        if state.stream.is_some() {
+            state.request(Request::Dir(path.to_path_buf()))?;
+        }
+        state.current_dir = Some(path.to_path_buf());
+        Ok(self.dir.path().join(path))
+    }
+
+    /// Extracts the next directory under `parent` in the order of the archive and returns its
+    /// path, or `None` if all the directories under `parent` have already been returned.
+    ///
+    /// The directory previously returned by this method or [`Self::dir`] is removed.
+    pub fn next_dir(&self, parent: impl AsRef<Path>) -> Result<Option<PathBuf>> {
+        let parent = parent.as_ref();
+        let mut state = self.state.lock().unwrap();
+        state.release(self.dir.path());
+
+        let next = if state.stream.is_some() {
+            let exclude = state.yielded.clone();
+            state.request(Request::NextDir { parent: parent.to_path_buf(), exclude })?
+        } else {
+            None
+        };
+
+        // once the whole archive has been extracted we can list the remaining directories
+        let next = if next.is_none() {
+            let mut dirs = Vec::new();
+            for entry in fs::read_dir(self.dir.path().join(parent))? {
+                let entry = entry?;
+                if entry.file_type()?.is_dir() {
+                    dirs.push(parent.join(entry.file_name()));
+                }
+            }
+            dirs.sort_unstable();
+            dirs.into_iter().find(|dir| !state.yielded.contains(dir))
+        } else {
+            next
+        };
+
+        if let Some(dir) = &next {
+            state.yielded.insert(dir.clone());
+            state.current_dir = Some(dir.clone());
+        }
+        Ok(next.map(|dir| self.dir.path().join(dir)))
+    }
+
+    /// Extracts all the remaining entries of the archive.
+    pub fn extract_all(&self) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.stream.is_some() {
+            state.request(Request::All)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<TempDir> for DumpArchive {
+    /// Wraps a dump that has already been entirely unpacked.
+    fn from(dir: TempDir) -> Self {
+        DumpArchive {
+            dir,
+            state: Mutex::new(State { stream: None, current_dir: None, yielded: HashSet::new() }),
+        }
+    }
+}
+
+impl Drop for DumpArchive {
+    fn drop(&mut self) {
+        if let Some(stream) = self.state.get_mut().unwrap().stream.take() {
+            stream.stop();
+        }
+    }
+}
+
+impl State {
+    fn request(&mut self, request: Request) -> io::Result<Option<PathBuf>> {
+        let stream = self.stream.as_ref().expect("the archive has already been extracted");
+        let stopped = || io::Error::new(io::ErrorKind::Other, "The dump extractor stopped");
+        stream.requests.send(request).map_err(|_| stopped())?;
+        let Reply { path, finished } = stream.replies.recv().map_err(|_| stopped())??;
+
+        if finished {
+            if let Some(stream) = self.stream.take() {
+                stream.stop();
+            }
+        }
+        Ok(path)
+    }
+
+    fn release(&mut self, root: &Path) {
+        if let Some(dir) = self.current_dir.take() {
+            if let Err(error) = fs::remove_dir_all(root.join(&dir)) {
+                tracing::warn!("Could not remove `{}` from the dump: {error}", dir.display());
+            }
+        }
+    }
+}
+
+impl Stream {
+    fn stop(self) {
+        let Stream { requests, replies: _, handle } = self;
+        // the extractor exits as soon as there are no more requests to process
+        drop(requests);
+        if handle.join().is_err() {
+            tracing::error!("The dump extractor panicked");
+        }
+    }
+}
+
+fn extract(
+    root: PathBuf,
+    dump: impl Read,
+    requests: Receiver<Request>,
+    replies: Sender<io::Result<Reply>>,
+) {
+    let mut archive = tar::Archive::new(GzDecoder::new(BufReader::new(dump)));
+    let mut entries = match archive.entries() {
+        Ok(entries) => entries,
+        Err(error) => {
+            if requests.recv().is_ok() {
+                let _ = replies.send(Err(error));
+            }
+            return;
+        }
+    };
+
+    let mut extractor = Extractor {
+        root,
+        ordered: None,
+        top_level_done: false,
+        open: Vec::new(),
+        completed: Vec::new(),
+        finished: false,
+    };
+    for request in requests {
+        let reply = extractor.process(&mut entries, request);
+        let failed = reply.is_err();
+        if replies.send(reply).is_err() || failed {
+            break;
+        }
+    }
+}
+
+struct Extractor {
+    root: PathBuf,
+    /// Whether the archive was written by a [`DumpWriter`](crate::DumpWriter) putting the
+    /// top level files before the indexes, known once the first entry has been read.
+    ordered: Option<bool>,
+    /// Whether all the files outside of the `indexes` directory have been extracted.
+    top_level_done: bool,
+    /// The directory of the last extracted entry along with its ancestors, outermost first.
+    open: Vec<PathBuf>,
+    /// The directories whose entries have all been extracted, in the order of the archive.
+    completed: Vec<PathBuf>,
+    finished: bool,
+}
+
+impl Extractor {
+    fn process<R: Read>(
+        &mut self,
+        entries: &mut tar::Entries<'_, R>,
+        request: Request,
+    ) -> io::Result<Reply> {
+        let path = match request {
+            Request::File(path) => {
+                while !self.finished
+                    && !self.root.join(&path).exists()
+                    && !path.ancestors().skip(1).any(|dir| self.completed.iter().any(|c| c == dir))
+                    && !(self.top_level_done && !path.starts_with("indexes"))
+                {
+                    self.advance(entries)?;
+                }
+                Some(path)
+            }
+            Request::Dir(path) => {
+                while !self.finished && !self.completed.contains(&path) {
+                    self.advance(entries)?;
+                }
+                Some(path)
+            }
+            Request::NextDir { parent, exclude } => loop {
+                let next = self.completed.iter().find(|dir| {
+                    dir.parent() == Some(parent.as_path()) && !exclude.contains(dir.as_path())
+                });
+                if next.is_some() || self.finished {
+                    break next.cloned();
+                }
+                self.advance(entries)?;
+            },
+            Request::All => {
+                while !self.finished {
+                    self.advance(entries)?;
+                }
+                None
+            }
+        };
+
+        Ok(Reply { path, finished: self.finished })
+    }
+
+    /// Extracts the next entry of the archive.
+    fn advance<R: Read>(&mut self, entries: &mut tar::Entries<'_, R>) -> io::Result<()> {
+        let mut entry = match entries.next() {
+            Some(entry) => entry?,
+            None => {
+                self.finished = true;
+                self.completed.extend(self.open.drain(..).rev());
+                return Ok(());
+            }
+        };
+
+        // the old dumps were written with a leading `./` in all the paths
+        let path: PathBuf = entry
+            .path()?
+            .components()
+            .filter(|component| !matches!(component, Component::CurDir))
+            .collect();
+
+        // The archives written by the previous versions may have top level files after the
+        // indexes, a missing top level file is only known once the whole archive is extracted.
+        let ordered = *self.ordered.get_or_insert(path == Path::new("metadata.json"));
+        if ordered && path.starts_with("indexes") {
+            self.top_level_done = true;
+        }
+
+        // The entries of a directory are contiguous in the archive, the open directories
+        // that don't contain the entry are complete.
+        let section = if entry.header().entry_type().is_dir() {
+            path.clone()
+        } else {
+            path.parent().map_or_else(PathBuf::new, Path::to_path_buf)
+        };
+        while let Some(dir) = self.open.last() {
+            if section.starts_with(dir) {
+                break;
+            }
+            self.completed.extend(self.open.pop());
+        }
+        let mut opened: Vec<_> = section
+            .ancestors()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .take_while(|dir| self.open.last().map_or(true, |open| open != dir))
+            .map(Path::to_path_buf)
+            .collect();
+        opened.reverse();
+        self.open.extend(opened);
+
+        entry.unpack_in(&self.root)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Seek;
+
+    use super::*;
+    use crate::test::{create_test_documents, create_test_index_metadata, create_test_settings};
+    use crate::{DumpWriter, IndexMetadata};
+
+    #[test]
+    fn extract_the_indexes_one_by_one() {
+        let dump = DumpWriter::new(None).unwrap();
+        for uid in ["doggos", "catto"] {
+            let metadata = IndexMetadata { uid: uid.to_string(), ..create_test_index_metadata() };
+            let mut index = dump.create_index(uid, &metadata).unwrap();
+            for document in &create_test_documents() {
+                index.push_document(document).unwrap();
+            }
+            index.flush().unwrap();
+            index.settings(&create_test_settings()).unwrap();
+        }
+        dump.create_keys().unwrap().flush().unwrap();
+        dump.create_tasks_queue().unwrap().flush().unwrap();
+
+        let mut file = tempfile::tempfile().unwrap();
+        dump.persist_to(&mut file).unwrap();
+        file.rewind().unwrap();
+
+        let archive = DumpArchive::open(file).unwrap();
+        assert!(archive.file("metadata.json").unwrap().exists());
+        assert!(archive.file("tasks/queue.jsonl").unwrap().exists());
+        // looking for a missing top level file doesn't extract the indexes
+        assert!(!archive.file("instance_uid.uuid").unwrap().exists());
+        assert!(!archive.path().join("indexes/catto/documents.jsonl").exists());
+
+        let catto = archive.next_dir("indexes").unwrap().unwrap();
+        assert!(catto.ends_with("catto"));
+        assert!(catto.join("documents.jsonl").exists());
+        assert!(!archive.path().join("indexes/doggos/documents.jsonl").exists());
+
+        let doggos = archive.next_dir("indexes").unwrap().unwrap();
+        assert!(doggos.ends_with("doggos"));
+        assert!(doggos.join("documents.jsonl").exists());
+        assert!(!catto.exists());
+
+        assert!(archive.next_dir("indexes").unwrap().is_none());
+        assert!(!doggos.exists());
+    }
+
+    #[test]
+    fn extract_the_indexes_of_a_whole_directory_archive() {
+        // the previous versions archived the whole dump directory under `.`
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("metadata.json"), "{}").unwrap();
+        for uid in ["catto", "doggos"] {
+            let index = dir.path().join("indexes").join(uid);
+            fs::create_dir_all(&index).unwrap();
+            fs::write(index.join("documents.jsonl"), "{}").unwrap();
+        }
+        let mut file = tempfile::tempfile().unwrap();
+        let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(
+            &mut file,
+            flate2::Compression::default(),
+        ));
+        tar.append_dir_all(".", dir.path()).unwrap();
+        tar.into_inner().unwrap().finish().unwrap();
+        file.rewind().unwrap();
+
+        // an index is returned as soon as it is complete, before the next one is extracted
+        let archive = DumpArchive::open(file).unwrap();
+        let first = archive.next_dir("indexes").unwrap().unwrap();
+        assert!(first.join("documents.jsonl").exists());
+        let other = if first.ends_with("catto") { "doggos" } else { "catto" };
+        assert!(!archive.path().join("indexes").join(other).join("documents.jsonl").exists());
+
+        let second = archive.next_dir("indexes").unwrap().unwrap();
+        assert!(second.ends_with(other));
+        assert!(second.join("documents.jsonl").exists());
+        assert!(!first.exists());
+
+        assert!(archive.next_dir("indexes").unwrap().is_none());
+        assert!(archive.file("metadata.json").unwrap().exists());
+    }
+
+    #[test]
+    fn extract_an_unordered_dump() {
+        let dump = std::fs::File::open("tests/assets/v5.dump").unwrap();
+        let archive = DumpArchive::open(dump).unwrap();
+
+        assert!(archive.file("metadata.json").unwrap().exists());
+        let mut indexes = Vec::new();
+        while let Some(index) = archive.next_dir("indexes").unwrap() {
+            assert!(index.join("documents.jsonl").exists());
+            indexes.push(index);
+        }
+        assert_eq!(indexes.len(), 3);
+        assert!(indexes.iter().all(|index| !index.exists()));
+    }
+}
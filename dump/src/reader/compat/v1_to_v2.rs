@@ -340,7 +340,7 @@ pub(crate) mod test {
         let mut archive = tar::Archive::new(gz);
         archive.unpack(dir.path()).unwrap();
 
-        let mut dump = v1::V1Reader::open(dir).unwrap().to_v2();
+        let mut dump = v1::V1Reader::open(dir.into()).unwrap().to_v2();
 
         // top level infos
         assert_eq!(dump.date(), None);
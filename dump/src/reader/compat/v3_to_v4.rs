@@ -355,7 +355,7 @@ pub(crate) mod test {
         let mut archive = tar::Archive::new(gz);
         archive.unpack(dir.path()).unwrap();
 
-        let mut dump = v3::V3Reader::open(dir).unwrap().to_v4();
+        let mut dump = v3::V3Reader::open(dir.into()).unwrap().to_v4();
 
         // top level infos
         insta::assert_display_snapshot!(dump.date().unwrap(), @"2022-10-07 11:39:03.709153554 +00:00:00");
@@ -391,7 +391,7 @@ pub(crate) mod test {
         let mut archive = tar::Archive::new(gz);
         archive.unpack(dir.path()).unwrap();
 
-        let mut dump = v4::V4Reader::open(dir).unwrap().to_v5();
+        let mut dump = v4::V4Reader::open(dir.into()).unwrap().to_v5();
 
         // top level infos
         insta::assert_display_snapshot!(dump.date().unwrap(), @"2022-10-06 12:53:49.131989609 +00:00:00");
@@ -123,7 +123,13 @@ impl CompatV5ToV6 {
                         },
                         v5::tasks::TaskContent::Dump { uid: _ } => {
                             // in v6 we compute the dump_uid from the started_at processing time
-                            v6::Kind::DumpCreation { keys: keys.clone(), instance_uid }
+                            v6::Kind::DumpCreation {
+                                keys: keys.clone(),
+                                instance_uid,
+                                index_uids: None,
+                                include_tasks: true,
+                                include_keys: true,
+                            }
                         }
                     },
                     canceled_by: None,
@@ -151,7 +157,7 @@ impl CompatV5ToV6 {
                             v6::Details::ClearAll { deleted_documents }
                         }
                         v5::Details::Dump { dump_uid } => {
-                            v6::Details::Dump { dump_uid: Some(dump_uid) }
+                            v6::Details::Dump { dump_uid: Some(dump_uid), upload_error: None }
                         }
                     }),
                     error: task_view.error.map(|e| e.into()),
@@ -380,6 +386,9 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             },
             embedders: v6::Setting::NotSet,
             search_cutoff_ms: v6::Setting::NotSet,
+            localized_attributes: v6::Setting::NotSet,
+            allow_ranking_rules_override: v6::Setting::NotSet,
+            merchandising_rules: v6::Setting::NotSet,
             _kind: std::marker::PhantomData,
         }
     }
@@ -439,7 +448,7 @@ pub(crate) mod test {
         let mut archive = tar::Archive::new(gz);
         archive.unpack(dir.path()).unwrap();
 
-        let mut dump = v5::V5Reader::open(dir).unwrap().to_v6();
+        let mut dump = v5::V5Reader::open(dir.into()).unwrap().to_v6();
 
         // top level infos
         insta::assert_display_snapshot!(dump.date().unwrap(), @"2022-10-04 15:55:10.344982459 +00:00:00");
@@ -1,16 +1,16 @@
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::Read;
 
-use flate2::bufread::GzDecoder;
 use serde::Deserialize;
-use tempfile::TempDir;
 
+use self::archive::DumpArchive;
 use self::compat::v4_to_v5::CompatV4ToV5;
 use self::compat::v5_to_v6::{CompatIndexV5ToV6, CompatV5ToV6};
 use self::v5::V5Reader;
 use self::v6::{V6IndexReader, V6Reader};
 use crate::{Result, Version};
 
+mod archive;
 mod compat;
 
 mod v1;
@@ -29,30 +29,27 @@ pub enum DumpReader {
 }
 
 impl DumpReader {
-    pub fn open(dump: impl Read) -> Result<DumpReader> {
-        let path = TempDir::new()?;
-        let mut dump = BufReader::new(dump);
-        let gz = GzDecoder::new(&mut dump);
-        let mut archive = tar::Archive::new(gz);
-        archive.unpack(path.path())?;
+    /// Opens a dump without unpacking it entirely, its entries are extracted as they are read.
+    pub fn open(dump: impl Read + Send + 'static) -> Result<DumpReader> {
+        let archive = DumpArchive::open(dump)?;
 
         #[derive(Deserialize)]
         #[serde(rename_all = "camelCase")]
         struct MetadataVersion {
             pub dump_version: Version,
         }
-        let mut meta_file = File::open(path.path().join("metadata.json"))?;
+        let mut meta_file = File::open(archive.file("metadata.json")?)?;
         let MetadataVersion { dump_version } = serde_json::from_reader(&mut meta_file)?;
 
         match dump_version {
             Version::V1 => {
-                Ok(v1::V1Reader::open(path)?.to_v2().to_v3().to_v4().to_v5().to_v6().into())
+                Ok(v1::V1Reader::open(archive)?.to_v2().to_v3().to_v4().to_v5().to_v6().into())
             }
-            Version::V2 => Ok(v2::V2Reader::open(path)?.to_v3().to_v4().to_v5().to_v6().into()),
-            Version::V3 => Ok(v3::V3Reader::open(path)?.to_v4().to_v5().to_v6().into()),
-            Version::V4 => Ok(v4::V4Reader::open(path)?.to_v5().to_v6().into()),
-            Version::V5 => Ok(v5::V5Reader::open(path)?.to_v6().into()),
-            Version::V6 => Ok(v6::V6Reader::open(path)?.into()),
+            Version::V2 => Ok(v2::V2Reader::open(archive)?.to_v3().to_v4().to_v5().to_v6().into()),
+            Version::V3 => Ok(v3::V3Reader::open(archive)?.to_v4().to_v5().to_v6().into()),
+            Version::V4 => Ok(v4::V4Reader::open(archive)?.to_v5().to_v6().into()),
+            Version::V5 => Ok(v5::V5Reader::open(archive)?.to_v6().into()),
+            Version::V6 => Ok(v6::V6Reader::open(archive)?.into()),
         }
     }
 
@@ -3,9 +3,9 @@ use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
-use tempfile::TempDir;
 use time::OffsetDateTime;
 
+use super::archive::DumpArchive;
 use super::compat::v1_to_v2::CompatV1ToV2;
 use super::Document;
 use crate::{IndexMetadata, Result, Version};
@@ -14,7 +14,7 @@ pub mod settings;
 pub mod update;
 
 pub struct V1Reader {
-    pub dump: TempDir,
+    pub dump: DumpArchive,
     pub db_version: String,
     pub dump_version: crate::Version,
     indexes: Vec<V1Index>,
@@ -82,7 +82,10 @@ impl V1IndexReader {
 }
 
 impl V1Reader {
-    pub fn open(dump: TempDir) -> Result<Self> {
+    pub fn open(dump: DumpArchive) -> Result<Self> {
+        // the updates of the tasks are stored alongside the indexes, we can't stream them
+        dump.extract_all()?;
+
         let meta_file = fs::read(dump.path().join("metadata.json"))?;
         let metadata: Metadata = serde_json::from_reader(&*meta_file)?;
 
@@ -188,7 +191,7 @@ pub(crate) mod test {
         let mut archive = tar::Archive::new(gz);
         archive.unpack(dir.path()).unwrap();
 
-        let dump = V1Reader::open(dir).unwrap();
+        let dump = V1Reader::open(dir.into()).unwrap();
 
         // top level infos
         assert_eq!(dump.date(), None);
@@ -27,7 +27,6 @@ use std::io::{BufRead, BufReader};
 use std::path::Path;
 
 use serde::{Deserialize, Serialize};
-use tempfile::TempDir;
 use time::OffsetDateTime;
 
 pub mod errors;
@@ -36,6 +35,7 @@ pub mod settings;
 pub mod updates;
 
 use self::meta::{DumpMeta, IndexUuid};
+use super::archive::DumpArchive;
 use super::compat::v2_to_v3::CompatV2ToV3;
 use super::Document;
 use crate::{IndexMetadata, Result, Version};
@@ -62,17 +62,17 @@ pub struct Metadata {
 }
 
 pub struct V2Reader {
-    dump: TempDir,
+    dump: DumpArchive,
     metadata: Metadata,
     tasks: BufReader<File>,
     pub index_uuid: Vec<IndexUuid>,
 }
 
 impl V2Reader {
-    pub fn open(dump: TempDir) -> Result<Self> {
-        let meta_file = fs::read(dump.path().join("metadata.json"))?;
+    pub fn open(dump: DumpArchive) -> Result<Self> {
+        let meta_file = fs::read(dump.file("metadata.json")?)?;
         let metadata = serde_json::from_reader(&*meta_file)?;
-        let index_uuid = File::open(dump.path().join("index_uuids/data.jsonl"))?;
+        let index_uuid = File::open(dump.file("index_uuids/data.jsonl")?)?;
         let index_uuid = BufReader::new(index_uuid);
         let index_uuid = index_uuid
             .lines()
@@ -81,9 +81,7 @@ impl V2Reader {
 
         Ok(V2Reader {
             metadata,
-            tasks: BufReader::new(
-                File::open(dump.path().join("updates").join("data.jsonl")).unwrap(),
-            ),
+            tasks: BufReader::new(File::open(dump.file("updates/data.jsonl")?)?),
             index_uuid,
             dump,
         })
@@ -108,11 +106,9 @@ impl V2Reader {
     pub fn indexes(&self) -> Result<impl Iterator<Item = Result<V2IndexReader>> + '_> {
         Ok(self.index_uuid.iter().map(|index| -> Result<_> {
             V2IndexReader::new(
-                &self.dump.path().join("indexes").join(format!("index-{}", index.uuid)),
+                &self.dump.dir(format!("indexes/index-{}", index.uuid))?,
                 index,
-                BufReader::new(
-                    File::open(self.dump.path().join("updates").join("data.jsonl")).unwrap(),
-                ),
+                BufReader::new(File::open(self.dump.file("updates/data.jsonl")?)?),
             )
         }))
     }
@@ -122,12 +118,8 @@ impl V2Reader {
             let task: Task = serde_json::from_str(&line?)?;
             if !task.is_finished() {
                 if let Some(uuid) = task.get_content_uuid() {
-                    let update_file_path = self
-                        .dump
-                        .path()
-                        .join("updates")
-                        .join("update_files")
-                        .join(format!("update_{}", uuid));
+                    let update_file_path =
+                        self.dump.file(format!("updates/update_files/update_{}", uuid))?;
                     Ok((task, Some(UpdateFile::new(&update_file_path)?)))
                 } else {
                     Ok((task, None))
@@ -249,7 +241,7 @@ pub(crate) mod test {
         let mut archive = tar::Archive::new(gz);
         archive.unpack(dir.path()).unwrap();
 
-        let mut dump = V2Reader::open(dir).unwrap();
+        let mut dump = V2Reader::open(dir.into()).unwrap();
 
         // top level infos
         insta::assert_display_snapshot!(dump.date().unwrap(), @"2022-10-09 20:27:59.904096267 +00:00:00");
@@ -346,7 +338,7 @@ pub(crate) mod test {
         let mut archive = tar::Archive::new(gz);
         archive.unpack(dir.path()).unwrap();
 
-        let mut dump = V2Reader::open(dir).unwrap();
+        let mut dump = V2Reader::open(dir.into()).unwrap();
 
         // top level infos
         insta::assert_display_snapshot!(dump.date().unwrap(), @"2023-01-30 16:26:09.247261 +00:00:00");
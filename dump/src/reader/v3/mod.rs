@@ -27,7 +27,6 @@ use std::io::{BufRead, BufReader};
 use std::path::Path;
 
 use serde::{Deserialize, Serialize};
-use tempfile::TempDir;
 use time::OffsetDateTime;
 
 pub mod errors;
@@ -36,6 +35,7 @@ pub mod settings;
 pub mod updates;
 
 use self::meta::{DumpMeta, IndexUuid};
+use super::archive::DumpArchive;
 use super::compat::v3_to_v4::CompatV3ToV4;
 use super::Document;
 use crate::{Error, IndexMetadata, Result, Version};
@@ -68,17 +68,17 @@ pub struct Metadata {
 }
 
 pub struct V3Reader {
-    dump: TempDir,
+    dump: DumpArchive,
     metadata: Metadata,
     tasks: BufReader<File>,
     index_uuid: Vec<IndexUuid>,
 }
 
 impl V3Reader {
-    pub fn open(dump: TempDir) -> Result<Self> {
-        let meta_file = fs::read(dump.path().join("metadata.json"))?;
+    pub fn open(dump: DumpArchive) -> Result<Self> {
+        let meta_file = fs::read(dump.file("metadata.json")?)?;
         let metadata = serde_json::from_reader(&*meta_file)?;
-        let index_uuid = File::open(dump.path().join("index_uuids/data.jsonl"))?;
+        let index_uuid = File::open(dump.file("index_uuids/data.jsonl")?)?;
         let index_uuid = BufReader::new(index_uuid);
         let index_uuid = index_uuid
             .lines()
@@ -87,7 +87,7 @@ impl V3Reader {
 
         Ok(V3Reader {
             metadata,
-            tasks: BufReader::new(File::open(dump.path().join("updates").join("data.jsonl"))?),
+            tasks: BufReader::new(File::open(dump.file("updates/data.jsonl")?)?),
             index_uuid,
             dump,
         })
@@ -112,7 +112,7 @@ impl V3Reader {
     pub fn indexes(&self) -> Result<impl Iterator<Item = Result<V3IndexReader>> + '_> {
         Ok(self.index_uuid.iter().map(|index| -> Result<_> {
             V3IndexReader::new(
-                &self.dump.path().join("indexes").join(index.uuid.to_string()),
+                &self.dump.dir(format!("indexes/{}", index.uuid))?,
                 index,
                 BufReader::new(
                     File::open(self.dump.path().join("updates").join("data.jsonl")).unwrap(),
@@ -128,12 +128,8 @@ impl V3Reader {
             let task: Task = serde_json::from_str(&line?)?;
             if !task.is_finished() {
                 if let Some(uuid) = task.get_content_uuid() {
-                    let update_file_path = self
-                        .dump
-                        .path()
-                        .join("updates")
-                        .join("updates_files")
-                        .join(uuid.to_string());
+                    let update_file_path =
+                        self.dump.file(format!("updates/updates_files/{}", uuid))?;
                     Ok((
                         task,
                         Some(
@@ -264,7 +260,7 @@ pub(crate) mod test {
         let mut archive = tar::Archive::new(gz);
         archive.unpack(dir.path()).unwrap();
 
-        let mut dump = V3Reader::open(dir).unwrap();
+        let mut dump = V3Reader::open(dir.into()).unwrap();
 
         // top level infos
         insta::assert_display_snapshot!(dump.date().unwrap(), @"2022-10-07 11:39:03.709153554 +00:00:00");
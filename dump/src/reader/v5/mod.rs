@@ -37,10 +37,10 @@ use std::io::{BufRead, BufReader, ErrorKind, Seek};
 use std::path::Path;
 
 use serde::{Deserialize, Serialize};
-use tempfile::TempDir;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+use super::archive::DumpArchive;
 use super::compat::v5_to_v6::CompatV5ToV6;
 use super::Document;
 use crate::{Error, IndexMetadata, Result, Version};
@@ -87,7 +87,7 @@ pub struct Metadata {
 }
 
 pub struct V5Reader {
-    dump: TempDir,
+    dump: DumpArchive,
     metadata: Metadata,
     tasks: BufReader<File>,
     keys: BufReader<File>,
@@ -95,10 +95,10 @@ pub struct V5Reader {
 }
 
 impl V5Reader {
-    pub fn open(dump: TempDir) -> Result<Self> {
-        let meta_file = fs::read(dump.path().join("metadata.json"))?;
+    pub fn open(dump: DumpArchive) -> Result<Self> {
+        let meta_file = fs::read(dump.file("metadata.json")?)?;
         let metadata = serde_json::from_reader(&*meta_file)?;
-        let index_uuid = File::open(dump.path().join("index_uuids/data.jsonl"))?;
+        let index_uuid = File::open(dump.file("index_uuids/data.jsonl")?)?;
         let index_uuid = BufReader::new(index_uuid);
         let index_uuid = index_uuid
             .lines()
@@ -107,10 +107,8 @@ impl V5Reader {
 
         Ok(V5Reader {
             metadata,
-            tasks: BufReader::new(
-                File::open(dump.path().join("updates").join("data.jsonl")).unwrap(),
-            ),
-            keys: BufReader::new(File::open(dump.path().join("keys"))?),
+            tasks: BufReader::new(File::open(dump.file("updates/data.jsonl")?).unwrap()),
+            keys: BufReader::new(File::open(dump.file("keys")?)?),
             index_uuid,
             dump,
         })
@@ -129,7 +127,7 @@ impl V5Reader {
     }
 
     pub fn instance_uid(&self) -> Result<Option<Uuid>> {
-        match fs::read_to_string(self.dump.path().join("instance-uid")) {
+        match fs::read_to_string(self.dump.file("instance-uid")?) {
             Ok(uuid) => Ok(Some(Uuid::parse_str(&uuid)?)),
             Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
             Err(e) => Err(e.into()),
@@ -140,7 +138,7 @@ impl V5Reader {
         Ok(self.index_uuid.iter().map(|index| -> Result<_> {
             V5IndexReader::new(
                 index.uid.clone(),
-                &self.dump.path().join("indexes").join(index.index_meta.uuid.to_string()),
+                &self.dump.dir(format!("indexes/{}", index.index_meta.uuid))?,
                 &index.index_meta,
                 BufReader::new(
                     File::open(self.dump.path().join("updates").join("data.jsonl")).unwrap(),
@@ -156,12 +154,8 @@ impl V5Reader {
             let task: Task = serde_json::from_str(&line?)?;
             if !task.is_finished() {
                 if let Some(uuid) = task.get_content_uuid() {
-                    let update_file_path = self
-                        .dump
-                        .path()
-                        .join("updates")
-                        .join("updates_files")
-                        .join(uuid.to_string());
+                    let update_file_path =
+                        self.dump.file(format!("updates/updates_files/{}", uuid))?;
                     Ok((
                         task,
                         Some(
@@ -296,7 +290,7 @@ pub(crate) mod test {
         let mut archive = tar::Archive::new(gz);
         archive.unpack(dir.path()).unwrap();
 
-        let mut dump = V5Reader::open(dir).unwrap();
+        let mut dump = V5Reader::open(dir.into()).unwrap();
 
         // top level infos
         insta::assert_display_snapshot!(dump.date().unwrap(), @"2022-10-04 15:55:10.344982459 +00:00:00");
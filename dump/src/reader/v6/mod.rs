@@ -3,11 +3,11 @@ use std::io::{BufRead, BufReader, ErrorKind};
 use std::path::Path;
 
 pub use meilisearch_types::milli;
-use tempfile::TempDir;
 use time::OffsetDateTime;
 use tracing::debug;
 use uuid::Uuid;
 
+use super::archive::DumpArchive;
 use super::Document;
 use crate::{Error, IndexMetadata, Result, Version};
 
@@ -44,7 +44,7 @@ pub type Code = meilisearch_types::error::Code;
 pub type RankingRuleView = meilisearch_types::settings::RankingRuleView;
 
 pub struct V6Reader {
-    dump: TempDir,
+    dump: DumpArchive,
     instance_uid: Option<Uuid>,
     metadata: Metadata,
     tasks: BufReader<File>,
@@ -53,15 +53,15 @@ pub struct V6Reader {
 }
 
 impl V6Reader {
-    pub fn open(dump: TempDir) -> Result<Self> {
-        let meta_file = fs::read(dump.path().join("metadata.json"))?;
-        let instance_uid = match fs::read_to_string(dump.path().join("instance_uid.uuid")) {
+    pub fn open(dump: DumpArchive) -> Result<Self> {
+        let meta_file = fs::read(dump.file("metadata.json")?)?;
+        let instance_uid = match fs::read_to_string(dump.file("instance_uid.uuid")?) {
             Ok(uuid) => Some(Uuid::parse_str(&uuid)?),
             Err(e) if e.kind() == ErrorKind::NotFound => None,
             Err(e) => return Err(e.into()),
         };
 
-        let feature_file = match fs::read(dump.path().join("experimental-features.json")) {
+        let feature_file = match fs::read(dump.file("experimental-features.json")?) {
             Ok(feature_file) => Some(feature_file),
             Err(error) => match error.kind() {
                 // Allows the file to be missing, this will only result in all experimental features disabled.
@@ -81,8 +81,8 @@ impl V6Reader {
         Ok(V6Reader {
             metadata: serde_json::from_reader(&*meta_file)?,
             instance_uid,
-            tasks: BufReader::new(File::open(dump.path().join("tasks").join("queue.jsonl"))?),
-            keys: BufReader::new(File::open(dump.path().join("keys.jsonl"))?),
+            tasks: BufReader::new(File::open(dump.file("tasks/queue.jsonl")?)?),
+            keys: BufReader::new(File::open(dump.file("keys.jsonl")?)?),
             features,
             dump,
         })
@@ -101,23 +101,18 @@ impl V6Reader {
     }
 
     pub fn indexes(&self) -> Result<Box<dyn Iterator<Item = Result<V6IndexReader>> + '_>> {
-        let entries = fs::read_dir(self.dump.path().join("indexes"))?;
-        Ok(Box::new(
-            entries
-                .map(|entry| -> Result<Option<_>> {
-                    let entry = entry?;
-                    if entry.file_type()?.is_dir() {
-                        let index = V6IndexReader::new(
-                            entry.file_name().to_str().ok_or(Error::BadIndexName)?.to_string(),
-                            &entry.path(),
-                        )?;
-                        Ok(Some(index))
-                    } else {
-                        Ok(None)
-                    }
-                })
-                .filter_map(|entry| entry.transpose()),
-        ))
+        // the indexes are extracted one by one and removed once the next one is requested
+        Ok(Box::new(std::iter::from_fn(|| {
+            let path = match self.dump.next_dir("indexes") {
+                Ok(path) => path?,
+                Err(e) => return Some(Err(e)),
+            };
+            let index = match path.file_name().and_then(|name| name.to_str()) {
+                Some(name) => V6IndexReader::new(name.to_string(), &path),
+                None => Err(Error::BadIndexName),
+            };
+            Some(index)
+        })))
     }
 
     pub fn tasks(
@@ -126,12 +121,8 @@ impl V6Reader {
         Box::new((&mut self.tasks).lines().map(|line| -> Result<_> {
             let task: Task = serde_json::from_str(&line?).unwrap();
 
-            let update_file_path = self
-                .dump
-                .path()
-                .join("tasks")
-                .join("update_files")
-                .join(format!("{}.jsonl", task.uid));
+            let update_file_path =
+                self.dump.file(format!("tasks/update_files/{}.jsonl", task.uid))?;
 
             if update_file_path.exists() {
                 Ok((
@@ -1,6 +1,6 @@
 use std::fs::{self, File};
 use std::io::{BufWriter, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use flate2::write::GzEncoder;
 use flate2::Compression;
@@ -64,7 +64,31 @@ impl DumpWriter {
     pub fn persist_to(self, mut writer: impl Write) -> Result<()> {
         let gz_encoder = GzEncoder::new(&mut writer, Compression::default());
         let mut tar_encoder = tar::Builder::new(gz_encoder);
-        tar_encoder.append_dir_all(".", self.dir.path())?;
+
+        // The top level files are written first and then the indexes one by one, so the
+        // readers can extract them lazily without having to unpack the whole dump.
+        let dir = self.dir.path();
+        tar_encoder.append_path_with_name(dir.join("metadata.json"), "metadata.json")?;
+        for name in ["instance_uid.uuid", "keys.jsonl", "experimental-features.json"] {
+            let path = dir.join(name);
+            if path.exists() {
+                tar_encoder.append_path_with_name(path, name)?;
+            }
+        }
+        if dir.join("tasks").exists() {
+            tar_encoder.append_dir_all("tasks", dir.join("tasks"))?;
+        }
+
+        tar_encoder.append_dir("indexes", dir.join("indexes"))?;
+        let mut indexes = fs::read_dir(dir.join("indexes"))?
+            .map(|entry| entry.map(|entry| entry.file_name()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        indexes.sort_unstable();
+        for index in indexes {
+            let name = Path::new("indexes").join(&index);
+            tar_encoder.append_dir_all(name, dir.join("indexes").join(&index))?;
+        }
+
         let gz_encoder = tar_encoder.into_inner()?;
         gz_encoder.finish()?;
         writer.flush()?;
@@ -26,6 +26,7 @@ enum AutobatchKind {
     },
     DocumentDeletion,
     DocumentDeletionByFilter,
+    DocumentEdition,
     DocumentClear,
     Settings {
         allow_index_creation: bool,
@@ -34,6 +35,7 @@ enum AutobatchKind {
     IndexDeletion,
     IndexUpdate,
     IndexSwap,
+    DumpImport,
 }
 
 impl AutobatchKind {
@@ -68,6 +70,7 @@ impl From<KindWithContent> for AutobatchKind {
             KindWithContent::DocumentDeletionByFilter { .. } => {
                 AutobatchKind::DocumentDeletionByFilter
             }
+            KindWithContent::DocumentEdition { .. } => AutobatchKind::DocumentEdition,
             KindWithContent::SettingsUpdate { allow_index_creation, is_deletion, .. } => {
                 AutobatchKind::Settings {
                     allow_index_creation: allow_index_creation && !is_deletion,
@@ -77,6 +80,7 @@ impl From<KindWithContent> for AutobatchKind {
             KindWithContent::IndexCreation { .. } => AutobatchKind::IndexCreation,
             KindWithContent::IndexUpdate { .. } => AutobatchKind::IndexUpdate,
             KindWithContent::IndexSwap { .. } => AutobatchKind::IndexSwap,
+            KindWithContent::DumpImport { .. } => AutobatchKind::DumpImport,
             KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
             | KindWithContent::DumpCreation { .. }
@@ -104,6 +108,9 @@ pub enum BatchKind {
     DocumentDeletionByFilter {
         id: TaskId,
     },
+    DocumentEdition {
+        id: TaskId,
+    },
     ClearAndSettings {
         other: Vec<TaskId>,
         allow_index_creation: bool,
@@ -132,6 +139,9 @@ pub enum BatchKind {
     IndexSwap {
         id: TaskId,
     },
+    DumpImport {
+        id: TaskId,
+    },
 }
 
 impl BatchKind {
@@ -175,6 +185,7 @@ impl BatchKind {
             K::IndexDeletion => (Break(BatchKind::IndexDeletion { ids: vec![task_id] }), false),
             K::IndexUpdate => (Break(BatchKind::IndexUpdate { id: task_id }), false),
             K::IndexSwap => (Break(BatchKind::IndexSwap { id: task_id }), false),
+            K::DumpImport => (Break(BatchKind::DumpImport { id: task_id }), false),
             K::DocumentClear => (Continue(BatchKind::DocumentClear { ids: vec![task_id] }), false),
             K::DocumentImport { method, allow_index_creation, primary_key: pk }
                 if primary_key.is_none() || pk.is_none() || primary_key == pk.as_deref() =>
@@ -205,6 +216,7 @@ impl BatchKind {
             K::DocumentDeletionByFilter => {
                 (Break(BatchKind::DocumentDeletionByFilter { id: task_id }), false)
             }
+            K::DocumentEdition => (Break(BatchKind::DocumentEdition { id: task_id }), false),
             K::Settings { allow_index_creation } => (
                 Continue(BatchKind::Settings { allow_index_creation, settings_ids: vec![task_id] }),
                 allow_index_creation,
@@ -222,7 +234,7 @@ impl BatchKind {
 
         match (self, kind) {
             // We don't batch any of these operations
-            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap | K::DocumentDeletionByFilter) => Break(this),
+            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap | K::DumpImport | K::DocumentDeletionByFilter | K::DocumentEdition) => Break(this),
             // We must not batch tasks that don't have the same index creation rights if the index doesn't already exists.
             (this, kind) if !index_already_exists && this.allow_index_creation() == Some(false) && kind.allow_index_creation() == Some(true) => {
                 Break(this)
@@ -519,7 +531,9 @@ impl BatchKind {
                 | BatchKind::IndexDeletion { .. }
                 | BatchKind::IndexUpdate { .. }
                 | BatchKind::IndexSwap { .. }
-                | BatchKind::DocumentDeletionByFilter { .. },
+                | BatchKind::DumpImport { .. }
+                | BatchKind::DocumentDeletionByFilter { .. }
+                | BatchKind::DocumentEdition { .. },
                 _,
             ) => {
                 unreachable!()
@@ -18,25 +18,30 @@ one indexing operation.
 */
 
 use std::collections::{BTreeSet, HashSet};
-use std::ffi::OsStr;
 use std::fmt;
 use std::fs::{self, File};
-use std::io::BufWriter;
+use std::io::{BufReader, BufWriter};
 
 use dump::IndexMetadata;
 use meilisearch_types::error::Code;
 use meilisearch_types::heed::{RoTxn, RwTxn};
-use meilisearch_types::milli::documents::{obkv_to_object, DocumentsBatchReader};
+use meilisearch_types::milli::documents::{
+    obkv_to_object, DocumentsBatchBuilder, DocumentsBatchReader,
+};
 use meilisearch_types::milli::heed::CompactionOption;
 use meilisearch_types::milli::update::{
     IndexDocumentsConfig, IndexDocumentsMethod, IndexerConfig, Settings as MilliSettings,
+    UpdateIndexingStep,
 };
 use meilisearch_types::milli::vector::parsed_vectors::{
     ExplicitVectors, VectorOrArrayOfVectors, RESERVED_VECTORS_FIELD_NAME,
 };
-use meilisearch_types::milli::{self, Filter};
+use meilisearch_types::milli::vector::EmbeddingConfigs;
+use meilisearch_types::milli::{self, Filter, Object};
 use meilisearch_types::settings::{apply_settings_to_builder, Settings, Unchecked};
-use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{
+    Details, DumpImportIndex, IndexSwap, Kind, KindWithContent, Status, Task,
+};
 use meilisearch_types::{compression, Index, VERSION_FILE_NAME};
 use roaring::RoaringBitmap;
 use time::macros::format_description;
@@ -44,6 +49,7 @@ use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::autobatcher::{self, BatchKind};
+use crate::incremental_snapshot::{make_readonly, IncrementalSnapshot};
 use crate::utils::{self, swap_index_uid_in_task};
 use crate::{Error, IndexScheduler, MustStopProcessing, ProcessingTasks, Result, TaskId};
 
@@ -87,6 +93,9 @@ pub(crate) enum Batch {
     IndexSwap {
         task: Task,
     },
+    DumpImport {
+        task: Task,
+    },
 }
 
 #[derive(Debug)]
@@ -110,6 +119,10 @@ pub(crate) enum IndexOperation {
         index_uid: String,
         task: Task,
     },
+    DocumentEdition {
+        index_uid: String,
+        task: Task,
+    },
     DocumentClear {
         index_uid: String,
         tasks: Vec<Task>,
@@ -164,7 +177,8 @@ impl Batch {
                 | IndexOperation::DocumentClear { tasks, .. } => {
                     RoaringBitmap::from_iter(tasks.iter().map(|task| task.uid))
                 }
-                IndexOperation::IndexDocumentDeletionByFilter { task, .. } => {
+                IndexOperation::IndexDocumentDeletionByFilter { task, .. }
+                | IndexOperation::DocumentEdition { task, .. } => {
                     RoaringBitmap::from_sorted_iter(std::iter::once(task.uid)).unwrap()
                 }
                 IndexOperation::SettingsAndDocumentOperation {
@@ -178,12 +192,17 @@ impl Batch {
                     ..
                 } => RoaringBitmap::from_iter(tasks.iter().chain(other).map(|task| task.uid)),
             },
-            Batch::IndexSwap { task } => {
+            Batch::IndexSwap { task } | Batch::DumpImport { task } => {
                 RoaringBitmap::from_sorted_iter(std::iter::once(task.uid)).unwrap()
             }
         }
     }
 
+    /// Return whether this batch can modify several indexes at once.
+    pub fn modifies_several_indexes(&self) -> bool {
+        matches!(self, Batch::IndexSwap { .. } | Batch::DumpImport { .. })
+    }
+
     /// Return the index UID associated with this batch
     pub fn index_uid(&self) -> Option<&str> {
         use Batch::*;
@@ -192,13 +211,40 @@ impl Batch {
             | TaskDeletions(_)
             | SnapshotCreation(_)
             | Dump(_)
-            | IndexSwap { .. } => None,
+            | IndexSwap { .. }
+            | DumpImport { .. } => None,
             IndexOperation { op, .. } => Some(op.index_uid()),
             IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
             | IndexDeletion { index_uid, .. } => Some(index_uid),
         }
     }
+
+    /// Return the name of the kind of batch, as exposed on the `/batches` route.
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            Batch::TaskCancelation { .. } => "taskCancelation",
+            Batch::TaskDeletions(_) => "taskDeletion",
+            Batch::SnapshotCreation(_) => "snapshotCreation",
+            Batch::Dump(_) => "dumpCreation",
+            Batch::IndexOperation { op, .. } => match op {
+                IndexOperation::DocumentOperation { .. } => "documentOperation",
+                IndexOperation::IndexDocumentDeletionByFilter { .. } => "documentDeletionByFilter",
+                IndexOperation::DocumentEdition { .. } => "documentEdition",
+                IndexOperation::DocumentClear { .. } => "documentClear",
+                IndexOperation::Settings { .. } => "settings",
+                IndexOperation::DocumentClearAndSetting { .. } => "documentClearAndSettings",
+                IndexOperation::SettingsAndDocumentOperation { .. } => {
+                    "settingsAndDocumentOperation"
+                }
+            },
+            Batch::IndexCreation { .. } => "indexCreation",
+            Batch::IndexUpdate { .. } => "indexUpdate",
+            Batch::IndexDeletion { .. } => "indexDeletion",
+            Batch::IndexSwap { .. } => "indexSwap",
+            Batch::DumpImport { .. } => "dumpImport",
+        }
+    }
 }
 
 impl fmt::Display for Batch {
@@ -216,6 +262,7 @@ impl fmt::Display for Batch {
             Batch::IndexUpdate { .. } => f.write_str("IndexUpdate")?,
             Batch::IndexDeletion { .. } => f.write_str("IndexDeletion")?,
             Batch::IndexSwap { .. } => f.write_str("IndexSwap")?,
+            Batch::DumpImport { .. } => f.write_str("DumpImport")?,
         };
         match index_uid {
             Some(name) => f.write_fmt(format_args!(" on {name:?} from tasks: {tasks:?}")),
@@ -229,6 +276,7 @@ impl IndexOperation {
         match self {
             IndexOperation::DocumentOperation { index_uid, .. }
             | IndexOperation::IndexDocumentDeletionByFilter { index_uid, .. }
+            | IndexOperation::DocumentEdition { index_uid, .. }
             | IndexOperation::DocumentClear { index_uid, .. }
             | IndexOperation::Settings { index_uid, .. }
             | IndexOperation::DocumentClearAndSetting { index_uid, .. }
@@ -246,6 +294,9 @@ impl fmt::Display for IndexOperation {
             IndexOperation::IndexDocumentDeletionByFilter { .. } => {
                 f.write_str("IndexOperation::IndexDocumentDeletionByFilter")
             }
+            IndexOperation::DocumentEdition { .. } => {
+                f.write_str("IndexOperation::DocumentEdition")
+            }
             IndexOperation::DocumentClear { .. } => f.write_str("IndexOperation::DocumentClear"),
             IndexOperation::Settings { .. } => f.write_str("IndexOperation::Settings"),
             IndexOperation::DocumentClearAndSetting { .. } => {
@@ -295,6 +346,21 @@ impl IndexScheduler {
                     _ => unreachable!(),
                 }
             }
+            BatchKind::DocumentEdition { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                match &task.kind {
+                    KindWithContent::DocumentEdition { index_uid, .. } => {
+                        Ok(Some(Batch::IndexOperation {
+                            op: IndexOperation::DocumentEdition {
+                                index_uid: index_uid.clone(),
+                                task,
+                            },
+                            must_create_index: false,
+                        }))
+                    }
+                    _ => unreachable!(),
+                }
+            }
             BatchKind::DocumentOperation { method, operation_ids, .. } => {
                 let tasks = self.get_existing_tasks(rtxn, operation_ids)?;
                 let primary_key = tasks
@@ -515,6 +581,10 @@ impl IndexScheduler {
                 let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
                 Ok(Some(Batch::IndexSwap { task }))
             }
+            BatchKind::DumpImport { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                Ok(Some(Batch::DumpImport { task }))
+            }
         }
     }
 
@@ -536,7 +606,7 @@ impl IndexScheduler {
         if let Some(task_id) = to_cancel.max() {
             // We retrieve the tasks that were processing before this tasks cancelation started.
             // We must *not* reset the processing tasks before calling this method.
-            let ProcessingTasks { started_at, processing } =
+            let ProcessingTasks { started_at, processing, .. } =
                 &*self.processing_tasks.read().unwrap();
             return Ok(Some(Batch::TaskCancelation {
                 task: self.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?,
@@ -730,6 +800,12 @@ impl IndexScheduler {
             Batch::SnapshotCreation(mut tasks) => {
                 fs::create_dir_all(&self.snapshots_path)?;
                 let temp_snapshot_dir = tempfile::tempdir()?;
+                let db_name = self.snapshot_db_name();
+                let mut incremental_snapshot = if self.incremental_snapshots {
+                    Some(IncrementalSnapshot::new(&self.snapshots_path, &db_name)?)
+                } else {
+                    None
+                };
 
                 // 1. Snapshot the version file.
                 let dst = temp_snapshot_dir.path().join(VERSION_FILE_NAME);
@@ -769,13 +845,25 @@ impl IndexScheduler {
                     }
                 }
 
-                // 3. Snapshot every indexes
+                // 3. Snapshot every indexes, or only the ones modified since
+                //    the previous snapshot when the snapshots are incremental
                 for result in self.index_mapper.index_mapping.iter(&rtxn)? {
                     let (name, uuid) = result?;
                     let index = self.index_mapper.index(&rtxn, name)?;
+                    if let Some(snapshot) = &mut incremental_snapshot {
+                        let updated_at = index.updated_at(&index.read_txn()?)?;
+                        let last_txn_id = index.last_txn_id() as u64;
+                        if !snapshot.register_index(uuid, name, updated_at, last_txn_id) {
+                            continue;
+                        }
+                    }
                     let dst = temp_snapshot_dir.path().join("indexes").join(uuid.to_string());
                     fs::create_dir_all(&dst)?;
-                    index.copy_to_file(dst.join("data.mdb"), CompactionOption::Enabled)?;
+                    let copy = dst.join("data.mdb");
+                    index.copy_to_file(&copy, CompactionOption::Enabled)?;
+                    if let Some(snapshot) = &mut incremental_snapshot {
+                        snapshot.register_index_copy(uuid, &copy)?;
+                    }
                 }
 
                 drop(rtxn);
@@ -793,64 +881,87 @@ impl IndexScheduler {
                 auth.copy_to_file(dst.join("data.mdb"), CompactionOption::Enabled)?;
 
                 // 5. Copy and tarball the flat snapshot
-                // 5.1 Find the original name of the database
-                // TODO find a better way to get this path
-                let mut base_path = self.env.path().to_owned();
-                base_path.pop();
-                let db_name = base_path.file_name().and_then(OsStr::to_str).unwrap_or("data.ms");
-
-                // 5.2 Tarball the content of the snapshot in a tempfile with a .snapshot extension
-                let snapshot_path = self.snapshots_path.join(format!("{}.snapshot", db_name));
-                let temp_snapshot_file = tempfile::NamedTempFile::new_in(&self.snapshots_path)?;
-                compression::to_tar_gz(temp_snapshot_dir.path(), temp_snapshot_file.path())?;
-                let file = temp_snapshot_file.persist(snapshot_path)?;
-
-                // 5.3 Change the permission to make the snapshot readonly
-                let mut permissions = file.metadata()?.permissions();
-                permissions.set_readonly(true);
-                #[cfg(unix)]
-                {
-                    use std::os::unix::fs::PermissionsExt;
-                    #[allow(clippy::non_octal_unix_permissions)]
-                    //                     rwxrwxrwx
-                    permissions.set_mode(0b100100100);
-                }
+                let upload_result = if let Some(incremental_snapshot) = incremental_snapshot {
+                    // 5.1 Add the snapshot to the chain of incremental snapshots
+                    incremental_snapshot.persist(temp_snapshot_dir.path())?;
+
+                    // 5.2 Mirror the chain on the upload backend
+                    self.upload_snapshot_chain(&db_name)
+                } else {
+                    // 5.2 Tarball the content of the snapshot in a tempfile with a .snapshot extension
+                    let snapshot_path = self.snapshots_path.join(format!("{}.snapshot", db_name));
+                    let temp_snapshot_file = tempfile::NamedTempFile::new_in(&self.snapshots_path)?;
+                    compression::to_tar_gz(temp_snapshot_dir.path(), temp_snapshot_file.path())?;
+                    let file = temp_snapshot_file.persist(&snapshot_path)?;
+
+                    // 5.3 Change the permission to make the snapshot readonly
+                    make_readonly(&file)?;
 
-                file.set_permissions(permissions)?;
+                    // 5.4 Upload the snapshot to the upload backend
+                    self.upload_snapshot(&snapshot_path, &db_name)
+                };
 
+                // 6. The snapshot is still available locally when the upload fails,
+                //    the error is reported in the details of the tasks
+                let upload_error = upload_result.err().map(|e| {
+                    tracing::error!("Could not upload the snapshot: {e}");
+                    e.to_string()
+                });
                 for task in &mut tasks {
                     task.status = Status::Succeeded;
+                    if let Some(upload_error) = &upload_error {
+                        task.details = Some(Details::SnapshotCreation {
+                            upload_error: Some(upload_error.clone()),
+                        });
+                    }
                 }
 
                 Ok(tasks)
             }
             Batch::Dump(mut task) => {
                 let started_at = OffsetDateTime::now_utc();
-                let (keys, instance_uid) =
-                    if let KindWithContent::DumpCreation { keys, instance_uid } = &task.kind {
-                        (keys, instance_uid)
-                    } else {
-                        unreachable!();
-                    };
+                let KindWithContent::DumpCreation {
+                    keys,
+                    instance_uid,
+                    index_uids,
+                    include_tasks,
+                    include_keys,
+                } = &task.kind
+                else {
+                    unreachable!();
+                };
                 let dump = dump::DumpWriter::new(*instance_uid)?;
+                let must_dump_index = |uid: &str| {
+                    index_uids.as_ref().map_or(true, |patterns| {
+                        patterns.iter().any(|pattern| pattern.matches_str(uid))
+                    })
+                };
 
-                // 1. dump the keys
+                // 1. dump the keys, the file is always written for the dump to stay valid
                 let mut dump_keys = dump.create_keys()?;
-                for key in keys {
-                    dump_keys.push_key(key)?;
+                if *include_keys {
+                    for key in keys {
+                        dump_keys.push_key(key)?;
+                    }
                 }
                 dump_keys.flush()?;
 
                 let rtxn = self.env.read_txn()?;
 
-                // 2. dump the tasks
+                // 2. dump the tasks, only the ones about the dumped indexes in a partial dump
                 let mut dump_tasks = dump.create_tasks_queue()?;
                 for ret in self.all_tasks.iter(&rtxn)? {
                     if self.must_stop_processing.get() {
                         return Err(Error::AbortedTask);
                     }
+                    if !*include_tasks {
+                        break;
+                    }
 
                     let (_, mut t) = ret?;
+                    if !t.indexes().into_iter().all(must_dump_index) {
+                        continue;
+                    }
                     let status = t.status;
                     let content_file = t.content_uuid();
 
@@ -897,15 +1008,20 @@ impl IndexScheduler {
                 dump_tasks.flush()?;
 
                 // 3. Dump the indexes
-                self.index_mapper.try_for_each_index(&rtxn, |uid, index| -> Result<()> {
+                for uid in self.index_mapper.index_names(&rtxn)? {
+                    if !must_dump_index(&uid) {
+                        continue;
+                    }
+
+                    let index = self.index_mapper.index(&rtxn, &uid)?;
                     let rtxn = index.read_txn()?;
                     let metadata = IndexMetadata {
-                        uid: uid.to_owned(),
+                        uid: uid.clone(),
                         primary_key: index.primary_key(&rtxn)?.map(String::from),
                         created_at: index.created_at(&rtxn)?,
                         updated_at: index.updated_at(&rtxn)?,
                     };
-                    let mut index_dumper = dump.create_index(uid, &metadata)?;
+                    let mut index_dumper = dump.create_index(&uid, &metadata)?;
 
                     let fields_ids_map = index.fields_ids_map(&rtxn)?;
                     let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
@@ -919,7 +1035,8 @@ impl IndexScheduler {
 
                         let (id, doc) = ret?;
 
-                        let mut document = milli::obkv_to_json(&all_fields, &fields_ids_map, doc)?;
+                        let mut document =
+                            milli::obkv_to_json(&all_fields, &fields_ids_map, doc.as_obkv())?;
 
                         'inject_vectors: {
                             let embeddings = index.embeddings(&rtxn, id)?;
@@ -963,10 +1080,10 @@ impl IndexScheduler {
                                     ),
                                     regenerate: !user_provided,
                                 };
-                                vectors.insert(
-                                    embedder_name,
-                                    serde_json::to_value(embeddings).unwrap(),
-                                );
+                                // the vectors kept in the document of a quantized embedder are the original ones
+                                vectors
+                                    .entry(embedder_name)
+                                    .or_insert(serde_json::to_value(embeddings).unwrap());
                             }
                         }
 
@@ -975,13 +1092,12 @@ impl IndexScheduler {
 
                     // 3.2. Dump the settings
                     let settings = meilisearch_types::settings::settings(
-                        index,
+                        &index,
                         &rtxn,
                         meilisearch_types::settings::SecretPolicy::RevealSecrets,
                     )?;
                     index_dumper.settings(&settings)?;
-                    Ok(())
-                })?;
+                }
 
                 // 4. Dump experimental feature settings
                 let features = self.features().runtime_features();
@@ -995,12 +1111,19 @@ impl IndexScheduler {
                     return Err(Error::AbortedTask);
                 }
                 let path = self.dumps_path.join(format!("{}.dump", dump_uid));
-                let file = File::create(path)?;
+                let file = File::create(&path)?;
                 dump.persist_to(BufWriter::new(file))?;
 
+                // 5. Upload the dump to the upload backend, the dump is still available
+                //    locally when the upload fails and the error is reported in the details
+                let upload_error = self.upload_dump(&path, &dump_uid).err().map(|e| {
+                    tracing::error!("Could not upload the dump `{dump_uid}`: {e}");
+                    e.to_string()
+                });
+
                 // if we reached this step we can tell the scheduler we succeeded to dump ourselves.
                 task.status = Status::Succeeded;
-                task.details = Some(Details::Dump { dump_uid: Some(dump_uid) });
+                task.details = Some(Details::Dump { dump_uid: Some(dump_uid), upload_error });
                 Ok(vec![task])
             }
             Batch::IndexOperation { op, must_create_index } => {
@@ -1067,10 +1190,7 @@ impl IndexScheduler {
                     );
                     builder.set_primary_key(primary_key);
                     let must_stop_processing = self.must_stop_processing.clone();
-                    builder.execute(
-                        |indexing_step| tracing::debug!(update = ?indexing_step),
-                        || must_stop_processing.get(),
-                    )?;
+                    builder.execute(self.indexing_progress(), || must_stop_processing.get())?;
                     index_wtxn.commit()?;
                 }
 
@@ -1168,9 +1288,207 @@ impl IndexScheduler {
                 task.status = Status::Succeeded;
                 Ok(vec![task])
             }
+            Batch::DumpImport { mut task } => {
+                let KindWithContent::DumpImport { dump_uid, indexes, overwrite } = &task.kind
+                else {
+                    unreachable!();
+                };
+
+                // 1. Without `overwrite`, the import fails if the name of an index is already used,
+                //    before anything is downloaded
+                let task_uid = task.uid;
+                if !*overwrite {
+                    let rtxn = self.env.read_txn()?;
+                    let mut conflicting_indexes = Vec::new();
+                    for index in indexes {
+                        if self.index_mapper.exists(&rtxn, index.target())? {
+                            conflicting_indexes.push(index.target().to_string());
+                        }
+                    }
+                    if !conflicting_indexes.is_empty() {
+                        return Err(Error::DumpImportIndexesAlreadyExist(conflicting_indexes));
+                    }
+                }
+
+                // 2. Open the dump, it may have to be downloaded from the upload backend
+                let download_dir = tempfile::tempdir_in(&self.dumps_path)?;
+                let path = self
+                    .find_dump(dump_uid, download_dir.path())?
+                    .ok_or_else(|| Error::DumpNotFound(dump_uid.clone()))?;
+                let dump_reader = dump::DumpReader::open(File::open(path)?)?;
+
+                // 3. Import the indexes under temporary names, they are all deleted if it fails.
+                //    The `.` is not allowed in the index uids so they can't conflict with an index of the user.
+                let temporary_uid =
+                    |index: &DumpImportIndex| format!("{}.dump-import-{task_uid}", index.target());
+                let res = self.import_dump_indexes(&dump_reader, dump_uid, indexes, &temporary_uid);
+                let imported_documents = match res {
+                    Ok(imported_documents) => imported_documents,
+                    Err(e) => {
+                        for index in indexes {
+                            self.delete_temporary_index(&temporary_uid(index));
+                        }
+                        return Err(e);
+                    }
+                };
+
+                // 4. Atomically give their final name to the imported indexes, the replaced indexes
+                //    end up under the temporary names and are deleted afterward.
+                let mut wtxn = self.env.write_txn()?;
+                let mut replaced_indexes = Vec::new();
+                for index in indexes {
+                    let temporary_uid = temporary_uid(index);
+                    if self.index_mapper.exists(&wtxn, index.target())? {
+                        self.index_mapper.swap(&mut wtxn, index.target(), &temporary_uid)?;
+                        replaced_indexes.push(temporary_uid);
+                    } else {
+                        self.index_mapper.rename(&mut wtxn, &temporary_uid, index.target())?;
+                    }
+                }
+                wtxn.commit()?;
+                for index_uid in &replaced_indexes {
+                    self.delete_temporary_index(index_uid);
+                }
+
+                let details = Details::DumpImport {
+                    dump_uid: dump_uid.clone(),
+                    indexes: indexes.clone(),
+                    overwrite: *overwrite,
+                    imported_documents: Some(imported_documents),
+                };
+                task.status = Status::Succeeded;
+                task.details = Some(details);
+                Ok(vec![task])
+            }
+        }
+    }
+
+    /// Delete an index created by a dump import, logging the failures.
+    fn delete_temporary_index(&self, index_uid: &str) {
+        let res = self
+            .env
+            .write_txn()
+            .map_err(Error::from)
+            .and_then(|wtxn| self.index_mapper.delete_index(wtxn, index_uid));
+        match res {
+            Ok(()) | Err(Error::IndexNotFound(_)) => (),
+            Err(e) => tracing::error!(
+                error = &e as &dyn std::error::Error,
+                "Could not delete the temporary index `{index_uid}` of the dump import"
+            ),
         }
     }
 
+    /// Import the given indexes of the dump, each under the uid returned by `index_uid`.
+    ///
+    /// ## Return
+    /// The number of imported documents.
+    fn import_dump_indexes(
+        &self,
+        dump_reader: &dump::DumpReader,
+        dump_uid: &str,
+        indexes: &[DumpImportIndex],
+        index_uid: impl Fn(&DumpImportIndex) -> String,
+    ) -> Result<u64> {
+        let indexer_config = self.index_mapper.indexer_config();
+        let must_stop_processing = self.must_stop_processing.clone();
+        let mut imported_indexes = HashSet::new();
+        let mut imported_documents = 0;
+
+        for index_reader in dump_reader.indexes()? {
+            if self.must_stop_processing.get() {
+                return Err(Error::AbortedTask);
+            }
+
+            let mut index_reader = index_reader?;
+            let metadata = index_reader.metadata().clone();
+            let Some(import) = indexes.iter().find(|index| index.index_uid == metadata.uid) else {
+                continue;
+            };
+            let index_uid = index_uid(import);
+            tracing::info!(
+                "Importing the index `{}` of the dump as `{}`.",
+                metadata.uid,
+                import.target()
+            );
+
+            // An index left by a previous attempt of this task must not be reused
+            self.delete_temporary_index(&index_uid);
+            let wtxn = self.env.write_txn()?;
+            let date = Some((metadata.created_at, metadata.updated_at));
+            let index = self.index_mapper.create_index(wtxn, &index_uid, date)?;
+            let mut index_wtxn = index.write_txn()?;
+
+            // Import the primary key and the settings
+            let mut builder = MilliSettings::new(&mut index_wtxn, &index, indexer_config);
+            if let Some(primary_key) = metadata.primary_key {
+                builder.set_primary_key(primary_key);
+            }
+            let settings = index_reader.settings()?;
+            apply_settings_to_builder(&settings, &mut builder);
+            builder.execute(self.indexing_progress(), || must_stop_processing.get())?;
+
+            // Import the documents, they must first be converted to the format accepted by milli
+            let mut builder = DocumentsBatchBuilder::new(BufWriter::new(tempfile::tempfile()?));
+            for document in index_reader.documents()? {
+                builder.append_json_object(&document?)?;
+            }
+            let file = builder.into_inner()?.into_inner().map_err(|e| e.into_error())?;
+            let reader = DocumentsBatchReader::from_reader(BufReader::new(file))
+                .map_err(milli::Error::from)?;
+
+            let embedders = self.embedders(index.embedding_configs(&index_wtxn)?)?;
+            let builder = milli::update::IndexDocuments::new(
+                &mut index_wtxn,
+                &index,
+                indexer_config,
+                IndexDocumentsConfig {
+                    update_method: IndexDocumentsMethod::ReplaceDocuments,
+                    ..Default::default()
+                },
+                self.indexing_progress(),
+                || must_stop_processing.get(),
+            )?;
+            let builder = builder.with_embedders(embedders);
+            let (builder, user_result) = builder.add_documents(reader)?;
+            imported_documents += user_result.map_err(milli::Error::from)?;
+            builder.execute()?;
+            index_wtxn.commit()?;
+
+            // The stats are not critical, failing to store them must not fail the import.
+            let res = || -> Result<()> {
+                let index_rtxn = index.read_txn()?;
+                let stats = crate::index_mapper::IndexStats::new(&index, &index_rtxn)?;
+                let mut wtxn = self.env.write_txn()?;
+                self.index_mapper.store_stats_of(&mut wtxn, &index_uid, &stats)?;
+                wtxn.commit()?;
+                Ok(())
+            }();
+            if let Err(e) = res {
+                tracing::error!(
+                    error = &e as &dyn std::error::Error,
+                    "Could not write the stats of the index"
+                );
+            }
+
+            imported_indexes.insert(metadata.uid);
+        }
+
+        let missing: Vec<_> = indexes
+            .iter()
+            .filter(|index| !imported_indexes.contains(&index.index_uid))
+            .map(|index| index.index_uid.clone())
+            .collect();
+        if !missing.is_empty() {
+            return Err(Error::DumpIndexesNotFound {
+                dump_uid: dump_uid.to_string(),
+                index_uids: missing,
+            });
+        }
+
+        Ok(imported_documents)
+    }
+
     /// Swap the index `lhs` with the index `rhs`.
     fn apply_index_swap(&self, wtxn: &mut RwTxn, task_id: u32, lhs: &str, rhs: &str) -> Result<()> {
         // 1. Verify that both lhs and rhs are existing indexes
@@ -1279,10 +1597,9 @@ impl IndexScheduler {
                             let mut builder =
                                 milli::update::Settings::new(index_wtxn, index, indexer_config);
                             builder.set_primary_key(primary_key);
-                            builder.execute(
-                                |indexing_step| tracing::debug!(update = ?indexing_step),
-                                || must_stop_processing.clone().get(),
-                            )?;
+                            builder.execute(self.indexing_progress(), || {
+                                must_stop_processing.clone().get()
+                            })?;
                             primary_key_has_been_set = true;
                         }
                     }
@@ -1299,7 +1616,7 @@ impl IndexScheduler {
                     index,
                     indexer_config,
                     config,
-                    |indexing_step| tracing::trace!(?indexing_step, "Update"),
+                    self.indexing_progress(),
                     || must_stop_processing.get(),
                 )?;
 
@@ -1378,10 +1695,8 @@ impl IndexScheduler {
                     let mut builder =
                         milli::update::Settings::new(index_wtxn, index, indexer_config);
                     builder.reset_primary_key();
-                    builder.execute(
-                        |indexing_step| tracing::trace!(update = ?indexing_step),
-                        || must_stop_processing.clone().get(),
-                    )?;
+                    builder
+                        .execute(self.indexing_progress(), || must_stop_processing.clone().get())?;
                 }
 
                 Ok(tasks)
@@ -1400,6 +1715,7 @@ impl IndexScheduler {
                     filter,
                     self.index_mapper.indexer_config(),
                     self.must_stop_processing.clone(),
+                    self.indexing_progress(),
                     index,
                 );
                 let original_filter = if let Some(Details::DocumentDeletionByFilter {
@@ -1433,6 +1749,58 @@ impl IndexScheduler {
 
                 Ok(vec![task])
             }
+            IndexOperation::DocumentEdition { mut task, .. } => {
+                let (filter, context, function) =
+                    if let KindWithContent::DocumentEdition {
+                        filter_expr, context, function, ..
+                    } = &task.kind
+                    {
+                        (filter_expr, context, function)
+                    } else {
+                        unreachable!()
+                    };
+
+                let embedder_configs = index.embedding_configs(index_wtxn)?;
+                let embedders = self.embedders(embedder_configs)?;
+
+                // A failed edition must abort the index transaction, as the documents to delete
+                // were already removed from it.
+                let (deleted_documents, edited_documents) = edit_documents_by_function(
+                    index_wtxn,
+                    filter,
+                    context.clone(),
+                    function,
+                    self.index_mapper.indexer_config(),
+                    embedders,
+                    self.must_stop_processing.clone(),
+                    self.indexing_progress(),
+                    index,
+                )?;
+
+                let (original_filter, context, function) = if let Some(Details::DocumentEdition {
+                    original_filter,
+                    context,
+                    function,
+                    ..
+                }) = task.details
+                {
+                    (original_filter, context, function)
+                } else {
+                    // In the case of a `documentEdition` the details MUST be set
+                    unreachable!();
+                };
+
+                task.status = Status::Succeeded;
+                task.details = Some(Details::DocumentEdition {
+                    original_filter,
+                    context,
+                    function,
+                    deleted_documents: Some(deleted_documents),
+                    edited_documents: Some(edited_documents),
+                });
+
+                Ok(vec![task])
+            }
             IndexOperation::Settings { index_uid: _, settings, mut tasks } => {
                 let indexer_config = self.index_mapper.indexer_config();
                 let mut builder = milli::update::Settings::new(index_wtxn, index, indexer_config);
@@ -1448,10 +1816,7 @@ impl IndexScheduler {
                 }
 
                 let must_stop_processing = self.must_stop_processing.clone();
-                builder.execute(
-                    |indexing_step| tracing::debug!(update = ?indexing_step),
-                    || must_stop_processing.get(),
-                )?;
+                builder.execute(self.indexing_progress(), || must_stop_processing.get())?;
 
                 Ok(tasks)
             }
@@ -1543,6 +1908,7 @@ impl IndexScheduler {
         let mut affected_statuses = HashSet::new();
         let mut affected_kinds = HashSet::new();
         let mut affected_canceled_by = RoaringBitmap::new();
+        let mut affected_batches = HashSet::new();
 
         for task_id in to_delete_tasks.iter() {
             let task = self.get_task(wtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
@@ -1564,6 +1930,9 @@ impl IndexScheduler {
             if let Some(canceled_by) = task.canceled_by {
                 affected_canceled_by.insert(canceled_by);
             }
+            if let Some(batch_uid) = task.batch_uid {
+                affected_batches.insert(batch_uid);
+            }
         }
 
         for index in affected_indexes {
@@ -1591,6 +1960,7 @@ impl IndexScheduler {
                 }
             }
         }
+        self.remove_tasks_from_batches(wtxn, affected_batches, &to_delete_tasks)?;
 
         Ok(to_delete_tasks)
     }
@@ -1640,6 +2010,7 @@ fn delete_document_by_filter<'a>(
     filter: &serde_json::Value,
     indexer_config: &IndexerConfig,
     must_stop_processing: MustStopProcessing,
+    progress: impl Fn(UpdateIndexingStep) + Send + Sync,
     index: &'a Index,
 ) -> Result<u64> {
     let filter = Filter::from_json(filter)?;
@@ -1661,7 +2032,7 @@ fn delete_document_by_filter<'a>(
             index,
             indexer_config,
             config,
-            |indexing_step| tracing::debug!(update = ?indexing_step),
+            progress,
             || must_stop_processing.get(),
         )?;
 
@@ -1674,3 +2045,45 @@ fn delete_document_by_filter<'a>(
         0
     })
 }
+
+#[allow(clippy::too_many_arguments)]
+fn edit_documents_by_function<'a>(
+    wtxn: &mut RwTxn<'a>,
+    filter: &Option<serde_json::Value>,
+    context: Option<Object>,
+    code: &str,
+    indexer_config: &IndexerConfig,
+    embedders: EmbeddingConfigs,
+    must_stop_processing: MustStopProcessing,
+    progress: impl Fn(UpdateIndexingStep) + Send + Sync,
+    index: &'a Index,
+) -> Result<(u64, u64)> {
+    let candidates = match filter.as_ref().map(Filter::from_json) {
+        Some(Ok(Some(filter))) => filter.evaluate(wtxn, index).map_err(|err| match err {
+            milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
+                Error::from(err).with_custom_error_code(Code::InvalidDocumentFilter)
+            }
+            e => e.into(),
+        })?,
+        None | Some(Ok(None)) => index.documents_ids(wtxn)?,
+        Some(Err(e)) => return Err(e.into()),
+    };
+
+    let config = IndexDocumentsConfig {
+        update_method: IndexDocumentsMethod::ReplaceDocuments,
+        ..Default::default()
+    };
+
+    let mut builder =
+        milli::update::IndexDocuments::new(wtxn, index, indexer_config, config, progress, || {
+            must_stop_processing.get()
+        })?;
+
+    let (new_builder, user_result) = builder.edit_documents(&candidates, context, code)?;
+    builder = new_builder.with_embedders(embedders);
+
+    let (deleted_documents, edited_documents) = user_result.map_err(milli::Error::from)?;
+
+    let _ = builder.execute()?;
+    Ok((deleted_documents, edited_documents))
+}
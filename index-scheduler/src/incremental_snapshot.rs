@@ -0,0 +1,363 @@
+//! Incremental snapshots only copy the indexes modified since the previous snapshot.
+//!
+//! The snapshots form a chain that starts with a full snapshot, containing every index,
+//! followed by incremental snapshots containing the indexes modified since the previous
+//! snapshot of the chain. Every snapshot of the chain, full or incremental, contains the
+//! version file, the task queue, the update files of the enqueued tasks and the auth env.
+//!
+//! The chain is described by a manifest stored along the snapshots, `{db_name}.snapshot-manifest.json`.
+//! For each snapshot of the chain, it records the SHA-256 of the snapshot file along with the
+//! `updatedAt` and the id of the last committed LMDB transaction of every index existing when
+//! the snapshot was made. An index is only copied when one of them differs from the one recorded
+//! in the last snapshot of the chain, the id of the last transaction making sure that a write not
+//! bumping the `updatedAt` of an index is never missed. The unchanged indexes are not copied at all.
+//!
+//! The SHA-256 of the compacted copy of an index is recorded by the snapshot copying it.
+//!
+//! The manifest is written after the snapshot file it references, so a crash while making a
+//! snapshot never leaves a manifest referencing an incomplete file.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use meilisearch_types::compression;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{Error, IndexScheduler, Result};
+
+/// The number of incremental snapshots following a full snapshot before a new chain is started.
+const MAX_INCREMENTAL_SNAPSHOTS: usize = 10;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotManifest {
+    /// The snapshots of the chain, starting with the full snapshot.
+    pub snapshots: Vec<SnapshotPiece>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotPiece {
+    pub id: u64,
+    pub kind: SnapshotKind,
+    /// The name of the snapshot file, in the same directory as the manifest.
+    pub file_name: String,
+    /// The SHA-256 of the snapshot file, as a lowercase hexadecimal string.
+    pub sha256: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    /// Every index existing when the snapshot was made, by uuid.
+    pub indexes: BTreeMap<Uuid, SnapshotIndex>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SnapshotKind {
+    Full,
+    Incremental,
+}
+
+impl SnapshotKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SnapshotKind::Full => "full",
+            SnapshotKind::Incremental => "incremental",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotIndex {
+    pub uid: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+    /// The id of the last transaction committed to the index when the snapshot was made.
+    ///
+    /// Missing from the manifests written before it was introduced.
+    #[serde(default)]
+    pub last_txn_id: Option<u64>,
+    /// The SHA-256 of the compacted copy of the index made by the snapshot of the chain
+    /// that last copied it, as a lowercase hexadecimal string.
+    ///
+    /// Missing from the manifests written before it was introduced.
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
+impl SnapshotManifest {
+    pub fn path(snapshots_path: &Path, db_name: &str) -> PathBuf {
+        snapshots_path.join(format!("{db_name}.snapshot-manifest.json"))
+    }
+
+    /// Reads the manifest, returns `None` if there is no manifest at this path.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        serde_json::from_reader(BufReader::new(file)).map(Some).map_err(|e| {
+            Error::CorruptedSnapshot(format!("cannot read the manifest `{}`: {e}", path.display()))
+        })
+    }
+
+    fn persist(&self, path: &Path) -> Result<()> {
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+        let mut file = BufWriter::new(tempfile::NamedTempFile::new_in(dir)?);
+        serde_json::to_writer_pretty(&mut file, self).map_err(io::Error::from)?;
+        file.flush()?;
+        file.into_inner().map_err(|e| e.into_error())?.persist(path)?;
+        Ok(())
+    }
+}
+
+/// A snapshot being made, that will be added to the chain of the previous snapshots
+/// or start a new chain.
+pub(crate) struct IncrementalSnapshot {
+    snapshots_path: PathBuf,
+    db_name: String,
+    /// The chain the snapshot is added to, empty when the snapshot starts a new chain.
+    manifest: SnapshotManifest,
+    /// The snapshots of the previous chain, to delete once the new chain is started.
+    obsolete_snapshots: Vec<SnapshotPiece>,
+    indexes: BTreeMap<Uuid, SnapshotIndex>,
+    id: u64,
+}
+
+impl IncrementalSnapshot {
+    pub(crate) fn new(snapshots_path: &Path, db_name: &str) -> Result<Self> {
+        let manifest_path = SnapshotManifest::path(snapshots_path, db_name);
+        let previous = match SnapshotManifest::load(&manifest_path) {
+            Ok(previous) => previous.unwrap_or_default(),
+            Err(e) => {
+                tracing::warn!("Starting a new chain of snapshots: {e}");
+                SnapshotManifest::default()
+            }
+        };
+        let id = previous.snapshots.iter().map(|snapshot| snapshot.id + 1).max().unwrap_or(0);
+
+        // A new chain is started when the previous one is too long or when one of its files is missing.
+        let can_continue = !previous.snapshots.is_empty()
+            && previous.snapshots.len() <= MAX_INCREMENTAL_SNAPSHOTS
+            && previous.snapshots.iter().all(|s| snapshots_path.join(&s.file_name).exists());
+        let (manifest, obsolete_snapshots) = if can_continue {
+            (previous, Vec::new())
+        } else {
+            (SnapshotManifest::default(), previous.snapshots)
+        };
+
+        Ok(Self {
+            snapshots_path: snapshots_path.to_owned(),
+            db_name: db_name.to_owned(),
+            manifest,
+            obsolete_snapshots,
+            indexes: BTreeMap::new(),
+            id,
+        })
+    }
+
+    /// Registers the index in the snapshot and returns whether it must be copied, i.e. whether
+    /// it was modified since the previous snapshot of the chain.
+    ///
+    /// The id of the last transaction is the one of the environment of the index, not the one of
+    /// a copy, as a compacted copy starts over from the first transaction.
+    pub(crate) fn register_index(
+        &mut self,
+        uuid: Uuid,
+        uid: &str,
+        updated_at: OffsetDateTime,
+        last_txn_id: u64,
+    ) -> bool {
+        let last_txn_id = Some(last_txn_id);
+        let unchanged = self.manifest.snapshots.last().and_then(|previous| {
+            previous.indexes.get(&uuid).filter(|previous| {
+                previous.updated_at == updated_at && previous.last_txn_id == last_txn_id
+            })
+        });
+        let sha256 = unchanged.and_then(|previous| previous.sha256.clone());
+        let modified = unchanged.is_none();
+        self.indexes
+            .insert(uuid, SnapshotIndex { uid: uid.to_owned(), updated_at, last_txn_id, sha256 });
+        modified
+    }
+
+    /// Records the SHA-256 of the compacted copy of an index registered as modified.
+    pub(crate) fn register_index_copy(&mut self, uuid: Uuid, copy_path: &Path) -> Result<()> {
+        let sha256 = sha256_of_file(copy_path)?;
+        if let Some(index) = self.indexes.get_mut(&uuid) {
+            index.sha256 = Some(sha256);
+        }
+        Ok(())
+    }
+
+    /// Tarballs the content of the snapshot directory and adds it to the chain.
+    pub(crate) fn persist(mut self, snapshot_dir: &Path) -> Result<()> {
+        let kind = if self.manifest.snapshots.is_empty() {
+            SnapshotKind::Full
+        } else {
+            SnapshotKind::Incremental
+        };
+        let file_name = format!("{}.{}.{}.snapshot", self.db_name, self.id, kind.as_str());
+
+        let temp_snapshot_file = tempfile::NamedTempFile::new_in(&self.snapshots_path)?;
+        compression::to_tar_gz(snapshot_dir, temp_snapshot_file.path())?;
+        let sha256 = sha256_of_file(temp_snapshot_file.path())?;
+        let file = temp_snapshot_file.persist(self.snapshots_path.join(&file_name))?;
+        make_readonly(&file)?;
+
+        self.manifest.snapshots.push(SnapshotPiece {
+            id: self.id,
+            kind,
+            file_name,
+            sha256,
+            created_at: OffsetDateTime::now_utc(),
+            indexes: self.indexes,
+        });
+        self.manifest.persist(&SnapshotManifest::path(&self.snapshots_path, &self.db_name))?;
+
+        for snapshot in self.obsolete_snapshots {
+            match fs::remove_file(self.snapshots_path.join(&snapshot.file_name)) {
+                Ok(()) => (),
+                Err(e) if e.kind() == ErrorKind::NotFound => (),
+                Err(e) => {
+                    tracing::warn!("Could not delete the snapshot `{}`: {e}", snapshot.file_name)
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl IndexScheduler {
+    /// The original name of the database, used to name the snapshots.
+    pub(crate) fn snapshot_db_name(&self) -> String {
+        // TODO find a better way to get this path
+        let mut base_path = self.env.path().to_owned();
+        base_path.pop();
+        base_path.file_name().and_then(|name| name.to_str()).unwrap_or("data.ms").to_owned()
+    }
+}
+
+/// Makes the snapshot file readonly.
+pub(crate) fn make_readonly(file: &File) -> Result<()> {
+    let mut permissions = file.metadata()?.permissions();
+    permissions.set_readonly(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        #[allow(clippy::non_octal_unix_permissions)]
+        //                     rwxrwxrwx
+        permissions.set_mode(0b100100100);
+    }
+
+    file.set_permissions(permissions)?;
+    Ok(())
+}
+
+fn sha256_of_file(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hasher.finalize().iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+/// Restores the chain of snapshots described by the manifest into the database directory.
+///
+/// The checksum of every snapshot of the chain is verified before anything is extracted.
+pub fn import_snapshot_chain(manifest_path: &Path, db_path: &Path) -> Result<()> {
+    let manifest = SnapshotManifest::load(manifest_path)?.ok_or_else(|| {
+        Error::CorruptedSnapshot(format!(
+            "the manifest `{}` does not exist",
+            manifest_path.display()
+        ))
+    })?;
+    let snapshots_path = manifest_path.parent().unwrap_or_else(|| Path::new("."));
+
+    let (full, incrementals) = match manifest.snapshots.split_first() {
+        Some((full, incrementals))
+            if full.kind == SnapshotKind::Full
+                && incrementals.iter().all(|s| s.kind == SnapshotKind::Incremental) =>
+        {
+            (full, incrementals)
+        }
+        _ => {
+            return Err(Error::CorruptedSnapshot(String::from(
+                "the chain must start with a full snapshot followed by incremental snapshots",
+            )))
+        }
+    };
+
+    for snapshot in &manifest.snapshots {
+        let path = snapshots_path.join(&snapshot.file_name);
+        if !path.exists() {
+            return Err(Error::CorruptedSnapshot(format!(
+                "the snapshot `{}` is missing",
+                snapshot.file_name
+            )));
+        }
+        let sha256 = sha256_of_file(&path)?;
+        if sha256 != snapshot.sha256 {
+            return Err(Error::CorruptedSnapshot(format!(
+                "the snapshot `{}` has the checksum `{sha256}` instead of `{}`",
+                snapshot.file_name, snapshot.sha256
+            )));
+        }
+    }
+
+    compression::from_tar_gz(snapshots_path.join(&full.file_name), db_path)?;
+
+    let indexes_path = db_path.join("indexes");
+    for snapshot in incrementals {
+        // Extracted in the database directory to move the files instead of copying them.
+        let temp_snapshot_dir = tempfile::tempdir_in(db_path)?;
+        compression::from_tar_gz(snapshots_path.join(&snapshot.file_name), &temp_snapshot_dir)?;
+
+        // Everything but the indexes is fully contained in each snapshot.
+        for entry in fs::read_dir(&temp_snapshot_dir)? {
+            let entry = entry?;
+            if entry.file_name() != "indexes" {
+                replace(&entry.path(), &db_path.join(entry.file_name()))?;
+            }
+        }
+
+        let snapshot_indexes_path = temp_snapshot_dir.path().join("indexes");
+        if snapshot_indexes_path.exists() {
+            fs::create_dir_all(&indexes_path)?;
+            for entry in fs::read_dir(snapshot_indexes_path)? {
+                let entry = entry?;
+                replace(&entry.path(), &indexes_path.join(entry.file_name()))?;
+            }
+        }
+    }
+
+    // Remove the indexes deleted since the full snapshot.
+    let last = manifest.snapshots.last().unwrap_or(full);
+    if indexes_path.exists() {
+        for entry in fs::read_dir(&indexes_path)? {
+            let entry = entry?;
+            let uuid = entry.file_name().to_str().and_then(|name| Uuid::parse_str(name).ok());
+            if uuid.map_or(true, |uuid| !last.indexes.contains_key(&uuid)) {
+                fs::remove_dir_all(entry.path())?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Moves `src` to `dst`, removing what was at `dst`.
+fn replace(src: &Path, dst: &Path) -> io::Result<()> {
+    let removed = if dst.is_dir() { fs::remove_dir_all(dst) } else { fs::remove_file(dst) };
+    match removed {
+        Ok(()) => (),
+        Err(e) if e.kind() == ErrorKind::NotFound => (),
+        Err(e) => return Err(e),
+    }
+    fs::rename(src, dst)
+}
@@ -0,0 +1,42 @@
+//! Counts the writes made to each index so that anything derived from the content of an index,
+//! like cached search results, can tell whether it is still up to date.
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering::Relaxed;
+use std::sync::RwLock;
+
+#[derive(Debug, Default)]
+pub(crate) struct IndexGenerations {
+    /// Bumped by the batches that can modify several indexes, the index swaps and dump imports.
+    global: AtomicU64,
+    /// Bumped by the batches of a single index.
+    indexes: RwLock<HashMap<String, u64>>,
+}
+
+impl IndexGenerations {
+    /// The generation of the index, it only ever increases and does so after each write.
+    ///
+    /// Both counters are monotonic so their sum is always greater than the generation
+    /// returned before any previous write, even though they are not read atomically.
+    pub fn get(&self, index_uid: &str) -> u64 {
+        let global = self.global.load(Relaxed);
+        let index = self.indexes.read().unwrap().get(index_uid).copied().unwrap_or_default();
+        global + index
+    }
+
+    /// Bumps the generation of the given index, or of all the indexes when `None`.
+    ///
+    /// Must only be called when the indexes were modified, as it invalidates everything
+    /// derived from their content.
+    pub fn bump(&self, index_uid: Option<&str>) {
+        match index_uid {
+            Some(index_uid) => {
+                *self.indexes.write().unwrap().entry(index_uid.to_string()).or_default() += 1
+            }
+            None => {
+                self.global.fetch_add(1, Relaxed);
+            }
+        }
+    }
+}
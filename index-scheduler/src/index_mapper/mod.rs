@@ -19,8 +19,8 @@ use crate::{Error, Result};
 
 mod index_map;
 
-const INDEX_MAPPING: &str = "index-mapping";
-const INDEX_STATS: &str = "index-stats";
+pub(crate) const INDEX_MAPPING: &str = "index-mapping";
+pub(crate) const INDEX_STATS: &str = "index-stats";
 
 /// Structure managing meilisearch's indexes.
 ///
@@ -441,6 +441,22 @@ impl IndexMapper {
         Ok(())
     }
 
+    /// Rename an index, the new name must not be used by another index.
+    pub fn rename(&self, wtxn: &mut RwTxn, from: &str, to: &str) -> Result<()> {
+        let uuid = self
+            .index_mapping
+            .get(wtxn, from)?
+            .ok_or_else(|| Error::IndexNotFound(from.to_string()))?;
+        if self.index_mapping.get(wtxn, to)?.is_some() {
+            return Err(Error::IndexAlreadyExists(to.to_string()));
+        }
+
+        self.index_mapping.delete(wtxn, from)?;
+        self.index_mapping.put(wtxn, to, &uuid)?;
+
+        Ok(())
+    }
+
     /// The stats of an index.
     ///
     /// If available in the cache, they are directly returned.
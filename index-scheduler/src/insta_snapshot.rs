@@ -28,13 +28,20 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
         enqueued_at,
         started_at,
         finished_at,
+        all_batches: _,
+        batch_to_tasks_mapping: _,
+        batch_metadata: _,
         index_mapper,
+        index_generations: _,
         features: _,
         max_number_of_tasks: _,
         max_number_of_batched_tasks: _,
         wake_up: _,
         dumps_path: _,
         snapshots_path: _,
+        incremental_snapshots: _,
+        upload_backend: _,
+        upload_retention: _,
         auth_path: _,
         version_file_path: _,
         webhook_url: _,
@@ -148,6 +155,7 @@ pub fn snapshot_task(task: &Task) -> String {
         error,
         canceled_by,
         details,
+        batch_uid: _,
         status,
         kind,
     } = task;
@@ -190,6 +198,9 @@ fn snapshot_details(d: &Details) -> String {
         Details::DocumentDeletionByFilter { original_filter, deleted_documents } => format!(
            "{{ original_filter: {original_filter}, deleted_documents: {deleted_documents:?} }}"
         ),
+        Details::DocumentEdition { deleted_documents, edited_documents, original_filter, context, function } => format!(
+           "{{ deleted_documents: {deleted_documents:?}, edited_documents: {edited_documents:?}, context: {context:?}, function: {function:?}, original_filter: {original_filter:?} }}"
+        ),
         Details::ClearAll { deleted_documents } => {
             format!("{{ deleted_documents: {deleted_documents:?} }}")
         },
@@ -207,12 +218,18 @@ fn snapshot_details(d: &Details) -> String {
         } => {
             format!("{{ matched_tasks: {matched_tasks:?}, deleted_tasks: {deleted_tasks:?}, original_filter: {original_filter:?} }}")
         },
-        Details::Dump { dump_uid } => {
-            format!("{{ dump_uid: {dump_uid:?} }}")
+        Details::Dump { dump_uid, upload_error } => {
+            format!("{{ dump_uid: {dump_uid:?}, upload_error: {upload_error:?} }}")
+        },
+        Details::SnapshotCreation { upload_error } => {
+            format!("{{ upload_error: {upload_error:?} }}")
         },
         Details::IndexSwap { swaps } => {
             format!("{{ swaps: {swaps:?} }}")
         }
+        Details::DumpImport { dump_uid, indexes, overwrite, imported_documents } => {
+            format!("{{ dump_uid: {dump_uid:?}, indexes: {indexes:?}, overwrite: {overwrite}, imported_documents: {imported_documents:?} }}")
+        }
     }
 }
 
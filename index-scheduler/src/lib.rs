@@ -22,10 +22,13 @@ mod autobatcher;
 mod batch;
 pub mod error;
 mod features;
+mod incremental_snapshot;
+mod index_generations;
 mod index_mapper;
 #[cfg(test)]
 mod insta_snapshot;
 mod lru;
+mod upload;
 mod utils;
 pub mod uuid_codec;
 
@@ -34,12 +37,13 @@ pub type TaskId = u32;
 
 use std::collections::{BTreeMap, HashMap};
 use std::io::{self, BufReader, Read};
+use std::num::NonZeroUsize;
 use std::ops::{Bound, RangeBounds};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::Ordering::{self, Relaxed};
 use std::sync::atomic::{AtomicBool, AtomicU32};
 use std::sync::{Arc, RwLock};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use dump::{KindDump, TaskDump, UpdateFile};
 pub use error::Error;
@@ -47,6 +51,10 @@ pub use features::RoFeatures;
 use file_store::FileStore;
 use flate2::bufread::GzEncoder;
 use flate2::Compression;
+pub use incremental_snapshot::{
+    import_snapshot_chain, SnapshotIndex, SnapshotKind, SnapshotManifest, SnapshotPiece,
+};
+use meilisearch_types::batches::{Batch, BatchId, BatchStats, BatchStep};
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::features::{InstanceTogglableFeatures, RuntimeTogglableFeatures};
 use meilisearch_types::heed::byteorder::BE;
@@ -54,20 +62,25 @@ use meilisearch_types::heed::types::{SerdeBincode, SerdeJson, Str, I128};
 use meilisearch_types::heed::{self, Database, Env, PutFlags, RoTxn, RwTxn};
 use meilisearch_types::milli::documents::DocumentsBatchBuilder;
 use meilisearch_types::milli::index::IndexEmbeddingConfig;
-use meilisearch_types::milli::update::IndexerConfig;
+use meilisearch_types::milli::update::{IndexerConfig, UpdateIndexingStep};
 use meilisearch_types::milli::vector::{Embedder, EmbedderOptions, EmbeddingConfigs};
 use meilisearch_types::milli::{self, CboRoaringBitmapCodec, Index, RoaringBitmapCodec, BEU32};
 use meilisearch_types::task_view::TaskView;
-use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task, TaskProgress};
 use rayon::current_num_threads;
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 use roaring::RoaringBitmap;
 use synchronoise::SignalEvent;
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
+pub use upload::{
+    download_from_backend, LocalBackend, S3Backend, S3Config, UploadBackend, DUMPS_DIR,
+    SNAPSHOTS_DIR,
+};
 use utils::{filter_out_references_to_newer_tasks, keep_tasks_within_datetimes, map_bound};
 use uuid::Uuid;
 
+use crate::index_generations::IndexGenerations;
 use crate::index_mapper::IndexMapper;
 use crate::utils::{check_index_swap_validity, clamp_to_page_size};
 
@@ -99,6 +112,8 @@ pub struct Query {
     pub index_uids: Option<Vec<String>>,
     /// The [task ids](`meilisearch_types::tasks::Task::uid`) to be matched
     pub uids: Option<Vec<TaskId>>,
+    /// The [batch ids](`meilisearch_types::tasks::Task::batch_uid`) of the matched tasks
+    pub batch_uids: Option<Vec<BatchId>>,
     /// The [task ids](`meilisearch_types::tasks::Task::uid`) of the [`TaskCancelation`](meilisearch_types::tasks::Task::Kind::TaskCancelation) tasks
     /// that canceled the matched tasks.
     pub canceled_by: Option<Vec<TaskId>>,
@@ -129,6 +144,7 @@ impl Query {
                 types: None,
                 index_uids: None,
                 uids: None,
+                batch_uids: None,
                 canceled_by: None,
                 before_enqueued_at: None,
                 after_enqueued_at: None,
@@ -160,22 +176,75 @@ struct ProcessingTasks {
     started_at: OffsetDateTime,
     /// The list of tasks ids that are currently running.
     processing: RoaringBitmap,
+    /// The steps of the processing batch that are over, with the time spent in each of them.
+    steps: Vec<BatchStep>,
+    /// The name of the step the processing batch is currently in, and when it started.
+    current_step: Option<(String, Instant)>,
+    /// The progress of the indexation reported by milli, if any.
+    progress: Option<TaskProgress>,
 }
 
 impl ProcessingTasks {
     /// Creates an empty `ProcessingAt` struct.
     fn new() -> ProcessingTasks {
-        ProcessingTasks { started_at: OffsetDateTime::now_utc(), processing: RoaringBitmap::new() }
+        ProcessingTasks {
+            started_at: OffsetDateTime::now_utc(),
+            processing: RoaringBitmap::new(),
+            steps: Vec::new(),
+            current_step: None,
+            progress: None,
+        }
     }
 
     /// Stores the currently processing tasks, and the date time at which it started.
     fn start_processing_at(&mut self, started_at: OffsetDateTime, processing: RoaringBitmap) {
         self.started_at = started_at;
         self.processing = processing;
+        self.steps.clear();
+        self.current_step = None;
+        self.progress = None;
+    }
+
+    /// Ends the current step of the processing batch, if any, and starts a new one.
+    fn start_step(&mut self, name: impl Into<String>) {
+        self.finish_current_step();
+        self.current_step = Some((name.into(), Instant::now()));
+    }
+
+    /// Ends the current step, the time spent in a step the batch already went through
+    /// (e.g. when several document additions are indexed) is added to the existing one.
+    fn finish_current_step(&mut self) {
+        if let Some((name, started_at)) = self.current_step.take() {
+            let duration = started_at.elapsed();
+            match self.steps.iter_mut().find(|step| step.name == name) {
+                Some(step) => step.duration += duration,
+                None => self.steps.push(BatchStep { name, duration }),
+            }
+        }
+    }
+
+    /// Ends the current step and returns all the steps the processing batch went through.
+    fn take_steps(&mut self) -> Vec<BatchStep> {
+        self.finish_current_step();
+        std::mem::take(&mut self.steps)
+    }
+
+    /// Registers a new indexing step reported by milli for the processing batch,
+    /// starting a new step of the batch when milli moves on to another one.
+    fn update_progress(&mut self, step: UpdateIndexingStep) {
+        let progress = self.progress.get_or_insert_with(TaskProgress::default);
+        progress.update(step);
+        let name = progress.current_step;
+        if !name.is_empty()
+            && self.current_step.as_ref().map_or(true, |(current, _)| current != name)
+        {
+            self.start_step(name);
+        }
     }
 
     /// Set the processing tasks to an empty list
     fn stop_processing(&mut self) -> RoaringBitmap {
+        self.progress = None;
         std::mem::take(&mut self.processing)
     }
 
@@ -212,8 +281,33 @@ mod db_name {
     pub const ENQUEUED_AT: &str = "enqueued-at";
     pub const STARTED_AT: &str = "started-at";
     pub const FINISHED_AT: &str = "finished-at";
+    pub const ALL_BATCHES: &str = "all-batches";
+    pub const BATCH_TO_TASKS_MAPPING: &str = "batch-to-tasks-mapping";
+    pub const BATCH_METADATA: &str = "batch-metadata";
+
+    /// All the databases of the tasks environment, including the ones of the index mapper
+    /// and of the features. The environment can't open more databases than listed here.
+    pub const ALL: &[&str] = &[
+        ALL_TASKS,
+        STATUS,
+        KIND,
+        INDEX_TASKS,
+        CANCELED_BY,
+        ENQUEUED_AT,
+        STARTED_AT,
+        FINISHED_AT,
+        ALL_BATCHES,
+        BATCH_TO_TASKS_MAPPING,
+        BATCH_METADATA,
+        crate::index_mapper::INDEX_MAPPING,
+        crate::index_mapper::INDEX_STATS,
+        crate::features::EXPERIMENTAL_FEATURES,
+    ];
 }
 
+/// The key under which the id of the next batch is stored in the batch metadata database.
+const NEXT_BATCH_ID_KEY: &str = "next-batch-id";
+
 #[cfg(test)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Breakpoint {
@@ -244,8 +338,14 @@ pub struct IndexSchedulerOptions {
     pub indexes_path: PathBuf,
     /// The path to the folder containing the snapshots.
     pub snapshots_path: PathBuf,
+    /// Whether the snapshots only copy the indexes modified since the previous snapshot.
+    pub incremental_snapshots: bool,
     /// The path to the folder containing the dumps.
     pub dumps_path: PathBuf,
+    /// The backend receiving a copy of the snapshots and dumps.
+    pub upload_backend: Option<Arc<dyn UploadBackend>>,
+    /// The number of snapshots and dumps kept on the upload backend, all of them when `None`.
+    pub upload_retention: Option<NonZeroUsize>,
     /// The URL on which we must send the tasks statuses
     pub webhook_url: Option<String>,
     /// The value we will send into the Authorization HTTP header on the webhook URL
@@ -316,9 +416,19 @@ pub struct IndexScheduler {
     /// Store the task ids of tasks which finished at a specific date
     pub(crate) finished_at: Database<BEI128, CboRoaringBitmapCodec>,
 
+    /// Contains all the processed batches accessible by their Id.
+    pub(crate) all_batches: Database<BEU32, SerdeJson<Batch>>,
+    /// Store the task ids of the tasks that were processed in a batch.
+    pub(crate) batch_to_tasks_mapping: Database<BEU32, RoaringBitmapCodec>,
+    /// Store the id of the next batch, so that the ids of deleted batches are never reused.
+    pub(crate) batch_metadata: Database<Str, BEU32>,
+
     /// In charge of creating, opening, storing and returning indexes.
     pub(crate) index_mapper: IndexMapper,
 
+    /// Counts the writes made to each index.
+    pub(crate) index_generations: Arc<IndexGenerations>,
+
     /// In charge of fetching and setting the status of experimental features.
     features: features::FeatureData,
 
@@ -349,6 +459,15 @@ pub struct IndexScheduler {
     /// The path used to create the snapshots.
     pub(crate) snapshots_path: PathBuf,
 
+    /// Whether the snapshots only copy the indexes modified since the previous snapshot.
+    pub(crate) incremental_snapshots: bool,
+
+    /// The backend receiving a copy of the snapshots and dumps.
+    pub(crate) upload_backend: Option<Arc<dyn UploadBackend>>,
+
+    /// The number of snapshots and dumps kept on the upload backend.
+    pub(crate) upload_retention: Option<NonZeroUsize>,
+
     /// The path to the folder containing the auth LMDB env.
     pub(crate) auth_path: PathBuf,
 
@@ -391,13 +510,20 @@ impl IndexScheduler {
             enqueued_at: self.enqueued_at,
             started_at: self.started_at,
             finished_at: self.finished_at,
+            all_batches: self.all_batches,
+            batch_to_tasks_mapping: self.batch_to_tasks_mapping,
+            batch_metadata: self.batch_metadata,
             index_mapper: self.index_mapper.clone(),
+            index_generations: self.index_generations.clone(),
             wake_up: self.wake_up.clone(),
             autobatching_enabled: self.autobatching_enabled,
             cleanup_enabled: self.cleanup_enabled,
             max_number_of_tasks: self.max_number_of_tasks,
             max_number_of_batched_tasks: self.max_number_of_batched_tasks,
             snapshots_path: self.snapshots_path.clone(),
+            incremental_snapshots: self.incremental_snapshots,
+            upload_backend: self.upload_backend.clone(),
+            upload_retention: self.upload_retention,
             dumps_path: self.dumps_path.clone(),
             auth_path: self.auth_path.clone(),
             version_file_path: self.version_file_path.clone(),
@@ -450,7 +576,7 @@ impl IndexScheduler {
 
         let env = unsafe {
             heed::EnvOpenOptions::new()
-                .max_dbs(11)
+                .max_dbs(db_name::ALL.len() as u32)
                 .map_size(budget.task_db_size)
                 .open(options.tasks_path)
         }?;
@@ -468,6 +594,10 @@ impl IndexScheduler {
         let enqueued_at = env.create_database(&mut wtxn, Some(db_name::ENQUEUED_AT))?;
         let started_at = env.create_database(&mut wtxn, Some(db_name::STARTED_AT))?;
         let finished_at = env.create_database(&mut wtxn, Some(db_name::FINISHED_AT))?;
+        let all_batches = env.create_database(&mut wtxn, Some(db_name::ALL_BATCHES))?;
+        let batch_to_tasks_mapping =
+            env.create_database(&mut wtxn, Some(db_name::BATCH_TO_TASKS_MAPPING))?;
+        let batch_metadata = env.create_database(&mut wtxn, Some(db_name::BATCH_METADATA))?;
         wtxn.commit()?;
 
         // allow unreachable_code to get rids of the warning in the case of a test build.
@@ -483,6 +613,9 @@ impl IndexScheduler {
             enqueued_at,
             started_at,
             finished_at,
+            all_batches,
+            batch_to_tasks_mapping,
+            batch_metadata,
             index_mapper: IndexMapper::new(
                 &env,
                 options.indexes_path,
@@ -492,6 +625,7 @@ impl IndexScheduler {
                 options.enable_mdb_writemap,
                 options.indexer_config,
             )?,
+            index_generations: Arc::default(),
             env,
             // we want to start the loop right away in case meilisearch was ctrl+Ced while processing things
             wake_up: Arc::new(SignalEvent::auto(true)),
@@ -501,6 +635,9 @@ impl IndexScheduler {
             max_number_of_batched_tasks: options.max_number_of_batched_tasks,
             dumps_path: options.dumps_path,
             snapshots_path: options.snapshots_path,
+            incremental_snapshots: options.incremental_snapshots,
+            upload_backend: options.upload_backend,
+            upload_retention: options.upload_retention,
             auth_path: options.auth_path,
             version_file_path: options.version_file_path,
             webhook_url: options.webhook_url,
@@ -663,6 +800,14 @@ impl IndexScheduler {
         self.index_mapper.index(&rtxn, name)
     }
 
+    /// Return the generation of an index, a number increasing after each write made to the index.
+    ///
+    /// Anything computed from the content of an index at a given generation is still valid
+    /// as long as the generation stays the same.
+    pub fn index_generation(&self, name: &str) -> u64 {
+        self.index_generations.get(name)
+    }
+
     /// Return the name of all indexes without opening them.
     pub fn index_names(&self) -> Result<Vec<String>> {
         let rtxn = self.env.read_txn()?;
@@ -721,6 +866,16 @@ impl IndexScheduler {
             tasks &= &uids;
         }
 
+        if let Some(batch_uids) = &query.batch_uids {
+            let mut batch_tasks = RoaringBitmap::new();
+            for batch_uid in batch_uids {
+                if let Some(tasks) = self.batch_to_tasks_mapping.get(rtxn, batch_uid)? {
+                    batch_tasks |= tasks;
+                }
+            }
+            tasks &= batch_tasks;
+        }
+
         if let Some(canceled_by) = &query.canceled_by {
             let mut all_canceled_tasks = RoaringBitmap::new();
             for cancel_task_uid in canceled_by {
@@ -967,6 +1122,114 @@ impl IndexScheduler {
         }
     }
 
+    /// Returns the indexing progress of the given task if it is currently processing.
+    pub fn get_task_progress(&self, task_id: TaskId) -> Result<Option<TaskProgress>> {
+        let processing_tasks =
+            self.processing_tasks.read().map_err(|_| Error::CorruptedTaskQueue)?;
+        if processing_tasks.processing.contains(task_id) {
+            Ok(processing_tasks.progress.clone())
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns a callback for milli that logs the indexing steps and reports them
+    /// as the progress of the processing batch.
+    fn indexing_progress(&self) -> impl Fn(UpdateIndexingStep) + Send + Sync {
+        let processing_tasks = self.processing_tasks.clone();
+        move |indexing_step| {
+            tracing::debug!(update = ?indexing_step);
+            if let Ok(mut processing_tasks) = processing_tasks.write() {
+                processing_tasks.update_progress(indexing_step);
+            }
+        }
+    }
+
+    /// Return the batches containing at least one of the tasks matched by the query from the
+    /// user's point of view with the ids of all the tasks they contain, along with the total
+    /// number of such batches, ignoring from and limit.
+    ///
+    /// The `batch_uids`, `from` and `limit` parameters of the query are applied to the batch
+    /// ids while all the other parameters filter the tasks the batches contain.
+    pub fn get_batches_from_authorized_indexes(
+        &self,
+        query: Query,
+        filters: &meilisearch_auth::AuthFilter,
+    ) -> Result<(Vec<(Batch, RoaringBitmap)>, u64)> {
+        let rtxn = self.env.read_txn()?;
+
+        let tasks_query = Query { from: None, limit: None, ..query.clone() };
+        let (tasks, _) = self.get_task_ids_from_authorized_indexes(&rtxn, &tasks_query, filters)?;
+
+        let from = query.from.unwrap_or(BatchId::MAX);
+        let limit = query.limit.unwrap_or(u32::MAX) as usize;
+        let matching_batch = |batch_uid: BatchId, batch_tasks: RoaringBitmap| -> Result<_> {
+            if batch_tasks.is_disjoint(&tasks) {
+                return Ok(None);
+            }
+            let batch = self.get_batch(&rtxn, batch_uid)?.ok_or(Error::CorruptedTaskQueue)?;
+            Ok(Some((batch, batch_tasks)))
+        };
+
+        // the batches are visited from the most recent one until the page is full
+        let mut batches = Vec::new();
+        match &query.batch_uids {
+            Some(batch_uids) => {
+                let mut batch_uids: Vec<_> =
+                    batch_uids.iter().copied().filter(|uid| *uid <= from).collect();
+                batch_uids.sort_unstable_by(|a, b| b.cmp(a));
+                batch_uids.dedup();
+                for batch_uid in batch_uids {
+                    if batches.len() == limit {
+                        break;
+                    }
+                    let Some(batch_tasks) = self.batch_to_tasks_mapping.get(&rtxn, &batch_uid)?
+                    else {
+                        continue;
+                    };
+                    batches.extend(matching_batch(batch_uid, batch_tasks)?);
+                }
+            }
+            None => {
+                for result in self.batch_to_tasks_mapping.rev_range(&rtxn, &(..=from))? {
+                    if batches.len() == limit {
+                        break;
+                    }
+                    let (batch_uid, batch_tasks) = result?;
+                    batches.extend(matching_batch(batch_uid, batch_tasks)?);
+                }
+            }
+        }
+
+        let total = self.count_batches_of_tasks(&rtxn, &tasks)?;
+
+        Ok((batches, total))
+    }
+
+    /// Counts the batches containing at least one of the given tasks.
+    ///
+    /// When there are fewer tasks than batches, the batch ids are read from the tasks
+    /// instead of going through all the batches.
+    fn count_batches_of_tasks(&self, rtxn: &RoTxn, tasks: &RoaringBitmap) -> Result<u64> {
+        if tasks.len() < self.all_batches.len(rtxn)? {
+            let mut batches = RoaringBitmap::new();
+            for task_id in tasks {
+                let task = self.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+                batches.extend(task.batch_uid);
+            }
+            return Ok(batches.len());
+        }
+
+        let mut count = 0;
+        for result in self.batch_to_tasks_mapping.iter(rtxn)? {
+            let (_, batch_tasks) = result?;
+            if !batch_tasks.is_disjoint(tasks) {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
     /// Register a new task in the scheduler.
     ///
     /// If it fails and data was associated with the task, it tries to delete the associated data.
@@ -1001,6 +1264,7 @@ impl IndexScheduler {
             error: None,
             canceled_by: None,
             details: kind.default_details(),
+            batch_uid: None,
             status: Status::Enqueued,
             kind: kind.clone(),
         };
@@ -1136,6 +1400,8 @@ impl IndexScheduler {
                 None => return Ok(TickOutcome::WaitForSignal),
             };
         let index_uid = batch.index_uid().map(ToOwned::to_owned);
+        let modifies_several_indexes = batch.modifies_several_indexes();
+        let batch_kind = batch.kind_name();
         drop(rtxn);
 
         // 1. store the starting date with the bitmap of processing tasks.
@@ -1151,6 +1417,7 @@ impl IndexScheduler {
         self.breakpoint(Breakpoint::BatchCreated);
 
         // 2. Process the tasks
+        self.processing_tasks.write().unwrap().start_step("processing tasks");
         let res = {
             let cloned_index_scheduler = self.private_clone();
             let handle = std::thread::Builder::new()
@@ -1160,6 +1427,12 @@ impl IndexScheduler {
             handle.join().unwrap_or(Err(Error::ProcessBatchPanicked))
         };
 
+        // Invalidate everything derived from the content of the modified indexes,
+        // the batches without an index like the snapshots and dumps don't modify any index.
+        if res.is_ok() && (index_uid.is_some() || modifies_several_indexes) {
+            self.index_generations.bump(index_uid.as_deref());
+        }
+
         // Reset the currently updating index to relinquish the index handle
         self.index_mapper.set_currently_updating_index(None);
 
@@ -1167,8 +1440,12 @@ impl IndexScheduler {
         self.maybe_fail(tests::FailureLocation::AcquiringWtxn)?;
 
         let mut wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
+        self.processing_tasks.write().unwrap().start_step("updating tasks");
 
         let finished_at = OffsetDateTime::now_utc();
+        let batch_uid = self.next_batch_id(&wtxn)?;
+        let mut batch_tasks = RoaringBitmap::new();
+        let mut batch_stats = BatchStats::default();
         match res {
             Ok(tasks) => {
                 #[cfg(test)]
@@ -1181,6 +1458,7 @@ impl IndexScheduler {
                 for (i, mut task) in tasks.into_iter().enumerate() {
                     task.started_at = Some(started_at);
                     task.finished_at = Some(finished_at);
+                    task.batch_uid = Some(batch_uid);
 
                     #[cfg(test)]
                     self.maybe_fail(
@@ -1194,6 +1472,8 @@ impl IndexScheduler {
                         None => success += 1,
                     }
 
+                    batch_tasks.insert(task.uid);
+                    batch_stats.register(&task);
                     self.update_task(&mut wtxn, &task)
                         .map_err(|e| Error::TaskDatabaseUpdate(Box::new(e)))?;
                 }
@@ -1249,18 +1529,41 @@ impl IndexScheduler {
                     task.status = Status::Failed;
                     task.error = Some(error.clone());
                     task.details = task.details.map(|d| d.to_failed());
+                    task.batch_uid = Some(batch_uid);
 
                     #[cfg(test)]
                     self.maybe_fail(tests::FailureLocation::UpdatingTaskAfterProcessBatchFailure)?;
 
                     tracing::info!("Batch failed {}", error);
 
+                    batch_tasks.insert(task.uid);
+                    batch_stats.register(&task);
                     self.update_task(&mut wtxn, &task)
                         .map_err(|e| Error::TaskDatabaseUpdate(Box::new(e)))?;
                 }
             }
         }
 
+        let steps = self.processing_tasks.write().unwrap().take_steps();
+        let batch = Batch {
+            uid: batch_uid,
+            kind: batch_kind.to_string(),
+            index_uid,
+            stats: batch_stats,
+            steps,
+            started_at,
+            finished_at,
+        };
+        self.all_batches
+            .put(&mut wtxn, &batch_uid, &batch)
+            .map_err(|e| Error::TaskDatabaseUpdate(Box::new(e.into())))?;
+        self.batch_to_tasks_mapping
+            .put(&mut wtxn, &batch_uid, &batch_tasks)
+            .map_err(|e| Error::TaskDatabaseUpdate(Box::new(e.into())))?;
+        self.batch_metadata
+            .put(&mut wtxn, NEXT_BATCH_ID_KEY, &(batch_uid + 1))
+            .map_err(|e| Error::TaskDatabaseUpdate(Box::new(e.into())))?;
+
         let processed = self.processing_tasks.write().unwrap().stop_processing();
 
         #[cfg(test)]
@@ -1467,7 +1770,8 @@ impl IndexScheduler {
             .map(
                 |IndexEmbeddingConfig {
                      name,
-                     config: milli::vector::EmbeddingConfig { embedder_options, prompt },
+                     config:
+                         milli::vector::EmbeddingConfig { embedder_options, prompt, quantized: _ },
                      ..
                  }| {
                     let prompt =
@@ -1578,6 +1882,7 @@ impl<'a> Dump<'a> {
             error: task.error,
             canceled_by: task.canceled_by,
             details: task.details,
+            batch_uid: None,
             status: task.status,
             kind: match task.kind {
                 KindDump::DocumentImport {
@@ -1603,6 +1908,14 @@ impl<'a> Dump<'a> {
                         index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                     }
                 }
+                KindDump::DocumentEdition { filter, context, function } => {
+                    KindWithContent::DocumentEdition {
+                        index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                        filter_expr: filter,
+                        context,
+                        function,
+                    }
+                }
                 KindDump::DocumentClear => KindWithContent::DocumentClear {
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                 },
@@ -1632,8 +1945,21 @@ impl<'a> Dump<'a> {
                 KindDump::TasksDeletion { query, tasks } => {
                     KindWithContent::TaskDeletion { query, tasks }
                 }
-                KindDump::DumpCreation { keys, instance_uid } => {
-                    KindWithContent::DumpCreation { keys, instance_uid }
+                KindDump::DumpCreation {
+                    keys,
+                    instance_uid,
+                    index_uids,
+                    include_tasks,
+                    include_keys,
+                } => KindWithContent::DumpCreation {
+                    keys,
+                    instance_uid,
+                    index_uids,
+                    include_tasks,
+                    include_keys,
+                },
+                KindDump::DumpImport { dump_uid, indexes, overwrite } => {
+                    KindWithContent::DumpImport { dump_uid, indexes, overwrite }
                 }
                 KindDump::SnapshotCreation => KindWithContent::SnapshotCreation,
             },
@@ -1750,6 +2076,7 @@ mod tests {
     use meilisearch_auth::AuthFilter;
     use meilisearch_types::document_formats::DocumentFormatError;
     use meilisearch_types::error::ErrorCode;
+    use meilisearch_types::heed::types::DecodeIgnore;
     use meilisearch_types::index_uid_pattern::IndexUidPattern;
     use meilisearch_types::milli::obkv_to_json;
     use meilisearch_types::milli::update::IndexDocumentsMethod::{
@@ -1805,7 +2132,10 @@ mod tests {
                 update_file_path: tempdir.path().join("file_store"),
                 indexes_path: tempdir.path().join("indexes"),
                 snapshots_path: tempdir.path().join("snapshots"),
+                incremental_snapshots: false,
                 dumps_path: tempdir.path().join("dumps"),
+                upload_backend: None,
+                upload_retention: None,
                 webhook_url: None,
                 webhook_authorization_header: None,
                 task_db_size: 1000 * 1000, // 1 MB, we don't use MiB on purpose.
@@ -2058,6 +2388,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn open_all_the_databases() {
+        let (index_scheduler, mut _handle) = IndexScheduler::test(true, vec![]);
+
+        // The unnamed database lists the names of all the databases of the environment
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let main = index_scheduler.env.open_database::<Str, DecodeIgnore>(&rtxn, None).unwrap();
+        let mut names: Vec<_> =
+            main.unwrap().iter(&rtxn).unwrap().map(|res| res.unwrap().0.to_string()).collect();
+        names.sort_unstable();
+
+        let mut expected: Vec<_> = db_name::ALL.iter().map(|name| name.to_string()).collect();
+        expected.sort_unstable();
+        assert_eq!(names, expected);
+    }
+
     #[test]
     fn register() {
         // In this test, the handle doesn't make any progress, we only check that the tasks are registered
@@ -2251,6 +2597,52 @@ mod tests {
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "task_deletion_done");
     }
 
+    #[test]
+    fn index_generation_only_bumped_by_index_writes() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        for index in ["catto", "doggo"] {
+            index_scheduler.register(index_creation_task(index, "id"), None, false).unwrap();
+            handle.advance_one_successful_batch();
+        }
+        let catto = index_scheduler.index_generation("catto");
+        let doggo = index_scheduler.index_generation("doggo");
+
+        // a batch of another index doesn't modify this one
+        index_scheduler.register(index_creation_task("ratto", "id"), None, false).unwrap();
+        handle.advance_one_successful_batch();
+        assert_eq!(index_scheduler.index_generation("catto"), catto);
+
+        // the batches without an index don't modify any index
+        index_scheduler
+            .register(
+                KindWithContent::TaskDeletion {
+                    query: S("test_query"),
+                    tasks: RoaringBitmap::from_iter([0]),
+                },
+                None,
+                false,
+            )
+            .unwrap();
+        handle.advance_one_successful_batch();
+        assert_eq!(index_scheduler.index_generation("catto"), catto);
+        assert_eq!(index_scheduler.index_generation("doggo"), doggo);
+
+        // a swap modifies several indexes at once
+        index_scheduler
+            .register(
+                KindWithContent::IndexSwap {
+                    swaps: vec![IndexSwap { indexes: (S("catto"), S("doggo")) }],
+                },
+                None,
+                false,
+            )
+            .unwrap();
+        handle.advance_one_successful_batch();
+        assert!(index_scheduler.index_generation("catto") > catto);
+        assert!(index_scheduler.index_generation("doggo") > doggo);
+    }
+
     #[test]
     fn task_deletion_deleteable() {
         let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
@@ -2470,7 +2862,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
@@ -2530,7 +2922,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
@@ -2909,7 +3301,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
@@ -2960,7 +3352,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
@@ -3016,7 +3408,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
@@ -3134,7 +3526,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
@@ -3189,7 +3581,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
@@ -3903,7 +4295,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
@@ -3974,7 +4366,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
@@ -4042,7 +4434,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
@@ -4103,7 +4495,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
@@ -4168,7 +4560,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
@@ -4229,7 +4621,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
@@ -4312,7 +4704,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
@@ -4398,7 +4790,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
@@ -4477,7 +4869,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
     }
@@ -4745,6 +5137,7 @@ mod tests {
             "documentAdditionOrUpdate": 0,
             "documentDeletion": 0,
             "dumpCreation": 0,
+            "dumpImport": 0,
             "indexCreation": 3,
             "indexDeletion": 0,
             "indexSwap": 0,
@@ -4776,6 +5169,7 @@ mod tests {
             "documentAdditionOrUpdate": 0,
             "documentDeletion": 0,
             "dumpCreation": 0,
+            "dumpImport": 0,
             "indexCreation": 3,
             "indexDeletion": 0,
             "indexSwap": 0,
@@ -4814,6 +5208,7 @@ mod tests {
             "documentAdditionOrUpdate": 0,
             "documentDeletion": 0,
             "dumpCreation": 0,
+            "dumpImport": 0,
             "indexCreation": 3,
             "indexDeletion": 0,
             "indexSwap": 0,
@@ -4853,6 +5248,7 @@ mod tests {
             "documentAdditionOrUpdate": 0,
             "documentDeletion": 0,
             "dumpCreation": 0,
+            "dumpImport": 0,
             "indexCreation": 3,
             "indexDeletion": 0,
             "indexSwap": 0,
@@ -4870,7 +5266,13 @@ mod tests {
     fn cancel_processing_dump() {
         let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
 
-        let dump_creation = KindWithContent::DumpCreation { keys: Vec::new(), instance_uid: None };
+        let dump_creation = KindWithContent::DumpCreation {
+            keys: Vec::new(),
+            instance_uid: None,
+            index_uids: None,
+            include_tasks: true,
+            include_keys: true,
+        };
         let dump_cancellation = KindWithContent::TaskCancelation {
             query: "cancel dump".to_owned(),
             tasks: RoaringBitmap::from_iter([0]),
@@ -5139,7 +5541,7 @@ mod tests {
             assert_json_snapshot!(embeddings[&simple_hf_name][0] == lab_embed, @"true");
             assert_json_snapshot!(embeddings[&fakerest_name][0] == beagle_embed, @"true");
 
-            let doc = index.documents(&rtxn, std::iter::once(0)).unwrap()[0].1;
+            let doc = index.documents(&rtxn, std::iter::once(0)).unwrap().remove(0).1;
             let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
             let doc = obkv_to_json(
                 &[
@@ -5148,7 +5550,7 @@ mod tests {
                     fields_ids_map.id("_vectors").unwrap(),
                 ],
                 &fields_ids_map,
-                doc,
+                doc.as_obkv(),
             )
             .unwrap();
             assert_json_snapshot!(doc, {"._vectors.A_fakerest.embeddings" => "[vector]"});
@@ -5216,7 +5618,7 @@ mod tests {
                 // remained beagle
                 assert_json_snapshot!(embeddings[&fakerest_name][0] == beagle_embed, @"true");
 
-                let doc = index.documents(&rtxn, std::iter::once(0)).unwrap()[0].1;
+                let doc = index.documents(&rtxn, std::iter::once(0)).unwrap().remove(0).1;
                 let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
                 let doc = obkv_to_json(
                     &[
@@ -5225,7 +5627,7 @@ mod tests {
                         fields_ids_map.id("_vectors").unwrap(),
                     ],
                     &fields_ids_map,
-                    doc,
+                    doc.as_obkv(),
                 )
                 .unwrap();
                 assert_json_snapshot!(doc, {"._vectors.A_fakerest.embeddings" => "[vector]"});
@@ -5314,7 +5716,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string(&documents).unwrap(), name: "documents after initial push");
 
@@ -5353,7 +5755,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         // the all the vectors linked to the new specified embedder have been removed
         // Only the unknown embedders stays in the document DB
@@ -5378,6 +5780,7 @@ mod tests {
                     prompt: PromptData {
                         template: "{{doc.doggo}}",
                     },
+                    quantized: None,
                 },
                 user_provided: RoaringBitmap<[1, 2]>,
             },
@@ -5457,8 +5860,8 @@ mod tests {
         // the document with the id 3 should have its original embedding updated
         let rtxn = index.read_txn().unwrap();
         let docid = index.external_documents_ids.get(&rtxn, "3").unwrap().unwrap();
-        let doc = index.documents(&rtxn, Some(docid)).unwrap()[0];
-        let doc = obkv_to_json(&field_ids, &field_ids_map, doc.1).unwrap();
+        let doc = index.documents(&rtxn, Some(docid)).unwrap().remove(0);
+        let doc = obkv_to_json(&field_ids, &field_ids_map, doc.1.as_obkv()).unwrap();
         snapshot!(json_string!(doc), @r###"
         {
           "id": 3,
@@ -5575,7 +5978,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string(&documents).unwrap(), @r###"[{"id":0,"doggo":"kefir"}]"###);
         let conf = index.embedding_configs(&rtxn).unwrap();
@@ -5593,6 +5996,7 @@ mod tests {
                     prompt: PromptData {
                         template: "{% for field in fields %} {{ field.name }}: {{ field.value }}\n{% endfor %}",
                     },
+                    quantized: None,
                 },
                 user_provided: RoaringBitmap<[0]>,
             },
@@ -5615,7 +6019,7 @@ mod tests {
         let documents = index
             .all_documents(&rtxn)
             .unwrap()
-            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+            .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap())
             .collect::<Vec<_>>();
         snapshot!(serde_json::to_string(&documents).unwrap(), @"[]");
         let conf = index.embedding_configs(&rtxn).unwrap();
@@ -5633,6 +6037,7 @@ mod tests {
                     prompt: PromptData {
                         template: "{% for field in fields %} {{ field.name }}: {{ field.value }}\n{% endfor %}",
                     },
+                    quantized: None,
                 },
                 user_provided: RoaringBitmap<[]>,
             },
@@ -5731,7 +6136,9 @@ mod tests {
             let documents = index
                 .all_documents(&rtxn)
                 .unwrap()
-                .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+                .map(|ret| {
+                    obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap()
+                })
                 .collect::<Vec<_>>();
             snapshot!(serde_json::to_string(&documents).unwrap(), @r###"[{"id":0,"doggo":"kefir"},{"id":1,"doggo":"intel"}]"###);
         }
@@ -5766,7 +6173,9 @@ mod tests {
             let documents = index
                 .all_documents(&rtxn)
                 .unwrap()
-                .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+                .map(|ret| {
+                    obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap()
+                })
                 .collect::<Vec<_>>();
             snapshot!(serde_json::to_string(&documents).unwrap(), @r###"[{"id":0,"doggo":"kefir","_vectors":{"manual":{"embeddings":[[0.0,0.0,0.0]],"regenerate":false}}},{"id":1,"doggo":"intel","_vectors":{"manual":{"embeddings":[[1.0,1.0,1.0]],"regenerate":false}}}]"###);
         }
@@ -5799,7 +6208,9 @@ mod tests {
             let documents = index
                 .all_documents(&rtxn)
                 .unwrap()
-                .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+                .map(|ret| {
+                    obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1.as_obkv()).unwrap()
+                })
                 .collect::<Vec<_>>();
 
             // FIXME: redaction
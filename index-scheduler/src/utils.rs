@@ -3,10 +3,13 @@
 use std::collections::{BTreeSet, HashSet};
 use std::ops::Bound;
 
+use meilisearch_types::batches::{Batch, BatchId};
 use meilisearch_types::heed::types::DecodeIgnore;
 use meilisearch_types::heed::{Database, RoTxn, RwTxn};
 use meilisearch_types::milli::CboRoaringBitmapCodec;
-use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Status};
+use meilisearch_types::tasks::{
+    Details, DumpImportIndex, IndexSwap, Kind, KindWithContent, Status,
+};
 use roaring::{MultiOps, RoaringBitmap};
 use time::OffsetDateTime;
 
@@ -29,6 +32,48 @@ impl IndexScheduler {
         Ok(self.all_tasks.get(rtxn, &task_id)?)
     }
 
+    /// Returns the id of the next batch, the ids of the deleted batches are never reused.
+    pub(crate) fn next_batch_id(&self, rtxn: &RoTxn) -> Result<BatchId> {
+        match self.batch_metadata.get(rtxn, crate::NEXT_BATCH_ID_KEY)? {
+            Some(batch_id) => Ok(batch_id),
+            None => Ok(self
+                .all_batches
+                .remap_data_type::<DecodeIgnore>()
+                .last(rtxn)?
+                .map(|(k, _)| k + 1)
+                .unwrap_or_default()),
+        }
+    }
+
+    pub(crate) fn get_batch(&self, rtxn: &RoTxn, batch_id: BatchId) -> Result<Option<Batch>> {
+        Ok(self.all_batches.get(rtxn, &batch_id)?)
+    }
+
+    /// Removes the given tasks from the batches they were processed in.
+    ///
+    /// A batch that doesn't contain any task anymore is deleted.
+    pub(crate) fn remove_tasks_from_batches(
+        &self,
+        wtxn: &mut RwTxn,
+        batch_ids: impl IntoIterator<Item = BatchId>,
+        tasks: &RoaringBitmap,
+    ) -> Result<()> {
+        for batch_id in batch_ids {
+            let Some(mut batch_tasks) = self.batch_to_tasks_mapping.get(wtxn, &batch_id)? else {
+                continue;
+            };
+            batch_tasks -= tasks;
+            if batch_tasks.is_empty() {
+                self.batch_to_tasks_mapping.delete(wtxn, &batch_id)?;
+                self.all_batches.delete(wtxn, &batch_id)?;
+            } else {
+                self.batch_to_tasks_mapping.put(wtxn, &batch_id, &batch_tasks)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Convert an iterator to a `Vec` of tasks. The tasks MUST exist or a
     /// `CorruptedTaskQueue` error will be throwed.
     pub(crate) fn get_existing_tasks(
@@ -240,6 +285,7 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
         K::DocumentAdditionOrUpdate { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentDeletion { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentDeletionByFilter { index_uid, .. } => index_uids.push(index_uid),
+        K::DocumentEdition { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentClear { index_uid } => index_uids.push(index_uid),
         K::SettingsUpdate { index_uid, .. } => index_uids.push(index_uid),
         K::IndexDeletion { index_uid } => index_uids.push(index_uid),
@@ -255,20 +301,27 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
                 }
             }
         }
+        K::DumpImport { indexes, .. } => index_uids.extend(imported_index_uids(indexes, swap)),
         K::TaskCancelation { .. }
         | K::TaskDeletion { .. }
         | K::DumpCreation { .. }
         | K::SnapshotCreation => (),
     };
-    if let Some(Details::IndexSwap { swaps }) = &mut task.details {
-        for IndexSwap { indexes: (lhs, rhs) } in swaps.iter_mut() {
-            if lhs == swap.0 || lhs == swap.1 {
-                index_uids.push(lhs);
-            }
-            if rhs == swap.0 || rhs == swap.1 {
-                index_uids.push(rhs);
+    match &mut task.details {
+        Some(Details::IndexSwap { swaps }) => {
+            for IndexSwap { indexes: (lhs, rhs) } in swaps.iter_mut() {
+                if lhs == swap.0 || lhs == swap.1 {
+                    index_uids.push(lhs);
+                }
+                if rhs == swap.0 || rhs == swap.1 {
+                    index_uids.push(rhs);
+                }
             }
         }
+        Some(Details::DumpImport { indexes, .. }) => {
+            index_uids.extend(imported_index_uids(indexes, swap))
+        }
+        _ => (),
     }
     for index_uid in index_uids {
         if index_uid == swap.0 {
@@ -298,6 +351,21 @@ pub(crate) fn filter_out_references_to_newer_tasks(task: &mut Task) {
     }
 }
 
+/// The names under which the swapped indexes were imported from a dump.
+///
+/// The dump still contains the indexes under their original names so they are renamed instead.
+fn imported_index_uids<'a>(
+    indexes: &'a mut [DumpImportIndex],
+    swap: (&'a str, &'a str),
+) -> impl Iterator<Item = &'a mut String> + 'a {
+    indexes
+        .iter_mut()
+        .filter(move |index| index.target() == swap.0 || index.target() == swap.1)
+        .map(|DumpImportIndex { index_uid, rename_to }| {
+            rename_to.get_or_insert_with(|| index_uid.clone())
+        })
+}
+
 pub(crate) fn check_index_swap_validity(task: &Task) -> Result<()> {
     let swaps =
         if let KindWithContent::IndexSwap { swaps } = &task.kind { swaps } else { return Ok(()) };
@@ -347,6 +415,7 @@ impl IndexScheduler {
                 error: _,
                 canceled_by,
                 details,
+                batch_uid,
                 status,
                 kind,
             } = task;
@@ -390,6 +459,12 @@ impl IndexScheduler {
                     _ => panic!(),
                 }
             }
+            if let Some(batch_uid) = batch_uid {
+                assert!(self.get_batch(&rtxn, batch_uid).unwrap().is_some());
+                let db_batch_tasks =
+                    self.batch_to_tasks_mapping.get(&rtxn, &batch_uid).unwrap().unwrap();
+                assert!(db_batch_tasks.contains(uid));
+            }
             if let Some(details) = details {
                 match details {
                     Details::IndexSwap { swaps: sw1 } => {
@@ -483,6 +558,28 @@ impl IndexScheduler {
                             }
                         }
                     }
+                    Details::DocumentEdition { deleted_documents, edited_documents, .. } => {
+                        assert_eq!(kind.as_kind(), Kind::DocumentEdition);
+                        let index_uid =
+                            if let KindWithContent::DocumentEdition { ref index_uid, .. } = kind {
+                                index_uid
+                            } else {
+                                unreachable!()
+                            };
+                        assert_eq!(&task_index_uid.unwrap(), index_uid);
+
+                        match status {
+                            Status::Enqueued | Status::Processing => (),
+                            Status::Succeeded => {
+                                assert!(deleted_documents.is_some());
+                                assert!(edited_documents.is_some());
+                            }
+                            Status::Failed | Status::Canceled => {
+                                assert!(deleted_documents == Some(0));
+                                assert!(edited_documents == Some(0));
+                            }
+                        }
+                    }
                     Details::ClearAll { deleted_documents } => {
                         assert!(matches!(
                             kind.as_kind(),
@@ -524,9 +621,23 @@ impl IndexScheduler {
                             assert_ne!(status, Status::Succeeded);
                         }
                     }
-                    Details::Dump { dump_uid: _ } => {
+                    Details::Dump { dump_uid: _, upload_error: _ } => {
                         assert_eq!(kind.as_kind(), Kind::DumpCreation);
                     }
+                    Details::SnapshotCreation { upload_error: _ } => {
+                        assert_eq!(kind.as_kind(), Kind::SnapshotCreation);
+                    }
+                    Details::DumpImport {
+                        dump_uid: _,
+                        indexes: _,
+                        overwrite: _,
+                        imported_documents,
+                    } => {
+                        assert_eq!(kind.as_kind(), Kind::DumpImport);
+                        if imported_documents.is_some() {
+                            assert_ne!(status, Status::Enqueued);
+                        }
+                    }
                 }
             }
 
@@ -344,9 +344,13 @@ impl IntoIterator for SearchRules {
 /// Contains the rules to apply on the top of the search query for a specific index.
 ///
 /// filter: search filter to apply in addition to query filters.
+/// allowRankingRulesOverride: whether the `rankingRules` search parameter can be used, defaults to `true`.
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct IndexSearchRules {
     pub filter: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_ranking_rules_override: Option<bool>,
 }
 
 fn generate_default_keys(store: &HeedAuthStore) -> Result<()> {
@@ -129,7 +129,7 @@ impl HeedAuthStore {
                     actions.extend([Action::SettingsGet, Action::SettingsUpdate].iter());
                 }
                 Action::DumpsAll => {
-                    actions.insert(Action::DumpsCreate);
+                    actions.extend([Action::DumpsCreate, Action::DumpsImport]);
                 }
                 Action::SnapshotsAll => {
                     actions.insert(Action::SnapshotsCreate);
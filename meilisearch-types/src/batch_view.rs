@@ -0,0 +1,54 @@
+use roaring::RoaringBitmap;
+use serde::Serialize;
+use time::{Duration, OffsetDateTime};
+
+use crate::batches::{Batch, BatchId, BatchStats};
+use crate::tasks::{serialize_duration, TaskId};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchView {
+    pub uid: BatchId,
+    pub kind: String,
+    pub index_uid: Option<String>,
+    pub task_uids: Vec<TaskId>,
+    pub stats: BatchStats,
+    pub steps: Vec<BatchStepView>,
+    #[serde(serialize_with = "serialize_duration", default)]
+    pub duration: Option<Duration>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub started_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub finished_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchStepView {
+    pub name: String,
+    #[serde(serialize_with = "serialize_duration")]
+    pub duration: Option<Duration>,
+}
+
+impl BatchView {
+    pub fn from_batch(batch: &Batch, tasks: &RoaringBitmap) -> BatchView {
+        BatchView {
+            uid: batch.uid,
+            kind: batch.kind.clone(),
+            index_uid: batch.index_uid.clone(),
+            task_uids: tasks.iter().collect(),
+            stats: batch.stats.clone(),
+            steps: batch
+                .steps
+                .iter()
+                .map(|step| BatchStepView {
+                    name: step.name.clone(),
+                    duration: Duration::try_from(step.duration).ok(),
+                })
+                .collect(),
+            duration: Some(batch.finished_at - batch.started_at),
+            started_at: batch.started_at,
+            finished_at: batch.finished_at,
+        }
+    }
+}
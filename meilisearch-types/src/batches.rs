@@ -0,0 +1,59 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::tasks::{Kind, Status, Task};
+
+pub type BatchId = u32;
+
+/// A group of tasks that were processed together by the scheduler.
+///
+/// The ids of the tasks contained in a batch are not stored in this structure
+/// but in a dedicated database of the index scheduler.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Batch {
+    pub uid: BatchId,
+    /// The kind of operation the autobatcher created out of the tasks, e.g. `documentOperation`.
+    pub kind: String,
+    pub index_uid: Option<String>,
+    pub stats: BatchStats,
+    /// The time spent in each step of the processing of the batch, in order.
+    pub steps: Vec<BatchStep>,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub started_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub finished_at: OffsetDateTime,
+}
+
+/// The number of tasks of a batch, grouped by status, type and index.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchStats {
+    pub total_nb_tasks: u32,
+    pub status: BTreeMap<Status, u32>,
+    pub types: BTreeMap<Kind, u32>,
+    pub index_uids: BTreeMap<String, u32>,
+}
+
+impl BatchStats {
+    /// Accounts for a processed task of the batch.
+    pub fn register(&mut self, task: &Task) {
+        self.total_nb_tasks = self.total_nb_tasks.saturating_add(1);
+        *self.status.entry(task.status).or_default() += 1;
+        *self.types.entry(task.kind.as_kind()).or_default() += 1;
+        if let Some(index_uid) = task.index_uid() {
+            *self.index_uids.entry(index_uid.to_string()).or_default() += 1;
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchStep {
+    pub name: String,
+    pub duration: Duration,
+}
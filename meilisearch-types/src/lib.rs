@@ -1,3 +1,5 @@
+pub mod batch_view;
+pub mod batches;
 pub mod compression;
 pub mod deserr;
 pub mod document_formats;
@@ -7,6 +9,8 @@ pub mod features;
 pub mod index_uid;
 pub mod index_uid_pattern;
 pub mod keys;
+pub mod locales;
+pub mod merchandising_rules;
 pub mod settings;
 pub mod star_or;
 pub mod task_view;
@@ -0,0 +1,116 @@
+use std::fmt;
+use std::str::FromStr;
+
+use deserr::Deserr;
+use milli::tokenizer::Language;
+use milli::LocalizedAttributesRule;
+use serde::{Deserialize, Serialize};
+
+use crate::deserr::DeserrJsonError;
+use crate::error::deserr_codes::*;
+
+/// Generates the `Locale` enum, a mirror of the languages supported by the tokenizer
+/// identified by their ISO-639-3 code, and its conversions from and to the tokenizer `Language`.
+macro_rules! make_locale {
+    ($($language:tt), +) => {
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Deserr, Serialize, Deserialize, enum_iterator::Sequence)]
+        #[deserr(try_from(&String) = FromStr::from_str -> LocaleFormatError)]
+        #[serde(rename_all = "camelCase")]
+        pub enum Locale {
+            $($language),+,
+        }
+
+        impl From<Locale> for Language {
+            fn from(other: Locale) -> Language {
+                match other {
+                    $(Locale::$language => Language::$language), +
+                }
+            }
+        }
+
+        impl TryFrom<Language> for Locale {
+            type Error = LocaleFormatError;
+
+            fn try_from(other: Language) -> Result<Locale, LocaleFormatError> {
+                match other {
+                    $(Language::$language => Ok(Locale::$language)), +,
+                    other => Err(LocaleFormatError { invalid_locale: other.name().to_string() }),
+                }
+            }
+        }
+
+        impl Locale {
+            /// The ISO-639-3 code of the locale.
+            pub fn code(&self) -> &'static str {
+                Language::from(*self).name()
+            }
+        }
+    };
+}
+
+make_locale! {
+    Epo, Eng, Rus, Cmn, Spa, Por, Ita, Ben, Fra, Deu, Ukr, Kat, Ara, Hin, Jpn, Heb, Yid, Pol,
+    Amh, Jav, Kor, Nob, Dan, Swe, Fin, Tur, Nld, Hun, Ces, Ell, Bul, Bel, Mar, Kan, Ron, Slv,
+    Hrv, Srp, Mkd, Lit, Lav, Est, Tam, Vie, Urd, Tha, Guj, Uzb, Pan, Aze, Ind, Tel, Pes, Mal,
+    Ori, Mya, Nep, Sin, Khm, Tuk, Aka, Zul, Sna, Afr, Lat, Slk, Cat, Tgl, Hye
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = LocaleFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        enum_iterator::all::<Locale>()
+            .find(|locale| locale.code() == s)
+            .ok_or_else(|| LocaleFormatError { invalid_locale: s.to_string() })
+    }
+}
+
+#[derive(Debug)]
+pub struct LocaleFormatError {
+    pub invalid_locale: String,
+}
+
+impl fmt::Display for LocaleFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let valid_locales = enum_iterator::all::<Locale>()
+            .map(|locale| format!("`{}`", locale.code()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "Unsupported locale `{}`, expected one of {}", self.invalid_locale, valid_locales)
+    }
+}
+
+impl std::error::Error for LocaleFormatError {}
+
+/// A `localizedAttributes` rule, as exposed by the settings routes.
+#[derive(Debug, Clone, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+#[deserr(error = DeserrJsonError<InvalidSettingsLocalizedAttributes>, rename_all = camelCase, deny_unknown_fields)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct LocalizedAttributesRuleView {
+    pub attribute_patterns: Vec<String>,
+    pub locales: Vec<Locale>,
+}
+
+impl From<LocalizedAttributesRule> for LocalizedAttributesRuleView {
+    fn from(rule: LocalizedAttributesRule) -> Self {
+        Self {
+            attribute_patterns: rule.attribute_patterns,
+            locales: rule.locales.into_iter().filter_map(|l| Locale::try_from(l).ok()).collect(),
+        }
+    }
+}
+
+impl From<LocalizedAttributesRuleView> for LocalizedAttributesRule {
+    fn from(view: LocalizedAttributesRuleView) -> Self {
+        Self {
+            attribute_patterns: view.attribute_patterns,
+            locales: view.locales.into_iter().map(|l| l.into()).collect(),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use deserr::Deserr;
+use milli::merchandising_rules::{
+    MerchandisingRule, PinnedDocument, QueryMatch, RuleActions, RuleCondition,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::deserr::DeserrJsonError;
+use crate::error::deserr_codes::*;
+
+/// A merchandising rule, as exposed by the `/indexes/{indexUid}/rules` routes
+/// and the `merchandisingRules` setting.
+#[derive(Debug, Clone, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+#[deserr(
+    rename_all = camelCase,
+    deny_unknown_fields,
+    where_predicate = __Deserr_E: deserr::MergeWithError<DeserrJsonError<InvalidRuleCondition>>,
+    where_predicate = __Deserr_E: deserr::MergeWithError<DeserrJsonError<InvalidRuleActions>>
+)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct MerchandisingRuleView {
+    #[deserr(error = DeserrJsonError<InvalidRuleCondition>)]
+    pub condition: RuleConditionView,
+    #[deserr(default, error = DeserrJsonError<InvalidRuleActions>)]
+    pub actions: RuleActionsView,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+#[deserr(error = DeserrJsonError<InvalidRuleCondition>, rename_all = camelCase, deny_unknown_fields)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct RuleConditionView {
+    pub query: String,
+    #[deserr(default, rename = "match")]
+    #[serde(rename = "match")]
+    pub query_match: QueryMatchView,
+    #[deserr(default)]
+    pub filter: Option<Value>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+#[deserr(rename_all = camelCase)]
+#[serde(rename_all = "camelCase")]
+pub enum QueryMatchView {
+    #[default]
+    Exact,
+    Contains,
+    Regex,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+#[deserr(error = DeserrJsonError<InvalidRuleActions>, rename_all = camelCase, deny_unknown_fields)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct RuleActionsView {
+    #[deserr(default)]
+    pub pin: Vec<PinnedDocumentView>,
+    #[deserr(default)]
+    pub hide: Vec<String>,
+    #[deserr(default)]
+    pub filter: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+#[deserr(error = DeserrJsonError<InvalidRuleActions>, rename_all = camelCase, deny_unknown_fields)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct PinnedDocumentView {
+    pub id: String,
+    pub position: usize,
+}
+
+impl From<MerchandisingRule> for MerchandisingRuleView {
+    fn from(rule: MerchandisingRule) -> Self {
+        let MerchandisingRule { condition, actions } = rule;
+        let RuleCondition { query, query_match, filter } = condition;
+        let RuleActions { pin, hide, filter: actions_filter } = actions;
+        Self {
+            condition: RuleConditionView { query, query_match: query_match.into(), filter },
+            actions: RuleActionsView {
+                pin: pin
+                    .into_iter()
+                    .map(|PinnedDocument { id, position }| PinnedDocumentView { id, position })
+                    .collect(),
+                hide,
+                filter: actions_filter,
+            },
+        }
+    }
+}
+
+impl From<MerchandisingRuleView> for MerchandisingRule {
+    fn from(view: MerchandisingRuleView) -> Self {
+        let MerchandisingRuleView { condition, actions } = view;
+        let RuleConditionView { query, query_match, filter } = condition;
+        let RuleActionsView { pin, hide, filter: actions_filter } = actions;
+        Self {
+            condition: RuleCondition { query, query_match: query_match.into(), filter },
+            actions: RuleActions {
+                pin: pin
+                    .into_iter()
+                    .map(|PinnedDocumentView { id, position }| PinnedDocument { id, position })
+                    .collect(),
+                hide,
+                filter: actions_filter,
+            },
+        }
+    }
+}
+
+impl From<QueryMatch> for QueryMatchView {
+    fn from(query_match: QueryMatch) -> Self {
+        match query_match {
+            QueryMatch::Exact => QueryMatchView::Exact,
+            QueryMatch::Contains => QueryMatchView::Contains,
+            QueryMatch::Regex => QueryMatchView::Regex,
+        }
+    }
+}
+
+impl From<QueryMatchView> for QueryMatch {
+    fn from(view: QueryMatchView) -> Self {
+        match view {
+            QueryMatchView::Exact => QueryMatch::Exact,
+            QueryMatchView::Contains => QueryMatch::Contains,
+            QueryMatchView::Regex => QueryMatch::Regex,
+        }
+    }
+}
@@ -3,10 +3,10 @@ use std::convert::Infallible;
 use std::fmt;
 use std::marker::PhantomData;
 use std::num::NonZeroUsize;
-use std::ops::{ControlFlow, Deref};
+use std::ops::Deref;
 use std::str::FromStr;
 
-use deserr::{DeserializeError, Deserr, ErrorKind, MergeWithError, ValuePointerRef};
+use deserr::{DeserializeError, Deserr, ErrorKind, ValuePointerRef};
 use fst::IntoStreamer;
 use milli::index::IndexEmbeddingConfig;
 use milli::proximity::ProximityPrecision;
@@ -17,6 +17,8 @@ use serde::{Deserialize, Serialize, Serializer};
 use crate::deserr::DeserrJsonError;
 use crate::error::deserr_codes::*;
 use crate::facet_values_sort::FacetValuesSort;
+use crate::locales::LocalizedAttributesRuleView;
+use crate::merchandising_rules::MerchandisingRuleView;
 
 /// The maximum number of results that the engine
 /// will be able to return in one search call.
@@ -119,20 +121,6 @@ pub struct PaginationSettings {
     pub max_total_hits: Setting<usize>,
 }
 
-impl MergeWithError<milli::CriterionError> for DeserrJsonError<InvalidSettingsRankingRules> {
-    fn merge(
-        _self_: Option<Self>,
-        other: milli::CriterionError,
-        merge_location: ValuePointerRef,
-    ) -> ControlFlow<Self, Self> {
-        Self::error::<Infallible>(
-            None,
-            ErrorKind::Unexpected { msg: other.to_string() },
-            merge_location,
-        )
-    }
-}
-
 /// Holds all the settings for an index. `T` can either be `Checked` if they represents settings
 /// whose validity is guaranteed, or `Unchecked` if they need to be validated. In the later case, a
 /// call to `check` will return a `Settings<Checked>` from a `Settings<Unchecked>`.
@@ -198,6 +186,16 @@ pub struct Settings<T> {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsSearchCutoffMs>)]
     pub search_cutoff_ms: Setting<u64>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsLocalizedAttributes>)]
+    pub localized_attributes: Setting<Vec<LocalizedAttributesRuleView>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsAllowRankingRulesOverride>)]
+    pub allow_ranking_rules_override: Setting<bool>,
+    /// The rules to create or replace, and the ones to delete when set to `null`.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsMerchandisingRules>)]
+    pub merchandising_rules: Setting<BTreeMap<String, Setting<MerchandisingRuleView>>>,
 
     #[serde(skip)]
     #[deserr(skip)]
@@ -261,6 +259,9 @@ impl Settings<Checked> {
             pagination: Setting::Reset,
             embedders: Setting::Reset,
             search_cutoff_ms: Setting::Reset,
+            localized_attributes: Setting::Reset,
+            allow_ranking_rules_override: Setting::Reset,
+            merchandising_rules: Setting::Reset,
             _kind: PhantomData,
         }
     }
@@ -284,6 +285,9 @@ impl Settings<Checked> {
             pagination,
             embedders,
             search_cutoff_ms,
+            localized_attributes,
+            allow_ranking_rules_override,
+            merchandising_rules,
             ..
         } = self;
 
@@ -305,6 +309,9 @@ impl Settings<Checked> {
             pagination,
             embedders,
             search_cutoff_ms,
+            localized_attributes,
+            allow_ranking_rules_override,
+            merchandising_rules,
             _kind: PhantomData,
         }
     }
@@ -352,6 +359,9 @@ impl Settings<Unchecked> {
             pagination: self.pagination,
             embedders: self.embedders,
             search_cutoff_ms: self.search_cutoff_ms,
+            localized_attributes: self.localized_attributes,
+            allow_ranking_rules_override: self.allow_ranking_rules_override,
+            merchandising_rules: self.merchandising_rules,
             _kind: PhantomData,
         }
     }
@@ -402,6 +412,9 @@ pub fn apply_settings_to_builder(
         pagination,
         embedders,
         search_cutoff_ms,
+        localized_attributes,
+        allow_ranking_rules_override,
+        merchandising_rules,
         _kind,
     } = settings;
 
@@ -583,6 +596,37 @@ pub fn apply_settings_to_builder(
         Setting::Reset => builder.reset_search_cutoff(),
         Setting::NotSet => (),
     }
+
+    match localized_attributes {
+        Setting::Set(rules) => builder
+            .set_localized_attributes_rules(rules.iter().cloned().map(|r| r.into()).collect()),
+        Setting::Reset => builder.reset_localized_attributes_rules(),
+        Setting::NotSet => (),
+    }
+
+    match allow_ranking_rules_override {
+        Setting::Set(allow) => builder.set_allow_ranking_rules_override(*allow),
+        Setting::Reset => builder.reset_allow_ranking_rules_override(),
+        Setting::NotSet => (),
+    }
+
+    match merchandising_rules {
+        Setting::Set(rules) => builder.set_merchandising_rules(
+            rules
+                .iter()
+                .map(|(uid, rule)| {
+                    let rule = match rule {
+                        Setting::Set(rule) => Setting::Set(rule.clone().into()),
+                        Setting::Reset => Setting::Reset,
+                        Setting::NotSet => Setting::NotSet,
+                    };
+                    (uid.clone(), rule)
+                })
+                .collect(),
+        ),
+        Setting::Reset => builder.reset_merchandising_rules(),
+        Setting::NotSet => (),
+    }
 }
 
 pub enum SecretPolicy {
@@ -679,6 +723,21 @@ pub fn settings(
 
     let search_cutoff_ms = index.search_cutoff(rtxn)?;
 
+    let localized_attributes_rules = index.localized_attributes_rules(rtxn)?;
+
+    let allow_ranking_rules_override = index.allow_ranking_rules_override(rtxn)?;
+
+    let merchandising_rules: BTreeMap<_, _> = index
+        .merchandising_rules(rtxn)?
+        .into_iter()
+        .map(|(uid, rule)| (uid, Setting::Set(rule.into())))
+        .collect();
+    let merchandising_rules = if merchandising_rules.is_empty() {
+        Setting::NotSet
+    } else {
+        Setting::Set(merchandising_rules)
+    };
+
     let mut settings = Settings {
         displayed_attributes: match displayed_attributes {
             Some(attrs) => Setting::Set(attrs),
@@ -711,6 +770,12 @@ pub fn settings(
             Some(cutoff) => Setting::Set(cutoff),
             None => Setting::Reset,
         },
+        localized_attributes: match localized_attributes_rules {
+            Some(rules) => Setting::Set(rules.into_iter().map(|r| r.into()).collect()),
+            None => Setting::Reset,
+        },
+        allow_ranking_rules_override: Setting::Set(allow_ranking_rules_override),
+        merchandising_rules,
         _kind: PhantomData,
     };
 
@@ -733,12 +798,22 @@ pub enum RankingRuleView {
     Proximity,
     /// Documents with quey words contained in more important
     /// attributes are considered better.
+    ///
+    /// This is an alias for [`RankingRuleView::AttributeRank`] followed by [`RankingRuleView::WordPosition`].
     Attribute,
+    /// Documents with query words contained in more important
+    /// attributes are considered better, regardless of the position of the words.
+    AttributeRank,
+    /// Documents with query words at the front of an attribute are considered better.
+    WordPosition,
     /// Dynamically sort at query time the documents. None, one or multiple Asc/Desc sortable
     /// attributes can be used in place of this criterion at query time.
     Sort,
     /// Sorted by the similarity of the matched words with the query words.
     Exactness,
+    /// Dynamically boost at query time the documents matching the boost filters
+    /// given in the search parameters.
+    Boost,
     /// Sorted by the increasing value of the field specified.
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
@@ -795,8 +870,11 @@ impl From<Criterion> for RankingRuleView {
             Criterion::Typo => RankingRuleView::Typo,
             Criterion::Proximity => RankingRuleView::Proximity,
             Criterion::Attribute => RankingRuleView::Attribute,
+            Criterion::AttributeRank => RankingRuleView::AttributeRank,
+            Criterion::WordPosition => RankingRuleView::WordPosition,
             Criterion::Sort => RankingRuleView::Sort,
             Criterion::Exactness => RankingRuleView::Exactness,
+            Criterion::Boost => RankingRuleView::Boost,
             Criterion::Asc(x) => RankingRuleView::Asc(x),
             Criterion::Desc(x) => RankingRuleView::Desc(x),
         }
@@ -809,8 +887,11 @@ impl From<RankingRuleView> for Criterion {
             RankingRuleView::Typo => Criterion::Typo,
             RankingRuleView::Proximity => Criterion::Proximity,
             RankingRuleView::Attribute => Criterion::Attribute,
+            RankingRuleView::AttributeRank => Criterion::AttributeRank,
+            RankingRuleView::WordPosition => Criterion::WordPosition,
             RankingRuleView::Sort => Criterion::Sort,
             RankingRuleView::Exactness => Criterion::Exactness,
+            RankingRuleView::Boost => Criterion::Boost,
             RankingRuleView::Asc(x) => Criterion::Asc(x),
             RankingRuleView::Desc(x) => Criterion::Desc(x),
         }
@@ -903,6 +984,9 @@ pub(crate) mod test {
             pagination: Setting::NotSet,
             embedders: Setting::NotSet,
             search_cutoff_ms: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            allow_ranking_rules_override: Setting::NotSet,
+            merchandising_rules: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -931,6 +1015,9 @@ pub(crate) mod test {
             pagination: Setting::NotSet,
             embedders: Setting::NotSet,
             search_cutoff_ms: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            allow_ranking_rules_override: Setting::NotSet,
+            merchandising_rules: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
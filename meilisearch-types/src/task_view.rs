@@ -1,9 +1,14 @@
+use milli::Object;
 use serde::Serialize;
 use time::{Duration, OffsetDateTime};
 
+use crate::batches::BatchId;
 use crate::error::ResponseError;
 use crate::settings::{Settings, Unchecked};
-use crate::tasks::{serialize_duration, Details, IndexSwap, Kind, Status, Task, TaskId};
+use crate::tasks::{
+    serialize_duration, Details, DumpImportIndex, IndexSwap, Kind, Status, Task, TaskId,
+    TaskProgress,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -16,8 +21,13 @@ pub struct TaskView {
     pub kind: Kind,
     pub canceled_by: Option<TaskId>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_uid: Option<BatchId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<DetailsView>,
     pub error: Option<ResponseError>,
+    /// The progress of the indexation, only available for the processing tasks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<TaskProgress>,
     #[serde(serialize_with = "serialize_duration", default)]
     pub duration: Option<Duration>,
     #[serde(with = "time::serde::rfc3339")]
@@ -36,8 +46,10 @@ impl TaskView {
             status: task.status,
             kind: task.kind.as_kind(),
             canceled_by: task.canceled_by,
+            batch_uid: task.batch_uid,
             details: task.details.clone().map(DetailsView::from),
             error: task.error.clone(),
+            progress: None,
             duration: task.started_at.zip(task.finished_at).map(|(start, end)| end - start),
             enqueued_at: task.enqueued_at,
             started_at: task.started_at,
@@ -60,6 +72,8 @@ pub struct DetailsView {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deleted_documents: Option<Option<u64>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub edited_documents: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub matched_tasks: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub canceled_tasks: Option<Option<u64>>,
@@ -70,10 +84,22 @@ pub struct DetailsView {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dump_uid: Option<Option<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<Option<Object>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(flatten)]
     pub settings: Option<Box<Settings<Unchecked>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub swaps: Option<Vec<IndexSwap>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexes: Option<Vec<DumpImportIndex>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overwrite: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub imported_documents: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload_error: Option<String>,
 }
 
 impl From<Details> for DetailsView {
@@ -86,6 +112,20 @@ impl From<Details> for DetailsView {
                     ..DetailsView::default()
                 }
             }
+            Details::DocumentEdition {
+                deleted_documents,
+                edited_documents,
+                original_filter,
+                context,
+                function,
+            } => DetailsView {
+                deleted_documents: Some(deleted_documents),
+                edited_documents: Some(edited_documents),
+                original_filter: Some(original_filter),
+                context: Some(context),
+                function: Some(function),
+                ..DetailsView::default()
+            },
             Details::SettingsUpdate { mut settings } => {
                 settings.hide_secrets();
                 DetailsView { settings: Some(settings), ..DetailsView::default() }
@@ -129,12 +169,24 @@ impl From<Details> for DetailsView {
                     ..DetailsView::default()
                 }
             }
-            Details::Dump { dump_uid } => {
-                DetailsView { dump_uid: Some(dump_uid), ..DetailsView::default() }
+            Details::Dump { dump_uid, upload_error } => {
+                DetailsView { dump_uid: Some(dump_uid), upload_error, ..DetailsView::default() }
+            }
+            Details::SnapshotCreation { upload_error } => {
+                DetailsView { upload_error, ..DetailsView::default() }
             }
             Details::IndexSwap { swaps } => {
                 DetailsView { swaps: Some(swaps), ..Default::default() }
             }
+            Details::DumpImport { dump_uid, indexes, overwrite, imported_documents } => {
+                DetailsView {
+                    dump_uid: Some(Some(dump_uid)),
+                    indexes: Some(indexes),
+                    overwrite: Some(overwrite),
+                    imported_documents: Some(imported_documents),
+                    ..DetailsView::default()
+                }
+            }
         }
     }
 }
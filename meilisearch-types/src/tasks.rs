@@ -1,16 +1,19 @@
 use core::fmt;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fmt::{Display, Write};
 use std::str::FromStr;
 
 use enum_iterator::Sequence;
-use milli::update::IndexDocumentsMethod;
+use milli::update::{IndexDocumentsMethod, UpdateIndexingStep};
+use milli::Object;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize, Serializer};
 use time::{Duration, OffsetDateTime};
 use uuid::Uuid;
 
+use crate::batches::BatchId;
 use crate::error::ResponseError;
+use crate::index_uid_pattern::IndexUidPattern;
 use crate::keys::Key;
 use crate::settings::{Settings, Unchecked};
 use crate::InstanceUid;
@@ -32,6 +35,8 @@ pub struct Task {
     pub error: Option<ResponseError>,
     pub canceled_by: Option<TaskId>,
     pub details: Option<Details>,
+    #[serde(default)]
+    pub batch_uid: Option<BatchId>,
 
     pub status: Status,
     pub kind: KindWithContent,
@@ -43,6 +48,7 @@ impl Task {
 
         match &self.kind {
             DumpCreation { .. }
+            | DumpImport { .. }
             | SnapshotCreation
             | TaskCancelation { .. }
             | TaskDeletion { .. }
@@ -50,6 +56,7 @@ impl Task {
             DocumentAdditionOrUpdate { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
             | DocumentDeletionByFilter { index_uid, .. }
+            | DocumentEdition { index_uid, .. }
             | DocumentClear { index_uid }
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
@@ -69,6 +76,7 @@ impl Task {
             KindWithContent::DocumentAdditionOrUpdate { content_file, .. } => Some(content_file),
             KindWithContent::DocumentDeletion { .. }
             | KindWithContent::DocumentDeletionByFilter { .. }
+            | KindWithContent::DocumentEdition { .. }
             | KindWithContent::DocumentClear { .. }
             | KindWithContent::SettingsUpdate { .. }
             | KindWithContent::IndexDeletion { .. }
@@ -78,6 +86,7 @@ impl Task {
             | KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
             | KindWithContent::DumpCreation { .. }
+            | KindWithContent::DumpImport { .. }
             | KindWithContent::SnapshotCreation => None,
         }
     }
@@ -102,6 +111,12 @@ pub enum KindWithContent {
         index_uid: String,
         filter_expr: serde_json::Value,
     },
+    DocumentEdition {
+        index_uid: String,
+        filter_expr: Option<serde_json::Value>,
+        context: Option<Object>,
+        function: String,
+    },
     DocumentClear {
         index_uid: String,
     },
@@ -136,6 +151,18 @@ pub enum KindWithContent {
     DumpCreation {
         keys: Vec<Key>,
         instance_uid: Option<InstanceUid>,
+        /// The indexes to dump, all of them when `None`.
+        #[serde(default)]
+        index_uids: Option<Vec<IndexUidPattern>>,
+        #[serde(default = "default_true")]
+        include_tasks: bool,
+        #[serde(default = "default_true")]
+        include_keys: bool,
+    },
+    DumpImport {
+        dump_uid: String,
+        indexes: Vec<DumpImportIndex>,
+        overwrite: bool,
     },
     SnapshotCreation,
 }
@@ -146,12 +173,32 @@ pub struct IndexSwap {
     pub indexes: (String, String),
 }
 
+/// An index to import from a dump, under its own name unless it is renamed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpImportIndex {
+    pub index_uid: String,
+    pub rename_to: Option<String>,
+}
+
+impl DumpImportIndex {
+    /// The name of the index once imported.
+    pub fn target(&self) -> &str {
+        self.rename_to.as_deref().unwrap_or(&self.index_uid)
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
 impl KindWithContent {
     pub fn as_kind(&self) -> Kind {
         match self {
             KindWithContent::DocumentAdditionOrUpdate { .. } => Kind::DocumentAdditionOrUpdate,
             KindWithContent::DocumentDeletion { .. } => Kind::DocumentDeletion,
             KindWithContent::DocumentDeletionByFilter { .. } => Kind::DocumentDeletion,
+            KindWithContent::DocumentEdition { .. } => Kind::DocumentEdition,
             KindWithContent::DocumentClear { .. } => Kind::DocumentDeletion,
             KindWithContent::SettingsUpdate { .. } => Kind::SettingsUpdate,
             KindWithContent::IndexCreation { .. } => Kind::IndexCreation,
@@ -161,6 +208,7 @@ impl KindWithContent {
             KindWithContent::TaskCancelation { .. } => Kind::TaskCancelation,
             KindWithContent::TaskDeletion { .. } => Kind::TaskDeletion,
             KindWithContent::DumpCreation { .. } => Kind::DumpCreation,
+            KindWithContent::DumpImport { .. } => Kind::DumpImport,
             KindWithContent::SnapshotCreation => Kind::SnapshotCreation,
         }
     }
@@ -176,6 +224,7 @@ impl KindWithContent {
             DocumentAdditionOrUpdate { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
             | DocumentDeletionByFilter { index_uid, .. }
+            | DocumentEdition { index_uid, .. }
             | DocumentClear { index_uid }
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
@@ -189,6 +238,7 @@ impl KindWithContent {
                 }
                 indexes.into_iter().collect()
             }
+            DumpImport { indexes, .. } => indexes.iter().map(DumpImportIndex::target).collect(),
         }
     }
 
@@ -214,6 +264,15 @@ impl KindWithContent {
                     deleted_documents: None,
                 })
             }
+            KindWithContent::DocumentEdition { index_uid: _, filter_expr, context, function } => {
+                Some(Details::DocumentEdition {
+                    deleted_documents: None,
+                    edited_documents: None,
+                    original_filter: filter_expr.as_ref().map(|v| v.to_string()),
+                    context: context.clone(),
+                    function: function.clone(),
+                })
+            }
             KindWithContent::DocumentClear { .. } | KindWithContent::IndexDeletion { .. } => {
                 Some(Details::ClearAll { deleted_documents: None })
             }
@@ -237,7 +296,17 @@ impl KindWithContent {
                 deleted_tasks: None,
                 original_filter: query.clone(),
             }),
-            KindWithContent::DumpCreation { .. } => Some(Details::Dump { dump_uid: None }),
+            KindWithContent::DumpCreation { .. } => {
+                Some(Details::Dump { dump_uid: None, upload_error: None })
+            }
+            KindWithContent::DumpImport { dump_uid, indexes, overwrite } => {
+                Some(Details::DumpImport {
+                    dump_uid: dump_uid.clone(),
+                    indexes: indexes.clone(),
+                    overwrite: *overwrite,
+                    imported_documents: None,
+                })
+            }
             KindWithContent::SnapshotCreation => None,
         }
     }
@@ -262,6 +331,15 @@ impl KindWithContent {
                     deleted_documents: Some(0),
                 })
             }
+            KindWithContent::DocumentEdition { index_uid: _, filter_expr, context, function } => {
+                Some(Details::DocumentEdition {
+                    deleted_documents: Some(0),
+                    edited_documents: Some(0),
+                    original_filter: filter_expr.as_ref().map(|v| v.to_string()),
+                    context: context.clone(),
+                    function: function.clone(),
+                })
+            }
             KindWithContent::DocumentClear { .. } => {
                 Some(Details::ClearAll { deleted_documents: None })
             }
@@ -286,7 +364,17 @@ impl KindWithContent {
                 deleted_tasks: Some(0),
                 original_filter: query.clone(),
             }),
-            KindWithContent::DumpCreation { .. } => Some(Details::Dump { dump_uid: None }),
+            KindWithContent::DumpCreation { .. } => {
+                Some(Details::Dump { dump_uid: None, upload_error: None })
+            }
+            KindWithContent::DumpImport { dump_uid, indexes, overwrite } => {
+                Some(Details::DumpImport {
+                    dump_uid: dump_uid.clone(),
+                    indexes: indexes.clone(),
+                    overwrite: *overwrite,
+                    imported_documents: Some(0),
+                })
+            }
             KindWithContent::SnapshotCreation => None,
         }
     }
@@ -303,6 +391,7 @@ impl From<&KindWithContent> for Option<Details> {
             }
             KindWithContent::DocumentDeletion { .. } => None,
             KindWithContent::DocumentDeletionByFilter { .. } => None,
+            KindWithContent::DocumentEdition { .. } => None,
             KindWithContent::DocumentClear { .. } => None,
             KindWithContent::SettingsUpdate { new_settings, .. } => {
                 Some(Details::SettingsUpdate { settings: new_settings.clone() })
@@ -325,13 +414,25 @@ impl From<&KindWithContent> for Option<Details> {
                 deleted_tasks: None,
                 original_filter: query.clone(),
             }),
-            KindWithContent::DumpCreation { .. } => Some(Details::Dump { dump_uid: None }),
+            KindWithContent::DumpCreation { .. } => {
+                Some(Details::Dump { dump_uid: None, upload_error: None })
+            }
+            KindWithContent::DumpImport { dump_uid, indexes, overwrite } => {
+                Some(Details::DumpImport {
+                    dump_uid: dump_uid.clone(),
+                    indexes: indexes.clone(),
+                    overwrite: *overwrite,
+                    imported_documents: None,
+                })
+            }
             KindWithContent::SnapshotCreation => None,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Sequence)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Sequence,
+)]
 #[serde(rename_all = "camelCase")]
 pub enum Status {
     Enqueued,
@@ -390,10 +491,13 @@ impl fmt::Display for ParseTaskStatusError {
 }
 impl std::error::Error for ParseTaskStatusError {}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Sequence)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Sequence,
+)]
 #[serde(rename_all = "camelCase")]
 pub enum Kind {
     DocumentAdditionOrUpdate,
+    DocumentEdition,
     DocumentDeletion,
     SettingsUpdate,
     IndexCreation,
@@ -404,12 +508,14 @@ pub enum Kind {
     TaskDeletion,
     DumpCreation,
     SnapshotCreation,
+    DumpImport,
 }
 
 impl Kind {
     pub fn related_to_one_index(&self) -> bool {
         match self {
             Kind::DocumentAdditionOrUpdate
+            | Kind::DocumentEdition
             | Kind::DocumentDeletion
             | Kind::SettingsUpdate
             | Kind::IndexCreation
@@ -419,7 +525,8 @@ impl Kind {
             | Kind::TaskCancelation
             | Kind::TaskDeletion
             | Kind::DumpCreation
-            | Kind::SnapshotCreation => false,
+            | Kind::SnapshotCreation
+            | Kind::DumpImport => false,
         }
     }
 }
@@ -427,6 +534,7 @@ impl Display for Kind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Kind::DocumentAdditionOrUpdate => write!(f, "documentAdditionOrUpdate"),
+            Kind::DocumentEdition => write!(f, "documentEdition"),
             Kind::DocumentDeletion => write!(f, "documentDeletion"),
             Kind::SettingsUpdate => write!(f, "settingsUpdate"),
             Kind::IndexCreation => write!(f, "indexCreation"),
@@ -437,6 +545,7 @@ impl Display for Kind {
             Kind::TaskDeletion => write!(f, "taskDeletion"),
             Kind::DumpCreation => write!(f, "dumpCreation"),
             Kind::SnapshotCreation => write!(f, "snapshotCreation"),
+            Kind::DumpImport => write!(f, "dumpImport"),
         }
     }
 }
@@ -454,6 +563,8 @@ impl FromStr for Kind {
             Ok(Kind::IndexDeletion)
         } else if kind.eq_ignore_ascii_case("documentAdditionOrUpdate") {
             Ok(Kind::DocumentAdditionOrUpdate)
+        } else if kind.eq_ignore_ascii_case("documentEdition") {
+            Ok(Kind::DocumentEdition)
         } else if kind.eq_ignore_ascii_case("documentDeletion") {
             Ok(Kind::DocumentDeletion)
         } else if kind.eq_ignore_ascii_case("settingsUpdate") {
@@ -466,6 +577,8 @@ impl FromStr for Kind {
             Ok(Kind::DumpCreation)
         } else if kind.eq_ignore_ascii_case("snapshotCreation") {
             Ok(Kind::SnapshotCreation)
+        } else if kind.eq_ignore_ascii_case("dumpImport") {
+            Ok(Kind::DumpImport)
         } else {
             Err(ParseTaskKindError(kind.to_owned()))
         }
@@ -495,16 +608,63 @@ impl std::error::Error for ParseTaskKindError {}
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub enum Details {
-    DocumentAdditionOrUpdate { received_documents: u64, indexed_documents: Option<u64> },
-    SettingsUpdate { settings: Box<Settings<Unchecked>> },
-    IndexInfo { primary_key: Option<String> },
-    DocumentDeletion { provided_ids: usize, deleted_documents: Option<u64> },
-    DocumentDeletionByFilter { original_filter: String, deleted_documents: Option<u64> },
-    ClearAll { deleted_documents: Option<u64> },
-    TaskCancelation { matched_tasks: u64, canceled_tasks: Option<u64>, original_filter: String },
-    TaskDeletion { matched_tasks: u64, deleted_tasks: Option<u64>, original_filter: String },
-    Dump { dump_uid: Option<String> },
-    IndexSwap { swaps: Vec<IndexSwap> },
+    DocumentAdditionOrUpdate {
+        received_documents: u64,
+        indexed_documents: Option<u64>,
+    },
+    DocumentEdition {
+        deleted_documents: Option<u64>,
+        edited_documents: Option<u64>,
+        original_filter: Option<String>,
+        context: Option<Object>,
+        function: String,
+    },
+    SettingsUpdate {
+        settings: Box<Settings<Unchecked>>,
+    },
+    IndexInfo {
+        primary_key: Option<String>,
+    },
+    DocumentDeletion {
+        provided_ids: usize,
+        deleted_documents: Option<u64>,
+    },
+    DocumentDeletionByFilter {
+        original_filter: String,
+        deleted_documents: Option<u64>,
+    },
+    ClearAll {
+        deleted_documents: Option<u64>,
+    },
+    TaskCancelation {
+        matched_tasks: u64,
+        canceled_tasks: Option<u64>,
+        original_filter: String,
+    },
+    TaskDeletion {
+        matched_tasks: u64,
+        deleted_tasks: Option<u64>,
+        original_filter: String,
+    },
+    Dump {
+        dump_uid: Option<String>,
+        /// The error of the upload of the dump to the upload backend, if it failed.
+        #[serde(default)]
+        upload_error: Option<String>,
+    },
+    /// Only set when the upload of the snapshot to the upload backend failed.
+    SnapshotCreation {
+        upload_error: Option<String>,
+    },
+    IndexSwap {
+        swaps: Vec<IndexSwap>,
+    },
+    DumpImport {
+        dump_uid: String,
+        indexes: Vec<DumpImportIndex>,
+        overwrite: bool,
+        imported_documents: Option<u64>,
+    },
 }
 
 impl Details {
@@ -514,6 +674,10 @@ impl Details {
             Self::DocumentAdditionOrUpdate { indexed_documents, .. } => {
                 *indexed_documents = Some(0)
             }
+            Self::DocumentEdition { edited_documents, deleted_documents, .. } => {
+                *edited_documents = Some(0);
+                *deleted_documents = Some(0);
+            }
             Self::DocumentDeletion { deleted_documents, .. } => *deleted_documents = Some(0),
             Self::DocumentDeletionByFilter { deleted_documents, .. } => {
                 *deleted_documents = Some(0)
@@ -521,9 +685,11 @@ impl Details {
             Self::ClearAll { deleted_documents } => *deleted_documents = Some(0),
             Self::TaskCancelation { canceled_tasks, .. } => *canceled_tasks = Some(0),
             Self::TaskDeletion { deleted_tasks, .. } => *deleted_tasks = Some(0),
+            Self::DumpImport { imported_documents, .. } => *imported_documents = Some(0),
             Self::SettingsUpdate { .. }
             | Self::IndexInfo { .. }
             | Self::Dump { .. }
+            | Self::SnapshotCreation { .. }
             | Self::IndexSwap { .. } => (),
         }
 
@@ -531,6 +697,68 @@ impl Details {
     }
 }
 
+/// The progress of the indexation of a processing task, as reported by milli.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskProgress {
+    pub current_step: &'static str,
+    pub finished_steps: u16,
+    pub total_steps: u16,
+    pub finished_documents: Option<u32>,
+    pub total_documents: Option<u32>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub embedders: BTreeMap<String, EmbedderProgress>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbedderProgress {
+    pub finished_documents: u32,
+    pub total_documents: u32,
+}
+
+impl TaskProgress {
+    pub fn update(&mut self, step: UpdateIndexingStep) {
+        let (finished_steps, total_steps) = (step.step() as u16, step.number_of_steps() as u16);
+        let (current_step, finished_documents, total_documents) = match step {
+            UpdateIndexingStep::RemapDocumentAddition { documents_seen } => {
+                ("remapping documents", Some(documents_seen), None)
+            }
+            UpdateIndexingStep::ComputeIdsAndMergeDocuments { documents_seen, total_documents } => {
+                ("computing documents ids", Some(documents_seen), Some(total_documents))
+            }
+            UpdateIndexingStep::IndexDocuments { documents_seen, total_documents } => {
+                ("indexing documents", Some(documents_seen), Some(total_documents))
+            }
+            UpdateIndexingStep::MergeDataIntoFinalDatabase { .. } => {
+                ("merging into the final databases", None, None)
+            }
+            UpdateIndexingStep::EmbedDocuments {
+                embedder_name,
+                documents_seen,
+                total_documents,
+            } => {
+                // The embeddings are stored along with the documents indexation,
+                // we don't want to change the current step for that.
+                self.embedders.insert(
+                    embedder_name,
+                    EmbedderProgress {
+                        finished_documents: documents_seen as u32,
+                        total_documents: total_documents as u32,
+                    },
+                );
+                return;
+            }
+        };
+
+        self.current_step = current_step;
+        self.finished_steps = finished_steps;
+        self.total_steps = total_steps;
+        self.finished_documents = finished_documents.map(|n| n as u32);
+        self.total_documents = total_documents.map(|n| n as u32);
+    }
+}
+
 /// Serialize a `time::Duration` as a best effort ISO 8601 while waiting for
 /// https://github.com/time-rs/time/issues/378.
 /// This code is a port of the old code of time that was removed in 0.2.
@@ -584,7 +812,9 @@ pub fn serialize_duration<S: Serializer>(
 
 #[cfg(test)]
 mod tests {
-    use super::Details;
+    use milli::update::UpdateIndexingStep;
+
+    use super::{Details, TaskProgress};
     use crate::heed::types::SerdeJson;
     use crate::heed::{BytesDecode, BytesEncode};
 
@@ -600,4 +830,53 @@ mod tests {
         meili_snap::snapshot!(format!("{:?}", details), @r###"TaskDeletion { matched_tasks: 1, deleted_tasks: None, original_filter: "hello" }"###);
         meili_snap::snapshot!(format!("{:?}", deserialised), @r###"TaskDeletion { matched_tasks: 1, deleted_tasks: None, original_filter: "hello" }"###);
     }
+
+    #[test]
+    fn task_progress() {
+        let mut progress = TaskProgress::default();
+        progress.update(UpdateIndexingStep::IndexDocuments {
+            documents_seen: 10,
+            total_documents: 100,
+        });
+        progress.update(UpdateIndexingStep::EmbedDocuments {
+            embedder_name: "default".to_owned(),
+            documents_seen: 5,
+            total_documents: 100,
+        });
+        meili_snap::snapshot!(meili_snap::json_string!(progress), @r###"
+        {
+          "currentStep": "indexing documents",
+          "finishedSteps": 2,
+          "totalSteps": 4,
+          "finishedDocuments": 10,
+          "totalDocuments": 100,
+          "embedders": {
+            "default": {
+              "finishedDocuments": 5,
+              "totalDocuments": 100
+            }
+          }
+        }
+        "###);
+
+        progress.update(UpdateIndexingStep::MergeDataIntoFinalDatabase {
+            databases_seen: 1,
+            total_databases: 10,
+        });
+        meili_snap::snapshot!(meili_snap::json_string!(progress), @r###"
+        {
+          "currentStep": "merging into the final databases",
+          "finishedSteps": 3,
+          "totalSteps": 4,
+          "finishedDocuments": null,
+          "totalDocuments": null,
+          "embedders": {
+            "default": {
+              "finishedDocuments": 5,
+              "totalDocuments": 100
+            }
+          }
+        }
+        "###);
+    }
 }
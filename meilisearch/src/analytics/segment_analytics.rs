@@ -1,6 +1,7 @@
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
 use std::fs;
 use std::mem::take;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -29,14 +30,14 @@ use super::{
 };
 use crate::analytics::Analytics;
 use crate::option::{
-    default_http_addr, IndexerOpts, LogMode, MaxMemory, MaxThreads, ScheduleSnapshot,
+    default_http_addr, IndexerOpts, LogMode, MaxMemory, MaxThreads, ScheduleSnapshot, UploadOpts,
 };
-use crate::routes::indexes::documents::UpdateDocumentsQuery;
+use crate::routes::indexes::documents::{DocumentEditionByFunction, UpdateDocumentsQuery};
 use crate::routes::indexes::facet_search::FacetSearchQuery;
 use crate::routes::{create_all_stats, Stats};
 use crate::search::{
-    FacetSearchResult, MatchingStrategy, SearchQuery, SearchQueryWithIndex, SearchResult,
-    SimilarQuery, SimilarResult, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
+    FacetSearchResult, FederatedSearch, MatchingStrategy, SearchQuery, SearchQueryWithIndex,
+    SearchResult, SimilarQuery, SimilarResult, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
     DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
     DEFAULT_SEMANTIC_RATIO,
 };
@@ -81,6 +82,7 @@ pub enum AnalyticsMsg {
     AggregateAddDocuments(DocumentsAggregator),
     AggregateDeleteDocuments(DocumentsDeletionAggregator),
     AggregateUpdateDocuments(DocumentsAggregator),
+    AggregateEditDocumentsByFunction(EditDocumentsByFunctionAggregator),
     AggregateGetFetchDocuments(DocumentsFetchAggregator),
     AggregatePostFetchDocuments(DocumentsFetchAggregator),
 }
@@ -150,6 +152,7 @@ impl SegmentAnalytics {
             add_documents_aggregator: DocumentsAggregator::default(),
             delete_documents_aggregator: DocumentsDeletionAggregator::default(),
             update_documents_aggregator: DocumentsAggregator::default(),
+            edit_documents_by_function_aggregator: EditDocumentsByFunctionAggregator::default(),
             get_fetch_documents_aggregator: DocumentsFetchAggregator::default(),
             post_fetch_documents_aggregator: DocumentsFetchAggregator::default(),
             get_similar_aggregator: SimilarAggregator::default(),
@@ -230,6 +233,15 @@ impl super::Analytics for SegmentAnalytics {
         let _ = self.sender.try_send(AnalyticsMsg::AggregateUpdateDocuments(aggregate));
     }
 
+    fn update_documents_by_function(
+        &self,
+        documents_query: &DocumentEditionByFunction,
+        request: &HttpRequest,
+    ) {
+        let aggregate = EditDocumentsByFunctionAggregator::from_query(documents_query, request);
+        let _ = self.sender.try_send(AnalyticsMsg::AggregateEditDocumentsByFunction(aggregate));
+    }
+
     fn get_fetch_documents(&self, documents_query: &DocumentFetchKind, request: &HttpRequest) {
         let aggregate = DocumentsFetchAggregator::from_query(documents_query, request);
         let _ = self.sender.try_send(AnalyticsMsg::AggregateGetFetchDocuments(aggregate));
@@ -251,11 +263,18 @@ struct Infos {
     env: String,
     experimental_enable_metrics: bool,
     experimental_search_queue_size: usize,
+    experimental_search_cache_size: usize,
+    experimental_search_cache_ttl: u64,
+    experimental_suggest_query_log: bool,
     experimental_logs_mode: LogMode,
     experimental_replication_parameters: bool,
     experimental_enable_logs_route: bool,
     experimental_reduce_indexing_memory_usage: bool,
+    experimental_document_compression: bool,
     experimental_max_number_of_batched_tasks: usize,
+    experimental_incremental_snapshots: bool,
+    experimental_upload_backend: Option<&'static str>,
+    experimental_upload_retention: Option<NonZeroUsize>,
     gpu_enabled: bool,
     db_path: bool,
     import_dump: bool,
@@ -293,11 +312,15 @@ impl From<Opt> for Infos {
             db_path,
             experimental_enable_metrics,
             experimental_search_queue_size,
+            experimental_search_cache_size,
+            experimental_search_cache_ttl,
+            experimental_suggest_query_log,
             experimental_logs_mode,
             experimental_replication_parameters,
             experimental_enable_logs_route,
             experimental_reduce_indexing_memory_usage,
             experimental_max_number_of_batched_tasks,
+            experimental_incremental_snapshots,
             http_addr,
             master_key: _,
             env,
@@ -324,6 +347,7 @@ impl From<Opt> for Infos {
             dump_dir,
             log_level,
             indexer_options,
+            upload_options,
             config_file_path,
             #[cfg(feature = "analytics")]
                 no_analytics: _,
@@ -334,8 +358,29 @@ impl From<Opt> for Infos {
             ScheduleSnapshot::Enabled(interval) => Some(interval),
         };
 
-        let IndexerOpts { max_indexing_memory, max_indexing_threads, skip_index_budget: _ } =
-            indexer_options;
+        let IndexerOpts {
+            max_indexing_memory,
+            max_indexing_threads,
+            skip_index_budget: _,
+            experimental_document_compression,
+        } = indexer_options;
+
+        let UploadOpts {
+            experimental_upload_dir,
+            experimental_upload_s3_endpoint,
+            experimental_upload_s3_bucket: _,
+            experimental_upload_s3_region: _,
+            experimental_upload_s3_prefix: _,
+            experimental_upload_s3_access_key: _,
+            experimental_upload_s3_secret_key: _,
+            experimental_upload_retention,
+        } = upload_options;
+        let experimental_upload_backend =
+            match (experimental_upload_dir, experimental_upload_s3_endpoint) {
+                (_, Some(_)) => Some("s3"),
+                (Some(_), None) => Some("local"),
+                (None, None) => None,
+            };
 
         // We're going to override every sensible information.
         // We consider information sensible if it contains a path, an address, or a key.
@@ -343,10 +388,14 @@ impl From<Opt> for Infos {
             env,
             experimental_enable_metrics,
             experimental_search_queue_size,
+            experimental_search_cache_size,
+            experimental_search_cache_ttl,
+            experimental_suggest_query_log,
             experimental_logs_mode,
             experimental_replication_parameters,
             experimental_enable_logs_route,
             experimental_reduce_indexing_memory_usage,
+            experimental_document_compression,
             gpu_enabled: meilisearch_types::milli::vector::is_cuda_enabled(),
             db_path: db_path != PathBuf::from("./data.ms"),
             import_dump: import_dump.is_some(),
@@ -361,6 +410,9 @@ impl From<Opt> for Infos {
             http_addr: http_addr != default_http_addr(),
             http_payload_size_limit,
             experimental_max_number_of_batched_tasks,
+            experimental_incremental_snapshots,
+            experimental_upload_backend,
+            experimental_upload_retention,
             task_queue_webhook: task_webhook_url.is_some(),
             task_webhook_authorization_header: task_webhook_authorization_header.is_some(),
             log_level: log_level.to_string(),
@@ -390,6 +442,7 @@ pub struct Segment {
     add_documents_aggregator: DocumentsAggregator,
     delete_documents_aggregator: DocumentsDeletionAggregator,
     update_documents_aggregator: DocumentsAggregator,
+    edit_documents_by_function_aggregator: EditDocumentsByFunctionAggregator,
     get_fetch_documents_aggregator: DocumentsFetchAggregator,
     post_fetch_documents_aggregator: DocumentsFetchAggregator,
     get_similar_aggregator: SimilarAggregator,
@@ -454,6 +507,7 @@ impl Segment {
                         Some(AnalyticsMsg::AggregateAddDocuments(agreg)) => self.add_documents_aggregator.aggregate(agreg),
                         Some(AnalyticsMsg::AggregateDeleteDocuments(agreg)) => self.delete_documents_aggregator.aggregate(agreg),
                         Some(AnalyticsMsg::AggregateUpdateDocuments(agreg)) => self.update_documents_aggregator.aggregate(agreg),
+                        Some(AnalyticsMsg::AggregateEditDocumentsByFunction(agreg)) => self.edit_documents_by_function_aggregator.aggregate(agreg),
                         Some(AnalyticsMsg::AggregateGetFetchDocuments(agreg)) => self.get_fetch_documents_aggregator.aggregate(agreg),
                         Some(AnalyticsMsg::AggregatePostFetchDocuments(agreg)) => self.post_fetch_documents_aggregator.aggregate(agreg),
                         Some(AnalyticsMsg::AggregateGetSimilar(agreg)) => self.get_similar_aggregator.aggregate(agreg),
@@ -509,6 +563,7 @@ impl Segment {
             add_documents_aggregator,
             delete_documents_aggregator,
             update_documents_aggregator,
+            edit_documents_by_function_aggregator,
             get_fetch_documents_aggregator,
             post_fetch_documents_aggregator,
             get_similar_aggregator,
@@ -550,6 +605,11 @@ impl Segment {
         {
             let _ = self.batcher.push(update_documents).await;
         }
+        if let Some(edit_documents_by_function) = take(edit_documents_by_function_aggregator)
+            .into_event(user, "Documents Edited By Function")
+        {
+            let _ = self.batcher.push(edit_documents_by_function).await;
+        }
         if let Some(get_fetch_documents) =
             take(get_fetch_documents_aggregator).into_event(user, "Documents Fetched GET")
         {
@@ -599,6 +659,8 @@ pub struct SearchAggregator {
 
     // distinct
     distinct: bool,
+    // every time a search is done returning the distinct groups of the hits
+    distinct_groups_total_number_of_uses: usize,
 
     // filter
     filter_with_geo_radius: bool,
@@ -613,6 +675,14 @@ pub struct SearchAggregator {
     // every time a search is done using attributes_to_search_on
     attributes_to_search_on_total_number_of_uses: usize,
 
+    // ranking_rules
+    // every time a search is done overriding the ranking rules
+    ranking_rules_total_number_of_uses: usize,
+
+    // boost
+    // every time a search is done using boost filters
+    boost_total_number_of_uses: usize,
+
     // q
     // The maximum number of terms in a q request
     max_terms_number: usize,
@@ -653,6 +723,9 @@ pub struct SearchAggregator {
     show_ranking_score: bool,
     show_ranking_score_details: bool,
     ranking_score_threshold: bool,
+
+    // locales
+    locales: BTreeSet<String>,
 }
 
 impl SearchAggregator {
@@ -676,7 +749,10 @@ impl SearchAggregator {
             filter,
             sort,
             distinct,
+            distinct_group_limit,
             facets: _,
+            facet_aggregations: _,
+            facet_histograms: _,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -684,6 +760,9 @@ impl SearchAggregator {
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
+            locales,
+            ranking_rules,
+            boost,
         } = query;
 
         let mut ret = Self::default();
@@ -699,6 +778,9 @@ impl SearchAggregator {
         }
 
         ret.distinct = distinct.is_some();
+        if distinct_group_limit.is_some() {
+            ret.distinct_groups_total_number_of_uses = 1;
+        }
 
         if let Some(ref filter) = filter {
             static RE: Lazy<Regex> = Lazy::new(|| Regex::new("AND | OR").unwrap());
@@ -729,6 +811,16 @@ impl SearchAggregator {
             ret.attributes_to_search_on_total_number_of_uses = 1;
         }
 
+        // ranking_rules
+        if ranking_rules.is_some() {
+            ret.ranking_rules_total_number_of_uses = 1;
+        }
+
+        // boost
+        if boost.is_some() {
+            ret.boost_total_number_of_uses = 1;
+        }
+
         if let Some(ref q) = q {
             ret.max_terms_number = q.split_whitespace().count();
         }
@@ -761,6 +853,10 @@ impl SearchAggregator {
         ret.show_ranking_score_details = *show_ranking_score_details;
         ret.ranking_score_threshold = ranking_score_threshold.is_some();
 
+        if let Some(locales) = locales {
+            ret.locales = locales.iter().map(|locale| locale.code().to_string()).collect();
+        }
+
         if let Some(hybrid) = hybrid {
             ret.semantic_ratio = hybrid.semantic_ratio != DEFAULT_SEMANTIC_RATIO();
             ret.embedder = hybrid.embedder.is_some();
@@ -779,6 +875,7 @@ impl SearchAggregator {
             semantic_hit_count: _,
             facet_distribution: _,
             facet_stats: _,
+            applied_rules: _,
             degraded,
             used_negative_operator,
         } = result;
@@ -805,12 +902,15 @@ impl SearchAggregator {
             sort_sum_of_criteria_terms,
             sort_total_number_of_criteria,
             distinct,
+            distinct_groups_total_number_of_uses,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
             attributes_to_search_on_total_number_of_uses,
+            ranking_rules_total_number_of_uses,
+            boost_total_number_of_uses,
             max_terms_number,
             max_vector_size,
             retrieve_vectors,
@@ -836,6 +936,7 @@ impl SearchAggregator {
             total_degraded,
             total_used_negative_operator,
             ranking_score_threshold,
+            ref mut locales,
         } = other;
 
         if self.timestamp.is_none() {
@@ -864,6 +965,9 @@ impl SearchAggregator {
 
         // distinct
         self.distinct |= distinct;
+        self.distinct_groups_total_number_of_uses = self
+            .distinct_groups_total_number_of_uses
+            .saturating_add(distinct_groups_total_number_of_uses);
 
         // filter
         self.filter_with_geo_radius |= filter_with_geo_radius;
@@ -882,6 +986,15 @@ impl SearchAggregator {
             .attributes_to_search_on_total_number_of_uses
             .saturating_add(attributes_to_search_on_total_number_of_uses);
 
+        // ranking_rules
+        self.ranking_rules_total_number_of_uses = self
+            .ranking_rules_total_number_of_uses
+            .saturating_add(ranking_rules_total_number_of_uses);
+
+        // boost
+        self.boost_total_number_of_uses =
+            self.boost_total_number_of_uses.saturating_add(boost_total_number_of_uses);
+
         // q
         self.max_terms_number = self.max_terms_number.max(max_terms_number);
 
@@ -924,6 +1037,9 @@ impl SearchAggregator {
         self.show_ranking_score |= show_ranking_score;
         self.show_ranking_score_details |= show_ranking_score_details;
         self.ranking_score_threshold |= ranking_score_threshold;
+
+        // locales
+        self.locales.append(locales);
     }
 
     pub fn into_event(self, user: &User, event_name: &str) -> Option<Track> {
@@ -937,12 +1053,15 @@ impl SearchAggregator {
             sort_sum_of_criteria_terms,
             sort_total_number_of_criteria,
             distinct,
+            distinct_groups_total_number_of_uses,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
             attributes_to_search_on_total_number_of_uses,
+            ranking_rules_total_number_of_uses,
+            boost_total_number_of_uses,
             max_terms_number,
             max_vector_size,
             retrieve_vectors,
@@ -968,6 +1087,7 @@ impl SearchAggregator {
             total_degraded,
             total_used_negative_operator,
             ranking_score_threshold,
+            locales,
         } = self;
 
         if total_received == 0 {
@@ -995,6 +1115,9 @@ impl SearchAggregator {
                     "avg_criteria_number": format!("{:.2}", sort_sum_of_criteria_terms as f64 / sort_total_number_of_criteria as f64),
                 },
                 "distinct": distinct,
+                "distinct_groups": {
+                   "total_number_of_uses": distinct_groups_total_number_of_uses,
+                },
                 "filter": {
                    "with_geoRadius": filter_with_geo_radius,
                    "with_geoBoundingBox": filter_with_geo_bounding_box,
@@ -1004,6 +1127,12 @@ impl SearchAggregator {
                 "attributes_to_search_on": {
                    "total_number_of_uses": attributes_to_search_on_total_number_of_uses,
                 },
+                "ranking_rules": {
+                   "total_number_of_uses": ranking_rules_total_number_of_uses,
+                },
+                "boost": {
+                   "total_number_of_uses": boost_total_number_of_uses,
+                },
                 "q": {
                    "max_terms_number": max_terms_number,
                 },
@@ -1042,6 +1171,7 @@ impl SearchAggregator {
                     "show_ranking_score_details": show_ranking_score_details,
                     "ranking_score_threshold": ranking_score_threshold,
                 },
+                "locales": locales,
             });
 
             Some(Track {
@@ -1071,6 +1201,9 @@ pub struct MultiSearchAggregator {
     // sum of the number of search queries in the requests, use with total_received to compute an average
     total_search_count: usize,
 
+    // federation
+    use_federation: bool,
+
     // scoring
     show_ranking_score: bool,
     show_ranking_score_details: bool,
@@ -1080,11 +1213,17 @@ pub struct MultiSearchAggregator {
 }
 
 impl MultiSearchAggregator {
-    pub fn from_queries(query: &[SearchQueryWithIndex], request: &HttpRequest) -> Self {
+    pub fn from_federated_search(
+        federated_search: &FederatedSearch,
+        request: &HttpRequest,
+    ) -> Self {
         let timestamp = Some(OffsetDateTime::now_utc());
 
         let user_agents = extract_user_agents(request).into_iter().collect();
 
+        let FederatedSearch { queries: query, federation } = federated_search;
+        let use_federation = federation.is_some();
+
         let distinct_indexes: HashSet<_> = query
             .iter()
             .map(|query| {
@@ -1108,7 +1247,10 @@ impl MultiSearchAggregator {
                     filter: _,
                     sort: _,
                     distinct: _,
+                    distinct_group_limit: _,
                     facets: _,
+                    facet_aggregations: _,
+                    facet_histograms: _,
                     highlight_pre_tag: _,
                     highlight_post_tag: _,
                     crop_marker: _,
@@ -1116,6 +1258,10 @@ impl MultiSearchAggregator {
                     attributes_to_search_on: _,
                     hybrid: _,
                     ranking_score_threshold: _,
+                    federation_options: _,
+                    locales: _,
+                    ranking_rules: _,
+                    boost: _,
                 } = query;
 
                 index_uid.as_str()
@@ -1132,6 +1278,7 @@ impl MultiSearchAggregator {
             total_distinct_index_count: distinct_indexes.len(),
             total_single_index: if distinct_indexes.len() == 1 { 1 } else { 0 },
             total_search_count: query.len(),
+            use_federation,
             show_ranking_score,
             show_ranking_score_details,
             user_agents,
@@ -1156,6 +1303,7 @@ impl MultiSearchAggregator {
             this.total_distinct_index_count.saturating_add(other.total_distinct_index_count);
         let total_single_index = this.total_single_index.saturating_add(other.total_single_index);
         let total_search_count = this.total_search_count.saturating_add(other.total_search_count);
+        let use_federation = this.use_federation || other.use_federation;
         let show_ranking_score = this.show_ranking_score || other.show_ranking_score;
         let show_ranking_score_details =
             this.show_ranking_score_details || other.show_ranking_score_details;
@@ -1173,6 +1321,7 @@ impl MultiSearchAggregator {
             total_distinct_index_count,
             total_single_index,
             total_search_count,
+            use_federation,
             user_agents,
             show_ranking_score,
             show_ranking_score_details,
@@ -1191,6 +1340,7 @@ impl MultiSearchAggregator {
             total_distinct_index_count,
             total_single_index,
             total_search_count,
+            use_federation,
             user_agents,
             show_ranking_score,
             show_ranking_score_details,
@@ -1218,6 +1368,9 @@ impl MultiSearchAggregator {
                 "scoring": {
                     "show_ranking_score": show_ranking_score,
                     "show_ranking_score_details": show_ranking_score_details,
+                },
+                "federation": {
+                    "use_federation": use_federation,
                 }
             });
 
@@ -1249,6 +1402,9 @@ pub struct FacetSearchAggregator {
 
     // As there been any other parameter than the facetName or facetQuery ones?
     additional_search_parameters_provided: bool,
+
+    // Has exact facet counts been requested at least once?
+    exhaustive_facet_count: bool,
 }
 
 impl FacetSearchAggregator {
@@ -1264,6 +1420,8 @@ impl FacetSearchAggregator {
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
+            locales,
+            exhaustive_facet_count,
         } = query;
 
         let mut ret = Self::default();
@@ -1271,6 +1429,7 @@ impl FacetSearchAggregator {
 
         ret.total_received = 1;
         ret.user_agents = extract_user_agents(request).into_iter().collect();
+        ret.exhaustive_facet_count = *exhaustive_facet_count;
         ret.facet_names = Some(facet_name.clone()).into_iter().collect();
 
         ret.additional_search_parameters_provided = q.is_some()
@@ -1279,13 +1438,19 @@ impl FacetSearchAggregator {
             || *matching_strategy != MatchingStrategy::default()
             || attributes_to_search_on.is_some()
             || hybrid.is_some()
-            || ranking_score_threshold.is_some();
+            || ranking_score_threshold.is_some()
+            || locales.is_some();
 
         ret
     }
 
     pub fn succeed(&mut self, result: &FacetSearchResult) {
-        let FacetSearchResult { facet_hits: _, facet_query: _, processing_time_ms } = result;
+        let FacetSearchResult {
+            facet_hits: _,
+            facet_query: _,
+            exhaustive_facet_count: _,
+            processing_time_ms,
+        } = result;
         self.total_succeeded = self.total_succeeded.saturating_add(1);
         self.time_spent.push(*processing_time_ms as usize);
     }
@@ -1300,6 +1465,7 @@ impl FacetSearchAggregator {
             ref mut time_spent,
             facet_names,
             additional_search_parameters_provided,
+            exhaustive_facet_count,
         } = other;
 
         if self.timestamp.is_none() {
@@ -1323,6 +1489,9 @@ impl FacetSearchAggregator {
 
         // additional_search_parameters_provided
         self.additional_search_parameters_provided |= additional_search_parameters_provided;
+
+        // exhaustive_facet_count
+        self.exhaustive_facet_count |= exhaustive_facet_count;
     }
 
     pub fn into_event(self, user: &User, event_name: &str) -> Option<Track> {
@@ -1334,6 +1503,7 @@ impl FacetSearchAggregator {
             time_spent,
             facet_names,
             additional_search_parameters_provided,
+            exhaustive_facet_count,
         } = self;
 
         if total_received == 0 {
@@ -1357,6 +1527,7 @@ impl FacetSearchAggregator {
                 "facets": {
                     "total_distinct_facet_count": facet_names.len(),
                     "additional_search_parameters_provided": additional_search_parameters_provided,
+                    "exhaustive_facet_count": exhaustive_facet_count,
                 },
             });
 
@@ -1466,6 +1637,69 @@ impl DocumentsAggregator {
     }
 }
 
+#[derive(Default, Serialize)]
+pub struct EditDocumentsByFunctionAggregator {
+    #[serde(skip)]
+    timestamp: Option<OffsetDateTime>,
+
+    // Set to true if at least one request was filtered
+    filtered: bool,
+    // Set to true if at least one request contained a context
+    with_context: bool,
+
+    // context
+    #[serde(rename = "user-agent")]
+    user_agents: HashSet<String>,
+
+    #[serde(rename = "requests.total_received")]
+    total_received: usize,
+}
+
+impl EditDocumentsByFunctionAggregator {
+    pub fn from_query(documents_query: &DocumentEditionByFunction, request: &HttpRequest) -> Self {
+        let DocumentEditionByFunction { filter, context, function: _ } = documents_query;
+
+        Self {
+            timestamp: Some(OffsetDateTime::now_utc()),
+            filtered: filter.is_some(),
+            with_context: context.is_some(),
+            user_agents: extract_user_agents(request).into_iter().collect(),
+            total_received: 1,
+        }
+    }
+
+    /// Aggregate one [EditDocumentsByFunctionAggregator] into another.
+    pub fn aggregate(&mut self, other: Self) {
+        let Self { timestamp, filtered, with_context, user_agents, total_received } = other;
+
+        if self.timestamp.is_none() {
+            self.timestamp = timestamp;
+        }
+
+        // we can't create a union because there is no `into_union` method
+        for user_agent in user_agents {
+            self.user_agents.insert(user_agent);
+        }
+        self.total_received = self.total_received.saturating_add(total_received);
+        self.filtered |= filtered;
+        self.with_context |= with_context;
+    }
+
+    pub fn into_event(self, user: &User, event_name: &str) -> Option<Track> {
+        // if we had no timestamp it means we never encountered any events and
+        // thus we don't need to send this event.
+        let timestamp = self.timestamp?;
+
+        Some(Track {
+            timestamp: Some(timestamp),
+            user: user.clone(),
+            event: event_name.to_string(),
+            properties: serde_json::to_value(self).ok()?,
+            ..Default::default()
+        })
+    }
+}
+
 #[derive(Default, Serialize)]
 pub struct DocumentsDeletionAggregator {
     #[serde(skip)]
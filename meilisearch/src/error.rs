@@ -39,6 +39,12 @@ pub enum MeilisearchHttpError {
         .0.iter().map(|uid| format!("\"{uid}\"")).collect::<Vec<_>>().join(", "), .0.len()
     )]
     SwapIndexPayloadWrongLength(Vec<IndexUid>),
+    #[error("`{0}` is not a valid dump uid. A dump uid is the name of a dump file without its `.dump` extension, it can only contain alphanumeric characters, hyphens (-) and underscores (_).")]
+    InvalidDumpUid(String),
+    #[error("At least one index must be given to import a dump.")]
+    EmptyDumpImportIndexes,
+    #[error("Indexes must be declared only once during a dump import. `{0}` was specified several times.")]
+    DumpImportDuplicateIndex(String),
     #[error(transparent)]
     IndexUid(#[from] IndexUidFormatError),
     #[error(transparent)]
@@ -57,8 +63,24 @@ pub enum MeilisearchHttpError {
     DocumentFormat(#[from] DocumentFormatError),
     #[error(transparent)]
     Join(#[from] JoinError),
+    #[error("Invalid value in `facetHistograms`: `{0}` is not one of the `facets` of the search. A histogram can only be computed on an attribute listed in `facets`.")]
+    FacetHistogramWithoutFacet(String),
     #[error("Invalid request: missing `hybrid` parameter when both `q` and `vector` are present.")]
     MissingSearchHybrid,
+    #[error("The tenant token forbids using the `rankingRules` search parameter.")]
+    RankingRulesOverrideForbidden,
+    #[error("The tenant token forbids suggestions on an index restricted by a filter.")]
+    SuggestForbidden,
+    #[error("`{0}` is not a valid rule uid. Rule uid can be an integer or a string containing only alphanumeric characters, hyphens (-) and underscores (_), and can not be more than 400 bytes.")]
+    InvalidRuleUid(String),
+    #[error("Rule `{0}` not found.")]
+    RuleNotFound(String),
+    #[error("Invalid regular expression `{0}` in the rule condition: {1}")]
+    InvalidRuleRegex(String, String),
+    #[error("Invalid filter in the rule condition: {0}")]
+    InvalidRuleConditionFilter(String),
+    #[error("Invalid filter in the rule actions: {0}")]
+    InvalidRuleActionsFilter(String),
 }
 
 impl ErrorCode for MeilisearchHttpError {
@@ -76,6 +98,9 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::TooManySearchRequests(_) => Code::TooManySearchRequests,
             MeilisearchHttpError::SearchLimiterIsDown => Code::Internal,
             MeilisearchHttpError::SwapIndexPayloadWrongLength(_) => Code::InvalidSwapIndexes,
+            MeilisearchHttpError::InvalidDumpUid(_) => Code::InvalidDumpImportDumpUid,
+            MeilisearchHttpError::EmptyDumpImportIndexes => Code::InvalidDumpImportIndexes,
+            MeilisearchHttpError::DumpImportDuplicateIndex(_) => Code::InvalidDumpImportIndexes,
             MeilisearchHttpError::IndexUid(e) => e.error_code(),
             MeilisearchHttpError::SerdeJson(_) => Code::Internal,
             MeilisearchHttpError::HeedError(_) => Code::Internal,
@@ -85,7 +110,17 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::FileStore(_) => Code::Internal,
             MeilisearchHttpError::DocumentFormat(e) => e.error_code(),
             MeilisearchHttpError::Join(_) => Code::Internal,
+            MeilisearchHttpError::FacetHistogramWithoutFacet(_) => {
+                Code::InvalidSearchFacetHistograms
+            }
             MeilisearchHttpError::MissingSearchHybrid => Code::MissingSearchHybrid,
+            MeilisearchHttpError::RankingRulesOverrideForbidden => Code::InvalidSearchRankingRules,
+            MeilisearchHttpError::SuggestForbidden => Code::InvalidApiKey,
+            MeilisearchHttpError::InvalidRuleUid(_) => Code::InvalidRuleUid,
+            MeilisearchHttpError::RuleNotFound(_) => Code::RuleNotFound,
+            MeilisearchHttpError::InvalidRuleRegex(_, _) => Code::InvalidRuleCondition,
+            MeilisearchHttpError::InvalidRuleConditionFilter(_) => Code::InvalidRuleCondition,
+            MeilisearchHttpError::InvalidRuleActionsFilter(_) => Code::InvalidRuleActions,
         }
     }
 }
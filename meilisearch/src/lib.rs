@@ -7,14 +7,16 @@ pub mod extractors;
 pub mod metrics;
 pub mod middleware;
 pub mod option;
+pub mod query_log;
 pub mod routes;
 pub mod search;
+pub mod search_cache;
 pub mod search_queue;
 
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
 use std::num::NonZeroUsize;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::thread::{self, available_parallelism};
 use std::time::Duration;
@@ -30,7 +32,7 @@ use anyhow::bail;
 use error::PayloadError;
 use extractors::payload::PayloadConfig;
 use http::header::CONTENT_TYPE;
-use index_scheduler::{IndexScheduler, IndexSchedulerOptions};
+use index_scheduler::{IndexScheduler, IndexSchedulerOptions, DUMPS_DIR, SNAPSHOTS_DIR};
 use meilisearch_auth::AuthController;
 use meilisearch_types::milli::documents::{DocumentsBatchBuilder, DocumentsBatchReader};
 use meilisearch_types::milli::update::{IndexDocumentsConfig, IndexDocumentsMethod};
@@ -40,7 +42,10 @@ use meilisearch_types::versioning::{check_version_file, create_version_file};
 use meilisearch_types::{compression, milli, VERSION_FILE_NAME};
 pub use option::Opt;
 use option::ScheduleSnapshot;
+use query_log::QueryLog;
+use search_cache::SearchCache;
 use search_queue::SearchQueue;
+use tempfile::TempDir;
 use tracing::{error, info_span};
 use tracing_subscriber::filter::Targets;
 
@@ -117,6 +122,8 @@ pub type LogStderrType = tracing_subscriber::filter::Filtered<
 pub fn create_app(
     index_scheduler: Data<IndexScheduler>,
     auth_controller: Data<AuthController>,
+    search_cache: Data<SearchCache>,
+    query_log: Data<QueryLog>,
     opt: Opt,
     logs: (LogRouteHandle, LogStderrHandle),
     analytics: Arc<dyn Analytics>,
@@ -136,6 +143,8 @@ pub fn create_app(
                 s,
                 index_scheduler.clone(),
                 auth_controller.clone(),
+                search_cache.clone(),
+                query_log.clone(),
                 &opt,
                 logs,
                 analytics.clone(),
@@ -204,10 +213,24 @@ enum OnFailure {
 pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Arc<AuthController>)> {
     let empty_db = is_empty_db(&opt.db_path);
     let (index_scheduler, auth_controller) = if let Some(ref snapshot_path) = opt.import_snapshot {
+        let downloaded = if empty_db {
+            download_import_file(opt, snapshot_path, SNAPSHOTS_DIR, &opt.snapshot_dir)?
+        } else {
+            None
+        };
+        let snapshot_path =
+            downloaded.as_ref().map_or(snapshot_path.as_path(), |(path, _)| path.as_path());
         let snapshot_path_exists = snapshot_path.exists();
         // the db is empty and the snapshot exists, import it
         if empty_db && snapshot_path_exists {
-            match compression::from_tar_gz(snapshot_path, &opt.db_path) {
+            // A chain of incremental snapshots is imported from its manifest
+            let imported = if snapshot_path.extension().map_or(false, |ext| ext == "json") {
+                index_scheduler::import_snapshot_chain(snapshot_path, &opt.db_path)
+                    .map_err(anyhow::Error::from)
+            } else {
+                compression::from_tar_gz(snapshot_path, &opt.db_path)
+            };
+            match imported {
                 Ok(()) => open_or_create_database_unchecked(opt, OnFailure::RemoveDb)?,
                 Err(e) => {
                     std::fs::remove_dir_all(&opt.db_path)?;
@@ -228,6 +251,12 @@ pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Arc<
             open_or_create_database(opt, empty_db)?
         }
     } else if let Some(ref path) = opt.import_dump {
+        let downloaded = if empty_db {
+            download_import_file(opt, path, DUMPS_DIR, &opt.dump_dir)?
+        } else {
+            None
+        };
+        let path = downloaded.as_ref().map_or(path.as_path(), |(path, _)| path.as_path());
         let src_path_exists = path.exists();
         // the db is empty and the dump exists, import it
         if empty_db && src_path_exists {
@@ -280,6 +309,33 @@ pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Arc<
     Ok((index_scheduler, auth_controller))
 }
 
+/// Downloads the snapshot or dump to import from the upload backend when it isn't on the local disk.
+///
+/// The file is downloaded into a temporary directory of `download_dir`,
+/// which must be kept until the end of the import.
+fn download_import_file(
+    opt: &Opt,
+    path: &Path,
+    backend_dir: &str,
+    download_dir: &Path,
+) -> anyhow::Result<Option<(PathBuf, TempDir)>> {
+    let backend = match opt.upload_options.backend()? {
+        Some(backend) if !path.exists() => backend,
+        _ => return Ok(None),
+    };
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else { return Ok(None) };
+
+    std::fs::create_dir_all(download_dir)?;
+    let temp_dir = tempfile::tempdir_in(download_dir)?;
+    match index_scheduler::download_from_backend(&*backend, backend_dir, name, temp_dir.path())? {
+        Some(path) => {
+            tracing::info!("Downloaded `{name}` from the upload backend");
+            Ok(Some((path, temp_dir)))
+        }
+        None => Ok(None),
+    }
+}
+
 /// Try to start the IndexScheduler and AuthController without checking the VERSION file or anything.
 fn open_or_create_database_unchecked(
     opt: &Opt,
@@ -297,7 +353,10 @@ fn open_or_create_database_unchecked(
             update_file_path: opt.db_path.join("update_files"),
             indexes_path: opt.db_path.join("indexes"),
             snapshots_path: opt.snapshot_dir.clone(),
+            incremental_snapshots: opt.experimental_incremental_snapshots,
             dumps_path: opt.dump_dir.clone(),
+            upload_backend: opt.upload_options.backend()?,
+            upload_retention: opt.upload_options.experimental_upload_retention,
             webhook_url: opt.task_webhook_url.as_ref().map(|url| url.to_string()),
             webhook_authorization_header: opt.task_webhook_authorization_header.clone(),
             task_db_size: opt.max_task_db_size.get_bytes() as usize,
@@ -468,6 +527,8 @@ pub fn configure_data(
     config: &mut web::ServiceConfig,
     index_scheduler: Data<IndexScheduler>,
     auth: Data<AuthController>,
+    search_cache: Data<SearchCache>,
+    query_log: Data<QueryLog>,
     opt: &Opt,
     (logs_route, logs_stderr): (LogRouteHandle, LogStderrHandle),
     analytics: Arc<dyn Analytics>,
@@ -481,6 +542,8 @@ pub fn configure_data(
         .app_data(index_scheduler)
         .app_data(auth)
         .app_data(web::Data::new(search_queue))
+        .app_data(search_cache)
+        .app_data(query_log)
         .app_data(web::Data::from(analytics))
         .app_data(web::Data::new(logs_route))
         .app_data(web::Data::new(logs_stderr))
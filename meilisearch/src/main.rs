@@ -3,6 +3,7 @@ use std::io::{stderr, LineWriter, Write};
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use actix_web::http::KeepAlive;
 use actix_web::web::Data;
@@ -11,6 +12,8 @@ use index_scheduler::IndexScheduler;
 use is_terminal::IsTerminal;
 use meilisearch::analytics::Analytics;
 use meilisearch::option::LogMode;
+use meilisearch::query_log::QueryLog;
+use meilisearch::search_cache::SearchCache;
 use meilisearch::{
     analytics, create_app, setup_meilisearch, LogRouteHandle, LogRouteType, LogStderrHandle,
     LogStderrType, Opt, SubscriberForSecondLayer,
@@ -25,6 +28,9 @@ use tracing_subscriber::Layer;
 #[global_allocator]
 static ALLOC: MiMalloc = MiMalloc;
 
+/// How often the query log is written to the disk.
+const QUERY_LOG_PERSIST_INTERVAL: Duration = Duration::from_secs(60);
+
 fn default_log_route_layer() -> LogRouteType {
     None.with_filter(tracing_subscriber::filter::Targets::new().with_target("", LevelFilter::OFF))
 }
@@ -135,11 +141,30 @@ async fn run_http(
     let opt_clone = opt.clone();
     let index_scheduler = Data::from(index_scheduler);
     let auth_controller = Data::from(auth_controller);
+    // The search cache is shared by all the HTTP workers.
+    let search_cache = Data::new(SearchCache::new(
+        opt.experimental_search_cache_size,
+        Duration::from_secs(opt.experimental_search_cache_ttl),
+    ));
+    let query_log = Data::new(QueryLog::new(&opt)?);
+    if query_log.is_enabled() {
+        let query_log = query_log.clone();
+        std::thread::Builder::new().name(String::from("query-log-persister")).spawn(move || {
+            loop {
+                std::thread::sleep(QUERY_LOG_PERSIST_INTERVAL);
+                if let Err(e) = query_log.persist() {
+                    tracing::error!("Could not persist the query log: {e}");
+                }
+            }
+        })?;
+    }
 
     let http_server = HttpServer::new(move || {
         create_app(
             index_scheduler.clone(),
             auth_controller.clone(),
+            search_cache.clone(),
+            query_log.clone(),
             opt.clone(),
             logs.clone(),
             analytics.clone(),
@@ -1,7 +1,8 @@
 use lazy_static::lazy_static;
 use prometheus::{
-    opts, register_histogram_vec, register_int_counter_vec, register_int_gauge,
-    register_int_gauge_vec, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec,
+    opts, register_histogram_vec, register_int_counter, register_int_counter_vec,
+    register_int_gauge, register_int_gauge_vec, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec,
 };
 
 lazy_static! {
@@ -15,6 +16,16 @@ lazy_static! {
         "Meilisearch number of degraded search requests"
     ))
     .expect("Can't create a metric");
+    pub static ref MEILISEARCH_SEARCH_CACHE_HITS: IntCounter = register_int_counter!(opts!(
+        "meilisearch_search_cache_hits",
+        "Meilisearch number of search requests answered from the search cache"
+    ))
+    .expect("Can't create a metric");
+    pub static ref MEILISEARCH_SEARCH_CACHE_MISSES: IntCounter = register_int_counter!(opts!(
+        "meilisearch_search_cache_misses",
+        "Meilisearch number of search requests not found in the search cache"
+    ))
+    .expect("Can't create a metric");
     pub static ref MEILISEARCH_DB_SIZE_BYTES: IntGauge =
         register_int_gauge!(opts!("meilisearch_db_size_bytes", "Meilisearch DB Size In Bytes"))
             .expect("Can't create a metric");
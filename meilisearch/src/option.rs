@@ -2,7 +2,7 @@ use std::env::VarError;
 use std::ffi::OsStr;
 use std::fmt::Display;
 use std::io::{BufReader, Read};
-use std::num::ParseIntError;
+use std::num::{NonZeroUsize, ParseIntError};
 use std::ops::Deref;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -11,6 +11,7 @@ use std::{env, fmt, fs};
 
 use byte_unit::{Byte, ByteError};
 use clap::Parser;
+use index_scheduler::{LocalBackend, S3Backend, S3Config, UploadBackend};
 use meilisearch_types::features::InstanceTogglableFeatures;
 use meilisearch_types::milli::update::IndexerConfig;
 use meilisearch_types::milli::ThreadPoolNoAbortBuilder;
@@ -56,8 +57,12 @@ const MEILI_EXPERIMENTAL_REPLICATION_PARAMETERS: &str = "MEILI_EXPERIMENTAL_REPL
 const MEILI_EXPERIMENTAL_ENABLE_LOGS_ROUTE: &str = "MEILI_EXPERIMENTAL_ENABLE_LOGS_ROUTE";
 const MEILI_EXPERIMENTAL_ENABLE_METRICS: &str = "MEILI_EXPERIMENTAL_ENABLE_METRICS";
 const MEILI_EXPERIMENTAL_SEARCH_QUEUE_SIZE: &str = "MEILI_EXPERIMENTAL_SEARCH_QUEUE_SIZE";
+const MEILI_EXPERIMENTAL_SEARCH_CACHE_SIZE: &str = "MEILI_EXPERIMENTAL_SEARCH_CACHE_SIZE";
+const MEILI_EXPERIMENTAL_SEARCH_CACHE_TTL: &str = "MEILI_EXPERIMENTAL_SEARCH_CACHE_TTL";
+const MEILI_EXPERIMENTAL_SUGGEST_QUERY_LOG: &str = "MEILI_EXPERIMENTAL_SUGGEST_QUERY_LOG";
 const MEILI_EXPERIMENTAL_REDUCE_INDEXING_MEMORY_USAGE: &str =
     "MEILI_EXPERIMENTAL_REDUCE_INDEXING_MEMORY_USAGE";
+const MEILI_EXPERIMENTAL_INCREMENTAL_SNAPSHOTS: &str = "MEILI_EXPERIMENTAL_INCREMENTAL_SNAPSHOTS";
 const MEILI_EXPERIMENTAL_MAX_NUMBER_OF_BATCHED_TASKS: &str =
     "MEILI_EXPERIMENTAL_MAX_NUMBER_OF_BATCHED_TASKS";
 
@@ -70,9 +75,20 @@ const DEFAULT_SNAPSHOT_DIR: &str = "snapshots/";
 const DEFAULT_SNAPSHOT_INTERVAL_SEC: u64 = 86400;
 const DEFAULT_SNAPSHOT_INTERVAL_SEC_STR: &str = "86400";
 const DEFAULT_DUMP_DIR: &str = "dumps/";
+const DEFAULT_SEARCH_CACHE_TTL_SEC: u64 = 60;
 
 const MEILI_MAX_INDEXING_MEMORY: &str = "MEILI_MAX_INDEXING_MEMORY";
 const MEILI_MAX_INDEXING_THREADS: &str = "MEILI_MAX_INDEXING_THREADS";
+const MEILI_EXPERIMENTAL_DOCUMENT_COMPRESSION: &str = "MEILI_EXPERIMENTAL_DOCUMENT_COMPRESSION";
+const MEILI_EXPERIMENTAL_UPLOAD_DIR: &str = "MEILI_EXPERIMENTAL_UPLOAD_DIR";
+const MEILI_EXPERIMENTAL_UPLOAD_S3_ENDPOINT: &str = "MEILI_EXPERIMENTAL_UPLOAD_S3_ENDPOINT";
+const MEILI_EXPERIMENTAL_UPLOAD_S3_BUCKET: &str = "MEILI_EXPERIMENTAL_UPLOAD_S3_BUCKET";
+const MEILI_EXPERIMENTAL_UPLOAD_S3_REGION: &str = "MEILI_EXPERIMENTAL_UPLOAD_S3_REGION";
+const MEILI_EXPERIMENTAL_UPLOAD_S3_PREFIX: &str = "MEILI_EXPERIMENTAL_UPLOAD_S3_PREFIX";
+const MEILI_EXPERIMENTAL_UPLOAD_S3_ACCESS_KEY: &str = "MEILI_EXPERIMENTAL_UPLOAD_S3_ACCESS_KEY";
+const MEILI_EXPERIMENTAL_UPLOAD_S3_SECRET_KEY: &str = "MEILI_EXPERIMENTAL_UPLOAD_S3_SECRET_KEY";
+const MEILI_EXPERIMENTAL_UPLOAD_RETENTION: &str = "MEILI_EXPERIMENTAL_UPLOAD_RETENTION";
+const DEFAULT_UPLOAD_S3_REGION: &str = "us-east-1";
 const DEFAULT_LOG_EVERY_N: usize = 100_000;
 
 // Each environment (index and task-db) is taking space in the virtual address space.
@@ -265,6 +281,9 @@ pub struct Opt {
     pub ssl_tickets: bool,
 
     /// Launches Meilisearch after importing a previously-generated snapshot at the given filepath.
+    ///
+    /// When the snapshots are incremental, give the path to the `.snapshot-manifest.json` file
+    /// to restore the full snapshot and all the incremental snapshots that follow it.
     #[clap(long, env = MEILI_IMPORT_SNAPSHOT)]
     pub import_snapshot: Option<PathBuf>,
 
@@ -355,6 +374,31 @@ pub struct Opt {
     #[serde(default)]
     pub experimental_search_queue_size: usize,
 
+    /// Experimental search cache feature.
+    ///
+    /// Lets you keep the results of the most recent search requests in memory and answer the identical
+    /// requests made afterward without searching again. A cached result is dropped as soon as its index is modified.
+    /// Sets the maximum number of cached search results, the default value is 0 and disables the cache.
+    #[clap(long, env = MEILI_EXPERIMENTAL_SEARCH_CACHE_SIZE, default_value_t = 0)]
+    #[serde(default)]
+    pub experimental_search_cache_size: usize,
+
+    /// Experimental search cache feature.
+    ///
+    /// Defines, in seconds, for how long a search result stays in the search cache.
+    /// The default value is 60 seconds.
+    #[clap(long, env = MEILI_EXPERIMENTAL_SEARCH_CACHE_TTL, default_value_t = default_search_cache_ttl())]
+    #[serde(default = "default_search_cache_ttl")]
+    pub experimental_search_cache_ttl: u64,
+
+    /// Experimental query suggestions feature.
+    ///
+    /// Lets you log the queries returning at least one hit and rank the query suggestions by how often their
+    /// words were searched. The log is stored in the database directory.
+    #[clap(long, env = MEILI_EXPERIMENTAL_SUGGEST_QUERY_LOG)]
+    #[serde(default)]
+    pub experimental_suggest_query_log: bool,
+
     /// Experimental logs mode feature. For more information, see: <https://github.com/orgs/meilisearch/discussions/723>
     ///
     /// Change the mode of the logs on the console.
@@ -389,10 +433,22 @@ pub struct Opt {
     #[serde(default = "default_limit_batched_tasks")]
     pub experimental_max_number_of_batched_tasks: usize,
 
+    /// Experimental incremental snapshots feature.
+    ///
+    /// Lets the snapshots only copy the indexes modified since the previous snapshot. The snapshots
+    /// form a chain described by a `.snapshot-manifest.json` file, starting with a full snapshot.
+    #[clap(long, env = MEILI_EXPERIMENTAL_INCREMENTAL_SNAPSHOTS)]
+    #[serde(default)]
+    pub experimental_incremental_snapshots: bool,
+
     #[serde(flatten)]
     #[clap(flatten)]
     pub indexer_options: IndexerOpts,
 
+    #[serde(flatten)]
+    #[clap(flatten)]
+    pub upload_options: UploadOpts,
+
     /// Set the path to a configuration file that should be used to setup the engine.
     /// Format must be TOML.
     #[clap(long)]
@@ -462,6 +518,7 @@ impl Opt {
             max_task_db_size: _,
             http_payload_size_limit,
             experimental_max_number_of_batched_tasks,
+            experimental_incremental_snapshots,
             ssl_cert_path,
             ssl_key_path,
             ssl_auth_path,
@@ -474,6 +531,7 @@ impl Opt {
             dump_dir,
             log_level,
             indexer_options,
+            upload_options,
             import_snapshot: _,
             ignore_missing_snapshot: _,
             ignore_snapshot_if_db_exists: _,
@@ -485,6 +543,9 @@ impl Opt {
             no_analytics,
             experimental_enable_metrics,
             experimental_search_queue_size,
+            experimental_search_cache_size,
+            experimental_search_cache_ttl,
+            experimental_suggest_query_log,
             experimental_logs_mode,
             experimental_enable_logs_route,
             experimental_replication_parameters,
@@ -518,6 +579,10 @@ impl Opt {
             MEILI_EXPERIMENTAL_MAX_NUMBER_OF_BATCHED_TASKS,
             experimental_max_number_of_batched_tasks.to_string(),
         );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_INCREMENTAL_SNAPSHOTS,
+            experimental_incremental_snapshots.to_string(),
+        );
         if let Some(ssl_cert_path) = ssl_cert_path {
             export_to_env_if_not_present(MEILI_SSL_CERT_PATH, ssl_cert_path);
         }
@@ -548,6 +613,18 @@ impl Opt {
             MEILI_EXPERIMENTAL_SEARCH_QUEUE_SIZE,
             experimental_search_queue_size.to_string(),
         );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_SEARCH_CACHE_SIZE,
+            experimental_search_cache_size.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_SEARCH_CACHE_TTL,
+            experimental_search_cache_ttl.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_SUGGEST_QUERY_LOG,
+            experimental_suggest_query_log.to_string(),
+        );
         export_to_env_if_not_present(
             MEILI_EXPERIMENTAL_LOGS_MODE,
             experimental_logs_mode.to_string(),
@@ -565,6 +642,7 @@ impl Opt {
             experimental_reduce_indexing_memory_usage.to_string(),
         );
         indexer_options.export_to_env();
+        upload_options.export_to_env();
     }
 
     pub fn get_ssl_config(&self) -> anyhow::Result<Option<rustls::ServerConfig>> {
@@ -644,12 +722,24 @@ pub struct IndexerOpts {
     #[clap(skip)]
     #[serde(skip)]
     pub skip_index_budget: bool,
+
+    /// Experimentally compresses the documents of the indexes with a zstd dictionary trained
+    /// on a sample of their documents. It reduces the size of the indexes at the cost of a
+    /// slower indexing and document retrieval.
+    #[clap(long, env = MEILI_EXPERIMENTAL_DOCUMENT_COMPRESSION)]
+    #[serde(default)]
+    pub experimental_document_compression: bool,
 }
 
 impl IndexerOpts {
     /// Exports the values to their corresponding env vars if they are not set.
     pub fn export_to_env(self) {
-        let IndexerOpts { max_indexing_memory, max_indexing_threads, skip_index_budget: _ } = self;
+        let IndexerOpts {
+            max_indexing_memory,
+            max_indexing_threads,
+            skip_index_budget: _,
+            experimental_document_compression,
+        } = self;
         if let Some(max_indexing_memory) = max_indexing_memory.0 {
             export_to_env_if_not_present(
                 MEILI_MAX_INDEXING_MEMORY,
@@ -660,6 +750,10 @@ impl IndexerOpts {
             MEILI_MAX_INDEXING_THREADS,
             max_indexing_threads.0.to_string(),
         );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_DOCUMENT_COMPRESSION,
+            experimental_document_compression.to_string(),
+        );
     }
 }
 
@@ -678,11 +772,144 @@ impl TryFrom<&IndexerOpts> for IndexerConfig {
             thread_pool: Some(thread_pool),
             max_positions_per_attributes: None,
             skip_index_budget: other.skip_index_budget,
+            document_compression: other.experimental_document_compression,
             ..Default::default()
         })
     }
 }
 
+#[derive(Debug, Clone, Parser, Deserialize)]
+pub struct UploadOpts {
+    /// Experimentally copies the snapshots and dumps into this directory once they are created,
+    /// e.g. a mounted network volume. They are stored in its `snapshots/` and `dumps/` subdirectories.
+    #[clap(long, env = MEILI_EXPERIMENTAL_UPLOAD_DIR)]
+    #[serde(default)]
+    pub experimental_upload_dir: Option<PathBuf>,
+
+    /// Experimentally uploads the snapshots and dumps to this S3-compatible object storage once
+    /// they are created, e.g. `https://s3.eu-west-3.amazonaws.com` or `http://localhost:9000`.
+    /// Requires the bucket, the access key and the secret key.
+    #[clap(long, env = MEILI_EXPERIMENTAL_UPLOAD_S3_ENDPOINT)]
+    #[serde(default)]
+    pub experimental_upload_s3_endpoint: Option<String>,
+
+    /// Sets the bucket receiving the snapshots and dumps.
+    #[clap(long, env = MEILI_EXPERIMENTAL_UPLOAD_S3_BUCKET)]
+    #[serde(default)]
+    pub experimental_upload_s3_bucket: Option<String>,
+
+    /// Sets the region of the bucket.
+    #[clap(long, env = MEILI_EXPERIMENTAL_UPLOAD_S3_REGION, default_value_t = default_upload_s3_region())]
+    #[serde(default = "default_upload_s3_region")]
+    pub experimental_upload_s3_region: String,
+
+    /// Sets the prefix of the keys of the snapshots and dumps in the bucket, e.g. `meilisearch/production`.
+    #[clap(long, env = MEILI_EXPERIMENTAL_UPLOAD_S3_PREFIX, default_value_t)]
+    #[serde(default)]
+    pub experimental_upload_s3_prefix: String,
+
+    /// Sets the access key used to sign the requests to the object storage.
+    #[clap(long, env = MEILI_EXPERIMENTAL_UPLOAD_S3_ACCESS_KEY)]
+    #[serde(default)]
+    pub experimental_upload_s3_access_key: Option<String>,
+
+    /// Sets the secret key used to sign the requests to the object storage.
+    #[clap(long, env = MEILI_EXPERIMENTAL_UPLOAD_S3_SECRET_KEY)]
+    #[serde(default)]
+    pub experimental_upload_s3_secret_key: Option<String>,
+
+    /// Experimentally keeps only this number of snapshots and dumps on the upload backend,
+    /// the oldest ones are deleted when a new one is uploaded. By default, all of them are kept.
+    #[clap(long, env = MEILI_EXPERIMENTAL_UPLOAD_RETENTION)]
+    #[serde(default)]
+    pub experimental_upload_retention: Option<NonZeroUsize>,
+}
+
+impl UploadOpts {
+    /// Exports the values to their corresponding env vars if they are not set.
+    pub fn export_to_env(self) {
+        let UploadOpts {
+            experimental_upload_dir,
+            experimental_upload_s3_endpoint,
+            experimental_upload_s3_bucket,
+            experimental_upload_s3_region,
+            experimental_upload_s3_prefix,
+            experimental_upload_s3_access_key,
+            experimental_upload_s3_secret_key,
+            experimental_upload_retention,
+        } = self;
+        if let Some(experimental_upload_dir) = experimental_upload_dir {
+            export_to_env_if_not_present(MEILI_EXPERIMENTAL_UPLOAD_DIR, experimental_upload_dir);
+        }
+        if let Some(endpoint) = experimental_upload_s3_endpoint {
+            export_to_env_if_not_present(MEILI_EXPERIMENTAL_UPLOAD_S3_ENDPOINT, endpoint);
+        }
+        if let Some(bucket) = experimental_upload_s3_bucket {
+            export_to_env_if_not_present(MEILI_EXPERIMENTAL_UPLOAD_S3_BUCKET, bucket);
+        }
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_UPLOAD_S3_REGION,
+            experimental_upload_s3_region,
+        );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_UPLOAD_S3_PREFIX,
+            experimental_upload_s3_prefix,
+        );
+        if let Some(access_key) = experimental_upload_s3_access_key {
+            export_to_env_if_not_present(MEILI_EXPERIMENTAL_UPLOAD_S3_ACCESS_KEY, access_key);
+        }
+        if let Some(secret_key) = experimental_upload_s3_secret_key {
+            export_to_env_if_not_present(MEILI_EXPERIMENTAL_UPLOAD_S3_SECRET_KEY, secret_key);
+        }
+        if let Some(retention) = experimental_upload_retention {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_UPLOAD_RETENTION,
+                retention.to_string(),
+            );
+        }
+    }
+
+    /// Returns the backend receiving the snapshots and dumps, if one is configured.
+    pub fn backend(&self) -> anyhow::Result<Option<Arc<dyn UploadBackend>>> {
+        let UploadOpts {
+            experimental_upload_dir,
+            experimental_upload_s3_endpoint,
+            experimental_upload_s3_bucket,
+            experimental_upload_s3_region,
+            experimental_upload_s3_prefix,
+            experimental_upload_s3_access_key,
+            experimental_upload_s3_secret_key,
+            experimental_upload_retention: _,
+        } = self;
+
+        match (experimental_upload_dir, experimental_upload_s3_endpoint) {
+            (None, None) => Ok(None),
+            (Some(dir), None) => Ok(Some(Arc::new(LocalBackend::new(dir)))),
+            (None, Some(endpoint)) => {
+                let (Some(bucket), Some(access_key), Some(secret_key)) = (
+                    experimental_upload_s3_bucket,
+                    experimental_upload_s3_access_key,
+                    experimental_upload_s3_secret_key,
+                ) else {
+                    anyhow::bail!("`--experimental-upload-s3-endpoint` requires `--experimental-upload-s3-bucket`, `--experimental-upload-s3-access-key` and `--experimental-upload-s3-secret-key`")
+                };
+                let backend = S3Backend::new(S3Config {
+                    endpoint: endpoint.clone(),
+                    bucket: bucket.clone(),
+                    prefix: experimental_upload_s3_prefix.clone(),
+                    region: experimental_upload_s3_region.clone(),
+                    access_key: access_key.clone(),
+                    secret_key: secret_key.clone(),
+                })?;
+                Ok(Some(Arc::new(backend)))
+            }
+            (Some(_), Some(_)) => anyhow::bail!(
+                "`--experimental-upload-dir` and `--experimental-upload-s3-endpoint` cannot be used together"
+            ),
+        }
+    }
+}
+
 /// A type used to detect the max memory available and use 2/3 of it.
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub struct MaxMemory(Option<Byte>);
@@ -871,6 +1098,14 @@ fn default_dump_dir() -> PathBuf {
     PathBuf::from(DEFAULT_DUMP_DIR)
 }
 
+fn default_search_cache_ttl() -> u64 {
+    DEFAULT_SEARCH_CACHE_TTL_SEC
+}
+
+fn default_upload_s3_region() -> String {
+    DEFAULT_UPLOAD_S3_REGION.to_string()
+}
+
 /// Indicates if a snapshot was scheduled, and if yes with which interval.
 #[derive(Debug, Default, Copy, Clone, Deserialize, Serialize)]
 pub enum ScheduleSnapshot {
@@ -0,0 +1,195 @@
+//! This file implements the log of the successful queries used to rank the query suggestions.
+//!
+//! For each index, the log counts how many times each query returned at least one hit.
+//! The queries are lowercased and trimmed so that the same query typed differently is counted once.
+//! The number of logged queries containing each word is kept along, it is what ranks the suggestions.
+//!
+//! The log lives in memory and is written to the database directory by [`QueryLog::persist`],
+//! it is loaded back when Meilisearch starts. The log is disabled unless
+//! `--experimental-suggest-query-log` is set.
+
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use meilisearch_types::milli::tokenizer::Tokenize;
+use tempfile::NamedTempFile;
+
+use crate::search::SearchResult;
+use crate::Opt;
+
+/// The name of the file of the query log, in the database directory.
+const QUERY_LOG_FILE_NAME: &str = "query-log.json";
+
+/// Maximum number of distinct queries logged for a single index.
+const MAX_LOGGED_QUERIES_PER_INDEX: usize = 10_000;
+
+/// The logged queries of an index.
+#[derive(Debug, Default)]
+struct IndexQueries {
+    /// The number of occurrences of each query.
+    counts: BTreeMap<String, u64>,
+    /// The queries sorted by number of occurrences, the first one is the least frequent.
+    by_count: BTreeSet<(u64, String)>,
+    /// The number of occurrences of the queries containing each word, shared with the
+    /// suggestions in progress and only copied when the log is updated meanwhile.
+    word_counts: Arc<BTreeMap<String, u64>>,
+}
+
+impl IndexQueries {
+    fn new(counts: BTreeMap<String, u64>) -> Self {
+        let by_count = counts.iter().map(|(query, count)| (*count, query.clone())).collect();
+        let mut word_counts = BTreeMap::new();
+        for (query, count) in &counts {
+            for word in query_words(query) {
+                *word_counts.entry(word).or_default() += count;
+            }
+        }
+        Self { counts, by_count, word_counts: Arc::new(word_counts) }
+    }
+
+    /// Counts one more occurrence of the query, forgetting the least frequent query when full.
+    fn increment(&mut self, query: String) {
+        match self.counts.get_mut(&query) {
+            Some(count) => {
+                self.by_count.remove(&(*count, query.clone()));
+                *count += 1;
+                self.by_count.insert((*count, query.clone()));
+            }
+            None => {
+                if self.counts.len() >= MAX_LOGGED_QUERIES_PER_INDEX {
+                    if let Some((count, least_frequent)) = self.by_count.pop_first() {
+                        self.counts.remove(&least_frequent);
+                        let word_counts = Arc::make_mut(&mut self.word_counts);
+                        for word in query_words(&least_frequent) {
+                            if let Entry::Occupied(mut entry) = word_counts.entry(word) {
+                                *entry.get_mut() = entry.get().saturating_sub(count);
+                                if *entry.get() == 0 {
+                                    entry.remove();
+                                }
+                            }
+                        }
+                    }
+                }
+                self.counts.insert(query.clone(), 1);
+                self.by_count.insert((1, query.clone()));
+            }
+        }
+
+        let word_counts = Arc::make_mut(&mut self.word_counts);
+        for word in query_words(&query) {
+            *word_counts.entry(word).or_default() += 1;
+        }
+    }
+}
+
+/// The distinct words of a query.
+fn query_words(query: &str) -> BTreeSet<String> {
+    query
+        .tokenize()
+        .filter(|token| token.is_word() && !token.lemma().is_empty())
+        .map(|token| token.lemma().to_string())
+        .collect()
+}
+
+#[derive(Debug, Default)]
+pub struct QueryLog {
+    /// The file of the log, `None` when the log is disabled.
+    path: Option<PathBuf>,
+    queries: Mutex<HashMap<String, IndexQueries>>,
+    /// Whether queries were logged since the last time the log was persisted.
+    dirty: AtomicBool,
+}
+
+impl QueryLog {
+    /// Loads the log when it is enabled by the options, otherwise returns a disabled log.
+    pub fn new(opt: &Opt) -> anyhow::Result<Self> {
+        if opt.experimental_suggest_query_log {
+            Self::load(&opt.db_path)
+        } else {
+            Ok(Self::disabled())
+        }
+    }
+
+    /// A log that never records anything.
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Loads the log stored in the database directory, if any.
+    pub fn load(db_path: &Path) -> anyhow::Result<Self> {
+        let path = db_path.join(QUERY_LOG_FILE_NAME);
+        let queries: HashMap<String, BTreeMap<String, u64>> = match File::open(&path) {
+            Ok(file) => serde_json::from_reader(BufReader::new(file))?,
+            Err(e) if e.kind() == ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let queries = queries
+            .into_iter()
+            .map(|(index_uid, counts)| (index_uid, IndexQueries::new(counts)))
+            .collect();
+        Ok(Self { path: Some(path), queries: Mutex::new(queries), dirty: AtomicBool::new(false) })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.path.is_some()
+    }
+
+    /// Records the query of the search result when it returned at least one hit.
+    ///
+    /// When the log of the index is full, the least frequent query is forgotten.
+    pub fn record(&self, index_uid: &str, search_result: &SearchResult) {
+        if !self.is_enabled() || search_result.hits.is_empty() {
+            return;
+        }
+        let query = search_result.query.trim().to_lowercase();
+        if query.is_empty() {
+            return;
+        }
+
+        let mut queries = self.queries.lock().unwrap();
+        queries.entry(index_uid.to_string()).or_default().increment(query);
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns the words of the logged queries of the index along with the number
+    /// of occurrences of the queries containing them.
+    pub fn word_counts(&self, index_uid: &str) -> Option<Arc<BTreeMap<String, u64>>> {
+        self.queries.lock().unwrap().get(index_uid).map(|queries| queries.word_counts.clone())
+    }
+
+    /// Writes the log to the database directory if queries were logged since the last call.
+    pub fn persist(&self) -> anyhow::Result<()> {
+        let Some(path) = &self.path else { return Ok(()) };
+        if !self.dirty.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let queries: HashMap<_, _> = self
+            .queries
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(index_uid, queries)| (index_uid.clone(), queries.counts.clone()))
+            .collect();
+        let result = (|| -> anyhow::Result<()> {
+            let dir = path.parent().unwrap_or_else(|| Path::new("."));
+            let file = NamedTempFile::new_in(dir)?;
+            let mut writer = BufWriter::new(file);
+            serde_json::to_writer(&mut writer, &queries)?;
+            writer.flush()?;
+            writer.into_inner()?.persist(path)?;
+            Ok(())
+        })();
+
+        if result.is_err() {
+            // Retry on the next call.
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+        result
+    }
+}
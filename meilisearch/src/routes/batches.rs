@@ -0,0 +1,89 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse};
+use deserr::actix_web::AwebQueryParameter;
+use index_scheduler::{IndexScheduler, Query};
+use meilisearch_types::batch_view::BatchView;
+use meilisearch_types::batches::BatchId;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::error::{Code, ResponseError};
+use serde::Serialize;
+
+use super::tasks::TasksFilterQuery;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(get_batches))))
+        .service(web::resource("/{batch_id}").route(web::get().to(SeqHandler(get_batch))));
+}
+
+#[derive(Debug, Serialize)]
+pub struct AllBatches {
+    results: Vec<BatchView>,
+    total: u64,
+    limit: u32,
+    from: Option<u32>,
+    next: Option<u32>,
+}
+
+async fn get_batches(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    params: AwebQueryParameter<TasksFilterQuery, DeserrQueryParamError>,
+) -> Result<HttpResponse, ResponseError> {
+    let mut params = params.into_inner();
+    // We +1 just to know if there is more after this "page" or not.
+    params.limit.0 = params.limit.0.saturating_add(1);
+    let limit = params.limit.0;
+    let mut query = params.into_query();
+    // On this route the `uids` refer to the batches and not to the tasks.
+    if let Some(uids) = query.uids.take() {
+        if query.batch_uids.is_some() {
+            return Err(ResponseError::from_msg(
+                "The `uids` and `batchUids` parameters cannot be used together on the `/batches` route, they both filter the batch uids.".to_string(),
+                Code::InvalidBatchUids,
+            ));
+        }
+        query.batch_uids = Some(uids);
+    }
+
+    let filters = index_scheduler.filters();
+    let (batches, total) = index_scheduler.get_batches_from_authorized_indexes(query, filters)?;
+    let mut results: Vec<_> =
+        batches.iter().map(|(batch, tasks)| BatchView::from_batch(batch, tasks)).collect();
+
+    // If we were able to fetch the number +1 batches we asked
+    // it means that there is more to come.
+    let next = if results.len() == limit as usize { results.pop().map(|b| b.uid) } else { None };
+
+    let from = results.first().map(|b| b.uid);
+    let batches = AllBatches { results, limit: limit.saturating_sub(1), total, from, next };
+
+    Ok(HttpResponse::Ok().json(batches))
+}
+
+async fn get_batch(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    batch_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let batch_uid_string = batch_uid.into_inner();
+
+    let batch_uid: BatchId = match batch_uid_string.parse() {
+        Ok(id) => id,
+        Err(_e) => {
+            return Err(
+                index_scheduler::Error::InvalidBatchUids { batch_uid: batch_uid_string }.into()
+            )
+        }
+    };
+
+    let query = Query { batch_uids: Some(vec![batch_uid]), ..Query::default() };
+    let filters = index_scheduler.filters();
+    let (batches, _) = index_scheduler.get_batches_from_authorized_indexes(query, filters)?;
+
+    if let Some((batch, tasks)) = batches.first() {
+        Ok(HttpResponse::Ok().json(BatchView::from_batch(batch, tasks)))
+    } else {
+        Err(index_scheduler::Error::BatchNotFound(batch_uid).into())
+    }
+}
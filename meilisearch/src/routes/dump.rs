@@ -1,35 +1,85 @@
-use actix_web::web::Data;
+use std::collections::HashSet;
+
+use actix_web::web::{Bytes, Data};
 use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
 use index_scheduler::IndexScheduler;
 use meilisearch_auth::AuthController;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::ResponseError;
-use meilisearch_types::tasks::KindWithContent;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::index_uid_pattern::IndexUidPattern;
+use meilisearch_types::tasks::{DumpImportIndex, KindWithContent};
 use serde_json::json;
 use tracing::debug;
 
 use crate::analytics::Analytics;
+use crate::error::{MeilisearchHttpError, PayloadError};
 use crate::extractors::authentication::policies::*;
-use crate::extractors::authentication::GuardedData;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::{get_task_id, is_dry_run, SummarizedTaskView};
 use crate::Opt;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
-    cfg.service(web::resource("").route(web::post().to(SeqHandler(create_dump))));
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(create_dump))))
+        .service(web::resource("/import").route(web::post().to(SeqHandler(import_dump))));
+}
+
+#[derive(Deserr, Debug)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct DumpPayload {
+    #[deserr(default, error = DeserrJsonError<InvalidDumpIndexUids>)]
+    pub index_uids: Option<Vec<IndexUidPattern>>,
+    #[deserr(default = true, error = DeserrJsonError<InvalidDumpIncludeTasks>)]
+    pub include_tasks: bool,
+    #[deserr(default = true, error = DeserrJsonError<InvalidDumpIncludeKeys>)]
+    pub include_keys: bool,
+}
+
+impl Default for DumpPayload {
+    fn default() -> Self {
+        DumpPayload { index_uids: None, include_tasks: true, include_keys: true }
+    }
 }
 
 pub async fn create_dump(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DUMPS_CREATE }>, Data<IndexScheduler>>,
     auth_controller: GuardedData<ActionPolicy<{ actions::DUMPS_CREATE }>, Data<AuthController>>,
+    body: Bytes,
     req: HttpRequest,
     opt: web::Data<Opt>,
     analytics: web::Data<dyn Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
-    analytics.publish("Dump Created".to_string(), json!({}), Some(&req));
+    // A dump can be created without any body or content-type, all the indexes are dumped then.
+    let payload = if body.is_empty() {
+        DumpPayload::default()
+    } else {
+        let value: serde_json::Value =
+            serde_json::from_slice(&body).map_err(PayloadError::MalformedPayload)?;
+        deserr::deserialize::<Option<DumpPayload>, _, DeserrJsonError>(value)?.unwrap_or_default()
+    };
+    debug!(parameters = ?payload, "Create dump");
+    let DumpPayload { index_uids, include_tasks, include_keys } = payload;
+
+    analytics.publish(
+        "Dump Created".to_string(),
+        json!({
+            "index_uids": index_uids.as_ref().map(|patterns| patterns.len()),
+            "include_tasks": include_tasks,
+            "include_keys": include_keys,
+        }),
+        Some(&req),
+    );
 
     let task = KindWithContent::DumpCreation {
-        keys: auth_controller.list_keys()?,
+        keys: if include_keys { auth_controller.list_keys()? } else { Vec::new() },
         instance_uid: analytics.instance_uid().cloned(),
+        index_uids,
+        include_tasks,
+        include_keys,
     };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
@@ -41,3 +91,88 @@ pub async fn create_dump(
     debug!(returns = ?task, "Create dump");
     Ok(HttpResponse::Accepted().json(task))
 }
+
+#[derive(Deserr, Debug)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct DumpImportPayload {
+    #[deserr(error = DeserrJsonError<InvalidDumpImportDumpUid>, missing_field_error = DeserrJsonError::missing_dump_import_dump_uid)]
+    pub dump_uid: String,
+    #[deserr(error = DeserrJsonError<InvalidDumpImportIndexes>, missing_field_error = DeserrJsonError::missing_dump_import_indexes)]
+    pub indexes: Vec<DumpImportIndexPayload>,
+    #[deserr(default, error = DeserrJsonError<InvalidDumpImportOverwrite>)]
+    pub overwrite: bool,
+}
+
+#[derive(Deserr, Debug)]
+#[deserr(error = DeserrJsonError<InvalidDumpImportIndexes>, rename_all = camelCase, deny_unknown_fields)]
+pub struct DumpImportIndexPayload {
+    pub index_uid: IndexUid,
+    #[deserr(default)]
+    pub rename_to: Option<IndexUid>,
+}
+
+pub async fn import_dump(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DUMPS_IMPORT }>, Data<IndexScheduler>>,
+    params: AwebJson<DumpImportPayload, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let params = params.into_inner();
+    debug!(parameters = ?params, "Import dump");
+    let DumpImportPayload { dump_uid, indexes, overwrite } = params;
+
+    analytics.publish(
+        "Dump Imported".to_string(),
+        json!({
+            "indexes": indexes.len(),
+            "renamed_indexes": indexes.iter().filter(|index| index.rename_to.is_some()).count(),
+            "overwrite": overwrite,
+        }),
+        Some(&req),
+    );
+
+    // The dump uid is used to find the dump file, it must not be able to point to another file.
+    if dump_uid.is_empty()
+        || !dump_uid.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(MeilisearchHttpError::InvalidDumpUid(dump_uid).into());
+    }
+    if indexes.is_empty() {
+        return Err(MeilisearchHttpError::EmptyDumpImportIndexes.into());
+    }
+
+    let filters = index_scheduler.filters();
+    let mut sources = HashSet::new();
+    let mut targets = HashSet::new();
+    let mut imports = Vec::new();
+    for DumpImportIndexPayload { index_uid, rename_to } in indexes {
+        let import = DumpImportIndex {
+            index_uid: index_uid.into_inner(),
+            rename_to: rename_to.map(IndexUid::into_inner),
+        };
+        if !sources.insert(import.index_uid.clone()) {
+            return Err(MeilisearchHttpError::DumpImportDuplicateIndex(import.index_uid).into());
+        }
+        let target = import.target().to_string();
+        if targets.contains(&target) {
+            return Err(MeilisearchHttpError::DumpImportDuplicateIndex(target).into());
+        }
+        if !filters.is_index_authorized(&target) {
+            return Err(AuthenticationError::InvalidToken.into());
+        }
+        targets.insert(target);
+        imports.push(import);
+    }
+
+    let task = KindWithContent::DumpImport { dump_uid, indexes: imports, overwrite };
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
+            .await??
+            .into();
+
+    debug!(returns = ?task, "Import dump");
+    Ok(HttpResponse::Accepted().json(task))
+}
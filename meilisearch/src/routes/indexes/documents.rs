@@ -82,6 +82,7 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         web::resource("/delete-batch").route(web::post().to(SeqHandler(delete_documents_batch))),
     )
     .service(web::resource("/delete").route(web::post().to(SeqHandler(delete_documents_by_filter))))
+    .service(web::resource("/edit").route(web::post().to(SeqHandler(edit_documents_by_function))))
     .service(web::resource("/fetch").route(web::post().to(SeqHandler(documents_by_query_post))))
     .service(
         web::resource("/{document_id}")
@@ -574,6 +575,65 @@ pub async fn delete_documents_by_filter(
     Ok(HttpResponse::Accepted().json(task))
 }
 
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct DocumentEditionByFunction {
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentFilter>)]
+    pub filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentEditionContext>)]
+    pub context: Option<Value>,
+    #[deserr(error = DeserrJsonError<InvalidDocumentEditionFunction>, missing_field_error = DeserrJsonError::missing_document_edition_function)]
+    pub function: String,
+}
+
+pub async fn edit_documents_by_function(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ALL }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebJson<DocumentEditionByFunction, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    debug!(parameters = ?params, "Edit documents by function");
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index_uid = index_uid.into_inner();
+    let params = params.into_inner();
+
+    analytics.update_documents_by_function(&params, &req);
+
+    let DocumentEditionByFunction { filter, context, function } = params;
+    if let Some(ref filter) = filter {
+        // we ensure the filter is well formed before enqueuing it
+        crate::search::parse_filter(filter).map_err(|err| {
+            // and whatever was the error, the error code should always be an InvalidDocumentFilter
+            ResponseError::from_msg(ResponseError::from(err).message, Code::InvalidDocumentFilter)
+        })?;
+    }
+    let context = match context {
+        Some(Value::Object(context)) => Some(context),
+        Some(_) => {
+            return Err(ResponseError::from_msg(
+                "The context must be an object.".to_string(),
+                Code::InvalidDocumentEditionContext,
+            ))
+        }
+        None => None,
+    };
+
+    let task =
+        KindWithContent::DocumentEdition { index_uid, filter_expr: filter, context, function };
+
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
+            .await??
+            .into();
+
+    debug!(returns = ?task, "Edit documents by function");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 pub async fn clear_all_documents(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_DELETE }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
@@ -608,7 +668,8 @@ fn some_documents<'a, 't: 'a>(
 
     Ok(index.iter_documents(rtxn, doc_ids)?.map(move |ret| {
         ret.map_err(ResponseError::from).and_then(|(key, document)| -> Result<_, ResponseError> {
-            let mut document = milli::obkv_to_json(&all_fields, &fields_ids_map, document)?;
+            let mut document =
+                milli::obkv_to_json(&all_fields, &fields_ids_map, document.as_obkv())?;
             match retrieve_vectors {
                 RetrieveVectors::Ignore => {}
                 RetrieveVectors::Hide => {
@@ -628,8 +689,8 @@ fn some_documents<'a, 't: 'a>(
                             embeddings: Some(vector.into()),
                             regenerate: !user_provided,
                         };
-                        vectors.insert(
-                            name,
+                        // the vectors kept in the document of a quantized embedder are the original ones
+                        vectors.entry(name).or_insert(
                             serde_json::to_value(embeddings).map_err(MeilisearchHttpError::from)?,
                         );
                     }
@@ -6,6 +6,7 @@ use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::locales::Locale;
 use serde_json::Value;
 use tracing::debug;
 
@@ -48,6 +49,10 @@ pub struct FacetSearchQuery {
     pub attributes_to_search_on: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>, default)]
     pub ranking_score_threshold: Option<RankingScoreThreshold>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>, default)]
+    pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidFacetSearchExhaustiveFacetCount>, default)]
+    pub exhaustive_facet_count: bool,
 }
 
 pub async fn search(
@@ -67,6 +72,7 @@ pub async fn search(
 
     let facet_query = query.facet_query.clone();
     let facet_name = query.facet_name.clone();
+    let exhaustive_facet_count = query.exhaustive_facet_count;
     let mut search_query = SearchQuery::from(query);
 
     // Tenant token search_rules.
@@ -79,7 +85,14 @@ pub async fn search(
     let search_kind = search_kind(&search_query, &index_scheduler, &index, features)?;
     let _permit = search_queue.try_get_search_permit().await?;
     let search_result = tokio::task::spawn_blocking(move || {
-        perform_facet_search(&index, search_query, facet_query, facet_name, search_kind)
+        perform_facet_search(
+            &index,
+            search_query,
+            facet_query,
+            facet_name,
+            exhaustive_facet_count,
+            search_kind,
+        )
     })
     .await?;
 
@@ -106,6 +119,8 @@ impl From<FacetSearchQuery> for SearchQuery {
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
+            locales,
+            exhaustive_facet_count: _,
         } = value;
 
         SearchQuery {
@@ -125,7 +140,10 @@ impl From<FacetSearchQuery> for SearchQuery {
             filter,
             sort: None,
             distinct: None,
+            distinct_group_limit: None,
             facets: None,
+            facet_aggregations: false,
+            facet_histograms: None,
             highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
             highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
             crop_marker: DEFAULT_CROP_MARKER(),
@@ -134,6 +152,9 @@ impl From<FacetSearchQuery> for SearchQuery {
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
+            locales,
+            ranking_rules: None,
+            boost: None,
         }
     }
 }
@@ -0,0 +1,204 @@
+use std::collections::BTreeMap;
+
+use actix_web::web::{self, Data};
+use actix_web::{HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::keys::actions;
+use meilisearch_types::merchandising_rules::{
+    MerchandisingRuleView, QueryMatchView, RuleActionsView, RuleConditionView,
+};
+use meilisearch_types::milli::update::Setting;
+use meilisearch_types::settings::Settings;
+use meilisearch_types::tasks::KindWithContent;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::debug;
+
+use super::ActionPolicy;
+use crate::analytics::Analytics;
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::routes::{get_task_id, is_dry_run, SummarizedTaskView};
+use crate::search::parse_filter;
+use crate::Opt;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(list_rules)))).service(
+        web::resource("/{rule_uid}")
+            .route(web::get().to(SeqHandler(get_rule)))
+            .route(web::put().to(SeqHandler(put_rule)))
+            .route(web::delete().to(SeqHandler(delete_rule))),
+    );
+}
+
+#[derive(Deserialize)]
+pub struct RuleParam {
+    index_uid: String,
+    rule_uid: String,
+}
+
+/// A merchandising rule along with its uid, as returned by the rules routes.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleView {
+    pub uid: String,
+    #[serde(flatten)]
+    pub rule: MerchandisingRuleView,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RulesView {
+    pub results: Vec<RuleView>,
+}
+
+pub async fn list_rules(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let index = index_scheduler.index(&index_uid)?;
+    let rtxn = index.read_txn()?;
+    let results = index
+        .merchandising_rules(&rtxn)?
+        .into_iter()
+        .map(|(uid, rule)| RuleView { uid, rule: rule.into() })
+        .collect();
+    let rules = RulesView { results };
+
+    debug!(returns = ?rules, "List rules");
+    Ok(HttpResponse::Ok().json(rules))
+}
+
+pub async fn get_rule(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_GET }>, Data<IndexScheduler>>,
+    path: web::Path<RuleParam>,
+) -> Result<HttpResponse, ResponseError> {
+    let RuleParam { index_uid, rule_uid } = path.into_inner();
+    let index_uid = IndexUid::try_from(index_uid)?;
+
+    let index = index_scheduler.index(&index_uid)?;
+    let rtxn = index.read_txn()?;
+    let rule = index
+        .merchandising_rules(&rtxn)?
+        .remove(&rule_uid)
+        .ok_or_else(|| MeilisearchHttpError::RuleNotFound(rule_uid.clone()))?;
+    let rule = RuleView { uid: rule_uid, rule: rule.into() };
+
+    debug!(returns = ?rule, "Get rule");
+    Ok(HttpResponse::Ok().json(rule))
+}
+
+pub async fn put_rule(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    path: web::Path<RuleParam>,
+    body: AwebJson<MerchandisingRuleView, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let RuleParam { index_uid, rule_uid } = path.into_inner();
+    let index_uid = IndexUid::try_from(index_uid)?;
+    let rule = body.into_inner();
+    debug!(parameters = ?rule, "Put rule");
+
+    validate_rule_uid(&rule_uid)?;
+    validate_rule(&rule)?;
+
+    analytics.publish(
+        "Merchandising Rule Updated".to_string(),
+        json!({
+            "match": rule.condition.query_match,
+            "filter_context": rule.condition.filter.is_some(),
+            "pin_total_number_of_documents": rule.actions.pin.len(),
+            "hide_total_number_of_documents": rule.actions.hide.len(),
+            "filter": rule.actions.filter.is_some(),
+        }),
+        Some(&req),
+    );
+
+    let rules = BTreeMap::from([(rule_uid, Setting::Set(rule))]);
+    let task = register_rules_update(index_scheduler, index_uid, rules, &req, &opt).await?;
+
+    debug!(returns = ?task, "Put rule");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+pub async fn delete_rule(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    path: web::Path<RuleParam>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+) -> Result<HttpResponse, ResponseError> {
+    let RuleParam { index_uid, rule_uid } = path.into_inner();
+    let index_uid = IndexUid::try_from(index_uid)?;
+
+    let rules = BTreeMap::from([(rule_uid, Setting::Reset)]);
+    let task = register_rules_update(index_scheduler, index_uid, rules, &req, &opt).await?;
+
+    debug!(returns = ?task, "Delete rule");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+/// Enqueues a settings update only touching the given rules, the other rules are kept as is.
+async fn register_rules_update(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: IndexUid,
+    rules: BTreeMap<String, Setting<MerchandisingRuleView>>,
+    req: &HttpRequest,
+    opt: &Opt,
+) -> Result<SummarizedTaskView, ResponseError> {
+    let new_settings = Settings { merchandising_rules: Setting::Set(rules), ..Default::default() };
+    let task = KindWithContent::SettingsUpdate {
+        index_uid: index_uid.into_inner(),
+        new_settings: Box::new(new_settings),
+        is_deletion: false,
+        allow_index_creation: false,
+    };
+    let uid = get_task_id(req, opt)?;
+    let dry_run = is_dry_run(req, opt)?;
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
+            .await??
+            .into();
+    Ok(task)
+}
+
+/// Rule uids follow the same format as index uids.
+pub(crate) fn validate_rule_uid(uid: &str) -> Result<(), MeilisearchHttpError> {
+    let is_valid = !uid.is_empty()
+        && uid.len() <= 400
+        && uid.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if is_valid {
+        Ok(())
+    } else {
+        Err(MeilisearchHttpError::InvalidRuleUid(uid.to_string()))
+    }
+}
+
+/// Checks the regex and the filters of the rule, so that a rule never fails the searches.
+pub(crate) fn validate_rule(rule: &MerchandisingRuleView) -> Result<(), MeilisearchHttpError> {
+    let MerchandisingRuleView { condition, actions } = rule;
+    let RuleConditionView { query, query_match, filter } = condition;
+    let RuleActionsView { pin: _, hide: _, filter: actions_filter } = actions;
+
+    if let QueryMatchView::Regex = query_match {
+        Regex::new(query)
+            .map_err(|e| MeilisearchHttpError::InvalidRuleRegex(query.clone(), e.to_string()))?;
+    }
+    if let Some(filter) = filter {
+        parse_filter(filter)
+            .map_err(|e| MeilisearchHttpError::InvalidRuleConditionFilter(e.to_string()))?;
+    }
+    if let Some(filter) = actions_filter {
+        parse_filter(filter)
+            .map_err(|e| MeilisearchHttpError::InvalidRuleActionsFilter(e.to_string()))?;
+    }
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
 use deserr::actix_web::{AwebJson, AwebQueryParameter};
@@ -7,8 +9,10 @@ use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::locales::Locale;
 use meilisearch_types::milli;
 use meilisearch_types::serde_cs::vec::CS;
+use meilisearch_types::settings::RankingRuleView;
 use serde_json::Value;
 use tracing::debug;
 
@@ -18,12 +22,15 @@ use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::metrics::MEILISEARCH_DEGRADED_SEARCH_REQUESTS;
+use crate::query_log::QueryLog;
 use crate::search::{
-    add_search_rules, perform_search, HybridQuery, MatchingStrategy, RankingScoreThreshold,
-    RetrieveVectors, SearchKind, SearchQuery, SemanticRatio, DEFAULT_CROP_LENGTH,
-    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
-    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
+    add_search_rules, check_ranking_rules_override, perform_search, DistinctAttributes,
+    HybridQuery, MatchingStrategy, RankingScoreThreshold, RetrieveVectors, SearchKind, SearchQuery,
+    SearchResult, SemanticRatio, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
+    DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
+    DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
 };
+use crate::search_cache::{CacheStatus, SearchCache, SEARCH_CACHE_HEADER};
 use crate::search_queue::SearchQueue;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
@@ -64,7 +71,9 @@ pub struct SearchQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchSort>)]
     sort: Option<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchDistinct>)]
-    distinct: Option<String>,
+    distinct: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchDistinctGroupLimit>)]
+    distinct_group_limit: Option<Param<usize>>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowMatchesPosition>)]
     show_matches_position: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowRankingScore>)]
@@ -73,6 +82,8 @@ pub struct SearchQueryGet {
     show_ranking_score_details: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchFacets>)]
     facets: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchFacetAggregations>)]
+    facet_aggregations: Param<bool>,
     #[deserr( default = DEFAULT_HIGHLIGHT_PRE_TAG(), error = DeserrQueryParamError<InvalidSearchHighlightPreTag>)]
     highlight_pre_tag: String,
     #[deserr( default = DEFAULT_HIGHLIGHT_POST_TAG(), error = DeserrQueryParamError<InvalidSearchHighlightPostTag>)]
@@ -89,6 +100,10 @@ pub struct SearchQueryGet {
     pub hybrid_semantic_ratio: Option<SemanticRatioGet>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchRankingScoreThreshold>)]
     pub ranking_score_threshold: Option<RankingScoreThresholdGet>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchLocales>)]
+    pub locales: Option<CS<Locale>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchRankingRules>)]
+    pub ranking_rules: Option<CS<RankingRuleView>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, deserr::Deserr)]
@@ -163,11 +178,14 @@ impl From<SearchQueryGet> for SearchQuery {
             attributes_to_highlight: other.attributes_to_highlight.map(|o| o.into_iter().collect()),
             filter,
             sort: other.sort.map(|attr| fix_sort_query_parameters(&attr)),
-            distinct: other.distinct,
+            distinct: other.distinct.map(|o| DistinctAttributes(o.into_iter().collect())),
+            distinct_group_limit: other.distinct_group_limit.as_deref().copied(),
             show_matches_position: other.show_matches_position.0,
             show_ranking_score: other.show_ranking_score.0,
             show_ranking_score_details: other.show_ranking_score_details.0,
             facets: other.facets.map(|o| o.into_iter().collect()),
+            facet_aggregations: other.facet_aggregations.0,
+            facet_histograms: None,
             highlight_pre_tag: other.highlight_pre_tag,
             highlight_post_tag: other.highlight_post_tag,
             crop_marker: other.crop_marker,
@@ -175,6 +193,9 @@ impl From<SearchQueryGet> for SearchQuery {
             attributes_to_search_on: other.attributes_to_search_on.map(|o| o.into_iter().collect()),
             hybrid,
             ranking_score_threshold: other.ranking_score_threshold.map(|o| o.0),
+            locales: other.locales.map(|o| o.into_iter().collect()),
+            ranking_rules: other.ranking_rules.map(|o| o.into_iter().collect()),
+            boost: None,
         }
     }
 }
@@ -183,30 +204,52 @@ impl From<SearchQueryGet> for SearchQuery {
 
 /// Transform the sort query parameter into something that matches the post expected format.
 fn fix_sort_query_parameters(sort_query: &str) -> Vec<String> {
+    let sort_query = sort_query.trim_matches('"');
     let mut sort_parameters = Vec::new();
-    let mut merge = false;
-    for current_sort in sort_query.trim_matches('"').split(',').map(|s| s.trim()) {
-        if current_sort.starts_with("_geoPoint(") {
-            sort_parameters.push(current_sort.to_string());
-            merge = true;
-        } else if merge && !sort_parameters.is_empty() {
-            let s = sort_parameters.last_mut().unwrap();
-            s.push(',');
-            s.push_str(current_sort);
-            if current_sort.ends_with("):desc") || current_sort.ends_with("):asc") {
-                merge = false;
+    // The offset in the query of the `_geoPoint` being merged
+    let mut merge_start = None;
+    let mut offset = 0;
+    for part in sort_query.split(',') {
+        let part_start = offset;
+        offset += part.len() + 1;
+        let current_sort = part.trim();
+        match (merge_start, sort_parameters.last_mut()) {
+            _ if current_sort.starts_with("_geoPoint(") => {
+                sort_parameters.push(current_sort.to_string());
+                merge_start = Some(part_start);
+            }
+            (Some(start), Some(s)) => {
+                s.push(',');
+                s.push_str(current_sort);
+                if current_sort.ends_with("):desc") || current_sort.ends_with("):asc") {
+                    merge_start = None;
+                } else if is_complete_geo_decay(s) {
+                    // The decay is kept as written as it is reported as is in the errors
+                    *s = sort_query[start..part_start + part.len()].trim().to_string();
+                    merge_start = None;
+                }
+            }
+            _ => {
+                sort_parameters.push(current_sort.to_string());
+                merge_start = None;
             }
-        } else {
-            sort_parameters.push(current_sort.to_string());
-            merge = false;
         }
     }
     sort_parameters
 }
 
+/// Whether the sort parameter is a whole `_geoPoint(lat, lng):decay(...)` expression.
+fn is_complete_geo_decay(sort: &str) -> bool {
+    sort.contains("):decay(")
+        && sort.ends_with(')')
+        && sort.matches('(').count() == sort.matches(')').count()
+}
+
 pub async fn search_with_url_query(
     index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
     search_queue: web::Data<SearchQueue>,
+    search_cache: web::Data<SearchCache>,
+    query_log: web::Data<QueryLog>,
     index_uid: web::Path<String>,
     params: AwebQueryParameter<SearchQueryGet, DeserrQueryParamError>,
     req: HttpRequest,
@@ -219,6 +262,7 @@ pub async fn search_with_url_query(
 
     // Tenant token search_rules.
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        check_ranking_rules_override(&query.ranking_rules, &search_rules)?;
         add_search_rules(&mut query.filter, search_rules);
     }
 
@@ -229,25 +273,38 @@ pub async fn search_with_url_query(
 
     let search_kind = search_kind(&query, index_scheduler.get_ref(), &index, features)?;
     let retrieve_vector = RetrieveVectors::new(query.retrieve_vectors, features)?;
-    let _permit = search_queue.try_get_search_permit().await?;
-    let search_result = tokio::task::spawn_blocking(move || {
-        perform_search(&index, query, search_kind, retrieve_vector)
-    })
+    let (search_result, cache_status) = perform_cached_search(
+        index_scheduler.get_ref(),
+        &search_queue,
+        &search_cache,
+        &index_uid,
+        index,
+        query,
+        search_kind,
+        retrieve_vector,
+    )
     .await?;
     if let Ok(ref search_result) = search_result {
         aggregate.succeed(search_result);
+        query_log.record(&index_uid, search_result);
     }
     analytics.get_search(aggregate);
 
     let search_result = search_result?;
 
     debug!(returns = ?search_result, "Search get");
-    Ok(HttpResponse::Ok().json(search_result))
+    let mut response = HttpResponse::Ok();
+    if let Some(cache_status) = cache_status {
+        response.insert_header((SEARCH_CACHE_HEADER, cache_status.as_str()));
+    }
+    Ok(response.json(search_result))
 }
 
 pub async fn search_with_post(
     index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
     search_queue: web::Data<SearchQueue>,
+    search_cache: web::Data<SearchCache>,
+    query_log: web::Data<QueryLog>,
     index_uid: web::Path<String>,
     params: AwebJson<SearchQuery, DeserrJsonError>,
     req: HttpRequest,
@@ -260,6 +317,7 @@ pub async fn search_with_post(
 
     // Tenant token search_rules.
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        check_ranking_rules_override(&query.ranking_rules, &search_rules)?;
         add_search_rules(&mut query.filter, search_rules);
     }
 
@@ -272,13 +330,20 @@ pub async fn search_with_post(
     let search_kind = search_kind(&query, index_scheduler.get_ref(), &index, features)?;
     let retrieve_vectors = RetrieveVectors::new(query.retrieve_vectors, features)?;
 
-    let _permit = search_queue.try_get_search_permit().await?;
-    let search_result = tokio::task::spawn_blocking(move || {
-        perform_search(&index, query, search_kind, retrieve_vectors)
-    })
+    let (search_result, cache_status) = perform_cached_search(
+        index_scheduler.get_ref(),
+        &search_queue,
+        &search_cache,
+        &index_uid,
+        index,
+        query,
+        search_kind,
+        retrieve_vectors,
+    )
     .await?;
     if let Ok(ref search_result) = search_result {
         aggregate.succeed(search_result);
+        query_log.record(&index_uid, search_result);
         if search_result.degraded {
             MEILISEARCH_DEGRADED_SEARCH_REQUESTS.inc();
         }
@@ -288,7 +353,51 @@ pub async fn search_with_post(
     let search_result = search_result?;
 
     debug!(returns = ?search_result, "Search post");
-    Ok(HttpResponse::Ok().json(search_result))
+    let mut response = HttpResponse::Ok();
+    if let Some(cache_status) = cache_status {
+        response.insert_header((SEARCH_CACHE_HEADER, cache_status.as_str()));
+    }
+    Ok(response.json(search_result))
+}
+
+/// Returns the cached result of the search when an identical search was made on the same
+/// generation of the index, otherwise performs the search and caches its result.
+///
+/// The cache status is `None` when the search cache is disabled.
+#[allow(clippy::too_many_arguments)]
+async fn perform_cached_search(
+    index_scheduler: &IndexScheduler,
+    search_queue: &SearchQueue,
+    search_cache: &SearchCache,
+    index_uid: &IndexUid,
+    index: milli::Index,
+    query: SearchQuery,
+    search_kind: SearchKind,
+    retrieve_vectors: RetrieveVectors,
+) -> Result<(Result<SearchResult, MeilisearchHttpError>, Option<CacheStatus>), ResponseError> {
+    let before_search = Instant::now();
+    // The generation must be read before searching, see the `search_cache` module.
+    let generation = index_scheduler.index_generation(index_uid);
+    let cache_key = search_cache.key(index_uid, generation, &query, retrieve_vectors);
+
+    if let Some(mut search_result) = cache_key.as_ref().and_then(|key| search_cache.get(key)) {
+        search_result.processing_time_ms = before_search.elapsed().as_millis();
+        return Ok((Ok(search_result), Some(CacheStatus::Hit)));
+    }
+
+    let _permit = search_queue.try_get_search_permit().await?;
+    let search_result = tokio::task::spawn_blocking(move || {
+        perform_search(&index, query, search_kind, retrieve_vectors)
+    })
+    .await?;
+
+    let cache_status = cache_key.map(|key| {
+        if let Ok(search_result) = &search_result {
+            search_cache.insert(key, search_result);
+        }
+        CacheStatus::Miss
+    });
+    Ok((search_result, cache_status))
 }
 
 pub fn search_kind(
@@ -370,4 +479,25 @@ mod test {
         // This is ugly but eh, I don't want to write a full parser just for this unused route
         assert_eq!(sort, vec!["doggo:asc".to_string(), "_geoPoint(1,2),catto:desc".to_string(),]);
     }
+
+    #[test]
+    fn test_fix_sort_query_parameters_with_geo_decay() {
+        let sort =
+            fix_sort_query_parameters("_geoPoint(45.4642, 9.19):decay(offset=1km),price:asc");
+        assert_eq!(
+            sort,
+            vec!["_geoPoint(45.4642, 9.19):decay(offset=1km)".to_string(), "price:asc".to_string()]
+        );
+        let sort = fix_sort_query_parameters(
+            "doggo:asc , _geoPoint(12.45,13.56):decay(scale=5km, fn=gauss) , catto:desc",
+        );
+        assert_eq!(
+            sort,
+            vec![
+                "doggo:asc".to_string(),
+                "_geoPoint(12.45,13.56):decay(scale=5km, fn=gauss)".to_string(),
+                "catto:desc".to_string(),
+            ]
+        );
+    }
 }
@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::time::Instant;
+
+use actix_web::web::{self, Data};
+use actix_web::HttpResponse;
+use deserr::actix_web::AwebJson;
+use deserr::{DeserializeError, ErrorKind, ValuePointerRef};
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::keys::actions;
+use meilisearch_types::milli;
+use serde::Serialize;
+use tracing::debug;
+
+use super::ActionPolicy;
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::query_log::QueryLog;
+use crate::search_queue::SearchQueue;
+
+pub const DEFAULT_SUGGEST_LIMIT: fn() -> usize = || 10;
+/// The maximum number of suggestions that can be requested at once.
+pub const MAX_SUGGEST_LIMIT: usize = 1000;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(suggest))));
+}
+
+#[derive(Debug, Clone, PartialEq, deserr::Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields, validate = validate_suggest_query -> DeserrJsonError<InvalidSuggestLimit>)]
+pub struct SuggestQuery {
+    #[deserr(error = DeserrJsonError<InvalidSuggestQ>, missing_field_error = DeserrJsonError::missing_suggest_q)]
+    pub q: String,
+    #[deserr(default = DEFAULT_SUGGEST_LIMIT(), error = DeserrJsonError<InvalidSuggestLimit>)]
+    pub limit: usize,
+}
+
+fn validate_suggest_query<E: DeserializeError>(
+    query: SuggestQuery,
+    location: ValuePointerRef,
+) -> Result<SuggestQuery, E> {
+    if query.limit > MAX_SUGGEST_LIMIT {
+        Err(deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: format!(
+                    "The `limit` must be lower than or equal to {MAX_SUGGEST_LIMIT}, but found `{}`.",
+                    query.limit
+                ),
+            },
+            location.push_key("limit"),
+        )))
+    } else {
+        Ok(query)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestionHit {
+    pub text: String,
+    pub word: String,
+    pub typos: u8,
+    pub document_count: u64,
+    pub query_count: u64,
+}
+
+impl From<milli::Suggestion> for SuggestionHit {
+    fn from(suggestion: milli::Suggestion) -> Self {
+        let milli::Suggestion { text, word, typos, document_count, query_count } = suggestion;
+        Self { text, word, typos, document_count, query_count }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestResult {
+    pub suggestions: Vec<SuggestionHit>,
+    pub query: String,
+    pub processing_time_ms: u128,
+}
+
+pub async fn suggest(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    search_queue: Data<SearchQueue>,
+    query_log: Data<QueryLog>,
+    index_uid: web::Path<String>,
+    params: AwebJson<SuggestQuery, DeserrJsonError>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let query = params.into_inner();
+    debug!(parameters = ?query, "Suggest");
+
+    // The suggestions are made from all the words of the index,
+    // they would leak the content of the documents hidden by the tenant token.
+    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        if search_rules.filter.is_some() {
+            return Err(MeilisearchHttpError::SuggestForbidden.into());
+        }
+    }
+
+    let index = index_scheduler.index(&index_uid)?;
+    let query_word_counts = query_log.word_counts(&index_uid);
+
+    let _permit = search_queue.try_get_search_permit().await?;
+    let result = tokio::task::spawn_blocking(move || {
+        perform_suggest(&index, query, query_word_counts.as_deref())
+    })
+    .await??;
+
+    debug!(returns = ?result, "Suggest");
+    Ok(HttpResponse::Ok().json(result))
+}
+
+fn perform_suggest(
+    index: &milli::Index,
+    query: SuggestQuery,
+    query_word_counts: Option<&BTreeMap<String, u64>>,
+) -> Result<SuggestResult, MeilisearchHttpError> {
+    let before_suggest = Instant::now();
+    let rtxn = index.read_txn()?;
+
+    let SuggestQuery { q, limit } = query;
+    let mut suggest = milli::Suggest::new(q.as_str(), index, &rtxn);
+    suggest.limit(limit);
+    if let Some(query_word_counts) = query_word_counts {
+        suggest.query_word_counts(query_word_counts);
+    }
+    let suggestions = suggest.execute()?.into_iter().map(SuggestionHit::from).collect();
+
+    Ok(SuggestResult {
+        suggestions,
+        query: q,
+        processing_time_ms: before_suggest.elapsed().as_millis(),
+    })
+}
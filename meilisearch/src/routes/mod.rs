@@ -19,18 +19,20 @@ use crate::Opt;
 const PAGINATION_DEFAULT_LIMIT: usize = 20;
 
 mod api_key;
+mod batches;
 mod dump;
 pub mod features;
 pub mod indexes;
 mod logs;
 mod metrics;
-mod multi_search;
+pub mod multi_search;
 mod snapshot;
 mod swap_indexes;
 pub mod tasks;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(web::scope("/tasks").configure(tasks::configure))
+        .service(web::scope("/batches").configure(batches::configure))
         .service(web::resource("/health").route(web::get().to(get_health)))
         .service(web::scope("/logs").configure(logs::configure))
         .service(web::scope("/keys").configure(api_key::configure))
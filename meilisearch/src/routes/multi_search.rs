@@ -4,7 +4,7 @@ use actix_web::{HttpRequest, HttpResponse};
 use deserr::actix_web::AwebJson;
 use index_scheduler::IndexScheduler;
 use meilisearch_types::deserr::DeserrJsonError;
-use meilisearch_types::error::ResponseError;
+use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::keys::actions;
 use serde::Serialize;
 use tracing::debug;
@@ -15,7 +15,8 @@ use crate::extractors::authentication::{AuthenticationError, GuardedData};
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::indexes::search::search_kind;
 use crate::search::{
-    add_search_rules, perform_search, RetrieveVectors, SearchQueryWithIndex, SearchResultWithIndex,
+    add_search_rules, check_ranking_rules_override, perform_federated_search, perform_search,
+    FederatedSearch, RetrieveVectors, SearchQueryWithIndex, SearchResultWithIndex,
 };
 use crate::search_queue::SearchQueue;
 
@@ -28,22 +29,18 @@ struct SearchResults {
     results: Vec<SearchResultWithIndex>,
 }
 
-#[derive(Debug, deserr::Deserr)]
-#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
-pub struct SearchQueries {
-    queries: Vec<SearchQueryWithIndex>,
-}
-
 pub async fn multi_search_with_post(
     index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
     search_queue: Data<SearchQueue>,
-    params: AwebJson<SearchQueries, DeserrJsonError>,
+    params: AwebJson<FederatedSearch, DeserrJsonError>,
     req: HttpRequest,
     analytics: web::Data<dyn Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
-    let queries = params.into_inner().queries;
+    let federated_search = params.into_inner();
+
+    let mut multi_aggregate = MultiSearchAggregator::from_federated_search(&federated_search, &req);
+    let FederatedSearch { mut queries, federation } = federated_search;
 
-    let mut multi_aggregate = MultiSearchAggregator::from_queries(&queries, &req);
     let features = index_scheduler.features();
 
     // Since we don't want to process half of the search requests and then get a permit refused
@@ -53,21 +50,69 @@ pub async fn multi_search_with_post(
     // Explicitly expect a `(ResponseError, usize)` for the error type rather than `ResponseError` only,
     // so that `?` doesn't work if it doesn't use `with_index`, ensuring that it is not forgotten in case of code
     // changes.
+    let authorization: Result<(), (ResponseError, usize)> = 'check_authorization: {
+        for (query_index, federated_query) in queries.iter_mut().enumerate() {
+            let index_uid = federated_query.index_uid.as_str();
+            // Check index from API key
+            if !index_scheduler.filters().is_index_authorized(index_uid) {
+                break 'check_authorization Err(AuthenticationError::InvalidToken)
+                    .with_index(query_index);
+            }
+            // Apply search rules from tenant token
+            if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(index_uid)
+            {
+                if let Err(err) =
+                    check_ranking_rules_override(&federated_query.ranking_rules, &search_rules)
+                {
+                    break 'check_authorization Err(err).with_index(query_index);
+                }
+                add_search_rules(&mut federated_query.filter, search_rules);
+            }
+        }
+        Ok(())
+    };
+
+    if let Some(federation) = federation {
+        let search_result = match authorization {
+            Ok(()) => {
+                let index_scheduler = (*index_scheduler).clone();
+                tokio::task::spawn_blocking(move || {
+                    perform_federated_search(&index_scheduler, queries, federation, features)
+                })
+                .await?
+            }
+            Err(err) => Err(err),
+        };
+
+        if search_result.is_ok() {
+            multi_aggregate.succeed();
+        }
+        analytics.post_multi_search(multi_aggregate);
+
+        let search_result = search_result.map_err(with_query_index_context)?;
+
+        debug!(returns = ?search_result, "Federated-search");
+        return Ok(HttpResponse::Ok().json(search_result));
+    }
+
     let search_results: Result<_, (ResponseError, usize)> = async {
+        authorization?;
         let mut search_results = Vec::with_capacity(queries.len());
-        for (query_index, (index_uid, mut query)) in
-            queries.into_iter().map(SearchQueryWithIndex::into_index_query).enumerate()
+        for (query_index, (index_uid, query, federation_options)) in queries
+            .into_iter()
+            .map(SearchQueryWithIndex::into_index_query_federation)
+            .enumerate()
         {
             debug!(on_index = query_index, parameters = ?query, "Multi-search");
 
-            // Check index from API key
-            if !index_scheduler.filters().is_index_authorized(&index_uid) {
-                return Err(AuthenticationError::InvalidToken).with_index(query_index);
-            }
-            // Apply search rules from tenant token
-            if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid)
-            {
-                add_search_rules(&mut query.filter, search_rules);
+            if federation_options.is_some() {
+                return Err((
+                    ResponseError::from_msg(
+                        "Using `federationOptions` is not allowed in a non-federated search.\n Hint: remove `federationOptions` from the query or add `federation` to the request".to_string(),
+                        Code::InvalidMultiSearchFederationOptions,
+                    ),
+                    query_index,
+                ));
             }
 
             let index = index_scheduler
@@ -106,21 +151,24 @@ pub async fn multi_search_with_post(
     }
     analytics.post_multi_search(multi_aggregate);
 
-    let search_results = search_results.map_err(|(mut err, query_index)| {
-        // Add the query index that failed as context for the error message.
-        // We're doing it only here and not directly in the `WithIndex` trait so that the `with_index` function returns a different type
-        // of result and we can benefit from static typing.
-        err.message = format!("Inside `.queries[{query_index}]`: {}", err.message);
-        err
-    })?;
+    let search_results = search_results.map_err(with_query_index_context)?;
 
     debug!(returns = ?search_results, "Multi-search");
 
     Ok(HttpResponse::Ok().json(SearchResults { results: search_results }))
 }
 
+/// Add the query index that failed as context for the error message.
+///
+/// We're doing it only here and not directly in the `WithIndex` trait so that the `with_index` function returns a different type
+/// of result and we can benefit from static typing.
+fn with_query_index_context((mut err, query_index): (ResponseError, usize)) -> ResponseError {
+    err.message = format!("Inside `.queries[{query_index}]`: {}", err.message);
+    err
+}
+
 /// Local `Result` extension trait to avoid `map_err` boilerplate.
-trait WithIndex {
+pub(crate) trait WithIndex {
     type T;
     /// convert the error type inside of the `Result` to a `ResponseError`, and return a couple of it + the usize.
     fn with_index(self, index: usize) -> Result<Self::T, (ResponseError, usize)>;
@@ -3,6 +3,7 @@ use actix_web::{web, HttpRequest, HttpResponse};
 use deserr::actix_web::AwebQueryParameter;
 use deserr::Deserr;
 use index_scheduler::{IndexScheduler, Query, TaskId};
+use meilisearch_types::batches::BatchId;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::DeserrQueryParamError;
 use meilisearch_types::error::deserr_codes::*;
@@ -46,6 +47,8 @@ pub struct TasksFilterQuery {
 
     #[deserr(default, error = DeserrQueryParamError<InvalidTaskUids>)]
     pub uids: OptionStarOrList<u32>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidBatchUids>)]
+    pub batch_uids: OptionStarOrList<BatchId>,
     #[deserr(default, error = DeserrQueryParamError<InvalidTaskCanceledBy>)]
     pub canceled_by: OptionStarOrList<u32>,
     #[deserr(default, error = DeserrQueryParamError<InvalidTaskTypes>)]
@@ -70,7 +73,7 @@ pub struct TasksFilterQuery {
 }
 
 impl TasksFilterQuery {
-    fn into_query(self) -> Query {
+    pub(crate) fn into_query(self) -> Query {
         Query {
             limit: Some(self.limit.0),
             from: self.from.as_deref().copied(),
@@ -78,6 +81,7 @@ impl TasksFilterQuery {
             types: self.types.merge_star_and_none(),
             index_uids: self.index_uids.map(|x| x.to_string()).merge_star_and_none(),
             uids: self.uids.merge_star_and_none(),
+            batch_uids: self.batch_uids.merge_star_and_none(),
             canceled_by: self.canceled_by.merge_star_and_none(),
             before_enqueued_at: self.before_enqueued_at.merge_star_and_none(),
             after_enqueued_at: self.after_enqueued_at.merge_star_and_none(),
@@ -147,6 +151,7 @@ impl TaskDeletionOrCancelationQuery {
             types: self.types.merge_star_and_none(),
             index_uids: self.index_uids.map(|x| x.to_string()).merge_star_and_none(),
             uids: self.uids.merge_star_and_none(),
+            batch_uids: None,
             canceled_by: self.canceled_by.merge_star_and_none(),
             before_enqueued_at: self.before_enqueued_at.merge_star_and_none(),
             after_enqueued_at: self.after_enqueued_at.merge_star_and_none(),
@@ -309,7 +314,10 @@ async fn get_task(
     let (tasks, _) = index_scheduler.get_tasks_from_authorized_indexes(query, filters)?;
 
     if let Some(task) = tasks.first() {
-        let task_view = TaskView::from_task(task);
+        let mut task_view = TaskView::from_task(task);
+        if task.status == Status::Processing {
+            task_view.progress = index_scheduler.get_task_progress(task.uid)?;
+        }
         Ok(HttpResponse::Ok().json(task_view))
     } else {
         Err(index_scheduler::Error::TaskNotFound(task_uid).into())
@@ -591,7 +599,7 @@ mod tests {
             let err = deserr_query_params::<TaskDeletionOrCancelationQuery>(params).unwrap_err();
             snapshot!(meili_snap::json_string!(err), @r###"
             {
-              "message": "Invalid value in parameter `types`: `createIndex` is not a valid task type. Available types are `documentAdditionOrUpdate`, `documentDeletion`, `settingsUpdate`, `indexCreation`, `indexDeletion`, `indexUpdate`, `indexSwap`, `taskCancelation`, `taskDeletion`, `dumpCreation`, `snapshotCreation`.",
+              "message": "Invalid value in parameter `types`: `createIndex` is not a valid task type. Available types are `documentAdditionOrUpdate`, `documentEdition`, `documentDeletion`, `settingsUpdate`, `indexCreation`, `indexDeletion`, `indexUpdate`, `indexSwap`, `taskCancelation`, `taskDeletion`, `dumpCreation`, `snapshotCreation`, `dumpImport`.",
               "code": "invalid_task_types",
               "type": "invalid_request",
               "link": "https://docs.meilisearch.com/errors#invalid_task_types"
@@ -642,14 +650,14 @@ mod tests {
         {
             let params = "from=12&limit=15&indexUids=toto,tata-78&statuses=succeeded,enqueued&afterEnqueuedAt=2012-04-23&uids=1,2,3";
             let query = deserr_query_params::<TasksFilterQuery>(params).unwrap();
-            snapshot!(format!("{:?}", query), @r###"TasksFilterQuery { limit: Param(15), from: Some(Param(12)), uids: List([1, 2, 3]), canceled_by: None, types: None, statuses: List([Succeeded, Enqueued]), index_uids: List([IndexUid("toto"), IndexUid("tata-78")]), after_enqueued_at: Other(2012-04-24 0:00:00.0 +00:00:00), before_enqueued_at: None, after_started_at: None, before_started_at: None, after_finished_at: None, before_finished_at: None }"###);
+            snapshot!(format!("{:?}", query), @r###"TasksFilterQuery { limit: Param(15), from: Some(Param(12)), uids: List([1, 2, 3]), batch_uids: None, canceled_by: None, types: None, statuses: List([Succeeded, Enqueued]), index_uids: List([IndexUid("toto"), IndexUid("tata-78")]), after_enqueued_at: Other(2012-04-24 0:00:00.0 +00:00:00), before_enqueued_at: None, after_started_at: None, before_started_at: None, after_finished_at: None, before_finished_at: None }"###);
         }
         {
             // Stars should translate to `None` in the query
             // Verify value of the default limit
             let params = "indexUids=*&statuses=succeeded,*&afterEnqueuedAt=2012-04-23&uids=1,2,3";
             let query = deserr_query_params::<TasksFilterQuery>(params).unwrap();
-            snapshot!(format!("{:?}", query), @"TasksFilterQuery { limit: Param(20), from: None, uids: List([1, 2, 3]), canceled_by: None, types: None, statuses: Star, index_uids: Star, after_enqueued_at: Other(2012-04-24 0:00:00.0 +00:00:00), before_enqueued_at: None, after_started_at: None, before_started_at: None, after_finished_at: None, before_finished_at: None }");
+            snapshot!(format!("{:?}", query), @"TasksFilterQuery { limit: Param(20), from: None, uids: List([1, 2, 3]), batch_uids: None, canceled_by: None, types: None, statuses: Star, index_uids: Star, after_enqueued_at: Other(2012-04-24 0:00:00.0 +00:00:00), before_enqueued_at: None, after_started_at: None, before_started_at: None, after_finished_at: None, before_finished_at: None }");
         }
         {
             // Stars should also translate to `None` in task deletion/cancelation queries
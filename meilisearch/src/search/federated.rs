@@ -0,0 +1,441 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
+use std::iter::Peekable;
+use std::time::{Duration, Instant};
+
+use actix_http::StatusCode;
+use deserr::Deserr;
+use index_scheduler::{IndexScheduler, RoFeatures};
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::heed::RoTxn;
+use meilisearch_types::milli::score_details::{ScoreDetails, ScoringStrategy};
+use meilisearch_types::milli::{self, DocumentId, TimeBudget};
+use roaring::RoaringBitmap;
+use serde::Serialize;
+use serde_json::json;
+
+use super::rules::AppliedRules;
+use super::{
+    distinct_groups, make_hits, prepare_search, search_from_kind, AttributesFormat, HitsInfo,
+    RetrieveVectors, SearchHit, SearchQueryWithIndex, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
+};
+use crate::routes::indexes::search::search_kind;
+use crate::routes::multi_search::WithIndex;
+
+pub const DEFAULT_FEDERATED_WEIGHT: fn() -> f64 = || 1.0;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidMultiSearchFederationOptions>, rename_all = camelCase, deny_unknown_fields)]
+pub struct FederationOptions {
+    #[deserr(default, error = DeserrJsonError<InvalidMultiSearchWeight>)]
+    pub weight: Weight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserr)]
+#[deserr(try_from(f64) = TryFrom::try_from -> InvalidMultiSearchWeight)]
+pub struct Weight(f64);
+
+impl Default for Weight {
+    fn default() -> Self {
+        Weight(DEFAULT_FEDERATED_WEIGHT())
+    }
+}
+
+impl std::convert::TryFrom<f64> for Weight {
+    type Error = InvalidMultiSearchWeight;
+
+    fn try_from(f: f64) -> Result<Self, Self::Error> {
+        if f < 0.0 {
+            Err(InvalidMultiSearchWeight)
+        } else {
+            Ok(Weight(f))
+        }
+    }
+}
+
+impl std::ops::Deref for Weight {
+    type Target = f64;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidMultiSearchFederated>, rename_all = camelCase, deny_unknown_fields)]
+pub struct Federation {
+    #[deserr(default = DEFAULT_SEARCH_LIMIT(), error = DeserrJsonError<InvalidSearchLimit>)]
+    pub limit: usize,
+    #[deserr(default = DEFAULT_SEARCH_OFFSET(), error = DeserrJsonError<InvalidSearchOffset>)]
+    pub offset: usize,
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct FederatedSearch {
+    pub queries: Vec<SearchQueryWithIndex>,
+    #[deserr(default, error = DeserrJsonError<InvalidMultiSearchFederated>)]
+    pub federation: Option<Federation>,
+}
+
+#[derive(Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FederatedSearchResult {
+    pub hits: Vec<SearchHit>,
+    pub processing_time_ms: u128,
+    #[serde(flatten)]
+    pub hits_info: HitsInfo,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub semantic_hit_count: Option<u32>,
+
+    /// The uids of the merchandising rules applied to the queries, by index.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub applied_rules: BTreeMap<String, Vec<String>>,
+
+    // These fields are only used for analytics purposes
+    #[serde(skip)]
+    pub degraded: bool,
+    #[serde(skip)]
+    pub used_negative_operator: bool,
+}
+
+impl std::fmt::Debug for FederatedSearchResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let FederatedSearchResult {
+            hits,
+            processing_time_ms,
+            hits_info,
+            semantic_hit_count,
+            applied_rules,
+            degraded,
+            used_negative_operator,
+        } = self;
+
+        let mut debug = f.debug_struct("FederatedSearchResult");
+        // The most important thing when looking at a search result is the time it took to process
+        debug.field("processing_time_ms", &processing_time_ms);
+        debug.field("hits", &format!("[{} hits returned]", hits.len()));
+        debug.field("hits_info", &hits_info);
+        if *used_negative_operator {
+            debug.field("used_negative_operator", used_negative_operator);
+        }
+        if *degraded {
+            debug.field("degraded", degraded);
+        }
+        if let Some(semantic_hit_count) = semantic_hit_count {
+            debug.field("semantic_hit_count", &semantic_hit_count);
+        }
+        if !applied_rules.is_empty() {
+            debug.field("applied_rules", &applied_rules);
+        }
+
+        debug.finish()
+    }
+}
+
+/// The hits of one of the queries of a federated search, not formatted yet.
+///
+/// Only the hits making it to the requested page of the merged list are formatted,
+/// with the index, transaction and format of their query.
+struct QueryHits {
+    index_uid: String,
+    query_index: usize,
+    index: milli::Index,
+    rtxn: RoTxn<'static>,
+    format: AttributesFormat,
+    matching_words: milli::MatchingWords,
+    hits: Peekable<std::vec::IntoIter<QueryHit>>,
+}
+
+/// A hit of a query of a federated search, along with its weighted ranking score.
+struct QueryHit {
+    docid: DocumentId,
+    score: Vec<ScoreDetails>,
+    group: Option<(u64, Vec<u32>)>,
+    weighted_score: f64,
+}
+
+/// Runs each query of a federated search and merges their hits in a single list,
+/// ordered by the weighted ranking score of each hit.
+///
+/// The tenant token search rules must already have been applied to the queries.
+pub fn perform_federated_search(
+    index_scheduler: &IndexScheduler,
+    queries: Vec<SearchQueryWithIndex>,
+    federation: Federation,
+    features: RoFeatures,
+) -> Result<FederatedSearchResult, (ResponseError, usize)> {
+    let before_search = Instant::now();
+
+    // the queries are not paginated individually, so each of them must return enough hits
+    // to fill the requested page of the merged list by itself.
+    let required_hit_count = federation.offset.saturating_add(federation.limit);
+
+    let mut query_hits = Vec::with_capacity(queries.len());
+    // a document matched by several queries of the same index must only be counted once
+    let mut candidates_per_index: BTreeMap<String, RoaringBitmap> = BTreeMap::new();
+    let mut semantic_hit_count = None;
+    let mut applied_rules_per_index: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut degraded = false;
+    let mut used_negative_operator = false;
+
+    for (query_index, federated_query) in queries.into_iter().enumerate() {
+        if let Some(pagination_field) = federated_query.has_pagination() {
+            return Err((
+                ResponseError::from_msg(
+                    format!("Using pagination options is not allowed in federated queries.\n Hint: remove `{pagination_field}` from the query or remove `federation` from the request"),
+                    Code::InvalidMultiSearchQueryPagination,
+                ),
+                query_index,
+            ));
+        }
+        if let Some(facets) = federated_query.has_facets() {
+            return Err((
+                ResponseError::from_msg(
+                    format!("Using facet options is not allowed in federated queries.\n Hint: remove `facets: {facets:?}` from the query or remove `federation` from the request"),
+                    Code::InvalidMultiSearchQueryFacets,
+                ),
+                query_index,
+            ));
+        }
+
+        let (index_uid, mut query, federation_options) =
+            federated_query.into_index_query_federation();
+        let weight = *federation_options.unwrap_or_default().weight;
+
+        query.offset = 0;
+        query.limit = required_hit_count;
+
+        let index = index_scheduler
+            .index(&index_uid)
+            .map_err(|err| {
+                let mut err = ResponseError::from(err);
+                // Patch the HTTP status code to 400 as it defaults to 404 for `index_not_found`, but
+                // here the resource not found is not part of the URL.
+                err.code = StatusCode::BAD_REQUEST;
+                err
+            })
+            .with_index(query_index)?;
+
+        let search_kind =
+            search_kind(&query, index_scheduler, &index, features).with_index(query_index)?;
+        let retrieve_vectors =
+            RetrieveVectors::new(query.retrieve_vectors, features).with_index(query_index)?;
+
+        // the transaction is kept until the hits of the merged list are formatted
+        let rtxn = index.static_read_txn().with_index(query_index)?;
+        let time_budget = match index.search_cutoff(&rtxn).with_index(query_index)? {
+            Some(cutoff) => TimeBudget::new(Duration::from_millis(cutoff)),
+            None => TimeBudget::default(),
+        };
+
+        // the merchandising rules of the index are applied to each query before the merge,
+        // the pinned documents have no score details and are given a perfect ranking score.
+        let applied_rules =
+            AppliedRules::new(&index, &rtxn, query.q.as_deref(), query.filter.as_ref())
+                .with_index(query_index)?;
+        query.filter = applied_rules.filter(query.filter.take());
+        let hidden = applied_rules.hidden_docids(&index, &rtxn).with_index(query_index)?;
+        let mut pinned = applied_rules
+            .pinned_docids(&index, &rtxn, query.filter.as_ref(), &hidden)
+            .with_index(query_index)?;
+
+        let (mut search, _is_finite_pagination, _max_total_hits, offset, limit) =
+            prepare_search(&index, &rtxn, &query, &search_kind, time_budget)
+                .with_index(query_index)?;
+        // the ranking score is what allows comparing hits coming from different queries
+        search.scoring_strategy(ScoringStrategy::Detailed);
+
+        if !applied_rules.is_empty() {
+            search.excluded_documents(hidden | pinned.docids());
+            let (ranked_offset, ranked_limit) = pinned.ranked_offset_limit(offset, limit);
+            search.offset(ranked_offset);
+            search.limit(ranked_limit);
+            let uids = applied_rules_per_index.entry(index_uid.to_string()).or_default();
+            for uid in &applied_rules.uids {
+                if !uids.contains(uid) {
+                    uids.push(uid.clone());
+                }
+            }
+        }
+
+        let (
+            milli::SearchResult {
+                documents_ids,
+                matching_words,
+                mut candidates,
+                document_scores,
+                degraded: query_degraded,
+                used_negative_operator: query_used_negative_operator,
+            },
+            query_semantic_hit_count,
+        ) = search_from_kind(&search_kind, &search).with_index(query_index)?;
+
+        let (documents_ids, document_scores) = if pinned.is_empty() {
+            (documents_ids, document_scores)
+        } else {
+            pinned.clamp(candidates.len() as usize);
+            candidates |= pinned.docids();
+            pinned.insert(offset, limit, documents_ids, document_scores)
+        };
+
+        *candidates_per_index.entry(index_uid.to_string()).or_default() |= candidates;
+        if let Some(query_semantic_hit_count) = query_semantic_hit_count {
+            semantic_hit_count = Some(semantic_hit_count.unwrap_or(0) + query_semantic_hit_count);
+        }
+        degraded |= query_degraded;
+        used_negative_operator |= query_used_negative_operator;
+
+        let groups = distinct_groups(&search, &documents_ids, query.distinct_group_limit)
+            .with_index(query_index)?;
+
+        let format = AttributesFormat {
+            attributes_to_retrieve: query.attributes_to_retrieve,
+            retrieve_vectors,
+            attributes_to_highlight: query.attributes_to_highlight,
+            attributes_to_crop: query.attributes_to_crop,
+            crop_length: query.crop_length,
+            crop_marker: query.crop_marker,
+            highlight_pre_tag: query.highlight_pre_tag,
+            highlight_post_tag: query.highlight_post_tag,
+            show_matches_position: query.show_matches_position,
+            sort: query.sort,
+            show_ranking_score: query.show_ranking_score,
+            show_ranking_score_details: query.show_ranking_score_details,
+            locales: query.locales.map(|l| l.iter().copied().map(Into::into).collect()),
+        };
+
+        // the groups are only computed when a group limit is requested, for all the hits
+        let mut groups = groups.into_iter();
+        let hits: Vec<_> = documents_ids
+            .into_iter()
+            .zip(document_scores)
+            .map(|(docid, score)| QueryHit {
+                docid,
+                weighted_score: ScoreDetails::global_score(score.iter()) * weight,
+                score,
+                group: groups.next(),
+            })
+            .collect();
+
+        query_hits.push(QueryHits {
+            index_uid: index_uid.into_inner(),
+            query_index,
+            index,
+            rtxn,
+            format,
+            matching_words,
+            hits: hits.into_iter().peekable(),
+        });
+    }
+
+    let estimated_total_hits =
+        candidates_per_index.values().map(|candidates| candidates.len() as usize).sum();
+
+    // a document matched by several queries of the same index is only returned once,
+    // with its best weighted ranking score, which is the first one to be merged.
+    let index_uids: Vec<_> = query_hits.iter().map(|query| query.index_uid.clone()).collect();
+    let mut seen = HashSet::new();
+    let page: Vec<_> = merge_hits(&mut query_hits)
+        .filter(|(position, hit)| seen.insert((&index_uids[*position], hit.docid)))
+        .skip(federation.offset)
+        .take(federation.limit)
+        .collect();
+
+    // the hits of the page are formatted query by query, then put back in the merged order.
+    let page_len = page.len();
+    let mut page_per_query: Vec<Vec<(usize, QueryHit)>> =
+        query_hits.iter().map(|_| Vec::new()).collect();
+    for (page_position, (position, hit)) in page.into_iter().enumerate() {
+        page_per_query[position].push((page_position, hit));
+    }
+
+    let mut hits: Vec<Option<SearchHit>> = std::iter::repeat_with(|| None).take(page_len).collect();
+    for (query, page_hits) in query_hits.into_iter().zip(page_per_query) {
+        if page_hits.is_empty() {
+            continue;
+        }
+        let QueryHits { index_uid, query_index, index, rtxn, format, matching_words, .. } = query;
+
+        let mut page_positions = Vec::with_capacity(page_hits.len());
+        let mut documents_ids = Vec::with_capacity(page_hits.len());
+        let mut document_scores = Vec::with_capacity(page_hits.len());
+        let mut groups = Vec::new();
+        let mut weighted_scores = Vec::with_capacity(page_hits.len());
+        for (page_position, hit) in page_hits {
+            page_positions.push(page_position);
+            documents_ids.push(hit.docid);
+            document_scores.push(hit.score);
+            groups.extend(hit.group);
+            weighted_scores.push(hit.weighted_score);
+        }
+
+        let formatted = make_hits(
+            &index,
+            &rtxn,
+            format,
+            matching_words,
+            documents_ids,
+            document_scores,
+            groups,
+        )
+        .with_index(query_index)?;
+
+        for ((page_position, mut hit), weighted_ranking_score) in
+            page_positions.into_iter().zip(formatted).zip(weighted_scores)
+        {
+            hit.document.insert(
+                "_federation".to_string(),
+                json!({
+                    "indexUid": index_uid,
+                    "queriesPosition": query_index,
+                    "weightedRankingScore": weighted_ranking_score,
+                }),
+            );
+            hits[page_position] = Some(hit);
+        }
+    }
+    let hits = hits.into_iter().flatten().collect();
+
+    Ok(FederatedSearchResult {
+        hits,
+        processing_time_ms: before_search.elapsed().as_millis(),
+        hits_info: HitsInfo::OffsetLimit {
+            limit: federation.limit,
+            offset: federation.offset,
+            estimated_total_hits,
+        },
+        semantic_hit_count,
+        applied_rules: applied_rules_per_index,
+        degraded,
+        used_negative_operator,
+    })
+}
+
+/// Lazily merges the hits of each query by decreasing weighted ranking score,
+/// along with the position of their query.
+///
+/// The hits of a single query are never reordered relative to each other, and when two hits
+/// from different queries have the same weighted score, the one from the first query wins.
+fn merge_hits(query_hits: &mut [QueryHits]) -> impl Iterator<Item = (usize, QueryHit)> + '_ {
+    std::iter::from_fn(move || {
+        let mut best: Option<(usize, f64)> = None;
+        for (position, query) in query_hits.iter_mut().enumerate() {
+            let Some(QueryHit { weighted_score: score, .. }) = query.hits.peek() else { continue };
+            let is_better = match best {
+                Some((_, best_score)) => score.total_cmp(&best_score) == Ordering::Greater,
+                None => true,
+            };
+            if is_better {
+                best = Some((position, *score));
+            }
+        }
+
+        let (position, _) = best?;
+        let hit = query_hits[position].hits.next()?;
+        Some((position, hit))
+    })
+}
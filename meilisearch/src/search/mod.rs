@@ -1,11 +1,12 @@
 use core::fmt;
-use std::cmp::min;
+use std::cmp::{min, Ordering};
 use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::convert::Infallible;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use deserr::Deserr;
+use deserr::{DeserializeError, Deserr, ErrorKind, ValueKind, ValuePointerRef};
 use either::Either;
 use indexmap::IndexMap;
 use meilisearch_auth::IndexSearchRules;
@@ -14,16 +15,18 @@ use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::locales::Locale;
 use meilisearch_types::milli::score_details::{ScoreDetails, ScoringStrategy};
 use meilisearch_types::milli::vector::parsed_vectors::ExplicitVectors;
 use meilisearch_types::milli::vector::Embedder;
 use meilisearch_types::milli::{FacetValueHit, OrderBy, SearchForFacetValues, TimeBudget};
-use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
+use meilisearch_types::settings::{RankingRuleView, DEFAULT_PAGINATION_MAX_TOTAL_HITS};
 use meilisearch_types::{milli, Document};
-use milli::tokenizer::TokenizerBuilder;
+use milli::localized_attributes_rules::locales_allow_list;
+use milli::tokenizer::{Language, TokenizerBuilder};
 use milli::{
-    AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, MatchBounds, MatcherBuilder,
-    SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    AscDesc, FacetAggregation, FieldId, FieldsIdsMap, Filter, FormatOptions, HistogramBuckets,
+    Index, MatchBounds, MatcherBuilder, SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
 };
 use regex::Regex;
 use serde::Serialize;
@@ -31,6 +34,11 @@ use serde_json::{json, Value};
 
 use crate::error::MeilisearchHttpError;
 
+mod federated;
+mod rules;
+pub use federated::{perform_federated_search, FederatedSearch, Federation, FederationOptions};
+use rules::AppliedRules;
+
 type MatchesPosition = BTreeMap<String, Vec<MatchBounds>>;
 
 pub const DEFAULT_SEARCH_OFFSET: fn() -> usize = || 0;
@@ -79,9 +87,15 @@ pub struct SearchQuery {
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
-    pub distinct: Option<String>,
+    pub distinct: Option<DistinctAttributes>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDistinctGroupLimit>)]
+    pub distinct_group_limit: Option<usize>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetAggregations>, default)]
+    pub facet_aggregations: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetHistograms>)]
+    pub facet_histograms: Option<BTreeMap<String, FacetHistogram>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -94,6 +108,39 @@ pub struct SearchQuery {
     pub attributes_to_search_on: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>, default)]
     pub ranking_score_threshold: Option<RankingScoreThreshold>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>, default)]
+    pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRankingRules>, default)]
+    pub ranking_rules: Option<Vec<RankingRuleView>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchBoost>, default)]
+    pub boost: Option<Vec<BoostQuery>>,
+}
+
+/// The attributes the hits are made distinct on, given either as a single attribute
+/// or as an array of attributes whose values form the distinct key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistinctAttributes(pub Vec<String>);
+
+impl<E: DeserializeError> Deserr<E> for DistinctAttributes {
+    fn deserialize_from_value<V: deserr::IntoValue>(
+        value: deserr::Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            deserr::Value::String(attribute) => Ok(DistinctAttributes(vec![attribute])),
+            deserr::Value::Sequence(_) => {
+                Vec::<String>::deserialize_from_value(value, location).map(DistinctAttributes)
+            }
+            _ => Err(deserr::take_cf_content(E::error::<V>(
+                None,
+                ErrorKind::IncorrectValueKind {
+                    actual: value,
+                    accepted: &[ValueKind::String, ValueKind::Sequence],
+                },
+                location,
+            ))),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Deserr)]
@@ -156,13 +203,19 @@ impl fmt::Debug for SearchQuery {
             filter,
             sort,
             distinct,
+            distinct_group_limit,
             facets,
+            facet_aggregations,
+            facet_histograms,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
             attributes_to_search_on,
             ranking_score_threshold,
+            locales,
+            ranking_rules,
+            boost,
         } = self;
 
         let mut debug = f.debug_struct("SearchQuery");
@@ -208,9 +261,24 @@ impl fmt::Debug for SearchQuery {
         if let Some(distinct) = distinct {
             debug.field("distinct", &distinct);
         }
+        if let Some(distinct_group_limit) = distinct_group_limit {
+            debug.field("distinct_group_limit", &distinct_group_limit);
+        }
         if let Some(facets) = facets {
             debug.field("facets", &facets);
         }
+        if *facet_aggregations {
+            debug.field("facet_aggregations", &facet_aggregations);
+        }
+        if let Some(facet_histograms) = facet_histograms {
+            debug.field("facet_histograms", &facet_histograms);
+        }
+        if let Some(ranking_rules) = ranking_rules {
+            debug.field("ranking_rules", &ranking_rules);
+        }
+        if let Some(boost) = boost {
+            debug.field("boost", &boost);
+        }
         debug.field("matching_strategy", &matching_strategy);
 
         // Then everything related to the formatting
@@ -243,11 +311,78 @@ impl fmt::Debug for SearchQuery {
         if let Some(ranking_score_threshold) = ranking_score_threshold {
             debug.field("ranking_score_threshold", &ranking_score_threshold);
         }
+        if let Some(locales) = locales {
+            debug.field("locales", &locales);
+        }
 
         debug.finish()
     }
 }
 
+/// The buckets of the histogram of a numeric facet, either of a fixed width or explicit ranges.
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchFacetHistograms>, rename_all = camelCase, deny_unknown_fields, validate = validate_facet_histogram -> DeserrJsonError<InvalidSearchFacetHistograms>)]
+pub struct FacetHistogram {
+    #[deserr(default)]
+    pub interval: Option<f64>,
+    #[deserr(default)]
+    pub ranges: Option<Vec<FacetHistogramRange>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchFacetHistograms>, rename_all = camelCase, deny_unknown_fields)]
+pub struct FacetHistogramRange {
+    pub from: f64,
+    pub to: f64,
+}
+
+fn validate_facet_histogram<E: DeserializeError>(
+    histogram: FacetHistogram,
+    location: ValuePointerRef,
+) -> Result<FacetHistogram, E> {
+    let msg = match (&histogram.interval, &histogram.ranges) {
+        (Some(_), Some(_)) | (None, None) => {
+            "Exactly one of `interval` or `ranges` must be specified.".to_string()
+        }
+        (Some(interval), None) if !(interval.is_finite() && *interval > 0.0) => {
+            format!("The `interval` must be a positive number, but found `{interval}`.")
+        }
+        (None, Some(ranges)) => {
+            let is_invalid = |range: &&FacetHistogramRange| {
+                range.from.partial_cmp(&range.to) != Some(Ordering::Less)
+            };
+            let Some(FacetHistogramRange { from, to }) = ranges.iter().find(is_invalid) else {
+                return Ok(histogram);
+            };
+            format!(
+                "The `from` bound of a range must be lower than its `to` bound, \
+                 but found `from: {from}` and `to: {to}`."
+            )
+        }
+        _ => return Ok(histogram),
+    };
+    Err(deserr::take_cf_content(E::error::<Infallible>(
+        None,
+        ErrorKind::Unexpected { msg },
+        location,
+    )))
+}
+
+impl From<FacetHistogram> for HistogramBuckets {
+    fn from(histogram: FacetHistogram) -> Self {
+        match histogram {
+            FacetHistogram { interval: Some(interval), .. } => HistogramBuckets::Interval(interval),
+            FacetHistogram { ranges, .. } => HistogramBuckets::Ranges(
+                ranges
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|range| (range.from, range.to))
+                    .collect(),
+            ),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Deserr)]
 #[deserr(error = DeserrJsonError<InvalidHybridQuery>, rename_all = camelCase, deny_unknown_fields)]
 pub struct HybridQuery {
@@ -257,6 +392,39 @@ pub struct HybridQuery {
     pub embedder: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchBoost>, rename_all = camelCase, deny_unknown_fields)]
+pub struct BoostQuery {
+    #[deserr(error = DeserrJsonError<InvalidSearchBoost>)]
+    pub filter: Value,
+    #[deserr(error = DeserrJsonError<InvalidSearchBoostWeight>)]
+    pub weight: BoostWeight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserr)]
+#[deserr(try_from(f64) = TryFrom::try_from -> InvalidSearchBoostWeight)]
+pub struct BoostWeight(f64);
+
+impl std::convert::TryFrom<f64> for BoostWeight {
+    type Error = InvalidSearchBoostWeight;
+
+    fn try_from(f: f64) -> Result<Self, Self::Error> {
+        if f.is_finite() && f > 0.0 {
+            Ok(BoostWeight(f))
+        } else {
+            Err(InvalidSearchBoostWeight)
+        }
+    }
+}
+
+impl std::ops::Deref for BoostWeight {
+    type Target = f64;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 pub enum SearchKind {
     KeywordOnly,
     SemanticOnly { embedder_name: String, embedder: Arc<Embedder> },
@@ -373,10 +541,12 @@ pub struct SearchQueryWithIndex {
     pub vector: Option<Vec<f32>>,
     #[deserr(default, error = DeserrJsonError<InvalidHybridQuery>)]
     pub hybrid: Option<HybridQuery>,
-    #[deserr(default = DEFAULT_SEARCH_OFFSET(), error = DeserrJsonError<InvalidSearchOffset>)]
-    pub offset: usize,
-    #[deserr(default = DEFAULT_SEARCH_LIMIT(), error = DeserrJsonError<InvalidSearchLimit>)]
-    pub limit: usize,
+    // `offset` and `limit` are kept optional to tell whether they were given in federated queries,
+    // they default to `DEFAULT_SEARCH_OFFSET` and `DEFAULT_SEARCH_LIMIT` otherwise.
+    #[deserr(default, error = DeserrJsonError<InvalidSearchOffset>)]
+    pub offset: Option<usize>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLimit>)]
+    pub limit: Option<usize>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchPage>)]
     pub page: Option<usize>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHitsPerPage>)]
@@ -402,9 +572,15 @@ pub struct SearchQueryWithIndex {
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
-    pub distinct: Option<String>,
+    pub distinct: Option<DistinctAttributes>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDistinctGroupLimit>)]
+    pub distinct_group_limit: Option<usize>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetAggregations>, default)]
+    pub facet_aggregations: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetHistograms>)]
+    pub facet_histograms: Option<BTreeMap<String, FacetHistogram>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -417,12 +593,40 @@ pub struct SearchQueryWithIndex {
     pub attributes_to_search_on: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>, default)]
     pub ranking_score_threshold: Option<RankingScoreThreshold>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>, default)]
+    pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRankingRules>, default)]
+    pub ranking_rules: Option<Vec<RankingRuleView>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchBoost>, default)]
+    pub boost: Option<Vec<BoostQuery>>,
+
+    #[deserr(default, error = DeserrJsonError<InvalidMultiSearchFederationOptions>)]
+    pub federation_options: Option<FederationOptions>,
 }
 
 impl SearchQueryWithIndex {
-    pub fn into_index_query(self) -> (IndexUid, SearchQuery) {
+    pub fn has_pagination(&self) -> Option<&'static str> {
+        if self.offset.is_some() {
+            Some("offset")
+        } else if self.limit.is_some() {
+            Some("limit")
+        } else if self.page.is_some() {
+            Some("page")
+        } else if self.hits_per_page.is_some() {
+            Some("hitsPerPage")
+        } else {
+            None
+        }
+    }
+
+    pub fn has_facets(&self) -> Option<&[String]> {
+        self.facets.as_deref().filter(|v| !v.is_empty())
+    }
+
+    pub fn into_index_query_federation(self) -> (IndexUid, SearchQuery, Option<FederationOptions>) {
         let SearchQueryWithIndex {
             index_uid,
+            federation_options,
             q,
             vector,
             offset,
@@ -440,7 +644,10 @@ impl SearchQueryWithIndex {
             filter,
             sort,
             distinct,
+            distinct_group_limit,
             facets,
+            facet_aggregations,
+            facet_histograms,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -448,14 +655,17 @@ impl SearchQueryWithIndex {
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
+            locales,
+            ranking_rules,
+            boost,
         } = self;
         (
             index_uid,
             SearchQuery {
                 q,
                 vector,
-                offset,
-                limit,
+                offset: offset.unwrap_or(DEFAULT_SEARCH_OFFSET()),
+                limit: limit.unwrap_or(DEFAULT_SEARCH_LIMIT()),
                 page,
                 hits_per_page,
                 attributes_to_retrieve,
@@ -469,7 +679,10 @@ impl SearchQueryWithIndex {
                 filter,
                 sort,
                 distinct,
+                distinct_group_limit,
                 facets,
+                facet_aggregations,
+                facet_histograms,
                 highlight_pre_tag,
                 highlight_post_tag,
                 crop_marker,
@@ -477,9 +690,13 @@ impl SearchQueryWithIndex {
                 attributes_to_search_on,
                 hybrid,
                 ranking_score_threshold,
+                locales,
+                ranking_rules,
+                boost,
                 // do not use ..Default::default() here,
                 // rather add any missing field from `SearchQuery` to `SearchQueryWithIndex`
             },
+            federation_options,
         )
     }
 }
@@ -600,6 +817,10 @@ pub struct SearchHit {
     pub ranking_score: Option<f64>,
     #[serde(rename = "_rankingScoreDetails", skip_serializing_if = "Option::is_none")]
     pub ranking_score_details: Option<serde_json::Map<String, serde_json::Value>>,
+    #[serde(rename = "_group", skip_serializing_if = "Option::is_none")]
+    pub group: Option<Vec<SearchHit>>,
+    #[serde(rename = "_groupSize", skip_serializing_if = "Option::is_none")]
+    pub group_size: Option<u64>,
 }
 
 #[derive(Serialize, Clone, PartialEq)]
@@ -618,6 +839,9 @@ pub struct SearchResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub semantic_hit_count: Option<u32>,
 
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub applied_rules: Vec<String>,
+
     // These fields are only used for analytics purposes
     #[serde(skip)]
     pub degraded: bool,
@@ -635,6 +859,7 @@ impl fmt::Debug for SearchResult {
             facet_distribution,
             facet_stats,
             semantic_hit_count,
+            applied_rules,
             degraded,
             used_negative_operator,
         } = self;
@@ -660,6 +885,9 @@ impl fmt::Debug for SearchResult {
         if let Some(semantic_hit_count) = semantic_hit_count {
             debug.field("semantic_hit_count", &semantic_hit_count);
         }
+        if !applied_rules.is_empty() {
+            debug.field("applied_rules", &applied_rules);
+        }
 
         debug.finish()
     }
@@ -696,6 +924,19 @@ pub enum HitsInfo {
 pub struct FacetStats {
     pub min: f64,
     pub max: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sum: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub histogram: Option<Vec<HistogramBucket>>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct HistogramBucket {
+    pub from: f64,
+    pub to: f64,
+    pub count: u64,
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
@@ -703,6 +944,7 @@ pub struct FacetStats {
 pub struct FacetSearchResult {
     pub facet_hits: Vec<FacetValueHit>,
     pub facet_query: Option<String>,
+    pub exhaustive_facet_count: bool,
     pub processing_time_ms: u128,
 }
 
@@ -726,21 +968,32 @@ pub fn add_search_rules(filter: &mut Option<Value>, rules: IndexSearchRules) {
     }
 }
 
+/// Returns an error if the search rules forbid the `rankingRules` search parameter used by the query.
+pub fn check_ranking_rules_override(
+    ranking_rules: &Option<Vec<RankingRuleView>>,
+    rules: &IndexSearchRules,
+) -> Result<(), MeilisearchHttpError> {
+    match (ranking_rules, rules.allow_ranking_rules_override) {
+        (Some(_), Some(false)) => Err(MeilisearchHttpError::RankingRulesOverrideForbidden),
+        _ => Ok(()),
+    }
+}
+
 fn prepare_search<'t>(
     index: &'t Index,
     rtxn: &'t RoTxn,
     query: &'t SearchQuery,
     search_kind: &SearchKind,
     time_budget: TimeBudget,
-) -> Result<(milli::Search<'t>, bool, usize, usize), MeilisearchHttpError> {
+) -> Result<(milli::Search<'t>, bool, usize, usize, usize), MeilisearchHttpError> {
     let mut search = index.search(rtxn);
     search.time_budget(time_budget);
     if let Some(ranking_score_threshold) = query.ranking_score_threshold {
         search.ranking_score_threshold(ranking_score_threshold.0);
     }
 
-    if let Some(distinct) = &query.distinct {
-        search.distinct(distinct.clone());
+    if let Some(DistinctAttributes(distinct)) = &query.distinct {
+        search.distinct_fields(distinct.clone());
     }
 
     match search_kind {
@@ -778,6 +1031,14 @@ fn prepare_search<'t>(
         search.searchable_attributes(searchable);
     }
 
+    if let Some(ref locales) = query.locales {
+        search.locales(locales.iter().copied().map(Into::into).collect());
+    }
+
+    if let Some(ref ranking_rules) = query.ranking_rules {
+        search.ranking_rules(ranking_rules.iter().cloned().map(Into::into).collect());
+    }
+
     let is_finite_pagination = query.is_finite_pagination();
     search.terms_matching_strategy(query.matching_strategy.into());
 
@@ -835,45 +1096,99 @@ fn prepare_search<'t>(
         search.sort_criteria(sort);
     }
 
-    Ok((search, is_finite_pagination, max_total_hits, offset))
+    if let Some(ref boost) = query.boost {
+        let mut boosts = Vec::with_capacity(boost.len());
+        for BoostQuery { filter, weight } in boost {
+            if let Some(filter) = parse_filter(filter)? {
+                boosts.push(milli::Boost { filter, weight: **weight });
+            }
+        }
+        search.boosts(boosts);
+    }
+
+    Ok((search, is_finite_pagination, max_total_hits, offset, limit))
+}
+
+fn search_from_kind(
+    search_kind: &SearchKind,
+    search: &milli::Search<'_>,
+) -> Result<(milli::SearchResult, Option<u32>), MeilisearchHttpError> {
+    let (milli_result, semantic_hit_count) = match search_kind {
+        SearchKind::KeywordOnly => (search.execute()?, None),
+        SearchKind::SemanticOnly { .. } => {
+            let results = search.execute()?;
+            let semantic_hit_count = results.document_scores.len() as u32;
+            (results, Some(semantic_hit_count))
+        }
+        SearchKind::Hybrid { semantic_ratio, .. } => search.execute_hybrid(*semantic_ratio)?,
+    };
+    Ok((milli_result, semantic_hit_count))
 }
 
 pub fn perform_search(
     index: &Index,
-    query: SearchQuery,
+    mut query: SearchQuery,
     search_kind: SearchKind,
     retrieve_vectors: RetrieveVectors,
 ) -> Result<SearchResult, MeilisearchHttpError> {
     let before_search = Instant::now();
+
+    // the histograms are computed along the facet stats, only on the requested facets
+    if let Some(facet_histograms) = &query.facet_histograms {
+        let fields = query.facets.as_deref().unwrap_or_default();
+        let all_facets = fields.iter().any(|f| f == "*");
+        if let Some(name) =
+            facet_histograms.keys().find(|name| !all_facets && !fields.contains(name))
+        {
+            return Err(MeilisearchHttpError::FacetHistogramWithoutFacet(name.clone()));
+        }
+    }
+
     let rtxn = index.read_txn()?;
     let time_budget = match index.search_cutoff(&rtxn)? {
         Some(cutoff) => TimeBudget::new(Duration::from_millis(cutoff)),
         None => TimeBudget::default(),
     };
 
-    let (search, is_finite_pagination, max_total_hits, offset) =
+    // the merchandising rules are applied before the pagination: the hidden and pinned documents
+    // are excluded from the ranking, then the pinned documents are inserted at their position.
+    let applied_rules = AppliedRules::new(index, &rtxn, query.q.as_deref(), query.filter.as_ref())?;
+    query.filter = applied_rules.filter(query.filter.take());
+    let hidden = applied_rules.hidden_docids(index, &rtxn)?;
+    let mut pinned = applied_rules.pinned_docids(index, &rtxn, query.filter.as_ref(), &hidden)?;
+
+    let (mut search, is_finite_pagination, max_total_hits, offset, page_limit) =
         prepare_search(index, &rtxn, &query, &search_kind, time_budget)?;
 
+    if !applied_rules.is_empty() {
+        search.excluded_documents(hidden | pinned.docids());
+        let (ranked_offset, ranked_limit) = pinned.ranked_offset_limit(offset, page_limit);
+        search.offset(ranked_offset);
+        search.limit(ranked_limit);
+    }
+
     let (
         milli::SearchResult {
             documents_ids,
             matching_words,
-            candidates,
+            mut candidates,
             document_scores,
             degraded,
             used_negative_operator,
         },
         semantic_hit_count,
-    ) = match &search_kind {
-        SearchKind::KeywordOnly => (search.execute()?, None),
-        SearchKind::SemanticOnly { .. } => {
-            let results = search.execute()?;
-            let semantic_hit_count = results.document_scores.len() as u32;
-            (results, Some(semantic_hit_count))
-        }
-        SearchKind::Hybrid { semantic_ratio, .. } => search.execute_hybrid(*semantic_ratio)?,
+    ) = search_from_kind(&search_kind, &search)?;
+
+    let (documents_ids, document_scores) = if pinned.is_empty() {
+        (documents_ids, document_scores)
+    } else {
+        pinned.clamp(candidates.len() as usize);
+        candidates |= pinned.docids();
+        pinned.insert(offset, page_limit, documents_ids, document_scores)
     };
 
+    let groups = distinct_groups(&search, &documents_ids, query.distinct_group_limit)?;
+
     let SearchQuery {
         q,
         limit,
@@ -890,9 +1205,12 @@ pub fn perform_search(
         show_ranking_score_details,
         sort,
         facets,
+        facet_aggregations,
+        facet_histograms,
         highlight_pre_tag,
         highlight_post_tag,
         crop_marker,
+        locales,
         // already used in prepare_search
         vector: _,
         hybrid: _,
@@ -902,6 +1220,9 @@ pub fn perform_search(
         attributes_to_search_on: _,
         filter: _,
         distinct: _,
+        distinct_group_limit: _,
+        ranking_rules: _,
+        boost: _,
     } = query;
 
     let format = AttributesFormat {
@@ -917,10 +1238,11 @@ pub fn perform_search(
         sort,
         show_ranking_score,
         show_ranking_score_details,
+        locales: locales.map(|l| l.iter().copied().map(Into::into).collect()),
     };
 
     let documents =
-        make_hits(index, &rtxn, format, matching_words, documents_ids, document_scores)?;
+        make_hits(index, &rtxn, format, matching_words, documents_ids, document_scores, groups)?;
 
     let number_of_hits = min(candidates.len() as usize, max_total_hits);
     let hits_info = if is_finite_pagination {
@@ -960,20 +1282,49 @@ pub fn perform_search(
                 facet_distribution.facets(fields);
             }
 
+            facet_distribution.aggregate_all_facets(facet_aggregations);
+            if let Some(facet_histograms) = facet_histograms {
+                facet_distribution.histograms(
+                    facet_histograms
+                        .into_iter()
+                        .map(|(name, histogram)| (name, histogram.into()))
+                        .collect(),
+                );
+            }
+
             let distribution = facet_distribution
                 .candidates(candidates)
                 .default_order_by(sort_facet_values_by.get("*"))
                 .execute()?;
             let stats = facet_distribution.compute_stats()?;
+            let mut aggregations = facet_distribution.compute_aggregations()?;
+            let stats = stats
+                .into_iter()
+                .map(|(name, (min, max))| {
+                    let (sum, avg, histogram) = match aggregations.remove(&name) {
+                        Some(FacetAggregation { sum, avg, histogram }) => {
+                            (Some(sum), Some(avg), histogram)
+                        }
+                        None => (None, None, None),
+                    };
+                    let histogram = histogram.map(|buckets| {
+                        buckets
+                            .into_iter()
+                            .map(|milli::HistogramBucket { from, to, count }| HistogramBucket {
+                                from,
+                                to,
+                                count,
+                            })
+                            .collect()
+                    });
+                    (name, FacetStats { min, max, sum, avg, histogram })
+                })
+                .collect();
             (Some(distribution), Some(stats))
         }
         None => (None, None),
     };
 
-    let facet_stats = facet_stats.map(|stats| {
-        stats.into_iter().map(|(k, (min, max))| (k, FacetStats { min, max })).collect()
-    });
-
     let result = SearchResult {
         hits: documents,
         hits_info,
@@ -984,6 +1335,7 @@ pub fn perform_search(
         degraded,
         used_negative_operator,
         semantic_hit_count,
+        applied_rules: applied_rules.uids,
     };
     Ok(result)
 }
@@ -1001,9 +1353,10 @@ struct AttributesFormat {
     sort: Option<Vec<String>>,
     show_ranking_score: bool,
     show_ranking_score_details: bool,
+    locales: Option<Vec<Language>>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RetrieveVectors {
     /// Do not touch the `_vectors` field
     ///
@@ -1033,14 +1386,44 @@ impl RetrieveVectors {
     }
 }
 
+/// The distinct group of each hit: the number of documents in the group, the hit included,
+/// and the other members of the group to return.
+type DistinctGroups = Vec<(u64, Vec<u32>)>;
+
+/// Computes the distinct groups of the hits when a group limit is requested.
+///
+/// The members of a group are the documents sharing the distinct key of the hit and matching
+/// the filter and the query of the search, they are returned in the indexing order.
+fn distinct_groups(
+    search: &milli::Search<'_>,
+    documents_ids: &[u32],
+    group_limit: Option<usize>,
+) -> Result<DistinctGroups, MeilisearchHttpError> {
+    let Some(group_limit) = group_limit else { return Ok(Vec::new()) };
+    let groups = search
+        .distinct_groups(documents_ids)?
+        .into_iter()
+        .map(|group| (group.len() + 1, group.into_iter().take(group_limit).collect()))
+        .collect();
+    Ok(groups)
+}
+
 fn make_hits(
     index: &Index,
     rtxn: &RoTxn<'_>,
     format: AttributesFormat,
     matching_words: milli::MatchingWords,
-    documents_ids: Vec<u32>,
-    document_scores: Vec<Vec<ScoreDetails>>,
+    mut documents_ids: Vec<u32>,
+    mut document_scores: Vec<Vec<ScoreDetails>>,
+    groups: DistinctGroups,
 ) -> Result<Vec<SearchHit>, MeilisearchHttpError> {
+    // the members of the groups are formatted along with the hits, then moved to their `_group`.
+    let hits_count = documents_ids.len();
+    for (_, members) in &groups {
+        documents_ids.extend(members);
+        document_scores.extend(members.iter().map(|_| Vec::new()));
+    }
+
     let fields_ids_map = index.fields_ids_map(rtxn).unwrap();
     let displayed_ids =
         index.displayed_fields_ids(rtxn)?.map(|fields| fields.into_iter().collect::<BTreeSet<_>>());
@@ -1103,7 +1486,10 @@ fn make_hits(
     );
     let mut tokenizer_builder = TokenizerBuilder::default();
     tokenizer_builder.create_char_map(true);
-    let script_lang_map = index.script_language(rtxn)?;
+    let script_lang_map = match format.locales {
+        Some(ref locales) if !locales.is_empty() => locales_allow_list(locales),
+        _ => index.script_language(rtxn)?,
+    };
     if !script_lang_map.is_empty() {
         tokenizer_builder.allow_list(&script_lang_map);
     }
@@ -1128,7 +1514,7 @@ fn make_hits(
     let documents_iter = index.documents(rtxn, documents_ids)?;
     for ((id, obkv), score) in documents_iter.into_iter().zip(document_scores.into_iter()) {
         // First generate a document with all the displayed fields
-        let displayed_document = make_document(&displayed_ids, &fields_ids_map, obkv)?;
+        let displayed_document = make_document(&displayed_ids, &fields_ids_map, obkv.as_obkv())?;
 
         let add_vectors_fid =
             vectors_fid.filter(|_fid| retrieve_vectors == RetrieveVectors::Retrieve);
@@ -1161,7 +1547,8 @@ fn make_hits(
                     .is_some_and(|conf| conf.user_provided.contains(id));
                 let embeddings =
                     ExplicitVectors { embeddings: Some(vector.into()), regenerate: !user_provided };
-                vectors.insert(name, serde_json::to_value(embeddings)?);
+                // the vectors kept in the document of a quantized embedder are the original ones
+                vectors.entry(name).or_insert(serde_json::to_value(embeddings)?);
             }
             document.insert("_vectors".into(), vectors.into());
         }
@@ -1190,9 +1577,27 @@ fn make_hits(
             matches_position,
             ranking_score_details,
             ranking_score,
+            group: None,
+            group_size: None,
         };
         documents.push(hit);
     }
+
+    let mut members = documents.split_off(hits_count).into_iter();
+    for (hit, (group_size, group)) in documents.iter_mut().zip(groups) {
+        let group = members
+            .by_ref()
+            .take(group.len())
+            .map(|mut member| {
+                // the members of a group are not ranked
+                member.ranking_score = None;
+                member.ranking_score_details = None;
+                member
+            })
+            .collect();
+        hit.group = Some(group);
+        hit.group_size = Some(group_size);
+    }
     Ok(documents)
 }
 
@@ -1201,6 +1606,7 @@ pub fn perform_facet_search(
     search_query: SearchQuery,
     facet_query: Option<String>,
     facet_name: String,
+    exhaustive_facet_count: bool,
     search_kind: SearchKind,
 ) -> Result<FacetSearchResult, MeilisearchHttpError> {
     let before_search = Instant::now();
@@ -1210,7 +1616,8 @@ pub fn perform_facet_search(
         None => TimeBudget::default(),
     };
 
-    let (search, _, _, _) = prepare_search(index, &rtxn, &search_query, &search_kind, time_budget)?;
+    let (search, _, _, _, _) =
+        prepare_search(index, &rtxn, &search_query, &search_kind, time_budget)?;
     let mut facet_search = SearchForFacetValues::new(
         facet_name,
         search,
@@ -1222,10 +1629,12 @@ pub fn perform_facet_search(
     if let Some(max_facets) = index.max_values_per_facet(&rtxn)? {
         facet_search.max_values(max_facets as usize);
     }
+    facet_search.exhaustive_facet_count(exhaustive_facet_count);
 
     Ok(FacetSearchResult {
         facet_hits: facet_search.execute()?,
         facet_query,
+        exhaustive_facet_count: facet_search.is_exhaustive()?,
         processing_time_ms: before_search.elapsed().as_millis(),
     })
 }
@@ -1305,9 +1714,18 @@ pub fn perform_similar(
         sort: None,
         show_ranking_score,
         show_ranking_score_details,
+        locales: None,
     };
 
-    let hits = make_hits(index, &rtxn, format, Default::default(), documents_ids, document_scores)?;
+    let hits = make_hits(
+        index,
+        &rtxn,
+        format,
+        Default::default(),
+        documents_ids,
+        document_scores,
+        Vec::new(),
+    )?;
 
     let max_total_hits = index
         .pagination_max_total_hits(&rtxn)
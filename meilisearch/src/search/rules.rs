@@ -0,0 +1,249 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+
+use meilisearch_types::heed::RoTxn;
+use meilisearch_types::milli::merchandising_rules::{
+    MerchandisingRule, PinnedDocument, QueryMatch, RuleActions, RuleCondition,
+};
+use meilisearch_types::milli::score_details::ScoreDetails;
+use meilisearch_types::milli::{self, DocumentId, Index};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use roaring::RoaringBitmap;
+use serde_json::Value;
+
+use super::parse_filter;
+use crate::error::MeilisearchHttpError;
+
+/// The maximum number of compiled regexes kept around, the cache is emptied when it is reached.
+const MAX_COMPILED_REGEXES: usize = 1000;
+
+/// The regexes of the rules compiled once and shared by all the searches, by pattern.
+static COMPILED_REGEXES: Lazy<RwLock<HashMap<String, Regex>>> = Lazy::new(Default::default);
+
+/// The merchandising rules applying to a search, merged together.
+#[derive(Debug, Default)]
+pub struct AppliedRules {
+    /// The uids of the rules applying to the search.
+    pub uids: Vec<String>,
+    /// The filter expressions to add to the filter of the search.
+    pub filters: Vec<Value>,
+    /// The external ids of the documents to remove from the results.
+    pub hidden: Vec<String>,
+    /// The documents to pin, sorted by position.
+    pub pinned: Vec<PinnedDocument>,
+}
+
+impl AppliedRules {
+    /// Selects the rules of the index whose condition matches the given query and filter.
+    pub fn new(
+        index: &Index,
+        rtxn: &RoTxn<'_>,
+        q: Option<&str>,
+        filter: Option<&Value>,
+    ) -> Result<Self, MeilisearchHttpError> {
+        let mut applied = AppliedRules::default();
+        let q = q.unwrap_or_default();
+        let search_filter = filter.map(filter_expressions).unwrap_or_default();
+
+        for (uid, rule) in index.merchandising_rules(rtxn)? {
+            let MerchandisingRule { condition, actions } = rule;
+            if !condition_matches(&condition, q, &search_filter) {
+                continue;
+            }
+
+            let RuleActions { pin, hide, filter } = actions;
+            // the filterable attributes may have changed since the rule was created,
+            // a rule whose filter can't be evaluated anymore is ignored.
+            if let Some(filter) = filter.as_ref().map(parse_filter).transpose()?.flatten() {
+                match filter.evaluate(rtxn, index) {
+                    Ok(_) => (),
+                    Err(milli::Error::UserError(_)) => continue,
+                    Err(error) => return Err(error.into()),
+                }
+            }
+            applied.uids.push(uid);
+            applied.filters.extend(filter.as_ref().map(filter_expressions).unwrap_or_default());
+            applied.hidden.extend(hide);
+            applied.pinned.extend(pin);
+        }
+
+        // the first rule pinning a document wins, and ties in position are kept in rule order.
+        let mut seen = HashSet::new();
+        applied.pinned.retain(|pinned| seen.insert(pinned.id.clone()));
+        applied.pinned.sort_by_key(|pinned| pinned.position);
+
+        Ok(applied)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.uids.is_empty()
+    }
+
+    /// Returns the filter of the search with the filters of the rules added to it.
+    pub fn filter(&self, filter: Option<Value>) -> Option<Value> {
+        if self.filters.is_empty() {
+            return filter;
+        }
+        let mut expressions = filter.as_ref().map(filter_expressions).unwrap_or_default();
+        expressions.extend(self.filters.iter().cloned());
+        Some(Value::Array(expressions))
+    }
+
+    /// Resolves the internal ids of the hidden documents, the ids that do not exist are ignored.
+    pub fn hidden_docids(
+        &self,
+        index: &Index,
+        rtxn: &RoTxn<'_>,
+    ) -> Result<RoaringBitmap, MeilisearchHttpError> {
+        let external_documents_ids = index.external_documents_ids();
+        let mut docids = RoaringBitmap::new();
+        for id in &self.hidden {
+            if let Some(docid) = external_documents_ids.get(rtxn, id)? {
+                docids.insert(docid);
+            }
+        }
+        Ok(docids)
+    }
+
+    /// Resolves the pinned documents to their internal ids and final positions.
+    ///
+    /// The pinned documents that do not exist, are hidden, or do not match the filter are ignored.
+    /// Two documents pinned at the same position are placed one after the other.
+    pub fn pinned_docids(
+        &self,
+        index: &Index,
+        rtxn: &RoTxn<'_>,
+        filter: Option<&Value>,
+        hidden: &RoaringBitmap,
+    ) -> Result<PinnedDocids, MeilisearchHttpError> {
+        let universe = match filter.map(parse_filter).transpose()?.flatten() {
+            Some(filter) => Some(filter.evaluate(rtxn, index)?),
+            None => None,
+        };
+
+        let external_documents_ids = index.external_documents_ids();
+        let mut pinned = Vec::new();
+        let mut next_position = 0;
+        for PinnedDocument { id, position } in &self.pinned {
+            let Some(docid) = external_documents_ids.get(rtxn, id)? else { continue };
+            if hidden.contains(docid) || universe.as_ref().is_some_and(|u| !u.contains(docid)) {
+                continue;
+            }
+            let position = (*position).max(next_position);
+            next_position = position.saturating_add(1);
+            pinned.push((position, docid));
+        }
+        Ok(PinnedDocids(pinned))
+    }
+}
+
+/// The pinned documents of a search, along with their final position, sorted by position.
+///
+/// The positions are distinct and increasing, which is kept by [`PinnedDocids::clamp`].
+#[derive(Debug, Default)]
+pub struct PinnedDocids(Vec<(usize, DocumentId)>);
+
+impl PinnedDocids {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn docids(&self) -> RoaringBitmap {
+        self.0.iter().map(|(_, docid)| *docid).collect()
+    }
+
+    /// Moves the documents pinned past the ranked documents right after them, in order.
+    ///
+    /// `ranked_total` is the number of ranked documents, the pinned documents excluded.
+    pub fn clamp(&mut self, ranked_total: usize) {
+        for (pinned_before, (position, _)) in self.0.iter_mut().enumerate() {
+            *position = (*position).min(ranked_total.saturating_add(pinned_before));
+        }
+    }
+
+    /// Computes the offset and limit to request to the ranking so that,
+    /// once the pinned documents are inserted, the page starts at `offset` and has `limit` hits.
+    ///
+    /// The number of ranked documents is not known yet, so the positions can't be clamped.
+    /// This doesn't change the ranked documents of the page as the clamped documents
+    /// all come after the last ranked one.
+    pub fn ranked_offset_limit(&self, offset: usize, limit: usize) -> (usize, usize) {
+        let before = self.0.iter().filter(|(position, _)| *position < offset).count();
+        let within = self
+            .0
+            .iter()
+            .filter(|(position, _)| (offset..offset.saturating_add(limit)).contains(position))
+            .count();
+        (offset - before, limit - within)
+    }
+
+    /// Inserts the pinned documents of the page starting at `offset` among the ranked documents.
+    ///
+    /// The positions must have been clamped to the number of ranked documents first.
+    /// The pinned documents are given empty score details as they are not ranked.
+    pub fn insert(
+        &self,
+        offset: usize,
+        limit: usize,
+        documents_ids: Vec<DocumentId>,
+        document_scores: Vec<Vec<ScoreDetails>>,
+    ) -> (Vec<DocumentId>, Vec<Vec<ScoreDetails>>) {
+        let mut pinned = self.0.iter().filter(|(position, _)| *position >= offset).peekable();
+        let mut ranked = documents_ids.into_iter().zip(document_scores);
+
+        let mut ids = Vec::new();
+        let mut scores = Vec::new();
+        for position in offset..offset.saturating_add(limit) {
+            let (docid, score) = match pinned.next_if(|(pinned, _)| *pinned == position) {
+                Some((_, docid)) => (*docid, Vec::new()),
+                None => match ranked.next() {
+                    Some(hit) => hit,
+                    None => break,
+                },
+            };
+            ids.push(docid);
+            scores.push(score);
+        }
+        (ids, scores)
+    }
+}
+
+fn condition_matches(condition: &RuleCondition, q: &str, search_filter: &[Value]) -> bool {
+    let RuleCondition { query, query_match, filter } = condition;
+    let query_matches = match query_match {
+        QueryMatch::Exact => q.trim().to_lowercase() == query.trim().to_lowercase(),
+        QueryMatch::Contains => q.to_lowercase().contains(&query.to_lowercase()),
+        // the regex is checked when the rule is created
+        QueryMatch::Regex => compiled_regex(query).is_some_and(|regex| regex.is_match(q)),
+    };
+    let filter_matches = match filter {
+        Some(filter) => {
+            filter_expressions(filter).iter().all(|expression| search_filter.contains(expression))
+        }
+        None => true,
+    };
+    query_matches && filter_matches
+}
+
+/// Returns the compiled regex of the pattern, compiling it only the first time it is seen.
+fn compiled_regex(pattern: &str) -> Option<Regex> {
+    if let Some(regex) = COMPILED_REGEXES.read().unwrap().get(pattern) {
+        return Some(regex.clone());
+    }
+    let regex = Regex::new(pattern).ok()?;
+    let mut regexes = COMPILED_REGEXES.write().unwrap();
+    if regexes.len() >= MAX_COMPILED_REGEXES {
+        regexes.clear();
+    }
+    regexes.insert(pattern.to_string(), regex.clone());
+    Some(regex)
+}
+
+/// Splits a filter in the list of expressions that must all be verified.
+fn filter_expressions(filter: &Value) -> Vec<Value> {
+    match filter {
+        Value::Array(expressions) => expressions.clone(),
+        expression => vec![expression.clone()],
+    }
+}
@@ -0,0 +1,224 @@
+//! This file implements an in-memory cache of search results.
+//!
+//! A cached result is only returned for a search request identical to the one that produced it,
+//! made on the same index and while the index has not been modified since.
+//! To know whether an index was modified, the cache keys contain the generation of the index,
+//! see [`IndexScheduler::index_generation`](index_scheduler::IndexScheduler::index_generation).
+//! The generation must be read *before* the search is performed, so that a result computed while
+//! the index is being modified is stored under the old generation and never returned afterward.
+//!
+//! The cache is disabled when its capacity is 0.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::metrics::{MEILISEARCH_SEARCH_CACHE_HITS, MEILISEARCH_SEARCH_CACHE_MISSES};
+use crate::search::{RetrieveVectors, SearchQuery, SearchResult};
+
+/// The response header telling whether the search result comes from the cache.
+pub const SEARCH_CACHE_HEADER: &str = "X-Meilisearch-Cache";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheStatus {
+    Hit,
+    Miss,
+}
+
+impl CacheStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheStatus::Hit => "HIT",
+            CacheStatus::Miss => "MISS",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SearchCacheKey {
+    index_uid: String,
+    generation: u64,
+    retrieve_vectors: RetrieveVectors,
+    query: String,
+}
+
+impl SearchCacheKey {
+    pub fn new(
+        index_uid: &str,
+        generation: u64,
+        query: &SearchQuery,
+        retrieve_vectors: RetrieveVectors,
+    ) -> Self {
+        Self {
+            index_uid: index_uid.to_string(),
+            generation,
+            retrieve_vectors,
+            query: canonicalize_query(query),
+        }
+    }
+}
+
+/// Writes every parameter of the query, in a fixed order and without depending on the
+/// iteration order of the sets, so that two identical queries always give the same string.
+fn canonicalize_query(query: &SearchQuery) -> String {
+    // We decompose the whole struct by hand to be sure a parameter added
+    // to the search query is also taken into account by the cache.
+    let SearchQuery {
+        q,
+        vector,
+        hybrid,
+        offset,
+        limit,
+        page,
+        hits_per_page,
+        attributes_to_retrieve,
+        retrieve_vectors,
+        attributes_to_crop,
+        crop_length,
+        attributes_to_highlight,
+        show_matches_position,
+        show_ranking_score,
+        show_ranking_score_details,
+        filter,
+        sort,
+        distinct,
+        distinct_group_limit,
+        facets,
+        facet_aggregations,
+        facet_histograms,
+        highlight_pre_tag,
+        highlight_post_tag,
+        crop_marker,
+        matching_strategy,
+        attributes_to_search_on,
+        ranking_score_threshold,
+        locales,
+        ranking_rules,
+        boost,
+    } = query;
+
+    let attributes_to_highlight: Option<BTreeSet<_>> =
+        attributes_to_highlight.as_ref().map(|attributes| attributes.iter().collect());
+
+    let mut canonical = String::new();
+    // Writing into a `String` never fails.
+    let _ = write!(
+        canonical,
+        "{q:?}\n{vector:?}\n{hybrid:?}\n{offset:?}\n{limit:?}\n{page:?}\n{hits_per_page:?}\n\
+         {attributes_to_retrieve:?}\n{retrieve_vectors:?}\n{attributes_to_crop:?}\n{crop_length:?}\n\
+         {attributes_to_highlight:?}\n{show_matches_position:?}\n{show_ranking_score:?}\n\
+         {show_ranking_score_details:?}\n{filter:?}\n{sort:?}\n{distinct:?}\n{distinct_group_limit:?}\n\
+         {facets:?}\n{facet_aggregations:?}\n{facet_histograms:?}\n{highlight_pre_tag:?}\n{highlight_post_tag:?}\n\
+         {crop_marker:?}\n{matching_strategy:?}\n{attributes_to_search_on:?}\n\
+         {ranking_score_threshold:?}\n{locales:?}\n{ranking_rules:?}\n{boost:?}"
+    );
+    canonical
+}
+
+#[derive(Debug)]
+struct Entry {
+    result: SearchResult,
+    inserted_at: Instant,
+    last_used: u64,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    entries: HashMap<SearchCacheKey, Entry>,
+    /// The keys of the entries by last use, the first one is the least recently used.
+    recency: BTreeMap<u64, SearchCacheKey>,
+    /// Incremented on each access, it gives the `last_used` of the accessed entry.
+    clock: u64,
+}
+
+impl Entries {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn remove(&mut self, key: &SearchCacheKey) -> Option<Entry> {
+        let entry = self.entries.remove(key)?;
+        self.recency.remove(&entry.last_used);
+        Some(entry)
+    }
+
+    fn remove_least_recently_used(&mut self) {
+        if let Some((_, key)) = self.recency.pop_first() {
+            self.entries.remove(&key);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SearchCache {
+    entries: Mutex<Entries>,
+    capacity: usize,
+    ttl: Duration,
+}
+
+impl SearchCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self { entries: Mutex::default(), capacity, ttl }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity != 0
+    }
+
+    /// Returns the key of the query, or `None` when the cache is disabled.
+    pub fn key(
+        &self,
+        index_uid: &str,
+        generation: u64,
+        query: &SearchQuery,
+        retrieve_vectors: RetrieveVectors,
+    ) -> Option<SearchCacheKey> {
+        self.is_enabled()
+            .then(|| SearchCacheKey::new(index_uid, generation, query, retrieve_vectors))
+    }
+
+    /// Returns a copy of the cached result of the query if it has not expired.
+    pub fn get(&self, key: &SearchCacheKey) -> Option<SearchResult> {
+        let mut entries = self.entries.lock().unwrap();
+
+        let result = match entries.remove(key) {
+            Some(mut entry) if entry.inserted_at.elapsed() <= self.ttl => {
+                entry.last_used = entries.tick();
+                let result = entry.result.clone();
+                entries.recency.insert(entry.last_used, key.clone());
+                entries.entries.insert(key.clone(), entry);
+                Some(result)
+            }
+            // an expired entry is removed from the cache
+            _ => None,
+        };
+
+        match result {
+            Some(_) => MEILISEARCH_SEARCH_CACHE_HITS.inc(),
+            None => MEILISEARCH_SEARCH_CACHE_MISSES.inc(),
+        }
+        result
+    }
+
+    /// Stores the result of the query, evicting the least recently used result if the cache is full.
+    ///
+    /// Degraded results are not stored, as searching again may give the complete result.
+    pub fn insert(&self, key: SearchCacheKey, result: &SearchResult) {
+        if !self.is_enabled() || result.degraded {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+
+        if entries.remove(&key).is_none() && entries.entries.len() >= self.capacity {
+            entries.remove_least_recently_used();
+        }
+
+        let last_used = entries.tick();
+        let entry = Entry { result: result.clone(), inserted_at: Instant::now(), last_used };
+        entries.recency.insert(last_used, key.clone());
+        entries.entries.insert(key, entry);
+    }
+}
@@ -596,3 +596,61 @@ async fn error_access_modified_token() {
     assert_eq!(response, INVALID_RESPONSE.clone());
     assert_eq!(code, 403);
 }
+
+#[actix_rt::test]
+async fn error_search_ranking_rules_forbidden_by_token() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key("MASTER_KEY").await;
+    let index = server.index("sales");
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+    index.update_settings(json!({"allowRankingRulesOverride": true})).await;
+    index.wait_task(1).await;
+    drop(index);
+
+    let content = json!({
+        "indexes": ["*"],
+        "actions": ["*"],
+        "expiresAt": (OffsetDateTime::now_utc() + Duration::hours(1)).format(&Rfc3339).unwrap(),
+    });
+
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(code, 201);
+    let key = response["key"].as_str().unwrap();
+    let uid = response["uid"].as_str().unwrap();
+
+    // by default, a tenant token does not restrict the ranking rules override
+    let tenant_token = hashmap! {
+        "searchRules" => json!({"sales": {}}),
+        "exp" => json!((OffsetDateTime::now_utc() + Duration::hours(1)).unix_timestamp())
+    };
+    let web_token = generate_tenant_token(uid, key, tenant_token);
+    server.use_api_key(&web_token);
+    let index = server.index("sales");
+    let (response, code) = index.search_post(json!({"rankingRules": ["words"]})).await;
+    assert_eq!(code, 200, "{}", response);
+
+    let tenant_token = hashmap! {
+        "searchRules" => json!({"sales": {"allowRankingRulesOverride": false}}),
+        "exp" => json!((OffsetDateTime::now_utc() + Duration::hours(1)).unix_timestamp())
+    };
+    let web_token = generate_tenant_token(uid, key, tenant_token);
+    server.use_api_key(&web_token);
+    let index = server.index("sales");
+    let (response, code) = index.search_post(json!({"rankingRules": ["words"]})).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "message": "The tenant token forbids using the `rankingRules` search parameter.",
+      "code": "invalid_search_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_rules"
+    }
+    "###);
+
+    // the search without the `rankingRules` parameter is still allowed
+    let (response, code) = index.search_post(json!({})).await;
+    assert_eq!(code, 200, "{}", response);
+}
@@ -0,0 +1,191 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn error_get_unexisting_batch_status() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+    let (response, code) = server.get_batch(1).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Batch `1` not found.",
+      "code": "batch_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#batch_not_found"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn error_get_batch_invalid_uid() {
+    let server = Server::new().await;
+    let (response, code) = server.service.get("/batches/lol").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Batch uid `lol` is invalid. It should only contain numeric characters.",
+      "code": "invalid_batch_uids",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_batch_uids"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn get_batch_status() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+    index.add_documents(json!([{ "id": 1, "content": "foobar" }]), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = server.get_batch(1).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".steps" => "[steps]", ".duration" => "[duration]", ".startedAt" => "[date]", ".finishedAt" => "[date]" }), @r###"
+    {
+      "uid": 1,
+      "kind": "documentOperation",
+      "indexUid": "test",
+      "taskUids": [
+        1
+      ],
+      "stats": {
+        "totalNbTasks": 1,
+        "status": {
+          "succeeded": 1
+        },
+        "types": {
+          "documentAdditionOrUpdate": 1
+        },
+        "indexUids": {
+          "test": 1
+        }
+      },
+      "steps": "[steps]",
+      "duration": "[duration]",
+      "startedAt": "[date]",
+      "finishedAt": "[date]"
+    }
+    "###);
+
+    // The indexing steps reported by milli are timed along with the steps of the scheduler.
+    let mut steps: Vec<_> = response["steps"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|step| step["name"].as_str().unwrap())
+        .collect();
+    snapshot!(steps.first().unwrap(), @"processing tasks");
+    snapshot!(steps.last().unwrap(), @"updating tasks");
+    steps.sort_unstable();
+    snapshot!(json_string!(steps), @r###"
+    [
+      "computing documents ids",
+      "indexing documents",
+      "merging into the final databases",
+      "processing tasks",
+      "remapping documents",
+      "updating tasks"
+    ]
+    "###);
+
+    // The task must reference the batch that processed it.
+    let (response, code) = index.get_task(1).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["batchUid"], @"1");
+}
+
+#[actix_rt::test]
+async fn list_batches() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+    index.add_documents(json!([{ "id": 1, "content": "foobar" }]), None).await;
+    index.wait_task(1).await;
+    server.index("other").create(None).await;
+    server.wait_task(2).await;
+
+    let (response, code) = server.batches().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["results"].as_array().unwrap().len(), @"3");
+    snapshot!(response["total"], @"3");
+    // The most recent batches are returned first.
+    snapshot!(response["results"][0]["uid"], @"2");
+    snapshot!(response["results"][0]["kind"], @r###""indexCreation""###);
+
+    let (response, code) = server.batches_filter("limit=1").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".results" => "[results]" }), @r###"
+    {
+      "results": "[results]",
+      "total": 3,
+      "limit": 1,
+      "from": 2,
+      "next": 1
+    }
+    "###);
+
+    // The task filters are applied to the tasks contained in the batches.
+    let (response, code) = server.batches_filter("indexUids=test").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["total"], @"2");
+    snapshot!(response["results"][0]["uid"], @"1");
+    snapshot!(response["results"][1]["uid"], @"0");
+
+    let (response, code) = server.batches_filter("types=documentAdditionOrUpdate").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["total"], @"1");
+    snapshot!(response["results"][0]["uid"], @"1");
+
+    // While the uids are matched against the batch uids.
+    let (response, code) = server.batches_filter("uids=0,2").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["total"], @"2");
+    snapshot!(response["results"][0]["uid"], @"2");
+    snapshot!(response["results"][1]["uid"], @"0");
+
+    let (response, code) = server.batches_filter("uids=0&batchUids=1").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The `uids` and `batchUids` parameters cannot be used together on the `/batches` route, they both filter the batch uids.",
+      "code": "invalid_batch_uids",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_batch_uids"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn list_tasks_filtered_by_batch_uids() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+    index.add_documents(json!([{ "id": 1, "content": "foobar" }]), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = server.tasks_filter("batchUids=1").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["total"], @"1");
+    snapshot!(response["results"][0]["uid"], @"1");
+    snapshot!(response["results"][0]["batchUid"], @"1");
+
+    let (response, code) = server.tasks_filter("batchUids=lol").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `batchUids`: could not parse `lol` as a positive integer",
+      "code": "invalid_batch_uids",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_batch_uids"
+    }
+    "###);
+}
@@ -220,6 +220,11 @@ impl Index<'_> {
         self.service.post_encoded(url, body, self.encoder).await
     }
 
+    pub async fn edit_documents_by_function(&self, body: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/documents/edit", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, body, self.encoder).await
+    }
+
     pub async fn clear_all_documents(&self) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/documents", urlencode(self.uid.as_ref()));
         self.service.delete(url).await
@@ -322,11 +327,40 @@ impl Index<'_> {
         self.service.put_encoded(url, settings, self.encoder).await
     }
 
+    pub async fn update_settings_localized_attributes(
+        &self,
+        settings: Value,
+    ) -> (Value, StatusCode) {
+        let url =
+            format!("/indexes/{}/settings/localized-attributes", urlencode(self.uid.as_ref()));
+        self.service.put_encoded(url, settings, self.encoder).await
+    }
+
     pub async fn delete_settings(&self) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/settings", urlencode(self.uid.as_ref()));
         self.service.delete(url).await
     }
 
+    pub async fn rules(&self) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/rules", urlencode(self.uid.as_ref()));
+        self.service.get(url).await
+    }
+
+    pub async fn get_rule(&self, uid: &str) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/rules/{}", urlencode(self.uid.as_ref()), urlencode(uid));
+        self.service.get(url).await
+    }
+
+    pub async fn put_rule(&self, uid: &str, rule: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/rules/{}", urlencode(self.uid.as_ref()), urlencode(uid));
+        self.service.put_encoded(url, rule, self.encoder).await
+    }
+
+    pub async fn delete_rule(&self, uid: &str) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/rules/{}", urlencode(self.uid.as_ref()), urlencode(uid));
+        self.service.delete(url).await
+    }
+
     pub async fn stats(&self) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/stats", urlencode(self.uid.as_ref()));
         self.service.get(url).await
@@ -411,6 +445,11 @@ impl Index<'_> {
         self.service.post_encoded(url, query, self.encoder).await
     }
 
+    pub async fn suggest(&self, query: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/suggest", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, query, self.encoder).await
+    }
+
     pub async fn update_distinct_attribute(&self, value: Value) -> (Value, StatusCode) {
         let url =
             format!("/indexes/{}/settings/{}", urlencode(self.uid.as_ref()), "distinct-attribute");
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
 
 use actix_http::body::MessageBody;
@@ -9,6 +10,8 @@ use actix_web::http::StatusCode;
 use byte_unit::{Byte, ByteUnit};
 use clap::Parser;
 use meilisearch::option::{IndexerOpts, MaxMemory, Opt};
+use meilisearch::query_log::QueryLog;
+use meilisearch::search_cache::SearchCache;
 use meilisearch::{analytics, create_app, setup_meilisearch, SubscriberForSecondLayer};
 use once_cell::sync::Lazy;
 use tempfile::TempDir;
@@ -43,7 +46,10 @@ impl Server {
         let options = default_settings(dir.path());
 
         let (index_scheduler, auth) = setup_meilisearch(&options).unwrap();
-        let service = Service { index_scheduler, auth, options, api_key: None };
+        let search_cache = search_cache(&options);
+        let query_log = Arc::new(QueryLog::new(&options).unwrap());
+        let service =
+            Service { index_scheduler, auth, search_cache, query_log, options, api_key: None };
 
         Server { service, _dir: Some(dir) }
     }
@@ -58,7 +64,10 @@ impl Server {
         options.master_key = Some("MASTER_KEY".to_string());
 
         let (index_scheduler, auth) = setup_meilisearch(&options).unwrap();
-        let service = Service { index_scheduler, auth, options, api_key: None };
+        let search_cache = search_cache(&options);
+        let query_log = Arc::new(QueryLog::new(&options).unwrap());
+        let service =
+            Service { index_scheduler, auth, search_cache, query_log, options, api_key: None };
 
         Server { service, _dir: Some(dir) }
     }
@@ -71,7 +80,10 @@ impl Server {
 
     pub async fn new_with_options(options: Opt) -> Result<Self, anyhow::Error> {
         let (index_scheduler, auth) = setup_meilisearch(&options)?;
-        let service = Service { index_scheduler, auth, options, api_key: None };
+        let search_cache = search_cache(&options);
+        let query_log = Arc::new(QueryLog::new(&options).unwrap());
+        let service =
+            Service { index_scheduler, auth, search_cache, query_log, options, api_key: None };
 
         Ok(Server { service, _dir: None })
     }
@@ -99,6 +111,8 @@ impl Server {
         actix_web::test::init_service(create_app(
             self.service.index_scheduler.clone().into(),
             self.service.auth.clone().into(),
+            self.service.search_cache.clone().into(),
+            self.service.query_log.clone().into(),
             self.service.options.clone(),
             (route_layer_handle, stderr_layer_handle),
             analytics::MockAnalytics::new(&self.service.options),
@@ -165,6 +179,18 @@ impl Server {
         self.service.get(format!("/tasks?{}", filter)).await
     }
 
+    pub async fn batches(&self) -> (Value, StatusCode) {
+        self.service.get("/batches").await
+    }
+
+    pub async fn batches_filter(&self, filter: &str) -> (Value, StatusCode) {
+        self.service.get(format!("/batches?{}", filter)).await
+    }
+
+    pub async fn get_batch(&self, batch_id: u64) -> (Value, StatusCode) {
+        self.service.get(format!("/batches/{}", batch_id)).await
+    }
+
     pub async fn get_dump_status(&self, uid: &str) -> (Value, StatusCode) {
         self.service.get(format!("/dumps/{}/status", uid)).await
     }
@@ -173,6 +199,14 @@ impl Server {
         self.service.post("/dumps", json!(null)).await
     }
 
+    pub async fn create_partial_dump(&self, value: Value) -> (Value, StatusCode) {
+        self.service.post("/dumps", value).await
+    }
+
+    pub async fn import_dump(&self, value: Value) -> (Value, StatusCode) {
+        self.service.post("/dumps/import", value).await
+    }
+
     pub async fn create_snapshot(&self) -> (Value, StatusCode) {
         self.service.post("/snapshots", json!(null)).await
     }
@@ -224,6 +258,13 @@ impl Server {
     }
 }
 
+fn search_cache(options: &Opt) -> Arc<SearchCache> {
+    Arc::new(SearchCache::new(
+        options.experimental_search_cache_size,
+        Duration::from_secs(options.experimental_search_cache_ttl),
+    ))
+}
+
 pub fn default_settings(dir: impl AsRef<Path>) -> Opt {
     Opt {
         db_path: dir.as_ref().join("db"),
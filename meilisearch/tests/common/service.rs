@@ -5,6 +5,8 @@ use actix_web::http::StatusCode;
 use actix_web::test;
 use actix_web::test::TestRequest;
 use index_scheduler::IndexScheduler;
+use meilisearch::query_log::QueryLog;
+use meilisearch::search_cache::SearchCache;
 use meilisearch::{analytics, create_app, Opt, SubscriberForSecondLayer};
 use meilisearch_auth::AuthController;
 use tracing::level_filters::LevelFilter;
@@ -16,6 +18,8 @@ use crate::common::Value;
 pub struct Service {
     pub index_scheduler: Arc<IndexScheduler>,
     pub auth: Arc<AuthController>,
+    pub search_cache: Arc<SearchCache>,
+    pub query_log: Arc<QueryLog>,
     pub options: Opt,
     pub api_key: Option<String>,
 }
@@ -123,6 +127,8 @@ impl Service {
         let app = test::init_service(create_app(
             self.index_scheduler.clone().into(),
             self.auth.clone().into(),
+            self.search_cache.clone().into(),
+            self.query_log.clone().into(),
             self.options.clone(),
             (route_layer_handle, stderr_layer_handle),
             analytics::MockAnalytics::new(&self.options),
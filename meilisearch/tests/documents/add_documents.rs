@@ -297,6 +297,7 @@ async fn add_csv_document() {
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 0,
       "details": {
         "receivedDocuments": 2,
         "indexedDocuments": 2
@@ -361,6 +362,7 @@ async fn add_csv_document_with_types() {
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 0,
       "details": {
         "receivedDocuments": 3,
         "indexedDocuments": 3
@@ -436,6 +438,7 @@ async fn add_csv_document_with_custom_delimiter() {
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 0,
       "details": {
         "receivedDocuments": 2,
         "indexedDocuments": 2
@@ -989,6 +992,7 @@ async fn add_documents_no_index_creation() {
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 0,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 1
@@ -1067,6 +1071,7 @@ async fn document_addition_with_primary_key() {
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 0,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 1
@@ -1115,6 +1120,7 @@ async fn document_addition_with_huge_int_primary_key() {
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 0,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 1
@@ -1187,6 +1193,7 @@ async fn replace_document() {
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 1,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 1
@@ -1275,6 +1282,7 @@ async fn error_add_documents_bad_document_id() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 1,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -1316,6 +1324,7 @@ async fn error_add_documents_missing_document_id() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 1,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -1365,6 +1374,7 @@ async fn error_document_field_limit_reached_in_one_document() {
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 1,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 1
@@ -1407,6 +1417,7 @@ async fn error_document_field_limit_reached_over_multiple_documents() {
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 1,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 1
@@ -1441,6 +1452,7 @@ async fn error_document_field_limit_reached_over_multiple_documents() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 2,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -1490,6 +1502,7 @@ async fn error_document_field_limit_reached_in_one_nested_document() {
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 1,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 1
@@ -1533,6 +1546,7 @@ async fn error_document_field_limit_reached_over_multiple_documents_with_nested_
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 1,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 1
@@ -1568,6 +1582,7 @@ async fn error_document_field_limit_reached_over_multiple_documents_with_nested_
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 2,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 1
@@ -1615,6 +1630,7 @@ async fn add_documents_with_geo_field() {
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 1,
       "details": {
         "receivedDocuments": 4,
         "indexedDocuments": 4
@@ -1655,6 +1671,7 @@ async fn add_documents_invalid_geo_field() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 2,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -1692,6 +1709,7 @@ async fn add_documents_invalid_geo_field() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 3,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -1729,6 +1747,7 @@ async fn add_documents_invalid_geo_field() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 4,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -1766,6 +1785,7 @@ async fn add_documents_invalid_geo_field() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 5,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -1803,6 +1823,7 @@ async fn add_documents_invalid_geo_field() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 6,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -1840,6 +1861,7 @@ async fn add_documents_invalid_geo_field() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 7,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -1877,6 +1899,7 @@ async fn add_documents_invalid_geo_field() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 8,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -1914,6 +1937,7 @@ async fn add_documents_invalid_geo_field() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 9,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -1951,6 +1975,7 @@ async fn add_documents_invalid_geo_field() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 10,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -1988,6 +2013,7 @@ async fn add_documents_invalid_geo_field() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 11,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -2025,6 +2051,7 @@ async fn add_documents_invalid_geo_field() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 12,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -2062,6 +2089,7 @@ async fn add_documents_invalid_geo_field() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 13,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -2100,6 +2128,7 @@ async fn add_documents_invalid_geo_field() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 14,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -2136,6 +2165,7 @@ async fn add_documents_invalid_geo_field() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 15,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -2172,6 +2202,7 @@ async fn add_documents_invalid_geo_field() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 16,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -2214,6 +2245,7 @@ async fn add_invalid_geo_and_then_settings() {
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 1,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 1
@@ -2236,6 +2268,7 @@ async fn add_invalid_geo_and_then_settings() {
       "status": "failed",
       "type": "settingsUpdate",
       "canceledBy": null,
+      "batchUid": 2,
       "details": {
         "sortableAttributes": [
           "_geo"
@@ -2307,6 +2340,7 @@ async fn error_primary_key_inference() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 0,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -2347,6 +2381,7 @@ async fn error_primary_key_inference() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 1,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -2385,6 +2420,7 @@ async fn error_primary_key_inference() {
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 2,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 1
@@ -188,6 +188,7 @@ async fn delete_document_by_filter() {
       "status": "succeeded",
       "type": "documentDeletion",
       "canceledBy": null,
+      "batchUid": 2,
       "details": {
         "providedIds": 0,
         "deletedDocuments": 2,
@@ -253,6 +254,7 @@ async fn delete_document_by_filter() {
       "status": "succeeded",
       "type": "documentDeletion",
       "canceledBy": null,
+      "batchUid": 3,
       "details": {
         "providedIds": 0,
         "deletedDocuments": 1,
@@ -337,6 +339,7 @@ async fn delete_document_by_complex_filter() {
       "status": "succeeded",
       "type": "documentDeletion",
       "canceledBy": null,
+      "batchUid": 2,
       "details": {
         "providedIds": 0,
         "deletedDocuments": 2,
@@ -395,6 +398,7 @@ async fn delete_document_by_complex_filter() {
       "status": "succeeded",
       "type": "documentDeletion",
       "canceledBy": null,
+      "batchUid": 3,
       "details": {
         "providedIds": 0,
         "deletedDocuments": 2,
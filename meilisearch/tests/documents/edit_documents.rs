@@ -0,0 +1,294 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::{GetAllDocumentsOptions, Server};
+use crate::json;
+
+#[actix_rt::test]
+async fn edit_documents_by_function() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+    index.update_settings_filterable_attributes(json!(["age"])).await;
+    index
+        .add_documents(
+            json!([
+                { "id": 0, "name": "kefir", "age": 4 },
+                { "id": 1, "name": "intel", "age": 3 },
+                { "id": 2, "name": "max", "age": 12 },
+            ]),
+            Some("id"),
+        )
+        .await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .edit_documents_by_function(json!({
+            "filter": "age < 10",
+            "context": { "increment": 1 },
+            "function": "doc.name = doc.name.to_upper(); doc.age += context.increment;",
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(json_string!(response, { ".enqueuedAt" => "[date]" }), @r###"
+    {
+      "taskUid": 2,
+      "indexUid": "doggo",
+      "status": "enqueued",
+      "type": "documentEdition",
+      "enqueuedAt": "[date]"
+    }
+    "###);
+
+    let response = index.wait_task(2).await;
+    snapshot!(json_string!(response, { ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]", ".duration" => "[duration]" }), @r###"
+    {
+      "uid": 2,
+      "indexUid": "doggo",
+      "status": "succeeded",
+      "type": "documentEdition",
+      "canceledBy": null,
+      "batchUid": 2,
+      "details": {
+        "deletedDocuments": 0,
+        "editedDocuments": 2,
+        "originalFilter": "\"age < 10\"",
+        "context": {
+          "increment": 1
+        },
+        "function": "doc.name = doc.name.to_upper(); doc.age += context.increment;"
+      },
+      "error": null,
+      "duration": "[duration]",
+      "enqueuedAt": "[date]",
+      "startedAt": "[date]",
+      "finishedAt": "[date]"
+    }
+    "###);
+
+    let (documents, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents), @r###"
+    {
+      "results": [
+        {
+          "id": 0,
+          "name": "KEFIR",
+          "age": 5
+        },
+        {
+          "id": 1,
+          "name": "INTEL",
+          "age": 4
+        },
+        {
+          "id": 2,
+          "name": "max",
+          "age": 12
+        }
+      ],
+      "offset": 0,
+      "limit": 20,
+      "total": 3
+    }
+    "###);
+
+    // the edited documents must still be reachable through the filterable attributes
+    let (response, code) = index.search_post(json!({ "filter": "age = 5" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 0,
+        "name": "KEFIR",
+        "age": 5
+      }
+    ]
+    "###);
+
+    // setting `doc` to `()` deletes the document
+    let (_response, code) = index
+        .edit_documents_by_function(json!({
+            "function": "if doc.age > 10 { doc = () }",
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+
+    let response = index.wait_task(3).await;
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "deletedDocuments": 1,
+      "editedDocuments": 0,
+      "originalFilter": null,
+      "context": null,
+      "function": "if doc.age > 10 { doc = () }"
+    }
+    "###);
+
+    let (stats, _) = index.stats().await;
+    snapshot!(stats["numberOfDocuments"], @"2");
+}
+
+#[actix_rt::test]
+async fn edit_documents_by_function_bad_requests() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index.edit_documents_by_function(json!({})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `function`",
+      "code": "missing_document_edition_function",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_document_edition_function"
+    }
+    "###);
+
+    let (response, code) = index.edit_documents_by_function(json!({ "function": 42 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.function`: expected a string, but found a positive integer: `42`",
+      "code": "invalid_document_edition_function",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_edition_function"
+    }
+    "###);
+
+    let (response, code) = index
+        .edit_documents_by_function(json!({ "function": "doc.id = 2", "context": "hello" }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The context must be an object.",
+      "code": "invalid_document_edition_context",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_edition_context"
+    }
+    "###);
+
+    let (response, code) = index
+        .edit_documents_by_function(json!({ "function": "doc.id = 2", "filter": "age >" }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_document_filter""###);
+}
+
+#[actix_rt::test]
+async fn edit_documents_by_function_failing_function() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+    index.add_documents(json!([{ "id": 0, "name": "kefir" }]), Some("id")).await;
+    index.wait_task(0).await;
+
+    // the function doesn't compile
+    let (_response, code) =
+        index.edit_documents_by_function(json!({ "function": "doc.name = " })).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(1).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(response["error"]["code"], @r###""invalid_document_edition_function""###);
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "deletedDocuments": 0,
+      "editedDocuments": 0,
+      "originalFilter": null,
+      "context": null,
+      "function": "doc.name = "
+    }
+    "###);
+
+    // the function tries to modify the primary key
+    let (_response, code) =
+        index.edit_documents_by_function(json!({ "function": "doc.id = 42" })).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(2).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "The document edition function cannot modify the primary key of a document. Was modifying the document with id `0`.",
+      "code": "invalid_document_edition_function",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_edition_function"
+    }
+    "###);
+
+    // the function replaces the document with something that isn't an object
+    let (_response, code) =
+        index.edit_documents_by_function(json!({ "function": "doc = 42" })).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(3).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(response["error"]["message"], @r###""The document edition function must leave `doc` as an object, or set it to `()` to delete the document. Was editing the document with id `0`.""###);
+
+    // the function doesn't compile even though the filter doesn't match any document
+    index.update_settings_filterable_attributes(json!(["name"])).await;
+    index.wait_task(4).await;
+    let (_response, code) = index
+        .edit_documents_by_function(json!({ "function": "doc.name = ", "filter": "name = ghost" }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(5).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(response["error"]["code"], @r###""invalid_document_edition_function""###);
+
+    // nothing was modified
+    let (documents, _code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 0,
+        "name": "kefir"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn edit_documents_by_function_failing_after_deletions() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+    index.update_settings_filterable_attributes(json!(["_geo"])).await;
+    index
+        .add_documents(
+            json!([
+                { "id": 0, "name": "kefir" },
+                { "id": 1, "name": "intel" },
+                { "id": 2, "name": "max" },
+            ]),
+            Some("id"),
+        )
+        .await;
+    index.wait_task(1).await;
+
+    // the function deletes a document and gives an invalid `_geo` to another one
+    let (_response, code) = index
+        .edit_documents_by_function(json!({
+            "function": "if doc.id == 0 { doc = () } else if doc.id == 1 { doc._geo = #{ lat: \"north\", lng: 0 } }",
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(2).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(response["error"]["code"], @r###""invalid_document_geo_field""###);
+    snapshot!(response["details"]["deletedDocuments"], @"0");
+
+    // the deleted document is still there
+    let (documents, _code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 0,
+        "name": "kefir"
+      },
+      {
+        "id": 1,
+        "name": "intel"
+      },
+      {
+        "id": 2,
+        "name": "max"
+      }
+    ]
+    "###);
+}
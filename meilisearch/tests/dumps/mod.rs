@@ -78,7 +78,9 @@ async fn import_dump_v1_movie_raw() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "allowRankingRulesOverride": false
     }
     "###
     );
@@ -240,7 +242,9 @@ async fn import_dump_v1_movie_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "allowRankingRulesOverride": false
     }
     "###
     );
@@ -388,7 +392,9 @@ async fn import_dump_v1_rubygems_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "allowRankingRulesOverride": false
     }
     "###
     );
@@ -522,7 +528,9 @@ async fn import_dump_v2_movie_raw() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "allowRankingRulesOverride": false
     }
     "###
     );
@@ -668,7 +676,9 @@ async fn import_dump_v2_movie_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "allowRankingRulesOverride": false
     }
     "###
     );
@@ -813,7 +823,9 @@ async fn import_dump_v2_rubygems_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "allowRankingRulesOverride": false
     }
     "###
     );
@@ -947,7 +959,9 @@ async fn import_dump_v3_movie_raw() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "allowRankingRulesOverride": false
     }
     "###
     );
@@ -1093,7 +1107,9 @@ async fn import_dump_v3_movie_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "allowRankingRulesOverride": false
     }
     "###
     );
@@ -1238,7 +1254,9 @@ async fn import_dump_v3_rubygems_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "allowRankingRulesOverride": false
     }
     "###
     );
@@ -1372,7 +1390,9 @@ async fn import_dump_v4_movie_raw() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "allowRankingRulesOverride": false
     }
     "###
     );
@@ -1518,7 +1538,9 @@ async fn import_dump_v4_movie_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "allowRankingRulesOverride": false
     }
     "###
     );
@@ -1663,7 +1685,9 @@ async fn import_dump_v4_rubygems_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "allowRankingRulesOverride": false
     }
     "###
     );
@@ -1907,7 +1931,9 @@ async fn import_dump_v6_containing_experimental_features() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "allowRankingRulesOverride": false
     }
     "###);
 
@@ -2081,7 +2107,9 @@ async fn generate_and_import_dump_containing_vectors() {
           "documentTemplate": "{{doc.doggo}}"
         }
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "allowRankingRulesOverride": false
     }
     "###);
 
@@ -2145,3 +2173,295 @@ async fn generate_and_import_dump_containing_vectors() {
         })
         .await;
 }
+
+#[actix_rt::test]
+#[cfg_attr(target_os = "windows", ignore)]
+async fn generate_and_import_partial_dump() {
+    let temp = tempfile::tempdir().unwrap();
+    let mut opt = default_settings(temp.path());
+    let server = Server::new_with_options(opt.clone()).await.unwrap();
+
+    for uid in ["tenant-a", "tenant-b", "other"] {
+        let index = server.index(uid);
+        let (response, code) = index.add_documents(json!([{ "id": 1, "name": uid }]), None).await;
+        snapshot!(code, @"202 Accepted");
+        let response = index.wait_task(response.uid()).await;
+        snapshot!(response["status"], @r###""succeeded""###);
+    }
+
+    let (response, code) = server
+        .create_partial_dump(
+            json!({ "indexUids": ["tenant-*"], "includeTasks": false, "includeKeys": false }),
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    // ========= Import the partial dump in a new instance
+    drop(server);
+    tokio::fs::remove_dir_all(&opt.db_path).await.unwrap();
+    let dump_name = format!("{}.dump", response["details"]["dumpUid"].as_str().unwrap());
+    opt.import_dump = Some(opt.dump_dir.join(dump_name));
+    opt.db_path = temp.path().join("data.ms");
+
+    let server = Server::new_with_options(opt).await.unwrap();
+
+    let (indexes, code) = server.list_indexes(None, None).await;
+    snapshot!(code, @"200 OK");
+    let uids: Vec<_> = indexes["results"].as_array().unwrap().iter().map(|i| &i["uid"]).collect();
+    snapshot!(json_string!(uids), @r###"
+    [
+      "tenant-a",
+      "tenant-b"
+    ]
+    "###);
+
+    let (documents, code) =
+        server.index("tenant-a").get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "name": "tenant-a"
+      }
+    ]
+    "###);
+
+    let (tasks, code) = server.tasks().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(tasks["results"].as_array().unwrap().len(), @"0");
+}
+
+#[actix_rt::test]
+async fn create_dump_bad_payload() {
+    let server = Server::new().await;
+
+    let (response, code) = server.create_partial_dump(json!({ "indexUids": ["doggo?"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.indexUids[0]`: `doggo?` is not a valid index uid pattern. Index uid patterns can be an integer or a string containing only alphanumeric characters, hyphens (-), underscores (_), and optionally end with a star (*).",
+      "code": "invalid_dump_index_uids",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_dump_index_uids"
+    }
+    "###);
+
+    let (response, code) = server.create_partial_dump(json!({ "includeTasks": "yes" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.includeTasks`: expected a boolean, but found a string: `\"yes\"`",
+      "code": "invalid_dump_include_tasks",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_dump_include_tasks"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+#[cfg_attr(target_os = "windows", ignore)]
+async fn import_dump_into_running_instance() {
+    let temp = tempfile::tempdir().unwrap();
+    let server = Server::new_with_options(default_settings(temp.path())).await.unwrap();
+
+    for uid in ["catto", "doggo"] {
+        let index = server.index(uid);
+        let (response, code) = index.add_documents(json!([{ "id": 1, "name": uid }]), None).await;
+        snapshot!(code, @"202 Accepted");
+        let response = index.wait_task(response.uid()).await;
+        snapshot!(response["status"], @r###""succeeded""###);
+    }
+
+    let (response, code) = server.create_dump().await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    let dump_uid = response["details"]["dumpUid"].as_str().unwrap().to_string();
+
+    // the index is modified after the dump was created
+    let index = server.index("catto");
+    let (response, code) =
+        index.add_documents(json!([{ "id": 2, "name": "corrupted" }]), None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await;
+
+    // ========= Without overwrite, the import fails when an index already exists and nothing is imported
+    let (response, code) = server
+        .import_dump(json!({
+            "dumpUid": dump_uid,
+            "indexes": [{ "indexUid": "catto" }, { "indexUid": "doggo", "renameTo": "doggo-copy" }]
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "Indexes `catto` already exist. Hint: set `overwrite` to `true` to replace them with the indexes of the dump.",
+      "code": "index_already_exists",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#index_already_exists"
+    }
+    "###);
+    snapshot!(response["details"]["importedDocuments"], @"0");
+
+    let (response, code) = server.index("doggo-copy").get().await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""index_not_found""###);
+
+    let (documents, code) =
+        server.index("catto").get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "name": "catto"
+      },
+      {
+        "id": 2,
+        "name": "corrupted"
+      }
+    ]
+    "###);
+
+    // ========= Import an index under another name
+    let (response, code) = server
+        .import_dump(json!({
+            "dumpUid": dump_uid,
+            "indexes": [{ "indexUid": "doggo", "renameTo": "doggo-restored" }]
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(response["type"], @r###""dumpImport""###);
+    snapshot!(response["details"]["importedDocuments"], @"1");
+
+    let (documents, code) =
+        server.index("doggo-restored").get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "name": "doggo"
+      }
+    ]
+    "###);
+
+    // ========= Restore the modified index
+    let (response, code) = server
+        .import_dump(json!({
+            "dumpUid": dump_uid,
+            "indexes": [{ "indexUid": "catto" }],
+            "overwrite": true
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    let (documents, code) =
+        server.index("catto").get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "name": "catto"
+      }
+    ]
+    "###);
+
+    // The replaced index and the temporary indexes of the imports are deleted
+    let (response, code) = server.list_indexes(None, None).await;
+    snapshot!(code, @"200 OK");
+    let uids: Vec<_> = response["results"].as_array().unwrap().iter().map(|i| &i["uid"]).collect();
+    snapshot!(json_string!(uids), @r###"
+    [
+      "catto",
+      "doggo",
+      "doggo-restored"
+    ]
+    "###);
+
+    // ========= Importing from an unknown dump fails
+    let (response, code) = server
+        .import_dump(json!({ "dumpUid": "unknown", "indexes": [{ "indexUid": "kefir" }] }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(response["error"]["code"], @r###""dump_not_found""###);
+
+    let (response, code) = server.index("kefir").get().await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""index_not_found""###);
+}
+
+#[actix_rt::test]
+async fn import_dump_bad_payload() {
+    let server = Server::new().await;
+
+    let (response, code) =
+        server.import_dump(json!({ "indexes": [{ "indexUid": "doggo" }] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `dumpUid`",
+      "code": "missing_dump_import_dump_uid",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_dump_import_dump_uid"
+    }
+    "###);
+
+    let (response, code) = server
+        .import_dump(json!({ "dumpUid": "../data", "indexes": [{ "indexUid": "doggo" }] }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`../data` is not a valid dump uid. A dump uid is the name of a dump file without its `.dump` extension, it can only contain alphanumeric characters, hyphens (-) and underscores (_).",
+      "code": "invalid_dump_import_dump_uid",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_dump_import_dump_uid"
+    }
+    "###);
+
+    let (response, code) = server.import_dump(json!({ "dumpUid": "dump", "indexes": [] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "At least one index must be given to import a dump.",
+      "code": "invalid_dump_import_indexes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_dump_import_indexes"
+    }
+    "###);
+
+    let (response, code) = server
+        .import_dump(json!({
+            "dumpUid": "dump",
+            "indexes": [{ "indexUid": "doggo" }, { "indexUid": "catto", "renameTo": "doggo" }]
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Indexes must be declared only once during a dump import. `doggo` was specified several times.",
+      "code": "invalid_dump_import_indexes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_dump_import_indexes"
+    }
+    "###);
+
+    let (response, code) = server
+        .import_dump(json!({ "dumpUid": "dump", "indexes": [{ "indexUid": "doggo?" }] }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_dump_import_indexes""###);
+}
@@ -0,0 +1,168 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        {
+            "id": 1,
+            "inStock": false,
+            "price": 10,
+        },
+        {
+            "id": 2,
+            "inStock": true,
+            "price": 20,
+        },
+        {
+            "id": 3,
+            "inStock": false,
+            "price": 30,
+        },
+    ])
+});
+
+#[actix_rt::test]
+async fn search_with_boost() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, _) = index
+        .update_settings(json!({
+            "filterableAttributes": ["inStock"],
+            "sortableAttributes": ["price"],
+            "rankingRules": ["boost", "price:asc"],
+        }))
+        .await;
+    index.wait_task(response.uid()).await;
+
+    let (response, _) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = index.search_post(json!({"attributesToRetrieve": ["id"]})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1
+      },
+      {
+        "id": 2
+      },
+      {
+        "id": 3
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "boost": [{ "filter": "inStock = true", "weight": 2 }],
+            "attributesToRetrieve": ["id"],
+            "showRankingScoreDetails": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 2,
+        "_rankingScoreDetails": {
+          "boost": {
+            "order": 0,
+            "weight": 2.0,
+            "maxWeight": 2.0,
+            "score": 1.0
+          },
+          "price:asc": {
+            "order": 1,
+            "value": 20.0
+          }
+        }
+      },
+      {
+        "id": 1,
+        "_rankingScoreDetails": {
+          "boost": {
+            "order": 0,
+            "weight": 0.0,
+            "maxWeight": 2.0,
+            "score": 0.009900990099009901
+          },
+          "price:asc": {
+            "order": 1,
+            "value": 10.0
+          }
+        }
+      },
+      {
+        "id": 3,
+        "_rankingScoreDetails": {
+          "boost": {
+            "order": 0,
+            "weight": 0.0,
+            "maxWeight": 2.0,
+            "score": 0.009900990099009901
+          },
+          "price:asc": {
+            "order": 1,
+            "value": 30.0
+          }
+        }
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_with_invalid_boost() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, _) = index
+        .update_settings(json!({
+            "filterableAttributes": ["inStock"],
+        }))
+        .await;
+    index.wait_task(response.uid()).await;
+
+    let (response, _) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(response.uid()).await;
+
+    let (response, code) =
+        index.search_post(json!({"boost": [{ "filter": "inStock = true", "weight": 2 }]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "You must specify where `boost` is listed in the rankingRules setting to use the boost parameter at search time.",
+      "code": "invalid_search_boost",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_boost"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"boost": [{ "filter": "inStock = true", "weight": -1 }]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.boost[0].weight`: the value of `weight` is invalid, expected a positive float.",
+      "code": "invalid_search_boost_weight",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_boost_weight"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"boost": [{ "weight": 1 }]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `filter` inside `.boost[0]`",
+      "code": "invalid_search_boost",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_boost"
+    }
+    "###);
+}
@@ -1,4 +1,4 @@
-use meili_snap::snapshot;
+use meili_snap::{json_string, snapshot};
 use once_cell::sync::Lazy;
 
 use crate::common::{Server, Value};
@@ -140,6 +140,16 @@ static NESTED_DOCUMENTS: Lazy<Value> = Lazy::new(|| {
     ])
 });
 
+static VARIANTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+      { "id": 1, "description": "Leather Jacket", "product_id": "123456", "color": "Brown", "size": "M" },
+      { "id": 2, "description": "Leather Jacket", "product_id": "123456", "color": "Brown", "size": "L" },
+      { "id": 3, "description": "Leather Jacket", "product_id": "123456", "color": "Black", "size": "M" },
+      { "id": 4, "description": "T-Shirt", "product_id": "789012", "color": "Red", "size": "M" },
+      { "id": 5, "description": "T-Shirt", "product_id": "789012", "color": "Red", "size": "S" },
+    ])
+});
+
 static DOCUMENT_PRIMARY_KEY: &str = "id";
 static DOCUMENT_DISTINCT_KEY: &str = "product_id";
 
@@ -304,3 +314,158 @@ async fn distinct_at_search_time() {
     snapshot!(response["totalPages"], @"1");
     snapshot!(response["totalHits"], @"3");
 }
+
+#[actix_rt::test]
+async fn distinct_on_several_attributes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _) = index.add_documents(VARIANTS.clone(), Some(DOCUMENT_PRIMARY_KEY)).await;
+    index.wait_task(task.uid()).await;
+    let (task, _) =
+        index.update_settings_filterable_attributes(json!(["product_id", "color"])).await;
+    let task = index.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let (response, code) = index
+        .search_post(json!({ "distinct": ["product_id", "color"], "attributesToRetrieve": ["id"] }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1
+      },
+      {
+        "id": 3
+      },
+      {
+        "id": 4
+      }
+    ]
+    "###);
+
+    // a single attribute can also be given in an array
+    let (response, code) = index
+        .search_post(json!({ "distinct": ["product_id"], "attributesToRetrieve": ["id"] }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1
+      },
+      {
+        "id": 4
+      }
+    ]
+    "###);
+
+    let (response, code) =
+        index.search_get("?distinct=product_id,color&attributesToRetrieve=id").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1
+      },
+      {
+        "id": 3
+      },
+      {
+        "id": 4
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn distinct_groups() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _) = index.add_documents(VARIANTS.clone(), Some(DOCUMENT_PRIMARY_KEY)).await;
+    index.wait_task(task.uid()).await;
+    let (task, _) =
+        index.update_settings_filterable_attributes(json!(["product_id", "color"])).await;
+    let task = index.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "distinct": "product_id",
+            "distinctGroupLimit": 1,
+            "attributesToRetrieve": ["id", "color"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1,
+        "color": "Brown",
+        "_group": [
+          {
+            "id": 2,
+            "color": "Brown"
+          }
+        ],
+        "_groupSize": 3
+      },
+      {
+        "id": 4,
+        "color": "Red",
+        "_group": [
+          {
+            "id": 5,
+            "color": "Red"
+          }
+        ],
+        "_groupSize": 2
+      }
+    ]
+    "###);
+
+    // the members of a group are highlighted and match the filter and the query
+    let (response, code) = index
+        .search_post(json!({
+            "q": "jacket",
+            "distinct": "product_id",
+            "distinctGroupLimit": 5,
+            "filter": "color = Brown",
+            "attributesToRetrieve": ["id"],
+            "attributesToHighlight": ["description"],
+            "showRankingScore": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"][0]["_group"]), @r###"
+    [
+      {
+        "id": 2,
+        "_formatted": {
+          "id": "2",
+          "description": "Leather <em>Jacket</em>"
+        }
+      }
+    ]
+    "###);
+    snapshot!(response["hits"][0]["_groupSize"], @"2");
+    snapshot!(response["hits"][0]["_rankingScore"].is_number(), @"true");
+
+    // the groups are only returned when requested
+    let (response, code) = index
+        .search_post(json!({ "distinct": "product_id", "attributesToRetrieve": ["id"] }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1
+      },
+      {
+        "id": 4
+      }
+    ]
+    "###);
+}
@@ -389,6 +389,168 @@ async fn search_bad_facets() {
     // Can't make the `attributes_to_highlight` fail with a get search since it'll accept anything as an array of strings.
 }
 
+#[actix_rt::test]
+async fn search_bad_facet_histograms() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"facetHistograms": ["price"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.facetHistograms`: expected an object, but found an array: `[\"price\"]`",
+      "code": "invalid_search_facet_histograms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_histograms"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({"facetHistograms": { "price": { "interval": 10, "ranges": [] } }}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.facetHistograms.price`: Exactly one of `interval` or `ranges` must be specified.",
+      "code": "invalid_search_facet_histograms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_histograms"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"facetHistograms": { "price": { "interval": -10 } }})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.facetHistograms.price`: The `interval` must be a positive number, but found `-10`.",
+      "code": "invalid_search_facet_histograms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_histograms"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(
+            json!({"facetHistograms": { "price": { "ranges": [{ "from": 10, "to": 5 }] } }}),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.facetHistograms.price`: The `from` bound of a range must be lower than its `to` bound, but found `from: 10` and `to: 5`.",
+      "code": "invalid_search_facet_histograms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_histograms"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_facet_histograms_without_facets() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.update_settings(json!({"filterableAttributes": ["title", "price"]})).await;
+    // Wait for the settings update to complete
+    index.wait_task(0).await;
+
+    let (response, code) = index
+        .search_post(
+            json!({"facets": ["title"], "facetHistograms": { "price": { "interval": 10 } }}),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in `facetHistograms`: `price` is not one of the `facets` of the search. A histogram can only be computed on an attribute listed in `facets`.",
+      "code": "invalid_search_facet_histograms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_histograms"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"facetHistograms": { "price": { "interval": 10 } }})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in `facetHistograms`: `price` is not one of the `facets` of the search. A histogram can only be computed on an attribute listed in `facets`.",
+      "code": "invalid_search_facet_histograms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_histograms"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_too_many_facet_histogram_buckets() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.update_settings(json!({"filterableAttributes": ["price"]})).await;
+    index.wait_task(0).await;
+    index.add_documents(json!([{ "id": 0, "price": 0 }, { "id": 1, "price": 5000 }]), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .search_post(
+            json!({"facets": ["price"], "facetHistograms": { "price": { "interval": 1 } }}),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The histogram of the facet `price` would contain 5001 buckets, but the maximum is 1000. Use a larger `interval` or fewer `ranges`.",
+      "code": "invalid_search_facet_histograms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_histograms"
+    }
+    "###);
+
+    let ranges: Vec<_> = (0..1001).map(|i| json!({ "from": i, "to": i + 1 })).collect();
+    let (response, code) = index
+        .search_post(
+            json!({"facets": ["price"], "facetHistograms": { "price": { "ranges": ranges } }}),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The histogram of the facet `price` would contain 1001 buckets, but the maximum is 1000. Use a larger `interval` or fewer `ranges`.",
+      "code": "invalid_search_facet_histograms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_histograms"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_facet_aggregations() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"facetAggregations": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.facetAggregations`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_facet_aggregations",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_aggregations"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?facetAggregations=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `facetAggregations`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_facet_aggregations",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_aggregations"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_threshold() {
     let server = Server::new().await;
@@ -649,7 +811,7 @@ async fn filter_invalid_syntax_object() {
     index.wait_task(1).await;
 
     let expected_response = json!({
-        "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+        "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -674,7 +836,7 @@ async fn filter_invalid_syntax_array() {
     index.wait_task(1).await;
 
     let expected_response = json!({
-        "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+        "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -1196,10 +1358,35 @@ async fn distinct_at_search_time() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(response, @r###"
     {
-      "message": "Invalid value type at `.distinct`: expected a string, but found a boolean: `true`",
+      "message": "Invalid value type at `.distinct`: expected a string or an array, but found a boolean: `true`",
       "code": "invalid_search_distinct",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_search_distinct"
     }
     "###);
+
+    let (response, code) = index
+        .search_post(json!({"page": 0, "hitsPerPage": 2, "distinct": ["color", "doggo.truc"]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Attribute `doggo.truc` is not filterable and thus, cannot be used as distinct attribute. Available filterable attributes are: `color, <..hidden-attributes>`.",
+      "code": "invalid_search_distinct",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_distinct"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"distinct": "color", "distinctGroupLimit": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid value type at `.distinctGroupLimit`: expected a positive integer, but found a string: `\"doggo\"`",
+      "code": "invalid_search_distinct_group_limit",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_distinct_group_limit"
+    }
+    "###);
 }
@@ -1,4 +1,4 @@
-use meili_snap::snapshot;
+use meili_snap::{json_string, snapshot};
 use once_cell::sync::Lazy;
 
 use crate::common::{Server, Value};
@@ -200,3 +200,104 @@ async fn simple_facet_search_with_sort_by_count() {
     assert_eq!(hits[0], json!({ "value": "Action", "count": 3 }));
     assert_eq!(hits[1], json!({ "value": "Adventure", "count": 2 }));
 }
+
+#[actix_rt::test]
+async fn facet_search_exhaustive_facet_count() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    // 50 documents sharing 25 brands, more than the default search limit
+    let documents: Vec<_> =
+        (0..50).map(|id| json!({ "id": id, "brand": id % 25, "color": "red" })).collect();
+    index.update_settings_filterable_attributes(json!(["color"])).await;
+    index.update_distinct_attribute(json!("brand")).await;
+    let (task, _code) = index.add_documents(json!(documents), None).await;
+    index.wait_task(task.uid()).await;
+
+    let (response, code) = index.facet_search(json!({"facetName": "color"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".processingTimeMs" => "[time]" }), @r###"
+    {
+      "facetHits": [
+        {
+          "value": "red",
+          "count": 30
+        }
+      ],
+      "facetQuery": null,
+      "exhaustiveFacetCount": false,
+      "processingTimeMs": "[time]"
+    }
+    "###);
+
+    let (response, code) =
+        index.facet_search(json!({"facetName": "color", "exhaustiveFacetCount": true})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".processingTimeMs" => "[time]" }), @r###"
+    {
+      "facetHits": [
+        {
+          "value": "red",
+          "count": 25
+        }
+      ],
+      "facetQuery": null,
+      "exhaustiveFacetCount": true,
+      "processingTimeMs": "[time]"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn facet_search_without_distinct_is_exhaustive() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["genres"])).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) =
+        index.facet_search(json!({"facetName": "genres", "facetQuery": "a"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["exhaustiveFacetCount"], @"true");
+}
+
+#[actix_rt::test]
+async fn facet_search_bad_exhaustive_facet_count() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.facet_search(json!({"facetName": "genres", "exhaustiveFacetCount": "yes"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.exhaustiveFacetCount`: expected a boolean, but found a string: `\"yes\"`",
+      "code": "invalid_facet_search_exhaustive_facet_count",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_facet_search_exhaustive_facet_count"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn facet_search_exhaustive_facet_count_respects_search_cutoff() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["genres"])).await;
+    // We can't really use anything else than 0ms here; otherwise, the test will get flaky.
+    index.update_settings(json!({ "searchCutoffMs": 0 })).await;
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await;
+
+    let (response, code) = index
+        .facet_search(json!({"facetName": "genres", "q": "the", "exhaustiveFacetCount": true}))
+        .await;
+    snapshot!(code, @"200 OK");
+    // The search ran out of time, the counts can't be exact
+    snapshot!(response["exhaustiveFacetCount"], @"false");
+}
@@ -184,3 +184,204 @@ async fn bug_4640() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn geo_polygon() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["_geo"])).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({
+                "filter": "_geoPolygon([45, 9], [46, 9], [45.5, 10])",
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response, { ".processingTimeMs" => "[time]" }), @r###"
+                {
+                  "hits": [
+                    {
+                      "id": 2,
+                      "name": "La Bella Italia",
+                      "address": "456 Elm Street, Townsville",
+                      "type": "Italian",
+                      "rating": 9,
+                      "_geo": {
+                        "lat": "45.4777599",
+                        "lng": "9.1967508"
+                      }
+                    }
+                  ],
+                  "query": "",
+                  "processingTimeMs": "[time]",
+                  "limit": 20,
+                  "offset": 0,
+                  "estimatedTotalHits": 1
+                }
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "filter": "_geoPolygon([45, 9], [46, 9])",
+            }),
+            |response, code| {
+                snapshot!(code, @"400 Bad Request");
+                snapshot!(json_string!(response), @r###"
+                {
+                  "message": "The `_geoPolygon` filter expects at least three pairs of arguments: `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)`.\n1:30 _geoPolygon([45, 9], [46, 9])",
+                  "code": "invalid_search_filter",
+                  "type": "invalid_request",
+                  "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+                }
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn geojson() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "name": "Milan", "_geojson": { "type": "Point", "coordinates": [9.19, 45.46] } },
+        { "id": 2, "name": "Naviglio Grande", "_geojson": { "type": "LineString", "coordinates": [[8.8, 45.4], [9.17, 45.45]] } },
+        { "id": 3, "name": "Lake Como", "_geojson": { "type": "Polygon", "coordinates": [[[9.0, 45.8], [9.4, 45.8], [9.4, 46.2], [9.0, 46.2], [9.0, 45.8]]] } },
+    ]);
+    index.update_settings_filterable_attributes(json!(["_geojson"])).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({
+                "filter": "_geoPolygon([45.3, 8.9], [45.6, 8.9], [45.6, 9.3], [45.3, 9.3])",
+                "attributesToRetrieve": ["id", "name"],
+            }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1,
+                    "name": "Milan"
+                  },
+                  {
+                    "id": 2,
+                    "name": "Naviglio Grande"
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "filter": "_geoRadius(46, 9.2, 1000)",
+                "attributesToRetrieve": ["id", "name"],
+            }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 3,
+                    "name": "Lake Como"
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    let (response, _code) = index
+        .add_documents(
+            json!([{ "id": 4, "_geojson": { "type": "Circle", "coordinates": [9.19, 45.46] } }]),
+            None,
+        )
+        .await;
+    let task = index.wait_task(response.uid()).await;
+    snapshot!(json_string!(task["error"]), @r###"
+    {
+      "message": "The `_geojson` field in the document with the id: `4` is not a valid GeoJSON geometry. Unsupported geometry type `Circle`. Supported types are `Point`, `MultiPoint`, `LineString`, `MultiLineString`, `Polygon`, and `MultiPolygon`.",
+      "code": "invalid_document_geo_field",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_geo_field"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn geo_sort_with_decay() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "name": "Pizzeria da Michele", "_geo": { "lat": 45.4642, "lng": 9.19 } },
+        { "id": 2, "name": "Pizzeria Spontini", "_geo": { "lat": 45.4660, "lng": 9.19 } },
+        { "id": 3, "name": "Pizzeria Napoli", "_geo": { "lat": 45.6, "lng": 9.19 } },
+    ]);
+    index.update_settings_sortable_attributes(json!(["_geo"])).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({
+                "sort": ["_geoPoint(45.4642, 9.19):decay(scale=5km, offset=1km)"],
+                "attributesToRetrieve": ["id"],
+                "showRankingScore": true,
+                "rankingScoreThreshold": 0.5,
+            }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                // the two closest documents are under the offset and get the same score
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1,
+                    "_geoDistance": 0,
+                    "_rankingScore": 1.0
+                  },
+                  {
+                    "id": 2,
+                    "_geoDistance": 200,
+                    "_rankingScore": 1.0
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "sort": ["_geoPoint(45.4642, 9.19):decay(offset=1km)"],
+            }),
+            |response, code| {
+                snapshot!(code, @"400 Bad Request");
+                snapshot!(json_string!(response), @r###"
+                {
+                  "message": "Invalid syntax for the geo decay parameter `_geoPoint(45.4642, 9.19):decay(offset=1km)`: The `scale` parameter is mandatory. Was expecting an expression formatted like `_geoPoint(latitude, longitude):decay(scale=5km, offset=1km, fn=gauss, decay=0.5)`.",
+                  "code": "invalid_search_sort",
+                  "type": "invalid_request",
+                  "link": "https://docs.meilisearch.com/errors#invalid_search_sort"
+                }
+                "###);
+            },
+        )
+        .await;
+}
@@ -0,0 +1,174 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        {
+            "id": 852,
+            "name_en": "Shinjuku station",
+            "name_ja": "新宿駅",
+        },
+        {
+            "id": 853,
+            "name_en": "Tokyo station",
+            "name_ja": "東京駅",
+        },
+        {
+            "id": 854,
+            "name_en": "Kyoto tower",
+            "name_ja": "京都タワー",
+        },
+    ])
+});
+
+#[actix_rt::test]
+async fn simple_search_with_localized_attributes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, _) = index
+        .update_settings(json!({
+            "localizedAttributes": [
+                {"attributePatterns": ["*_ja"], "locales": ["jpn"]},
+                {"attributePatterns": ["*_en"], "locales": ["eng"]},
+            ]
+        }))
+        .await;
+    index.wait_task(response.uid()).await;
+
+    let (response, _) = index.settings().await;
+    snapshot!(json_string!(response["localizedAttributes"]), @r###"
+    [
+      {
+        "attributePatterns": [
+          "*_ja"
+        ],
+        "locales": [
+          "jpn"
+        ]
+      },
+      {
+        "attributePatterns": [
+          "*_en"
+        ],
+        "locales": [
+          "eng"
+        ]
+      }
+    ]
+    "###);
+
+    let (response, _) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(response.uid()).await;
+
+    index
+        .search(
+            json!({"q": "東京", "locales": ["jpn"], "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 853
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({"q": "station", "locales": ["eng"], "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(response["hits"].as_array().unwrap().len(), @"2");
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn reset_localized_attributes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings_localized_attributes(
+            json!([{"attributePatterns": ["*_ja"], "locales": ["jpn"]}]),
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await;
+
+    let (response, _) = index.update_settings(json!({ "localizedAttributes": null })).await;
+    index.wait_task(response.uid()).await;
+
+    let (response, _) = index.settings().await;
+    snapshot!(json_string!(response["localizedAttributes"]), @"null");
+}
+
+#[actix_rt::test]
+async fn search_bad_locales() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"q": "doggo", "locales": ["doggo"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.locales[0]`: Unsupported locale `doggo`, expected one of `epo`, `eng`, `rus`, `cmn`, `spa`, `por`, `ita`, `ben`, `fra`, `deu`, `ukr`, `kat`, `ara`, `hin`, `jpn`, `heb`, `yid`, `pol`, `amh`, `jav`, `kor`, `nob`, `dan`, `swe`, `fin`, `tur`, `nld`, `hun`, `ces`, `ell`, `bul`, `bel`, `mar`, `kan`, `ron`, `slv`, `hrv`, `srp`, `mkd`, `lit`, `lav`, `est`, `tam`, `vie`, `urd`, `tha`, `guj`, `uzb`, `pan`, `aze`, `ind`, `tel`, `pes`, `mal`, `ori`, `mya`, `nep`, `sin`, `khm`, `tuk`, `aka`, `zul`, `sna`, `afr`, `lat`, `slk`, `cat`, `tgl`, `hye`",
+      "code": "invalid_search_locales",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_locales"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"q": "doggo", "locales": "jpn"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.locales`: expected an array, but found a string: `\"jpn\"`",
+      "code": "invalid_search_locales",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_locales"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_localized_attributes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings(json!({
+            "localizedAttributes": [{"attributePatterns": ["*_ja"], "locales": ["doggo"]}]
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.localizedAttributes[0].locales[0]`: Unsupported locale `doggo`, expected one of `epo`, `eng`, `rus`, `cmn`, `spa`, `por`, `ita`, `ben`, `fra`, `deu`, `ukr`, `kat`, `ara`, `hin`, `jpn`, `heb`, `yid`, `pol`, `amh`, `jav`, `kor`, `nob`, `dan`, `swe`, `fin`, `tur`, `nld`, `hun`, `ces`, `ell`, `bul`, `bel`, `mar`, `kan`, `ron`, `slv`, `hrv`, `srp`, `mkd`, `lit`, `lav`, `est`, `tam`, `vie`, `urd`, `tha`, `guj`, `uzb`, `pan`, `aze`, `ind`, `tel`, `pes`, `mal`, `ori`, `mya`, `nep`, `sin`, `khm`, `tuk`, `aka`, `zul`, `sna`, `afr`, `lat`, `slk`, `cat`, `tgl`, `hye`",
+      "code": "invalid_settings_localized_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_localized_attributes"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings_localized_attributes(json!([{"attributePatterns": ["*_ja"], "doggo": []}]))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown field `doggo` inside `[0]`: expected one of `attributePatterns`, `locales`",
+      "code": "invalid_settings_localized_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_localized_attributes"
+    }
+    "###);
+}
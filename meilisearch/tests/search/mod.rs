@@ -1,17 +1,23 @@
 // This modules contains all the test concerning search. Each particular feature of the search
 // should be tested in its own module to isolate tests and keep the tests readable.
 
+mod boost;
 mod distinct;
 mod errors;
 mod facet_search;
 mod formatted;
 mod geo;
 mod hybrid;
+mod locales;
 mod matching_strategy;
 mod multi;
 mod pagination;
+mod ranking_rules;
 mod restrict_searchable;
+mod rules;
+mod search_cache;
 mod search_queue;
+mod suggest;
 
 use once_cell::sync::Lazy;
 
@@ -611,6 +617,123 @@ async fn search_with_multiple_sort() {
     assert_eq!(response["hits"].as_array().unwrap().len(), 5);
 }
 
+#[actix_rt::test]
+async fn search_facet_stats_aggregations() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({"filterableAttributes": ["price", "title"]})).await;
+
+    let documents = json!([
+        { "id": 0, "title": "shirt", "price": 12.5 },
+        { "id": 1, "title": "shirt", "price": 30 },
+        { "id": 2, "title": "pants", "price": 55 },
+        { "id": 3, "title": "pants", "price": 80 },
+        { "id": 4, "title": "coat", "price": [120, 150] },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "facets": ["price"],
+            "facetHistograms": { "price": { "interval": 50 } }
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["facetStats"]), @r###"
+    {
+      "price": {
+        "min": 12.5,
+        "max": 150.0,
+        "sum": 447.5,
+        "avg": 74.58333333333333,
+        "histogram": [
+          {
+            "from": 0.0,
+            "to": 50.0,
+            "count": 2
+          },
+          {
+            "from": 50.0,
+            "to": 100.0,
+            "count": 2
+          },
+          {
+            "from": 100.0,
+            "to": 150.0,
+            "count": 1
+          },
+          {
+            "from": 150.0,
+            "to": 200.0,
+            "count": 1
+          }
+        ]
+      }
+    }
+    "###);
+
+    // The stats are restricted to the documents matching the query
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "title = pants OR title = coat",
+            "facets": ["price"],
+            "facetHistograms": { "price": { "ranges": [{ "from": 0, "to": 100 }, { "from": 100, "to": 1000 }] } }
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["facetStats"]), @r###"
+    {
+      "price": {
+        "min": 55.0,
+        "max": 150.0,
+        "sum": 405.0,
+        "avg": 101.25,
+        "histogram": [
+          {
+            "from": 0.0,
+            "to": 100.0,
+            "count": 2
+          },
+          {
+            "from": 100.0,
+            "to": 1000.0,
+            "count": 2
+          }
+        ]
+      }
+    }
+    "###);
+
+    // Without histograms, the sum and average are only computed when requested
+    let (response, code) = index.search_post(json!({ "q": "shirt", "facets": ["price"] })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["facetStats"]), @r###"
+    {
+      "price": {
+        "min": 12.5,
+        "max": 30.0
+      }
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({ "q": "shirt", "facets": ["price"], "facetAggregations": true }))
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["facetStats"]), @r###"
+    {
+      "price": {
+        "min": 12.5,
+        "max": 30.0,
+        "sum": 42.5,
+        "avg": 21.25
+      }
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_facet_distribution() {
     let server = Server::new().await;
@@ -985,6 +1108,84 @@ async fn test_score_details() {
         .await;
 }
 
+#[actix_rt::test]
+async fn test_score_details_with_split_attribute_ranking_rule() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, _) = index
+        .update_settings(json!({
+            "rankingRules": ["words", "typo", "proximity", "attributeRank", "wordPosition", "exactness"],
+        }))
+        .await;
+    index.wait_task(response.uid()).await;
+
+    let documents = DOCUMENTS.clone();
+
+    let res = index.add_documents(json!(documents), None).await;
+    index.wait_task(res.0.uid()).await;
+
+    index
+        .search(
+            json!({
+                "q": "train dragon",
+                "showRankingScoreDetails": true,
+            }),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "title": "How to Train Your Dragon: The Hidden World",
+                    "id": "166428",
+                    "_vectors": {
+                      "manual": [
+                        -100.0,
+                        231.0,
+                        32.0
+                      ]
+                    },
+                    "_rankingScoreDetails": {
+                      "words": {
+                        "order": 0,
+                        "matchingWords": 2,
+                        "maxMatchingWords": 2,
+                        "score": 1.0
+                      },
+                      "typo": {
+                        "order": 1,
+                        "typoCount": 0,
+                        "maxTypoCount": 2,
+                        "score": 1.0
+                      },
+                      "proximity": {
+                        "order": 2,
+                        "score": 0.75
+                      },
+                      "attributeRank": {
+                        "order": 3,
+                        "score": 1.0
+                      },
+                      "wordPosition": {
+                        "order": 4,
+                        "score": 0.8095238095238095
+                      },
+                      "exactness": {
+                        "order": 5,
+                        "matchType": "noExactMatch",
+                        "matchingWords": 2,
+                        "maxMatchingWords": 2,
+                        "score": 0.3333333333333333
+                      }
+                    }
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
 #[actix_rt::test]
 async fn test_score() {
     let server = Server::new().await;
@@ -376,3 +376,177 @@ async fn search_multiple_query_errors() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn federation_two_indexes() {
+    let server = Server::new().await;
+
+    let index = server.index("test");
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let index = server.index("nested");
+    let documents = NESTED_DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass"},
+        {"indexUid": "nested", "q": "pésti"},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["limit"]), @"20");
+    snapshot!(json_string!(response["offset"]), @"0");
+    snapshot!(json_string!(response["estimatedTotalHits"]), @"3");
+
+    let hits = response["hits"].as_array().unwrap();
+    assert_eq!(hits.len(), 3);
+    let mut sources: Vec<_> = hits
+        .iter()
+        .map(|hit| {
+            let federation = &hit["_federation"];
+            (
+                federation["indexUid"].as_str().unwrap().to_string(),
+                federation["queriesPosition"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    sources.sort();
+    assert_eq!(
+        sources,
+        vec![("nested".to_string(), 1), ("nested".to_string(), 1), ("test".to_string(), 0)]
+    );
+    // hits are sorted by decreasing weighted ranking score
+    let scores: Vec<f64> = hits
+        .iter()
+        .map(|hit| hit["_federation"]["weightedRankingScore"].as_f64().unwrap())
+        .collect();
+    assert!(scores.windows(2).all(|w| w[0] >= w[1]), "{scores:?}");
+}
+
+#[actix_rt::test]
+async fn federation_weight_and_pagination() {
+    let server = Server::new().await;
+
+    let index = server.index("test");
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    // the document matched by both queries is only returned once, with its best weighted score
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass", "federationOptions": {"weight": 0.0}},
+        {"indexUid": "test", "q": "glass"},
+        {"indexUid": "test", "q": "captain", "federationOptions": {"weight": 0.5}},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    let sources: Vec<_> = response["hits"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| json!([hit["id"], hit["_federation"]["queriesPosition"]]))
+        .collect();
+    snapshot!(json_string!(sources), @r###"
+    [
+      [
+        "450465",
+        1
+      ],
+      [
+        "299537",
+        2
+      ]
+    ]
+    "###);
+    snapshot!(json_string!(response["estimatedTotalHits"]), @"2");
+
+    // the duplicates are removed before paginating
+    let (response, code) = server
+        .multi_search(json!({"federation": {"offset": 1, "limit": 1}, "queries": [
+        {"indexUid" : "test", "q": "glass", "federationOptions": {"weight": 0.0}},
+        {"indexUid": "test", "q": "glass"},
+        {"indexUid": "test", "q": "captain", "federationOptions": {"weight": 0.5}},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"][0]["id"]), @r###""299537""###);
+    snapshot!(json_string!(response["hits"][0]["_federation"]["queriesPosition"]), @"2");
+    snapshot!(json_string!(response["hits"].as_array().unwrap().len()), @"1");
+}
+
+#[actix_rt::test]
+async fn federation_errors() {
+    let server = Server::new().await;
+
+    let index = server.index("test");
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = server
+        .multi_search(json!({"queries": [
+        {"indexUid" : "test", "q": "glass", "federationOptions": {"weight": 2.0}},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Inside `.queries[0]`: Using `federationOptions` is not allowed in a non-federated search.\n Hint: remove `federationOptions` from the query or add `federation` to the request",
+      "code": "invalid_multi_search_federation_options",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_federation_options"
+    }
+    "###);
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass"},
+        {"indexUid" : "test", "q": "glass", "limit": 5},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Inside `.queries[1]`: Using pagination options is not allowed in federated queries.\n Hint: remove `limit` from the query or remove `federation` from the request",
+      "code": "invalid_multi_search_query_pagination",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_query_pagination"
+    }
+    "###);
+
+    // the pagination options are rejected even when they are set to their default values
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass", "offset": 0},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Inside `.queries[0]`: Using pagination options is not allowed in federated queries.\n Hint: remove `offset` from the query or remove `federation` from the request",
+      "code": "invalid_multi_search_query_pagination",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_query_pagination"
+    }
+    "###);
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass", "federationOptions": {"weight": -1.0}},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.queries[0].federationOptions.weight`: the value of `weight` is invalid, expected a positive float (>= 0.0).",
+      "code": "invalid_multi_search_weight",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_weight"
+    }
+    "###);
+}
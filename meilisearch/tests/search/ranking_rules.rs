@@ -0,0 +1,160 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        {
+            "id": 1,
+            "title": "Pride and Prejudice",
+            "price": 30,
+        },
+        {
+            "id": 2,
+            "title": "Le Petit Prince",
+            "price": 20,
+        },
+        {
+            "id": 3,
+            "title": "Hamlet",
+            "price": 10,
+        },
+    ])
+});
+
+#[actix_rt::test]
+async fn search_with_ranking_rules_override() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, _) = index
+        .update_settings(json!({
+            "sortableAttributes": ["price"],
+            "rankingRules": ["price:asc"],
+        }))
+        .await;
+    index.wait_task(response.uid()).await;
+
+    let (response, _) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = index
+        .search_post(json!({"rankingRules": ["price:desc"], "attributesToRetrieve": ["id"]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The ranking rules of this index cannot be overridden at search time. Set the `allowRankingRulesOverride` setting to `true` to use the `rankingRules` search parameter.",
+      "code": "invalid_search_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_rules"
+    }
+    "###);
+
+    let (response, _) = index.update_settings(json!({ "allowRankingRulesOverride": true })).await;
+    index.wait_task(response.uid()).await;
+
+    index
+        .search(json!({"attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 3
+              },
+              {
+                "id": 2
+              },
+              {
+                "id": 1
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    index
+        .search(
+            json!({
+                "rankingRules": ["price:desc"],
+                "attributesToRetrieve": ["id"],
+                "showRankingScoreDetails": true,
+            }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1,
+                    "_rankingScoreDetails": {
+                      "price:desc": {
+                        "order": 0,
+                        "value": 30.0
+                      }
+                    }
+                  },
+                  {
+                    "id": 2,
+                    "_rankingScoreDetails": {
+                      "price:desc": {
+                        "order": 0,
+                        "value": 20.0
+                      }
+                    }
+                  },
+                  {
+                    "id": 3,
+                    "_rankingScoreDetails": {
+                      "price:desc": {
+                        "order": 0,
+                        "value": 10.0
+                      }
+                    }
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_invalid_ranking_rules() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, _) = index
+        .update_settings(json!({
+            "sortableAttributes": ["price"],
+            "allowRankingRulesOverride": true,
+        }))
+        .await;
+    index.wait_task(response.uid()).await;
+
+    let (response, _) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = index.search_post(json!({"rankingRules": ["title:asc"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `title` is not sortable and thus, cannot be used in the `rankingRules` search parameter. Available sortable attributes are: `price`.",
+      "code": "invalid_search_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_rules"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"rankingRules": ["doggo"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.rankingRules[0]`: `doggo` ranking rule is invalid. Valid ranking rules are words, typo, sort, proximity, attribute, attributeRank, wordPosition, exactness, boost and custom ranking rules.",
+      "code": "invalid_search_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_rules"
+    }
+    "###);
+}
@@ -0,0 +1,597 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::index::Index;
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "title": "phone", "brand": "A", "inStock": true },
+        { "id": 2, "title": "phone case", "brand": "B", "inStock": true },
+        { "id": 3, "title": "phone charger", "brand": "A", "inStock": false },
+        { "id": 4, "title": "phone stand", "brand": "B", "inStock": true },
+        { "id": 5, "title": "laptop", "brand": "A", "inStock": true },
+    ])
+});
+
+async fn index_with_documents(server: &Server) -> Index<'_> {
+    let index = server.index("test");
+
+    let (response, _) = index
+        .update_settings(json!({
+            "filterableAttributes": ["brand", "inStock"],
+            "rankingRules": ["words"],
+        }))
+        .await;
+    index.wait_task(response.uid()).await;
+
+    let (response, _) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(response.uid()).await;
+    index
+}
+
+#[actix_rt::test]
+async fn crud_rules() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server).await;
+
+    let (response, code) = index.rules().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": []
+    }
+    "###);
+
+    let (response, _) = index.get().await;
+    let updated_at = response["updatedAt"].clone();
+
+    let (response, code) = index
+        .put_rule(
+            "promo",
+            json!({
+                "condition": { "query": "phone", "match": "contains" },
+                "actions": { "pin": [{ "id": "5", "position": 0 }], "hide": ["2"] },
+            }),
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{response}");
+
+    let (response, code) = index.get_rule("promo").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "uid": "promo",
+      "condition": {
+        "query": "phone",
+        "match": "contains",
+        "filter": null
+      },
+      "actions": {
+        "pin": [
+          {
+            "id": "5",
+            "position": 0
+          }
+        ],
+        "hide": [
+          "2"
+        ],
+        "filter": null
+      }
+    }
+    "###);
+
+    // updating the rules is a write to the index
+    let (response, _) = index.get().await;
+    assert_ne!(response["updatedAt"], updated_at);
+
+    let (response, code) =
+        index.put_rule("laptops", json!({ "condition": { "query": "laptop" } })).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{response}");
+
+    let (response, code) = index.get_rule("laptops").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["condition"]), @r###"
+    {
+      "query": "laptop",
+      "match": "exact",
+      "filter": null
+    }
+    "###);
+
+    let (response, code) = index.rules().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"].as_array().unwrap().iter().map(|rule| rule["uid"].clone()).collect::<Vec<_>>()), @r###"
+    [
+      "laptops",
+      "promo"
+    ]
+    "###);
+
+    // the rules are part of the settings, and thus of the dumps
+    let (response, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["merchandisingRules"]), @r###"
+    {
+      "laptops": {
+        "condition": {
+          "query": "laptop",
+          "match": "exact",
+          "filter": null
+        },
+        "actions": {
+          "pin": [],
+          "hide": [],
+          "filter": null
+        }
+      },
+      "promo": {
+        "condition": {
+          "query": "phone",
+          "match": "contains",
+          "filter": null
+        },
+        "actions": {
+          "pin": [
+            {
+              "id": "5",
+              "position": 0
+            }
+          ],
+          "hide": [
+            "2"
+          ],
+          "filter": null
+        }
+      }
+    }
+    "###);
+
+    let (response, code) = index.delete_rule("laptops").await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{response}");
+
+    let (response, code) = index.get_rule("laptops").await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Rule `laptops` not found.",
+      "code": "rule_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#rule_not_found"
+    }
+    "###);
+
+    // deleting a missing rule is a no-op
+    let (response, code) = index.delete_rule("laptops").await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{response}");
+
+    let (response, code) = index.rules().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"].as_array().unwrap().iter().map(|rule| rule["uid"].clone()).collect::<Vec<_>>()), @r###"
+    [
+      "promo"
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn rules_in_settings() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server).await;
+
+    let (response, code) = index
+        .update_settings(json!({
+            "merchandisingRules": {
+                "promo": { "condition": { "query": "phone" }, "actions": { "hide": ["2"] } },
+                "laptops": { "condition": { "query": "laptop" } },
+            }
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await;
+
+    // the rules missing from the update are kept, and the ones set to null are deleted
+    let (response, code) = index
+        .update_settings(json!({
+            "merchandisingRules": {
+                "laptops": null,
+                "sales": { "condition": { "query": "sales" } },
+            }
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = index.rules().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"].as_array().unwrap().iter().map(|rule| rule["uid"].clone()).collect::<Vec<_>>()), @r###"
+    [
+      "promo",
+      "sales"
+    ]
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({
+            "merchandisingRules": { "promo": { "condition": { "query": "phone(", "match": "regex" } } }
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response["code"]), @r###""invalid_rule_condition""###);
+
+    let (response, code) = index.delete_settings().await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = index.rules().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": []
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn invalid_rules() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server).await;
+
+    let (response, code) =
+        index.put_rule("not valid", json!({ "condition": { "query": "phone" } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`not valid` is not a valid rule uid. Rule uid can be an integer or a string containing only alphanumeric characters, hyphens (-) and underscores (_), and can not be more than 400 bytes.",
+      "code": "invalid_rule_uid",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_rule_uid"
+    }
+    "###);
+
+    let (response, code) = index
+        .put_rule("promo", json!({ "condition": { "query": "phone", "match": "fuzzy" } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `fuzzy` at `.condition.match`: expected one of `exact`, `contains`, `regex`",
+      "code": "invalid_rule_condition",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_rule_condition"
+    }
+    "###);
+
+    let (response, code) = index
+        .put_rule("promo", json!({ "condition": { "query": "phone(", "match": "regex" } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response["code"]), @r###""invalid_rule_condition""###);
+
+    let (response, code) = index
+        .put_rule(
+            "promo",
+            json!({
+                "condition": { "query": "phone" },
+                "actions": { "pin": [{ "id": "5" }] },
+            }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `position` inside `.actions.pin[0]`",
+      "code": "invalid_rule_actions",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_rule_actions"
+    }
+    "###);
+
+    let (response, code) = index
+        .put_rule(
+            "promo",
+            json!({
+                "condition": { "query": "phone" },
+                "actions": { "filter": "brand =" },
+            }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response["code"]), @r###""invalid_rule_actions""###);
+
+    let (response, code) = index.rules().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": []
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_with_rules() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server).await;
+
+    let (response, code) = index
+        .put_rule(
+            "promo",
+            json!({
+                "condition": { "query": "phone", "match": "contains" },
+                "actions": { "pin": [{ "id": "5", "position": 0 }], "hide": ["2"] },
+            }),
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await;
+
+    let (response, code) =
+        index.search_post(json!({ "q": "phone", "attributesToRetrieve": ["id"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 5
+      },
+      {
+        "id": 1
+      },
+      {
+        "id": 3
+      },
+      {
+        "id": 4
+      }
+    ]
+    "###);
+    snapshot!(json_string!(response["appliedRules"]), @r###"
+    [
+      "promo"
+    ]
+    "###);
+    snapshot!(response["estimatedTotalHits"], @"4");
+
+    // the pinned document keeps its position across pages
+    let (response, code) = index
+        .search_post(
+            json!({ "q": "cheap phone", "offset": 1, "limit": 2, "attributesToRetrieve": ["id"] }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1
+      },
+      {
+        "id": 3
+      }
+    ]
+    "###);
+
+    // the rule does not apply to other queries
+    let (response, code) =
+        index.search_post(json!({ "q": "laptop", "attributesToRetrieve": ["id"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 5
+      }
+    ]
+    "###);
+    snapshot!(response["appliedRules"], @"null");
+}
+
+#[actix_rt::test]
+async fn search_with_documents_pinned_past_the_results() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server).await;
+
+    let (response, code) = index
+        .put_rule(
+            "far",
+            json!({
+                "condition": { "query": "laptop" },
+                "actions": { "pin": [{ "id": "1", "position": 10 }, { "id": "2", "position": 20 }] },
+            }),
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await;
+
+    // the documents pinned past the results come right after them
+    let (response, code) =
+        index.search_post(json!({ "q": "laptop", "attributesToRetrieve": ["id"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 5
+      },
+      {
+        "id": 1
+      },
+      {
+        "id": 2
+      }
+    ]
+    "###);
+
+    // and each of them is returned on a single page
+    let (response, code) = index
+        .search_post(json!({ "q": "laptop", "hitsPerPage": 2, "attributesToRetrieve": ["id"] }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 5
+      },
+      {
+        "id": 1
+      }
+    ]
+    "###);
+    snapshot!(response["totalHits"], @"3");
+
+    let (response, code) = index
+        .search_post(
+            json!({ "q": "laptop", "hitsPerPage": 2, "page": 2, "attributesToRetrieve": ["id"] }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 2
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(
+            json!({ "q": "laptop", "hitsPerPage": 2, "page": 3, "attributesToRetrieve": ["id"] }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @"[]");
+}
+
+#[actix_rt::test]
+async fn search_with_rule_filters() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server).await;
+
+    let (response, code) = index
+        .put_rule(
+            "in-stock",
+            json!({
+                "condition": { "query": "phone", "filter": "brand = A" },
+                "actions": { "pin": [{ "id": "5", "position": 0 }], "filter": "inStock = true" },
+            }),
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await;
+
+    // the filter context of the rule is not part of the filter of the search
+    let (response, code) =
+        index.search_post(json!({ "q": "phone", "attributesToRetrieve": ["id"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1
+      },
+      {
+        "id": 2
+      },
+      {
+        "id": 3
+      },
+      {
+        "id": 4
+      }
+    ]
+    "###);
+    snapshot!(response["appliedRules"], @"null");
+
+    let (response, code) = index
+        .search_post(
+            json!({ "q": "Phone", "filter": ["brand = A"], "attributesToRetrieve": ["id"] }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 5
+      },
+      {
+        "id": 1
+      }
+    ]
+    "###);
+    snapshot!(json_string!(response["appliedRules"]), @r###"
+    [
+      "in-stock"
+    ]
+    "###);
+
+    // the rule is ignored once its filter can't be evaluated anymore
+    let (response, code) =
+        index.update_settings(json!({ "filterableAttributes": ["brand"] })).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = index
+        .search_post(
+            json!({ "q": "Phone", "filter": ["brand = A"], "attributesToRetrieve": ["id"] }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1
+      },
+      {
+        "id": 3
+      }
+    ]
+    "###);
+    snapshot!(response["appliedRules"], @"null");
+}
+
+#[actix_rt::test]
+async fn federated_search_with_rules() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server).await;
+
+    let (response, code) = index
+        .put_rule(
+            "promo",
+            json!({
+                "condition": { "query": "phone", "match": "contains" },
+                "actions": { "pin": [{ "id": "5", "position": 0 }], "hide": ["2"] },
+            }),
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = server
+        .multi_search(json!({ "federation": {}, "queries": [
+            { "indexUid": "test", "q": "phone", "attributesToRetrieve": ["id"] },
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    snapshot!(json_string!(ids), @r###"
+    [
+      5,
+      1,
+      3,
+      4
+    ]
+    "###);
+    snapshot!(json_string!(response["appliedRules"]), @r###"
+    {
+      "test": [
+        "promo"
+      ]
+    }
+    "###);
+    snapshot!(response["estimatedTotalHits"], @"4");
+}
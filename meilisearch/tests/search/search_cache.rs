@@ -0,0 +1,166 @@
+use actix_http::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::test;
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
+use tempfile::TempDir;
+
+use crate::common::{default_settings, Server, Value};
+use crate::json;
+
+/// Performs the search on the given app and returns the response along with its cache header.
+async fn search<S, B>(app: &S, request: test::TestRequest) -> (Value, Option<String>)
+where
+    S: Service<actix_http::Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let res = test::call_service(app, request.to_request()).await;
+    assert!(res.status().is_success(), "the search failed with the status {}", res.status());
+    let cache =
+        res.headers().get("x-meilisearch-cache").map(|value| value.to_str().unwrap().to_string());
+    let body = test::read_body(res).await;
+    let mut response: Value = serde_json::from_slice(&body).unwrap();
+    response["processingTimeMs"] = serde_json::json!("[time]");
+    (response, cache)
+}
+
+fn post_search(query: Value) -> test::TestRequest {
+    test::TestRequest::post().uri("/indexes/test/search").set_json(query)
+}
+
+#[actix_rt::test]
+async fn search_cache_hit_and_miss() {
+    let dir = TempDir::new().unwrap();
+    let options = Opt { experimental_search_cache_size: 10, ..default_settings(dir.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+
+    let (task, _code) = index
+        .add_documents(
+            json!([{ "id": 1, "title": "Shazam!" }, { "id": 2, "title": "Glass" }]),
+            None,
+        )
+        .await;
+    index.wait_task(task.uid()).await;
+
+    let app = server.init_web_app().await;
+
+    let (response, cache) = search(&app, post_search(json!({ "q": "glass" }))).await;
+    snapshot!(format!("{cache:?}"), @r###"Some("MISS")"###);
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 2,
+        "title": "Glass"
+      }
+    ]
+    "###);
+    let first_response = response;
+
+    let (response, cache) = search(&app, post_search(json!({ "q": "glass" }))).await;
+    snapshot!(format!("{cache:?}"), @r###"Some("HIT")"###);
+    assert_eq!(response, first_response);
+
+    // The same query made on the GET route is answered from the cache too
+    let request = test::TestRequest::get().uri("/indexes/test/search?q=glass");
+    let (response, cache) = search(&app, request).await;
+    snapshot!(format!("{cache:?}"), @r###"Some("HIT")"###);
+    assert_eq!(response, first_response);
+
+    // Any other parameter makes it a different query
+    let (_response, cache) =
+        search(&app, post_search(json!({ "q": "glass", "showRankingScore": true }))).await;
+    snapshot!(format!("{cache:?}"), @r###"Some("MISS")"###);
+
+    // Modifying the index invalidates the cached results
+    let (task, _code) =
+        index.add_documents(json!([{ "id": 3, "title": "Glass Onion" }]), None).await;
+    index.wait_task(task.uid()).await;
+
+    let (response, cache) = search(&app, post_search(json!({ "q": "glass" }))).await;
+    snapshot!(format!("{cache:?}"), @r###"Some("MISS")"###);
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 2,
+        "title": "Glass"
+      },
+      {
+        "id": 3,
+        "title": "Glass Onion"
+      }
+    ]
+    "###);
+
+    let (_response, cache) = search(&app, post_search(json!({ "q": "glass" }))).await;
+    snapshot!(format!("{cache:?}"), @r###"Some("HIT")"###);
+}
+
+#[actix_rt::test]
+async fn search_cache_expires() {
+    let dir = TempDir::new().unwrap();
+    let options = Opt {
+        experimental_search_cache_size: 10,
+        experimental_search_cache_ttl: 0,
+        ..default_settings(dir.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+
+    let (task, _code) = index.add_documents(json!([{ "id": 1, "title": "Glass" }]), None).await;
+    index.wait_task(task.uid()).await;
+
+    let app = server.init_web_app().await;
+
+    let (_response, cache) = search(&app, post_search(json!({ "q": "glass" }))).await;
+    snapshot!(format!("{cache:?}"), @r###"Some("MISS")"###);
+
+    std::thread::sleep(std::time::Duration::from_millis(10));
+
+    let (_response, cache) = search(&app, post_search(json!({ "q": "glass" }))).await;
+    snapshot!(format!("{cache:?}"), @r###"Some("MISS")"###);
+}
+
+#[actix_rt::test]
+async fn search_cache_evicts_the_least_recently_used() {
+    let dir = TempDir::new().unwrap();
+    let options = Opt { experimental_search_cache_size: 2, ..default_settings(dir.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+
+    let (task, _code) = index.add_documents(json!([{ "id": 1, "title": "Glass" }]), None).await;
+    index.wait_task(task.uid()).await;
+
+    let app = server.init_web_app().await;
+
+    let (_response, cache) = search(&app, post_search(json!({ "q": "glass" }))).await;
+    snapshot!(format!("{cache:?}"), @r###"Some("MISS")"###);
+    let (_response, cache) = search(&app, post_search(json!({ "q": "split" }))).await;
+    snapshot!(format!("{cache:?}"), @r###"Some("MISS")"###);
+    let (_response, cache) = search(&app, post_search(json!({ "q": "glass" }))).await;
+    snapshot!(format!("{cache:?}"), @r###"Some("HIT")"###);
+
+    // The cache is full, "split" is the least recently used result
+    let (_response, cache) = search(&app, post_search(json!({ "q": "unbreakable" }))).await;
+    snapshot!(format!("{cache:?}"), @r###"Some("MISS")"###);
+    let (_response, cache) = search(&app, post_search(json!({ "q": "glass" }))).await;
+    snapshot!(format!("{cache:?}"), @r###"Some("HIT")"###);
+    let (_response, cache) = search(&app, post_search(json!({ "q": "split" }))).await;
+    snapshot!(format!("{cache:?}"), @r###"Some("MISS")"###);
+}
+
+#[actix_rt::test]
+async fn search_cache_disabled_by_default() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _code) = index.add_documents(json!([{ "id": 1, "title": "Glass" }]), None).await;
+    index.wait_task(task.uid()).await;
+
+    let app = server.init_web_app().await;
+
+    let (_response, cache) = search(&app, post_search(json!({ "q": "glass" }))).await;
+    snapshot!(format!("{cache:?}"), @"None");
+    let (_response, cache) = search(&app, post_search(json!({ "q": "glass" }))).await;
+    snapshot!(format!("{cache:?}"), @"None");
+}
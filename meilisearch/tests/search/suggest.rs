@@ -0,0 +1,226 @@
+use meili_snap::{json_string, snapshot};
+use meilisearch::query_log::QueryLog;
+use meilisearch::Opt;
+use once_cell::sync::Lazy;
+use tempfile::TempDir;
+
+use crate::common::index::Index;
+use crate::common::{default_settings, Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 0, "title": "red shirt" },
+        { "id": 1, "title": "blue shirt" },
+        { "id": 2, "title": "shiny shoes" },
+        { "id": 3, "title": "shirt and shoes" },
+        { "id": 4, "title": "short skirt" },
+    ])
+});
+
+async fn index_with_documents<'a>(server: &'a Server, documents: &Value) -> Index<'a> {
+    let index = server.index("test");
+
+    let (task, _code) = index.add_documents(documents.clone(), None).await;
+    index.wait_task(task.uid()).await;
+    index
+}
+
+#[actix_rt::test]
+async fn suggest_completions() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server, &DOCUMENTS).await;
+
+    let (response, code) = index.suggest(json!({ "q": "red sh", "limit": 3 })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".processingTimeMs" => "[time]" }), @r###"
+    {
+      "suggestions": [
+        {
+          "text": "red shirt",
+          "word": "shirt",
+          "typos": 0,
+          "documentCount": 3,
+          "queryCount": 0
+        },
+        {
+          "text": "red shoes",
+          "word": "shoes",
+          "typos": 0,
+          "documentCount": 2,
+          "queryCount": 0
+        },
+        {
+          "text": "red shiny",
+          "word": "shiny",
+          "typos": 0,
+          "documentCount": 1,
+          "queryCount": 0
+        }
+      ],
+      "query": "red sh",
+      "processingTimeMs": "[time]"
+    }
+    "###);
+
+    let (response, code) = index.suggest(json!({ "q": "skurt" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "text": "skirt",
+        "word": "skirt",
+        "typos": 1,
+        "documentCount": 1,
+        "queryCount": 0
+      }
+    ]
+    "###);
+
+    let (response, code) = index.suggest(json!({ "q": "red " })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @"[]");
+}
+
+#[actix_rt::test]
+async fn suggest_with_query_log() {
+    let dir = TempDir::new().unwrap();
+    let options = Opt { experimental_suggest_query_log: true, ..default_settings(dir.path()) };
+    let db_path = options.db_path.clone();
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = index_with_documents(&server, &DOCUMENTS).await;
+
+    for query in ["Shiny shoes", "shiny shoes ", "short", "shiny dragon"] {
+        let (_response, code) = index.search_post(json!({ "q": query })).await;
+        snapshot!(code, @"200 OK");
+    }
+
+    // The queries without any hit are not logged
+    let (response, code) = index.search_post(json!({ "q": "dragon" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @"[]");
+
+    let (response, code) = index.suggest(json!({ "q": "sh" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "text": "shiny",
+        "word": "shiny",
+        "typos": 0,
+        "documentCount": 1,
+        "queryCount": 3
+      },
+      {
+        "text": "shoes",
+        "word": "shoes",
+        "typos": 0,
+        "documentCount": 2,
+        "queryCount": 2
+      },
+      {
+        "text": "short",
+        "word": "short",
+        "typos": 0,
+        "documentCount": 1,
+        "queryCount": 1
+      },
+      {
+        "text": "shirt",
+        "word": "shirt",
+        "typos": 0,
+        "documentCount": 3,
+        "queryCount": 0
+      }
+    ]
+    "###);
+
+    // The log is loaded back from the database directory
+    server.service.query_log.persist().unwrap();
+    let query_log = QueryLog::load(&db_path).unwrap();
+    snapshot!(format!("{:?}", query_log.queries("test")), @r###"Some({"shiny dragon": 1, "shiny shoes": 2, "short": 1})"###);
+}
+
+#[actix_rt::test]
+async fn suggest_without_query_log() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server, &DOCUMENTS).await;
+
+    let (_response, code) = index.search_post(json!({ "q": "shiny" })).await;
+    snapshot!(code, @"200 OK");
+
+    let (response, code) = index.suggest(json!({ "q": "shi", "limit": 1 })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "text": "shirt",
+        "word": "shirt",
+        "typos": 0,
+        "documentCount": 3,
+        "queryCount": 0
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn suggest_bad_parameters() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server, &DOCUMENTS).await;
+
+    let (response, code) = index.suggest(json!({ "limit": 2 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `q`",
+      "code": "missing_suggest_q",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_suggest_q"
+    }
+    "###);
+
+    let (response, code) = index.suggest(json!({ "q": ["shirt"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.q`: expected a string, but found an array: `[\"shirt\"]`",
+      "code": "invalid_suggest_q",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_suggest_q"
+    }
+    "###);
+
+    let (response, code) = index.suggest(json!({ "q": "shirt", "limit": "doggo" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.limit`: expected a positive integer, but found a string: `\"doggo\"`",
+      "code": "invalid_suggest_limit",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_suggest_limit"
+    }
+    "###);
+
+    let (response, code) = index.suggest(json!({ "q": "shirt", "limit": 1001 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.limit`: The `limit` must be lower than or equal to 1000, but found `1001`.",
+      "code": "invalid_suggest_limit",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_suggest_limit"
+    }
+    "###);
+
+    let (response, code) = index.suggest(json!({ "q": "shirt", "doggo": true })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown field `doggo`: expected one of `q`, `limit`",
+      "code": "bad_request",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#bad_request"
+    }
+    "###);
+}
@@ -1,7 +1,10 @@
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
 use std::time::Duration;
 
+use clap::Parser;
 use meili_snap::{json_string, snapshot};
-use meilisearch::option::ScheduleSnapshot;
+use meilisearch::option::{ScheduleSnapshot, UploadOpts};
 use meilisearch::Opt;
 
 use crate::common::server::default_settings;
@@ -155,6 +158,7 @@ async fn perform_on_demand_snapshot() {
       "status": "succeeded",
       "type": "snapshotCreation",
       "canceledBy": null,
+      "batchUid": 4,
       "error": null,
       "duration": "[duration]",
       "enqueuedAt": "[date]",
@@ -202,3 +206,204 @@ async fn perform_on_demand_snapshot() {
         server.index("doggo").settings(),
     );
 }
+
+#[actix_rt::test]
+async fn perform_incremental_snapshots() {
+    let temp = tempfile::tempdir().unwrap();
+    let snapshot_dir = tempfile::tempdir().unwrap();
+
+    let options = Opt {
+        snapshot_dir: snapshot_dir.path().to_owned(),
+        experimental_incremental_snapshots: true,
+        ..default_settings(temp.path())
+    };
+
+    let server = Server::new_with_options(options).await.unwrap();
+
+    for (uid, documents) in [
+        ("catto", json!([{ "id": 1, "name": "Tom" }])),
+        ("doggo", json!([{ "id": 1, "name": "Rex" }])),
+        ("ratto", json!([{ "id": 1, "name": "Remy" }])),
+    ] {
+        let (task, _code) = server.index(uid).add_documents(documents, None).await;
+        server.wait_task(task.uid()).await;
+    }
+
+    let (task, code) = server.create_snapshot().await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    // Only the doggo index is modified, and the ratto index is deleted
+    let (task, _code) =
+        server.index("doggo").add_documents(json!([{ "id": 2, "name": "Snoopy" }]), None).await;
+    server.wait_task(task.uid()).await;
+    let (task, _code) = server.index("ratto").delete().await;
+    server.wait_task(task.uid()).await;
+
+    let (task, code) = server.create_snapshot().await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let mut snapshots: Vec<String> = std::fs::read_dir(&snapshot_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path().file_name().unwrap().to_str().unwrap().to_string())
+        .collect();
+    snapshots.sort();
+    snapshot!(format!("{snapshots:?}"), @r###"["db.0.full.snapshot", "db.1.incremental.snapshot", "db.snapshot-manifest.json"]"###);
+
+    let manifest_path = snapshot_dir.path().join("db.snapshot-manifest.json");
+    let manifest = index_scheduler::SnapshotManifest::load(&manifest_path).unwrap().unwrap();
+    let summary: Vec<_> = manifest
+        .snapshots
+        .iter()
+        .map(|snapshot| {
+            let uids: Vec<_> = snapshot.indexes.values().map(|index| index.uid.as_str()).collect();
+            format!("{:?} {}", snapshot.kind, uids.join(","))
+        })
+        .collect();
+    snapshot!(format!("{summary:#?}"), @r###"
+    [
+        "Full catto,doggo,ratto",
+        "Incremental catto,doggo",
+    ]
+    "###);
+
+    // The unmodified index is not copied again and keeps the hash of its first copy
+    let hashes: Vec<_> = manifest
+        .snapshots
+        .iter()
+        .map(|snapshot| {
+            let catto = snapshot.indexes.values().find(|index| index.uid == "catto").unwrap();
+            catto.sha256.clone().unwrap()
+        })
+        .collect();
+    assert_eq!(hashes[0], hashes[1]);
+
+    // The incremental snapshot only contains the modified index
+    let incremental = tempfile::tempdir().unwrap();
+    meilisearch_types::compression::from_tar_gz(
+        snapshot_dir.path().join("db.1.incremental.snapshot"),
+        incremental.path(),
+    )
+    .unwrap();
+    let indexes: Vec<String> = std::fs::read_dir(incremental.path().join("indexes"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_str().unwrap().to_string())
+        .collect();
+    let doggo = manifest.snapshots[1].indexes.iter().find(|(_, index)| index.uid == "doggo");
+    assert_eq!(indexes, vec![doggo.unwrap().0.to_string()]);
+
+    let temp = tempfile::tempdir().unwrap();
+    let options =
+        Opt { import_snapshot: Some(manifest_path.clone()), ..default_settings(temp.path()) };
+    let snapshot_server = Server::new_with_options(options).await.unwrap();
+
+    verify_snapshot!(server, snapshot_server, |server| =>
+        server.list_indexes(None, None),
+        server.index("catto").get_all_documents(GetAllDocumentsOptions::default()),
+        server.index("catto").settings(),
+        server.index("doggo").get_all_documents(GetAllDocumentsOptions::default()),
+        server.index("doggo").settings(),
+    );
+
+    // A snapshot of the chain that doesn't match its checksum is refused
+    let mut corrupted = manifest.clone();
+    corrupted.snapshots[1].sha256 = "0".repeat(64);
+    let corrupted_path = snapshot_dir.path().join("corrupted.snapshot-manifest.json");
+    std::fs::write(&corrupted_path, serde_json::to_vec(&corrupted).unwrap()).unwrap();
+
+    let temp = tempfile::tempdir().unwrap();
+    let err = index_scheduler::import_snapshot_chain(&corrupted_path, &temp.path().join("db"))
+        .unwrap_err();
+    snapshot!(err.to_string().replace(&manifest.snapshots[1].sha256, "[sha256]"), @"Corrupted snapshot: the snapshot `db.1.incremental.snapshot` has the checksum `[sha256]` instead of `0000000000000000000000000000000000000000000000000000000000000000`");
+}
+
+#[actix_rt::test]
+async fn upload_snapshots_to_local_backend() {
+    let temp = tempfile::tempdir().unwrap();
+    let snapshot_dir = tempfile::tempdir().unwrap();
+    let upload_dir = tempfile::tempdir().unwrap();
+
+    let upload_options = UploadOpts {
+        experimental_upload_dir: Some(upload_dir.path().to_owned()),
+        experimental_upload_retention: NonZeroUsize::new(2),
+        ..Parser::parse_from(None as Option<&str>)
+    };
+    let options = Opt {
+        snapshot_dir: snapshot_dir.path().to_owned(),
+        upload_options: upload_options.clone(),
+        ..default_settings(temp.path())
+    };
+
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+    let (task, _code) = index.add_documents(json!([{ "id": 1, "name": "Tom" }]), None).await;
+    server.wait_task(task.uid()).await;
+
+    for _ in 0..3 {
+        let (task, code) = server.create_snapshot().await;
+        snapshot!(code, @"202 Accepted");
+        let task = server.wait_task(task.uid()).await;
+        snapshot!(task["status"], @r###""succeeded""###);
+    }
+
+    // Only the two most recent snapshots are kept on the backend
+    let mut snapshots: Vec<String> = std::fs::read_dir(upload_dir.path().join("snapshots"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_str().unwrap().to_string())
+        .collect();
+    snapshots.sort();
+    assert_eq!(snapshots.len(), 2);
+    assert!(snapshots.iter().all(|name| name.starts_with("db.") && name.ends_with(".snapshot")));
+
+    // The snapshot missing from the local disk is downloaded from the backend
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt {
+        import_snapshot: Some(PathBuf::from(&snapshots[1])),
+        snapshot_dir: temp.path().join("snapshots"),
+        upload_options,
+        ..default_settings(temp.path())
+    };
+    let snapshot_server = Server::new_with_options(options).await.unwrap();
+
+    verify_snapshot!(server, snapshot_server, |server| =>
+        server.list_indexes(None, None),
+        server.index("test").get_all_documents(GetAllDocumentsOptions::default()),
+        server.index("test").settings(),
+    );
+}
+
+#[actix_rt::test]
+async fn report_the_failed_uploads() {
+    let temp = tempfile::tempdir().unwrap();
+    let snapshot_dir = tempfile::tempdir().unwrap();
+    // the backend directory is a file, nothing can be uploaded into it
+    let upload_dir = tempfile::NamedTempFile::new().unwrap();
+
+    let options = Opt {
+        snapshot_dir: snapshot_dir.path().to_owned(),
+        upload_options: UploadOpts {
+            experimental_upload_dir: Some(upload_dir.path().to_owned()),
+            ..Parser::parse_from(None as Option<&str>)
+        },
+        ..default_settings(temp.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+
+    // the snapshot and the dump are still created locally
+    let (task, code) = server.create_snapshot().await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+    assert!(task["details"]["uploadError"].is_string(), "{task}");
+    assert!(snapshot_dir.path().join("db.snapshot").exists());
+
+    let (task, code) = server.create_dump().await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+    assert!(task["details"]["uploadError"].is_string(), "{task}");
+    assert!(task["details"]["dumpUid"].is_string(), "{task}");
+}
@@ -28,6 +28,7 @@ async fn swap_indexes() {
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
           "canceledBy": null,
+          "batchUid": 1,
           "details": {
             "receivedDocuments": 1,
             "indexedDocuments": 1
@@ -44,6 +45,7 @@ async fn swap_indexes() {
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
           "canceledBy": null,
+          "batchUid": 0,
           "details": {
             "receivedDocuments": 1,
             "indexedDocuments": 1
@@ -80,6 +82,7 @@ async fn swap_indexes() {
           "status": "succeeded",
           "type": "indexSwap",
           "canceledBy": null,
+          "batchUid": 2,
           "details": {
             "swaps": [
               {
@@ -102,6 +105,7 @@ async fn swap_indexes() {
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
           "canceledBy": null,
+          "batchUid": 1,
           "details": {
             "receivedDocuments": 1,
             "indexedDocuments": 1
@@ -118,6 +122,7 @@ async fn swap_indexes() {
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
           "canceledBy": null,
+          "batchUid": 0,
           "details": {
             "receivedDocuments": 1,
             "indexedDocuments": 1
@@ -168,6 +173,7 @@ async fn swap_indexes() {
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
           "canceledBy": null,
+          "batchUid": 4,
           "details": {
             "receivedDocuments": 1,
             "indexedDocuments": 1
@@ -184,6 +190,7 @@ async fn swap_indexes() {
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
           "canceledBy": null,
+          "batchUid": 3,
           "details": {
             "receivedDocuments": 1,
             "indexedDocuments": 1
@@ -230,6 +237,7 @@ async fn swap_indexes() {
           "status": "succeeded",
           "type": "indexSwap",
           "canceledBy": null,
+          "batchUid": 5,
           "details": {
             "swaps": [
               {
@@ -258,6 +266,7 @@ async fn swap_indexes() {
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
           "canceledBy": null,
+          "batchUid": 4,
           "details": {
             "receivedDocuments": 1,
             "indexedDocuments": 1
@@ -274,6 +283,7 @@ async fn swap_indexes() {
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
           "canceledBy": null,
+          "batchUid": 3,
           "details": {
             "receivedDocuments": 1,
             "indexedDocuments": 1
@@ -290,6 +300,7 @@ async fn swap_indexes() {
           "status": "succeeded",
           "type": "indexSwap",
           "canceledBy": null,
+          "batchUid": 2,
           "details": {
             "swaps": [
               {
@@ -312,6 +323,7 @@ async fn swap_indexes() {
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
           "canceledBy": null,
+          "batchUid": 1,
           "details": {
             "receivedDocuments": 1,
             "indexedDocuments": 1
@@ -328,6 +340,7 @@ async fn swap_indexes() {
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
           "canceledBy": null,
+          "batchUid": 0,
           "details": {
             "receivedDocuments": 1,
             "indexedDocuments": 1
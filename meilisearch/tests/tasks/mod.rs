@@ -201,7 +201,7 @@ async fn get_task_filter_error() {
     assert_eq!(code, 400, "{}", response);
     meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
     {
-      "message": "Unknown parameter `lol`: expected one of `limit`, `from`, `uids`, `canceledBy`, `types`, `statuses`, `indexUids`, `afterEnqueuedAt`, `beforeEnqueuedAt`, `afterStartedAt`, `beforeStartedAt`, `afterFinishedAt`, `beforeFinishedAt`",
+      "message": "Unknown parameter `lol`: expected one of `limit`, `from`, `uids`, `batchUids`, `canceledBy`, `types`, `statuses`, `indexUids`, `afterEnqueuedAt`, `beforeEnqueuedAt`, `afterStartedAt`, `beforeStartedAt`, `afterFinishedAt`, `beforeFinishedAt`",
       "code": "bad_request",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#bad_request"
@@ -377,6 +377,7 @@ async fn test_summarized_document_addition_or_update() {
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 0,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 1
@@ -401,6 +402,7 @@ async fn test_summarized_document_addition_or_update() {
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 1,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 1
@@ -430,6 +432,7 @@ async fn test_summarized_delete_documents_by_batch() {
       "status": "failed",
       "type": "documentDeletion",
       "canceledBy": null,
+      "batchUid": 0,
       "details": {
         "providedIds": 3,
         "deletedDocuments": 0,
@@ -461,6 +464,7 @@ async fn test_summarized_delete_documents_by_batch() {
       "status": "succeeded",
       "type": "documentDeletion",
       "canceledBy": null,
+      "batchUid": 2,
       "details": {
         "providedIds": 1,
         "deletedDocuments": 0,
@@ -492,6 +496,7 @@ async fn test_summarized_delete_documents_by_filter() {
       "status": "failed",
       "type": "documentDeletion",
       "canceledBy": null,
+      "batchUid": 0,
       "details": {
         "providedIds": 0,
         "deletedDocuments": 0,
@@ -523,6 +528,7 @@ async fn test_summarized_delete_documents_by_filter() {
       "status": "failed",
       "type": "documentDeletion",
       "canceledBy": null,
+      "batchUid": 2,
       "details": {
         "providedIds": 0,
         "deletedDocuments": 0,
@@ -554,6 +560,7 @@ async fn test_summarized_delete_documents_by_filter() {
       "status": "succeeded",
       "type": "documentDeletion",
       "canceledBy": null,
+      "batchUid": 4,
       "details": {
         "providedIds": 0,
         "deletedDocuments": 0,
@@ -584,6 +591,7 @@ async fn test_summarized_delete_document_by_id() {
       "status": "failed",
       "type": "documentDeletion",
       "canceledBy": null,
+      "batchUid": 0,
       "details": {
         "providedIds": 1,
         "deletedDocuments": 0,
@@ -615,6 +623,7 @@ async fn test_summarized_delete_document_by_id() {
       "status": "succeeded",
       "type": "documentDeletion",
       "canceledBy": null,
+      "batchUid": 2,
       "details": {
         "providedIds": 1,
         "deletedDocuments": 0,
@@ -638,7 +647,7 @@ async fn test_summarized_settings_update() {
     meili_snap::snapshot!(code, @"400 Bad Request");
     meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
     {
-      "message": "Invalid value at `.rankingRules[0]`: `custom` ranking rule is invalid. Valid ranking rules are words, typo, sort, proximity, attribute, exactness and custom ranking rules.",
+      "message": "Invalid value at `.rankingRules[0]`: `custom` ranking rule is invalid. Valid ranking rules are words, typo, sort, proximity, attribute, attributeRank, wordPosition, exactness, boost and custom ranking rules.",
       "code": "invalid_settings_ranking_rules",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_settings_ranking_rules"
@@ -657,6 +666,7 @@ async fn test_summarized_settings_update() {
       "status": "succeeded",
       "type": "settingsUpdate",
       "canceledBy": null,
+      "batchUid": 0,
       "details": {
         "displayedAttributes": [
           "doggos",
@@ -695,6 +705,7 @@ async fn test_summarized_index_creation() {
       "status": "succeeded",
       "type": "indexCreation",
       "canceledBy": null,
+      "batchUid": 0,
       "details": {
         "primaryKey": null
       },
@@ -718,6 +729,7 @@ async fn test_summarized_index_creation() {
       "status": "failed",
       "type": "indexCreation",
       "canceledBy": null,
+      "batchUid": 1,
       "details": {
         "primaryKey": "doggos"
       },
@@ -749,6 +761,7 @@ async fn test_summarized_index_deletion() {
       "status": "failed",
       "type": "indexDeletion",
       "canceledBy": null,
+      "batchUid": 0,
       "details": {
         "deletedDocuments": 0
       },
@@ -779,6 +792,7 @@ async fn test_summarized_index_deletion() {
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 1,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 1
@@ -801,6 +815,7 @@ async fn test_summarized_index_deletion() {
       "status": "succeeded",
       "type": "indexDeletion",
       "canceledBy": null,
+      "batchUid": 2,
       "details": {
         "deletedDocuments": 1
       },
@@ -823,6 +838,7 @@ async fn test_summarized_index_deletion() {
       "status": "failed",
       "type": "indexDeletion",
       "canceledBy": null,
+      "batchUid": 3,
       "details": {
         "deletedDocuments": 0
       },
@@ -857,6 +873,7 @@ async fn test_summarized_index_update() {
       "status": "failed",
       "type": "indexUpdate",
       "canceledBy": null,
+      "batchUid": 0,
       "details": {
         "primaryKey": null
       },
@@ -885,6 +902,7 @@ async fn test_summarized_index_update() {
       "status": "failed",
       "type": "indexUpdate",
       "canceledBy": null,
+      "batchUid": 1,
       "details": {
         "primaryKey": "bones"
       },
@@ -916,6 +934,7 @@ async fn test_summarized_index_update() {
       "status": "succeeded",
       "type": "indexUpdate",
       "canceledBy": null,
+      "batchUid": 3,
       "details": {
         "primaryKey": null
       },
@@ -939,6 +958,7 @@ async fn test_summarized_index_update() {
       "status": "succeeded",
       "type": "indexUpdate",
       "canceledBy": null,
+      "batchUid": 4,
       "details": {
         "primaryKey": "bones"
       },
@@ -970,6 +990,7 @@ async fn test_summarized_index_swap() {
       "status": "failed",
       "type": "indexSwap",
       "canceledBy": null,
+      "batchUid": 0,
       "details": {
         "swaps": [
           {
@@ -1011,6 +1032,7 @@ async fn test_summarized_index_swap() {
       "status": "succeeded",
       "type": "indexSwap",
       "canceledBy": null,
+      "batchUid": 3,
       "details": {
         "swaps": [
           {
@@ -1049,6 +1071,7 @@ async fn test_summarized_task_cancelation() {
       "status": "succeeded",
       "type": "taskCancelation",
       "canceledBy": null,
+      "batchUid": 1,
       "details": {
         "matchedTasks": 1,
         "canceledTasks": 0,
@@ -1082,6 +1105,7 @@ async fn test_summarized_task_deletion() {
       "status": "succeeded",
       "type": "taskDeletion",
       "canceledBy": null,
+      "batchUid": 1,
       "details": {
         "matchedTasks": 1,
         "deletedTasks": 1,
@@ -1111,6 +1135,7 @@ async fn test_summarized_dump_creation() {
       "status": "succeeded",
       "type": "dumpCreation",
       "canceledBy": null,
+      "batchUid": 0,
       "details": {
         "dumpUid": "[dumpUid]"
       },
@@ -98,7 +98,7 @@ async fn test_basic_webhook() {
             nb_tasks += 1;
             let json: serde_json::Value = serde_json::from_str(json).unwrap();
             snapshot!(
-                json_string!(json, { ".uid" => "[uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" }),
+                json_string!(json, { ".uid" => "[uid]", ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" }),
             @r###"
             {
               "uid": "[uid]",
@@ -106,6 +106,7 @@ async fn test_basic_webhook() {
               "status": "succeeded",
               "type": "documentAdditionOrUpdate",
               "canceledBy": null,
+              "batchUid": "[batch_uid]",
               "details": {
                 "receivedDocuments": 1,
                 "indexedDocuments": 1
@@ -1,3 +1,4 @@
+mod binary_quantized;
 mod settings;
 
 use meili_snap::{json_string, snapshot};
@@ -208,6 +209,7 @@ async fn user_provided_embeddings_error() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 2,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -237,6 +239,7 @@ async fn user_provided_embeddings_error() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 3,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -267,6 +270,7 @@ async fn user_provided_embeddings_error() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 4,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -296,6 +300,7 @@ async fn user_provided_embeddings_error() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 5,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -325,6 +330,7 @@ async fn user_provided_embeddings_error() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 6,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -354,6 +360,7 @@ async fn user_provided_embeddings_error() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 7,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -395,6 +402,7 @@ async fn user_provided_embeddings_error() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 10,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -423,6 +431,7 @@ async fn user_provided_embeddings_error() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 11,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -451,6 +460,7 @@ async fn user_provided_embeddings_error() {
       "status": "failed",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": 12,
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 0
@@ -46,6 +46,7 @@ async fn update_embedder() {
       "status": "succeeded",
       "type": "settingsUpdate",
       "canceledBy": null,
+      "batchUid": 1,
       "details": {
         "embedders": {
           "manual": {
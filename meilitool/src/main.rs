@@ -9,7 +9,11 @@ use file_store::FileStore;
 use meilisearch_auth::AuthController;
 use meilisearch_types::heed::types::{SerdeJson, Str};
 use meilisearch_types::heed::{Database, Env, EnvOpenOptions, RoTxn, RwTxn, Unspecified};
+use meilisearch_types::milli::arroy;
+use meilisearch_types::milli::arroy::distances::Cosine;
 use meilisearch_types::milli::documents::{obkv_to_object, DocumentsBatchReader};
+use meilisearch_types::milli::index::{db_name, main_key};
+use meilisearch_types::milli::vector::VECTOR_STORE_VERSION;
 use meilisearch_types::milli::{obkv_to_json, BEU32};
 use meilisearch_types::tasks::{Status, Task};
 use meilisearch_types::versioning::check_version_file;
@@ -62,6 +66,12 @@ enum Command {
         #[arg(long)]
         skip_enqueued_tasks: bool,
     },
+
+    /// Upgrades the vector stores of the indexes written by arroy 0.4 to the format of arroy 0.5.
+    ///
+    /// Make sure to run this command when Meilisearch is not running. The indexes that were
+    /// already upgraded are skipped, so this command can be executed again if it is interrupted.
+    UpgradeVectorStores,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -74,6 +84,7 @@ fn main() -> anyhow::Result<()> {
         Command::ExportADump { dump_dir, skip_enqueued_tasks } => {
             export_a_dump(db_path, dump_dir, skip_enqueued_tasks)
         }
+        Command::UpgradeVectorStores => upgrade_vector_stores(db_path),
     }
 }
 
@@ -95,6 +106,8 @@ fn clear_task_queue(db_path: PathBuf) -> anyhow::Result<()> {
     let enqueued_at = try_opening_poly_database(&env, &wtxn, "enqueued-at")?;
     let started_at = try_opening_poly_database(&env, &wtxn, "started-at")?;
     let finished_at = try_opening_poly_database(&env, &wtxn, "finished-at")?;
+    let all_batches = try_opening_poly_database(&env, &wtxn, "all-batches")?;
+    let batch_to_tasks_mapping = try_opening_poly_database(&env, &wtxn, "batch-to-tasks-mapping")?;
 
     try_clearing_poly_database(&mut wtxn, all_tasks, "all-tasks")?;
     try_clearing_poly_database(&mut wtxn, status, "status")?;
@@ -104,6 +117,8 @@ fn clear_task_queue(db_path: PathBuf) -> anyhow::Result<()> {
     try_clearing_poly_database(&mut wtxn, enqueued_at, "enqueued-at")?;
     try_clearing_poly_database(&mut wtxn, started_at, "started-at")?;
     try_clearing_poly_database(&mut wtxn, finished_at, "finished-at")?;
+    try_clearing_poly_database(&mut wtxn, all_batches, "all-batches")?;
+    try_clearing_poly_database(&mut wtxn, batch_to_tasks_mapping, "batch-to-tasks-mapping")?;
 
     wtxn.commit().context("While committing the transaction")?;
 
@@ -132,6 +147,55 @@ fn clear_task_queue(db_path: PathBuf) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Upgrades the vector stores of the indexes located at `db_path` and marks them as upgraded.
+fn upgrade_vector_stores(db_path: PathBuf) -> anyhow::Result<()> {
+    let index_scheduler_path = db_path.join("tasks");
+    let env = unsafe { EnvOpenOptions::new().max_dbs(100).open(&index_scheduler_path) }
+        .with_context(|| format!("While trying to open {:?}", index_scheduler_path.display()))?;
+
+    let rtxn = env.read_txn()?;
+    let index_mapping: Database<Str, UuidCodec> =
+        try_opening_database(&env, &rtxn, "index-mapping")?;
+
+    let mut count = 0;
+    for result in index_mapping.iter(&rtxn)? {
+        let (uid, uuid) = result?;
+        let index_path = db_path.join("indexes").join(uuid.to_string());
+        let index_env = unsafe { EnvOpenOptions::new().max_dbs(100).open(&index_path) }
+            .with_context(|| format!("While trying to open {:?}", index_path.display()))?;
+
+        let index_rtxn = index_env.read_txn()?;
+        let main: Database<Str, Str> =
+            try_opening_database(&index_env, &index_rtxn, db_name::MAIN)?;
+        if main.get(&index_rtxn, main_key::VECTOR_STORE_VERSION)? == Some(VECTOR_STORE_VERSION) {
+            eprintln!("The vector stores of the index {uid:?} are already upgraded");
+            continue;
+        }
+
+        eprintln!("Upgrading the vector stores of the index {uid:?}...");
+
+        let read_database: arroy::Database<Cosine> =
+            try_opening_database(&index_env, &index_rtxn, db_name::VECTOR_ARROY)?;
+        let mut index_wtxn = index_env.write_txn()?;
+        let write_database: arroy::Database<Cosine> =
+            try_opening_database(&index_env, &index_wtxn, db_name::VECTOR_ARROY)?;
+        arroy::upgrade::cosine_from_0_4_to_0_5(
+            &index_rtxn,
+            read_database,
+            &mut index_wtxn,
+            write_database,
+        )
+        .with_context(|| format!("While upgrading the vector stores of the index {uid:?}"))?;
+        main.put(&mut index_wtxn, main_key::VECTOR_STORE_VERSION, VECTOR_STORE_VERSION)?;
+        index_wtxn.commit().context("While committing the transaction")?;
+        count += 1;
+    }
+
+    eprintln!("Successfully upgraded the vector stores of {count} indexes!");
+
+    Ok(())
+}
+
 fn try_opening_database<KC: 'static, DC: 'static>(
     env: &Env,
     rtxn: &RoTxn,
@@ -282,7 +346,7 @@ fn export_a_dump(
         // 4.1. Dump the documents
         for ret in index.all_documents(&rtxn)? {
             let (_id, doc) = ret?;
-            let document = obkv_to_json(&all_fields, &fields_ids_map, doc)?;
+            let document = obkv_to_json(&all_fields, &fields_ids_map, doc.as_obkv())?;
             index_dumper.push_document(&document)?;
         }
 
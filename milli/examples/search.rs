@@ -68,6 +68,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                 logger,
                 TimeBudget::max(),
                 None,
+                None,
             )?;
             if let Some((logger, dir)) = detailed_logger {
                 logger.finish(&mut ctx, Path::new(dir))?;
@@ -82,7 +83,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                     .map(|(id, obkv)| {
                         let mut object = serde_json::Map::default();
                         for (fid, fid_name) in index.fields_ids_map(&txn).unwrap().iter() {
-                            let value = obkv.get(fid).unwrap();
+                            let value = obkv.as_obkv().get(fid).unwrap();
                             let value: serde_json::Value = serde_json::from_slice(value).unwrap();
                             object.insert(fid_name.to_owned(), value);
                         }
@@ -102,7 +103,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                     .map(|(id, obkv)| {
                         let mut object = serde_json::Map::default();
                         for (fid, fid_name) in index.fields_ids_map(&txn).unwrap().iter() {
-                            let value = obkv.get(fid).unwrap();
+                            let value = obkv.as_obkv().get(fid).unwrap();
                             let value: serde_json::Value = serde_json::from_slice(value).unwrap();
                             object.insert(fid_name.to_owned(), value);
                         }
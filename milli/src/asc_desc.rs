@@ -20,6 +20,8 @@ pub enum AscDescError {
     InvalidSyntax { name: String },
     #[error("`{name}` is a reserved keyword and thus can't be used as a asc/desc rule.")]
     ReservedKeyword { name: String },
+    #[error("Invalid syntax for the geo decay parameter `{name}`: {error}")]
+    BadGeoDecay { name: String, error: String },
 }
 
 impl From<BadGeoError> for AscDescError {
@@ -31,7 +33,7 @@ impl From<BadGeoError> for AscDescError {
 impl From<AscDescError> for CriterionError {
     fn from(error: AscDescError) -> Self {
         match error {
-            AscDescError::GeoError(_) => {
+            AscDescError::GeoError(_) | AscDescError::BadGeoDecay { .. } => {
                 CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() }
             }
             AscDescError::InvalidSyntax { name } => CriterionError::InvalidName { name },
@@ -53,6 +55,8 @@ impl From<AscDescError> for CriterionError {
 pub enum Member {
     Field(String),
     Geo([f64; 2]),
+    /// Sorts the documents by their score, decayed as they get farther from the point.
+    GeoDecay([f64; 2], GeoDecay),
 }
 
 impl FromStr for Member {
@@ -96,7 +100,9 @@ impl fmt::Display for Member {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Member::Field(name) => f.write_str(name),
-            Member::Geo([lat, lng]) => write!(f, "_geoPoint({}, {})", lat, lng),
+            Member::Geo([lat, lng]) | Member::GeoDecay([lat, lng], _) => {
+                write!(f, "_geoPoint({}, {})", lat, lng)
+            }
         }
     }
 }
@@ -105,18 +111,143 @@ impl Member {
     pub fn field(&self) -> Option<&str> {
         match self {
             Member::Field(field) => Some(field),
-            Member::Geo(_) => None,
+            Member::Geo(_) | Member::GeoDecay(..) => None,
         }
     }
 
     pub fn geo_point(&self) -> Option<&[f64; 2]> {
         match self {
-            Member::Geo(point) => Some(point),
+            Member::Geo(point) | Member::GeoDecay(point, _) => Some(point),
             Member::Field(_) => None,
         }
     }
 }
 
+/// The function used to turn the distance to the geo point into a score.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DecayFunction {
+    Gauss,
+    Exp,
+    Linear,
+}
+
+impl fmt::Display for DecayFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecayFunction::Gauss => f.write_str("gauss"),
+            DecayFunction::Exp => f.write_str("exp"),
+            DecayFunction::Linear => f.write_str("linear"),
+        }
+    }
+}
+
+/// Describes how the score of a document decreases as it gets farther from the geo point.
+///
+/// It is written `decay(scale=5km, offset=1km, fn=gauss, decay=0.5)`, only the `scale` is
+/// mandatory and the distances are expressed in meters when they have no unit.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct GeoDecay {
+    pub function: DecayFunction,
+    /// The distance, in meters, from the offset at which the score is equal to `decay`.
+    pub scale: f64,
+    /// The distance, in meters, under which the documents get the maximum score.
+    pub offset: f64,
+    /// The score of the documents at `offset + scale` meters of the geo point.
+    pub decay: f64,
+}
+
+impl GeoDecay {
+    /// Returns the score, between 0 and 1, of a document at `distance` meters of the geo point.
+    pub fn score(&self, distance: f64) -> f64 {
+        let distance = (distance - self.offset).max(0.0) / self.scale;
+        match self.function {
+            DecayFunction::Gauss => self.decay.powf(distance * distance),
+            DecayFunction::Exp => self.decay.powf(distance),
+            DecayFunction::Linear => (1.0 - (1.0 - self.decay) * distance).max(0.0),
+        }
+    }
+}
+
+impl FromStr for GeoDecay {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<GeoDecay, Self::Err> {
+        let Some(parameters) = text.strip_prefix("decay(").and_then(|text| text.strip_suffix(')'))
+        else {
+            return Err(format!("Was expecting `decay(...)` but instead got `{text}`."));
+        };
+
+        let mut function = DecayFunction::Gauss;
+        let mut scale = None;
+        let mut offset = 0.0;
+        let mut decay = 0.5;
+        for parameter in parameters.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let Some((name, value)) = parameter.split_once('=') else {
+                return Err(format!(
+                    "Was expecting a parameter formatted like `name=value` but instead got `{parameter}`."
+                ));
+            };
+            match (name.trim(), value.trim()) {
+                ("scale", value) => scale = Some(parse_distance(value)?),
+                ("offset", value) => offset = parse_distance(value)?,
+                ("fn", "gauss") => function = DecayFunction::Gauss,
+                ("fn", "exp") => function = DecayFunction::Exp,
+                ("fn", "linear") => function = DecayFunction::Linear,
+                ("fn", value) => {
+                    return Err(format!(
+                        "Unknown decay function `{value}`. Available functions are `gauss`, `exp`, and `linear`."
+                    ))
+                }
+                ("decay", value) => {
+                    decay = match value.parse::<f64>() {
+                        Ok(decay) if decay > 0.0 && decay < 1.0 => decay,
+                        _ => {
+                            return Err(format!(
+                                "The `decay` must be a number strictly between 0 and 1 but instead got `{value}`."
+                            ))
+                        }
+                    }
+                }
+                (name, _) => {
+                    return Err(format!(
+                        "Unknown parameter `{name}`. Available parameters are `scale`, `offset`, `fn`, and `decay`."
+                    ))
+                }
+            }
+        }
+
+        match scale {
+            Some(scale) if scale > 0.0 => Ok(GeoDecay { function, scale, offset, decay }),
+            Some(_) => Err("The `scale` must be strictly positive.".to_string()),
+            None => Err("The `scale` parameter is mandatory.".to_string()),
+        }
+    }
+}
+
+impl fmt::Display for GeoDecay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let GeoDecay { function, scale, offset, decay } = self;
+        write!(f, "decay(scale={scale}m, offset={offset}m, fn={function}, decay={decay})")
+    }
+}
+
+/// Parses a distance in meters, the `m` and `km` units are supported.
+fn parse_distance(text: &str) -> Result<f64, String> {
+    let (number, factor) = if let Some(number) = text.strip_suffix("km") {
+        (number, 1000.0)
+    } else if let Some(number) = text.strip_suffix('m') {
+        (number, 1.0)
+    } else {
+        (text, 1.0)
+    };
+    match number.trim().parse::<f64>() {
+        Ok(distance) if distance.is_finite() && distance >= 0.0 => Ok(distance * factor),
+        _ => Err(format!(
+            "Was expecting a positive distance like `500m` or `5km` but instead got `{text}`."
+        )),
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum AscDesc {
     Asc(Member),
@@ -142,6 +273,18 @@ impl FromStr for AscDesc {
     fn from_str(text: &str) -> Result<AscDesc, Self::Err> {
         match text.rsplit_once(':') {
             Some((left, "asc")) => Ok(AscDesc::Asc(left.parse()?)),
+            // the closest documents have the highest score and must come first
+            Some((left, right)) if right.starts_with("decay(") => {
+                let bad_decay = |error| AscDescError::BadGeoDecay { name: text.to_string(), error };
+                let decay = right.parse().map_err(bad_decay)?;
+                match left.parse()? {
+                    Member::Geo(point) => Ok(AscDesc::Desc(Member::GeoDecay(point, decay))),
+                    _ => Err(bad_decay(
+                        "A decay can only be applied to a `_geoPoint(latitude, longitude)`."
+                            .to_string(),
+                    )),
+                }
+            }
             Some((left, "desc")) => Ok(AscDesc::Desc(left.parse()?)),
             _ => Err(AscDescError::InvalidSyntax { name: text.to_string() }),
         }
@@ -165,12 +308,16 @@ pub enum SortError {
     #[error("`{name}` is a reserved keyword and thus can't be used as a sort expression. \
                     Use the _geoPoint(latitude, longitude) built-in rule to sort on _geo field coordinates.")]
     ReservedNameForFilter { name: String },
+    #[error("Invalid syntax for the geo decay parameter `{name}`: {error} Was expecting an expression \
+                    formatted like `_geoPoint(latitude, longitude):decay(scale=5km, offset=1km, fn=gauss, decay=0.5)`.")]
+    BadGeoDecay { name: String, error: String },
 }
 
 impl From<AscDescError> for SortError {
     fn from(error: AscDescError) -> Self {
         match error {
             AscDescError::GeoError(error) => SortError::ParseGeoError { error },
+            AscDescError::BadGeoDecay { name, error } => SortError::BadGeoDecay { name, error },
             AscDescError::InvalidSyntax { name } => SortError::InvalidName { name },
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoPoint") => {
                 SortError::BadGeoPointUsage { name }
@@ -222,6 +369,30 @@ mod tests {
             ("_geoPoint(42.0002, 59.895):desc", Desc(Geo([42.0002, 59.895]))),
             ("_geoPoint(42., 59.):desc", Desc(Geo([42., 59.]))),
             ("truc(12, 13):desc", Desc(Field(S("truc(12, 13)")))),
+            (
+                "_geoPoint(42, 59):decay(scale=5km)",
+                Desc(Member::GeoDecay(
+                    [42., 59.],
+                    super::GeoDecay {
+                        function: DecayFunction::Gauss,
+                        scale: 5000.,
+                        offset: 0.,
+                        decay: 0.5,
+                    },
+                )),
+            ),
+            (
+                "_geoPoint(42, 59):decay(scale=500m, offset=1.5km, fn=linear, decay=0.2)",
+                Desc(Member::GeoDecay(
+                    [42., 59.],
+                    super::GeoDecay {
+                        function: DecayFunction::Linear,
+                        scale: 500.,
+                        offset: 1500.,
+                        decay: 0.2,
+                    },
+                )),
+            ),
         ];
 
         for (req, expected) in valid_req {
@@ -274,6 +445,41 @@ mod tests {
                 "_geoDistance(12, -2021):desc",
                 ReservedKeyword { name: S("_geoDistance(12, -2021)") },
             ),
+            (
+                "_geoPoint(42, 59):decay(offset=1km)",
+                BadGeoDecay {
+                    name: S("_geoPoint(42, 59):decay(offset=1km)"),
+                    error: S("The `scale` parameter is mandatory."),
+                },
+            ),
+            (
+                "_geoPoint(42, 59):decay(scale=5km, fn=cubic)",
+                BadGeoDecay {
+                    name: S("_geoPoint(42, 59):decay(scale=5km, fn=cubic)"),
+                    error: S("Unknown decay function `cubic`. Available functions are `gauss`, `exp`, and `linear`."),
+                },
+            ),
+            (
+                "_geoPoint(42, 59):decay(scale=5miles)",
+                BadGeoDecay {
+                    name: S("_geoPoint(42, 59):decay(scale=5miles)"),
+                    error: S("Was expecting a positive distance like `500m` or `5km` but instead got `5miles`."),
+                },
+            ),
+            (
+                "_geoPoint(42, 59):decay(scale=5km, decay=1)",
+                BadGeoDecay {
+                    name: S("_geoPoint(42, 59):decay(scale=5km, decay=1)"),
+                    error: S("The `decay` must be a number strictly between 0 and 1 but instead got `1`."),
+                },
+            ),
+            (
+                "price:decay(scale=5km)",
+                BadGeoDecay {
+                    name: S("price:decay(scale=5km)"),
+                    error: S("A decay can only be applied to a `_geoPoint(latitude, longitude)`."),
+                },
+            ),
         ];
 
         for (req, expected_error) in invalid_req {
@@ -295,4 +501,26 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn geo_decay_score() {
+        let mut decay = super::GeoDecay {
+            function: DecayFunction::Gauss,
+            scale: 1000.,
+            offset: 500.,
+            decay: 0.5,
+        };
+        for function in [DecayFunction::Gauss, DecayFunction::Exp, DecayFunction::Linear] {
+            decay.function = function;
+            // every document under the offset gets the maximum score
+            assert_eq!(decay.score(0.), 1.);
+            assert_eq!(decay.score(500.), 1.);
+            // the score is equal to the decay at `offset + scale`
+            assert!((decay.score(1500.) - 0.5).abs() < f64::EPSILON, "{function}");
+            assert!(decay.score(1000.) > decay.score(2000.), "{function}");
+        }
+
+        decay.function = DecayFunction::Linear;
+        assert_eq!(decay.score(10_000.), 0.);
+    }
 }
@@ -8,7 +8,7 @@ use crate::{AscDesc, Member};
 
 #[derive(Error, Debug)]
 pub enum CriterionError {
-    #[error("`{name}` ranking rule is invalid. Valid ranking rules are words, typo, sort, proximity, attribute, exactness and custom ranking rules.")]
+    #[error("`{name}` ranking rule is invalid. Valid ranking rules are words, typo, sort, proximity, attribute, attributeRank, wordPosition, exactness, boost and custom ranking rules.")]
     InvalidName { name: String },
     #[error("`{name}` is a reserved keyword and thus can't be used as a ranking rule")]
     ReservedName { name: String },
@@ -35,12 +35,22 @@ pub enum Criterion {
     Proximity,
     /// Documents with quey words contained in more important
     /// attributes are considered better.
+    ///
+    /// This is an alias for [`Criterion::AttributeRank`] followed by [`Criterion::WordPosition`].
     Attribute,
+    /// Documents with query words contained in more important
+    /// attributes are considered better, regardless of the position of the words.
+    AttributeRank,
+    /// Documents with query words at the front of an attribute are considered better.
+    WordPosition,
     /// Dynamically sort at query time the documents. None, one or multiple Asc/Desc sortable
     /// attributes can be used in place of this criterion at query time.
     Sort,
     /// Sorted by the similarity of the matched words with the query words.
     Exactness,
+    /// Dynamically boost at query time the documents matching the boost filters
+    /// given in the search parameters.
+    Boost,
     /// Sorted by the increasing value of the field specified.
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
@@ -66,12 +76,16 @@ impl FromStr for Criterion {
             "typo" => Ok(Criterion::Typo),
             "proximity" => Ok(Criterion::Proximity),
             "attribute" => Ok(Criterion::Attribute),
+            "attributeRank" => Ok(Criterion::AttributeRank),
+            "wordPosition" => Ok(Criterion::WordPosition),
             "sort" => Ok(Criterion::Sort),
             "exactness" => Ok(Criterion::Exactness),
+            "boost" => Ok(Criterion::Boost),
             text => match AscDesc::from_str(text)? {
                 AscDesc::Asc(Member::Field(field)) => Ok(Criterion::Asc(field)),
                 AscDesc::Desc(Member::Field(field)) => Ok(Criterion::Desc(field)),
-                AscDesc::Asc(Member::Geo(_)) | AscDesc::Desc(Member::Geo(_)) => {
+                AscDesc::Asc(Member::Geo(_) | Member::GeoDecay(..))
+                | AscDesc::Desc(Member::Geo(_) | Member::GeoDecay(..)) => {
                     Err(CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() })?
                 }
             },
@@ -99,8 +113,11 @@ impl fmt::Display for Criterion {
             Typo => f.write_str("typo"),
             Proximity => f.write_str("proximity"),
             Attribute => f.write_str("attribute"),
+            AttributeRank => f.write_str("attributeRank"),
+            WordPosition => f.write_str("wordPosition"),
             Sort => f.write_str("sort"),
             Exactness => f.write_str("exactness"),
+            Boost => f.write_str("boost"),
             Asc(attr) => write!(f, "{}:asc", attr),
             Desc(attr) => write!(f, "{}:desc", attr),
         }
@@ -121,8 +138,11 @@ mod tests {
             ("typo", Criterion::Typo),
             ("proximity", Criterion::Proximity),
             ("attribute", Criterion::Attribute),
+            ("attributeRank", Criterion::AttributeRank),
+            ("wordPosition", Criterion::WordPosition),
             ("sort", Criterion::Sort),
             ("exactness", Criterion::Exactness),
+            ("boost", Criterion::Boost),
             ("price:asc", Criterion::Asc(S("price"))),
             ("price:desc", Criterion::Desc(S("price"))),
             ("price:asc:desc", Criterion::Desc(S("price:asc"))),
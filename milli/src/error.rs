@@ -66,6 +66,8 @@ pub enum InternalError {
     InvalidMatchingWords,
     #[error(transparent)]
     ArroyError(#[from] arroy::Error),
+    #[error("The vector stores of the index were written by an older version of Meilisearch, run `meilitool upgrade-vector-stores` to upgrade them.")]
+    OutdatedVectorStore,
     #[error(transparent)]
     VectorEmbeddingError(#[from] crate::vector::Error),
 }
@@ -113,6 +115,8 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
         invalid_facets_name: BTreeSet<String>,
         valid_facets_name: BTreeSet<String>,
     },
+    #[error("The histogram of the facet `{facet_name}` would contain {buckets} buckets, but the maximum is {max_buckets}. Use a larger `interval` or fewer `ranges`.")]
+    TooManyHistogramBuckets { facet_name: String, buckets: u64, max_buckets: u64 },
     #[error(transparent)]
     InvalidGeoField(#[from] GeoError),
     #[error("Invalid vector dimensions: expected: `{}`, found: `{}`.", .expected, .found)]
@@ -121,6 +125,14 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     InvalidVectorsMapType { document_id: String, value: Value },
     #[error("Bad embedder configuration in the document with id: `{document_id}`. {error}")]
     InvalidVectorsEmbedderConf { document_id: String, error: deserr::errors::JsonError },
+    #[error("The document edition function failed to compile: {0}")]
+    DocumentEditionCompilationError(rhai::ParseError),
+    #[error("The document edition function failed: {0}")]
+    DocumentEditionRuntimeError(Box<rhai::EvalAltResult>),
+    #[error("The document edition function cannot modify the primary key of a document. Was modifying the document with id `{document_id}`.")]
+    DocumentEditionCannotModifyPrimaryKey { document_id: String },
+    #[error("The document edition function must leave `doc` as an object, or set it to `()` to delete the document. Was editing the document with id `{document_id}`.")]
+    DocumentEditionDocumentMustBeObject { document_id: String },
     #[error("{0}")]
     InvalidFilter(String),
     #[error("Invalid type for filter subexpression: expected: {}, found: {1}.", .0.join(", "))]
@@ -176,6 +188,25 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     InvalidLmdbOpenOptions,
     #[error("You must specify where `sort` is listed in the rankingRules setting to use the sort parameter at search time.")]
     SortRankingRuleMissing,
+    #[error("You must specify where `boost` is listed in the rankingRules setting to use the boost parameter at search time.")]
+    BoostRankingRuleMissing,
+    #[error("The ranking rules of this index cannot be overridden at search time. Set the `allowRankingRulesOverride` setting to `true` to use the `rankingRules` search parameter.")]
+    RankingRulesOverrideNotAllowed,
+    #[error("Attribute `{}` is not sortable and thus, cannot be used in the `rankingRules` search parameter. {}",
+        .field,
+        match .valid_fields.is_empty() {
+            true => "This index does not have configured sortable attributes.".to_string(),
+            false => format!("Available sortable attributes are: `{}{}`.",
+                    valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+                    .hidden_fields.then_some(", <..hidden-attributes>").unwrap_or(""),
+                ),
+        }
+    )]
+    InvalidRankingRuleSortableAttribute {
+        field: String,
+        valid_fields: BTreeSet<String>,
+        hidden_fields: bool,
+    },
     #[error("The database file is in an invalid state.")]
     InvalidStoreFile,
     #[error("Maximum database size has been reached.")]
@@ -257,6 +288,8 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     },
     #[error("`.embedders.{embedder_name}.dimensions`: `dimensions` cannot be zero")]
     InvalidSettingsDimensions { embedder_name: String },
+    #[error("`.embedders.{embedder_name}.binaryQuantized`: Cannot disable the binary quantization.\n - Note: Binary quantization is a lossy operation that cannot be reverted.\n - Hint: Add a new embedder that is non-quantized and regenerate the vectors.")]
+    InvalidDisableBinaryQuantization { embedder_name: String },
     #[error("`.embedders.{embedder_name}.url`: could not parse `{url}`: {inner_error}")]
     InvalidUrl { embedder_name: String, inner_error: url::ParseError, url: String },
 }
@@ -288,6 +321,7 @@ impl From<arroy::Error> for Error {
             | arroy::Error::MissingMetadata(_) => {
                 Error::InternalError(InternalError::ArroyError(value))
             }
+            arroy::Error::BuildCancelled => Error::InternalError(InternalError::AbortedIndexation),
         }
     }
 }
@@ -310,6 +344,8 @@ pub enum GeoError {
     BadLatitude { document_id: Value, value: Value },
     #[error("Could not parse longitude in the document with the id: `{document_id}`. Was expecting a finite number but instead got `{value}`.")]
     BadLongitude { document_id: Value, value: Value },
+    #[error("The `_geojson` field in the document with the id: `{document_id}` is not a valid GeoJSON geometry. {error}")]
+    BadGeoJson { document_id: Value, error: String },
 }
 
 fn format_invalid_filter_distribution(
@@ -0,0 +1,466 @@
+//! The geometries of the `_geojson` reserved field.
+//!
+//! The geometries are stored with their coordinates expressed as `[latitude, longitude]`,
+//! the same way the `_geo` points are, GeoJSON expresses them as `[longitude, latitude]`.
+//! All the computations are done in the latitude/longitude plane, the same way the sides
+//! of a `_geoBoundingBox` or a `_geoPolygon` are straight lines in this plane.
+
+use rstar::primitives::Rectangle;
+use rstar::AABB;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{distance_between_two_points, DocumentId, GeoJsonShape};
+
+pub const RESERVED_GEOJSON_FIELD_NAME: &str = "_geojson";
+
+/// The mean radius of the earth in meters, used to convert distances to degrees.
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// A GeoJSON geometry.
+///
+/// The longitudes of the lines and rings are unwrapped so that two consecutive positions are
+/// never more than 180 degrees apart, a geometry crossing the antimeridian can therefore
+/// have longitudes outside of the `[-180, 180]` range.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Geometry {
+    Point([f64; 2]),
+    MultiPoint(Vec<[f64; 2]>),
+    LineString(Vec<[f64; 2]>),
+    MultiLineString(Vec<Vec<[f64; 2]>>),
+    /// The first ring is the exterior one, the following ones are the holes.
+    Polygon(Vec<Vec<[f64; 2]>>),
+    MultiPolygon(Vec<Vec<Vec<[f64; 2]>>>),
+}
+
+impl Geometry {
+    /// Parses a GeoJSON geometry object, returns a description of the problem on failure.
+    pub fn from_geojson(value: &Value) -> Result<Geometry, String> {
+        let object = match value {
+            Value::Object(object) => object,
+            value => return Err(format!("Was expecting an object but instead got `{value}`.")),
+        };
+        let kind = match object.get("type") {
+            Some(Value::String(kind)) => kind.as_str(),
+            Some(value) => {
+                return Err(format!("Was expecting a string for `type` but instead got `{value}`."))
+            }
+            None => return Err("Missing the `type` field.".to_string()),
+        };
+        let coordinates = match object.get("coordinates") {
+            Some(coordinates) => coordinates,
+            None => return Err("Missing the `coordinates` field.".to_string()),
+        };
+
+        match kind {
+            "Point" => parse_position(coordinates).map(Geometry::Point),
+            "MultiPoint" => parse_multi(coordinates, parse_position).map(Geometry::MultiPoint),
+            "LineString" => parse_line(coordinates).map(Geometry::LineString),
+            "MultiLineString" => {
+                parse_multi(coordinates, parse_line).map(Geometry::MultiLineString)
+            }
+            "Polygon" => parse_polygon(coordinates).map(Geometry::Polygon),
+            "MultiPolygon" => parse_multi(coordinates, parse_polygon).map(Geometry::MultiPolygon),
+            kind => Err(format!(
+                "Unsupported geometry type `{kind}`. Supported types are `Point`, `MultiPoint`, `LineString`, `MultiLineString`, `Polygon`, and `MultiPolygon`."
+            )),
+        }
+    }
+
+    /// Creates the envelope to insert in the `_geojson` rtree.
+    pub fn shape(&self, docid: DocumentId) -> GeoJsonShape {
+        GeoJsonShape::new(Rectangle::from_aabb(self.envelope()), docid)
+    }
+
+    /// The smallest latitude/longitude envelope containing the geometry.
+    pub fn envelope(&self) -> AABB<[f64; 2]> {
+        let positions: Vec<[f64; 2]> = match self {
+            Geometry::Point(point) => vec![*point],
+            Geometry::MultiPoint(points) | Geometry::LineString(points) => points.clone(),
+            Geometry::MultiLineString(lines) | Geometry::Polygon(lines) => lines.concat(),
+            Geometry::MultiPolygon(polygons) => polygons.concat().concat(),
+        };
+        AABB::from_points(&positions)
+    }
+
+    /// Returns `true` if the geometry intersects the polygon described by the given vertices.
+    ///
+    /// The longitudes of the vertices must be unwrapped.
+    pub fn intersects_polygon(&self, vertices: &[[f64; 2]]) -> bool {
+        // the geometry and the polygon can be on different sides of the antimeridian
+        [0.0, -360.0, 360.0].iter().any(|&shift| {
+            let vertices: Vec<_> = vertices.iter().map(|&[lat, lng]| [lat, lng + shift]).collect();
+            self.intersects_unwrapped_polygon(&vertices)
+        })
+    }
+
+    fn intersects_unwrapped_polygon(&self, vertices: &[[f64; 2]]) -> bool {
+        let line_intersects = |line: &[[f64; 2]]| {
+            line.iter().any(|point| ring_contains(vertices, point))
+                || segments(line, false).any(|segment| {
+                    segments(vertices, true).any(|edge| segments_intersect(segment, edge))
+                })
+        };
+        let polygon_intersects = |rings: &[Vec<[f64; 2]>]| {
+            rings.first().map_or(false, |exterior| line_intersects(exterior))
+                || vertices.iter().any(|vertex| polygon_contains(rings, vertex))
+        };
+
+        match self {
+            Geometry::Point(point) => ring_contains(vertices, point),
+            Geometry::MultiPoint(points) => {
+                points.iter().any(|point| ring_contains(vertices, point))
+            }
+            Geometry::LineString(line) => line_intersects(line),
+            Geometry::MultiLineString(lines) => lines.iter().any(|line| line_intersects(line)),
+            Geometry::Polygon(rings) => polygon_intersects(rings),
+            Geometry::MultiPolygon(polygons) => {
+                polygons.iter().any(|rings| polygon_intersects(rings))
+            }
+        }
+    }
+
+    /// Returns `true` if a point of the geometry is at less than `radius` meters of the center.
+    pub fn intersects_circle(&self, center: &[f64; 2], radius: f64) -> bool {
+        let [lat, lng] = *center;
+        // the geometry and the circle can be on different sides of the antimeridian
+        [lng, lng - 360.0, lng + 360.0]
+            .iter()
+            .any(|&lng| self.intersects_unwrapped_circle(&[lat, lng], radius))
+    }
+
+    fn intersects_unwrapped_circle(&self, center: &[f64; 2], radius: f64) -> bool {
+        let in_circle = |point: &[f64; 2]| distance_between_two_points(center, point) <= radius;
+        let line_intersects = |line: &[[f64; 2]], closed: bool| {
+            line.iter().any(in_circle)
+                || segments(line, closed)
+                    .any(|segment| in_circle(&closest_point_on_segment(center, segment)))
+        };
+        let polygon_intersects = |rings: &[Vec<[f64; 2]>]| {
+            polygon_contains(rings, center) || rings.iter().any(|ring| line_intersects(ring, true))
+        };
+
+        match self {
+            Geometry::Point(point) => in_circle(point),
+            Geometry::MultiPoint(points) => points.iter().any(in_circle),
+            Geometry::LineString(line) => line_intersects(line, false),
+            Geometry::MultiLineString(lines) => {
+                lines.iter().any(|line| line_intersects(line, false))
+            }
+            Geometry::Polygon(rings) => polygon_intersects(rings),
+            Geometry::MultiPolygon(polygons) => {
+                polygons.iter().any(|rings| polygon_intersects(rings))
+            }
+        }
+    }
+}
+
+/// Returns the envelope and its copies shifted by a full turn on each side, the geometries
+/// crossing the antimeridian have longitudes outside of the `[-180, 180]` range.
+pub fn wrapped_envelopes(envelope: AABB<[f64; 2]>) -> [AABB<[f64; 2]>; 3] {
+    let [min_lat, min_lng] = envelope.lower();
+    let [max_lat, max_lng] = envelope.upper();
+    [0.0, -360.0, 360.0]
+        .map(|shift| AABB::from_corners([min_lat, min_lng + shift], [max_lat, max_lng + shift]))
+}
+
+/// The latitude/longitude envelope containing the circle of `radius` meters around the center.
+pub fn circle_envelope(center: &[f64; 2], radius: f64) -> AABB<[f64; 2]> {
+    let [lat, lng] = *center;
+    let delta_lat = (radius / EARTH_RADIUS).to_degrees();
+    let min_lat = lat - delta_lat;
+    let max_lat = lat + delta_lat;
+    if min_lat <= -90.0 || max_lat >= 90.0 {
+        // the circle contains a pole and thus every longitude
+        return AABB::from_corners([min_lat, -180.0], [max_lat, 180.0]);
+    }
+    let max_abs_lat = min_lat.abs().max(max_lat.abs());
+    let delta_lng = delta_lat / max_abs_lat.to_radians().cos();
+    AABB::from_corners([min_lat, lng - delta_lng], [max_lat, lng + delta_lng])
+}
+
+/// Returns `true` if the point is inside the ring, using the ray casting algorithm.
+///
+/// The ring is implicitly closed, its last position is linked to the first one.
+pub fn ring_contains(ring: &[[f64; 2]], point: &[f64; 2]) -> bool {
+    let [lat, lng] = *point;
+    let mut inside = false;
+    for [[lat_a, lng_a], [lat_b, lng_b]] in segments(ring, true) {
+        if (lat_a > lat) != (lat_b > lat) {
+            let crossing_lng = lng_a + (lat - lat_a) / (lat_b - lat_a) * (lng_b - lng_a);
+            if lng < crossing_lng {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Returns `true` if the point is inside the exterior ring but not in one of the holes.
+fn polygon_contains(rings: &[Vec<[f64; 2]>], point: &[f64; 2]) -> bool {
+    match rings.split_first() {
+        Some((exterior, holes)) => {
+            ring_contains(exterior, point) && !holes.iter().any(|hole| ring_contains(hole, point))
+        }
+        None => false,
+    }
+}
+
+/// Iterates over the segments of the line, including the one linking the last position
+/// to the first one when the line is `closed`.
+fn segments(line: &[[f64; 2]], closed: bool) -> impl Iterator<Item = [[f64; 2]; 2]> + '_ {
+    let closing = match (closed, line.first(), line.last()) {
+        (true, Some(first), Some(last)) if first != last => Some([*last, *first]),
+        _ => None,
+    };
+    line.windows(2).map(|window| [window[0], window[1]]).chain(closing)
+}
+
+fn segments_intersect([a, b]: [[f64; 2]; 2], [c, d]: [[f64; 2]; 2]) -> bool {
+    fn orientation(p: [f64; 2], q: [f64; 2], r: [f64; 2]) -> f64 {
+        (q[0] - p[0]) * (r[1] - p[1]) - (q[1] - p[1]) * (r[0] - p[0])
+    }
+    fn on_segment(p: [f64; 2], q: [f64; 2], r: [f64; 2]) -> bool {
+        r[0] >= p[0].min(q[0])
+            && r[0] <= p[0].max(q[0])
+            && r[1] >= p[1].min(q[1])
+            && r[1] <= p[1].max(q[1])
+    }
+
+    let d1 = orientation(c, d, a);
+    let d2 = orientation(c, d, b);
+    let d3 = orientation(a, b, c);
+    let d4 = orientation(a, b, d);
+
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0))
+        && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0))
+    {
+        return true;
+    }
+
+    (d1 == 0.0 && on_segment(c, d, a))
+        || (d2 == 0.0 && on_segment(c, d, b))
+        || (d3 == 0.0 && on_segment(a, b, c))
+        || (d4 == 0.0 && on_segment(a, b, d))
+}
+
+/// Finds the point of the segment closest to the center, the longitudes are scaled
+/// by the cosine of the latitude of the center to limit the distortion.
+fn closest_point_on_segment(center: &[f64; 2], [a, b]: [[f64; 2]; 2]) -> [f64; 2] {
+    let scale = center[0].to_radians().cos();
+    let project = |[lat, lng]: [f64; 2]| [lat - center[0], (lng - center[1]) * scale];
+    let (pa, pb) = (project(a), project(b));
+    let direction = [pb[0] - pa[0], pb[1] - pa[1]];
+    let length = direction[0] * direction[0] + direction[1] * direction[1];
+    if length == 0.0 {
+        return a;
+    }
+    let t = (-(pa[0] * direction[0] + pa[1] * direction[1]) / length).clamp(0.0, 1.0);
+    [a[0] + t * (b[0] - a[0]), a[1] + t * (b[1] - a[1])]
+}
+
+fn parse_array<T>(
+    value: &Value,
+    parse: impl Fn(&Value) -> Result<T, String>,
+) -> Result<Vec<T>, String> {
+    match value {
+        Value::Array(values) => values.iter().map(parse).collect(),
+        value => Err(format!("Was expecting an array but instead got `{value}`.")),
+    }
+}
+
+fn parse_multi<T>(
+    value: &Value,
+    parse: impl Fn(&Value) -> Result<T, String>,
+) -> Result<Vec<T>, String> {
+    let values = parse_array(value, parse)?;
+    if values.is_empty() {
+        return Err(format!(
+            "A multi geometry must contain at least one element but instead got `{value}`."
+        ));
+    }
+    Ok(values)
+}
+
+/// Parses a GeoJSON `[longitude, latitude]` position into a `[latitude, longitude]` point.
+fn parse_position(value: &Value) -> Result<[f64; 2], String> {
+    let position = match value.as_array() {
+        Some(position) if position.len() == 2 || position.len() == 3 => position,
+        _ => {
+            return Err(format!(
+                "Was expecting a position `[longitude, latitude]` but instead got `{value}`."
+            ))
+        }
+    };
+    let number = |value: &Value| match value.as_f64() {
+        Some(number) if number.is_finite() => Ok(number),
+        _ => Err(format!("Was expecting a finite number but instead got `{value}`.")),
+    };
+    let lng = number(&position[0])?;
+    let lat = number(&position[1])?;
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err(format!(
+            "Bad latitude `{lat}`. Latitude must be contained between -90 and 90 degrees."
+        ));
+    }
+    if !(-180.0..=180.0).contains(&lng) {
+        return Err(format!(
+            "Bad longitude `{lng}`. Longitude must be contained between -180 and 180 degrees."
+        ));
+    }
+    Ok([lat, lng])
+}
+
+fn parse_line(value: &Value) -> Result<Vec<[f64; 2]>, String> {
+    let line = parse_array(value, parse_position)?;
+    if line.len() < 2 {
+        return Err(format!(
+            "A line must contain at least two positions but instead got `{value}`."
+        ));
+    }
+    Ok(unwrap_longitudes(line))
+}
+
+fn parse_polygon(value: &Value) -> Result<Vec<Vec<[f64; 2]>>, String> {
+    let rings = parse_array(value, |ring| {
+        let positions = parse_array(ring, parse_position)?;
+        if positions.len() < 4 || positions.first() != positions.last() {
+            return Err(format!(
+                "A polygon ring must contain at least four positions and be closed but instead got `{ring}`."
+            ));
+        }
+        Ok(unwrap_longitudes(positions))
+    })?;
+    if rings.is_empty() {
+        return Err("A polygon must contain at least one ring.".to_string());
+    }
+    Ok(rings)
+}
+
+/// Unwraps the longitudes so that two consecutive positions are never more than 180 degrees apart.
+fn unwrap_longitudes(mut positions: Vec<[f64; 2]>) -> Vec<[f64; 2]> {
+    for i in 1..positions.len() {
+        let previous_lng = positions[i - 1][1];
+        let lng = &mut positions[i][1];
+        while *lng - previous_lng > 180.0 {
+            *lng -= 360.0;
+        }
+        while *lng - previous_lng < -180.0 {
+            *lng += 360.0;
+        }
+    }
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parse_geometries() {
+        let point =
+            Geometry::from_geojson(&json!({ "type": "Point", "coordinates": [2.35, 48.85] }));
+        assert_eq!(point, Ok(Geometry::Point([48.85, 2.35])));
+
+        let polygon = Geometry::from_geojson(&json!({
+            "type": "Polygon",
+            "coordinates": [[[170, -10], [-170, -10], [-170, 10], [170, 10], [170, -10]]]
+        }))
+        .unwrap();
+        // the longitudes are unwrapped across the antimeridian
+        assert_eq!(
+            polygon,
+            Geometry::Polygon(vec![vec![
+                [-10.0, 170.0],
+                [-10.0, 190.0],
+                [10.0, 190.0],
+                [10.0, 170.0],
+                [-10.0, 170.0]
+            ]])
+        );
+
+        let error = Geometry::from_geojson(&json!({ "type": "Circle", "coordinates": [1, 2] }));
+        assert_eq!(error, Err("Unsupported geometry type `Circle`. Supported types are `Point`, `MultiPoint`, `LineString`, `MultiLineString`, `Polygon`, and `MultiPolygon`.".to_string()));
+
+        let error = Geometry::from_geojson(&json!({ "type": "Point", "coordinates": [1, 91] }));
+        assert_eq!(
+            error,
+            Err("Bad latitude `91`. Latitude must be contained between -90 and 90 degrees."
+                .to_string())
+        );
+
+        let error = Geometry::from_geojson(&json!({
+            "type": "Polygon",
+            "coordinates": [[[0, 0], [0, 1], [1, 1]]]
+        }));
+        assert!(error
+            .unwrap_err()
+            .starts_with("A polygon ring must contain at least four positions"));
+    }
+
+    #[test]
+    fn intersects_polygon() {
+        let square = [[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]];
+
+        assert!(Geometry::Point([5.0, 5.0]).intersects_polygon(&square));
+        assert!(!Geometry::Point([15.0, 5.0]).intersects_polygon(&square));
+
+        // a line going through the square without any position inside
+        let line = Geometry::LineString(vec![[5.0, -5.0], [5.0, 15.0]]);
+        assert!(line.intersects_polygon(&square));
+        let line = Geometry::LineString(vec![[15.0, -5.0], [15.0, 15.0]]);
+        assert!(!line.intersects_polygon(&square));
+
+        // a polygon containing the whole square
+        let polygon = Geometry::Polygon(vec![vec![
+            [-5.0, -5.0],
+            [-5.0, 15.0],
+            [15.0, 15.0],
+            [15.0, -5.0],
+            [-5.0, -5.0],
+        ]]);
+        assert!(polygon.intersects_polygon(&square));
+
+        // the same polygon with a hole containing the whole square
+        let polygon = Geometry::Polygon(vec![
+            vec![[-5.0, -5.0], [-5.0, 15.0], [15.0, 15.0], [15.0, -5.0], [-5.0, -5.0]],
+            vec![[-1.0, -1.0], [-1.0, 11.0], [11.0, 11.0], [11.0, -1.0], [-1.0, -1.0]],
+        ]);
+        assert!(!polygon.intersects_polygon(&square));
+
+        // a polygon crossing the antimeridian
+        let polygon = Geometry::Polygon(vec![vec![
+            [-10.0, 170.0],
+            [-10.0, 190.0],
+            [10.0, 190.0],
+            [10.0, 170.0],
+            [-10.0, 170.0],
+        ]]);
+        let square = [[-1.0, -179.0], [-1.0, -178.0], [1.0, -178.0], [1.0, -179.0]];
+        assert!(polygon.intersects_polygon(&square));
+    }
+
+    #[test]
+    fn intersects_circle() {
+        // one degree of latitude is about 111km
+        let line = Geometry::LineString(vec![[1.0, -5.0], [1.0, 5.0]]);
+        assert!(line.intersects_circle(&[0.0, 0.0], 120_000.0));
+        assert!(!line.intersects_circle(&[0.0, 0.0], 100_000.0));
+
+        // the center is inside the polygon
+        let polygon = Geometry::Polygon(vec![vec![
+            [-5.0, -5.0],
+            [-5.0, 5.0],
+            [5.0, 5.0],
+            [5.0, -5.0],
+            [-5.0, -5.0],
+        ]]);
+        assert!(polygon.intersects_circle(&[0.0, 0.0], 1.0));
+
+        // across the antimeridian
+        let point = Geometry::Point([0.0, 179.9]);
+        assert!(point.intersects_circle(&[0.0, -179.9], 30_000.0));
+    }
+}
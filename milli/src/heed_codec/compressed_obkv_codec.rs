@@ -0,0 +1,101 @@
+use std::borrow::Cow;
+use std::io::{self, Read};
+
+use heed::BoxedError;
+use obkv::KvReaderU16;
+use zstd::dict::DecoderDictionary;
+use zstd::stream::read::Decoder;
+
+pub struct CompressedObkvCodec;
+
+impl<'a> heed::BytesDecode<'a> for CompressedObkvCodec {
+    type DItem = CompressedObkvU16<'a>;
+
+    fn bytes_decode(bytes: &'a [u8]) -> Result<Self::DItem, BoxedError> {
+        Ok(CompressedObkvU16(bytes))
+    }
+}
+
+impl heed::BytesEncode<'_> for CompressedObkvCodec {
+    type EItem = [u8];
+
+    fn bytes_encode(item: &Self::EItem) -> Result<Cow<[u8]>, BoxedError> {
+        Ok(Cow::Borrowed(item))
+    }
+}
+
+/// An obkv document as it is stored in the documents database.
+///
+/// When the index has a document compression dictionary, every document is
+/// compressed with it, otherwise the documents are stored as raw obkvs.
+#[derive(Debug, Clone, Copy)]
+pub struct CompressedObkvU16<'a>(&'a [u8]);
+
+impl<'a> CompressedObkvU16<'a> {
+    /// Decompresses this document if there is a dictionary, otherwise considers it as a raw obkv.
+    pub fn decompress_with_optional_dictionary<'b>(
+        &self,
+        buffer: &'b mut Vec<u8>,
+        dictionary: Option<&DecoderDictionary>,
+    ) -> io::Result<KvReaderU16<'b>>
+    where
+        'a: 'b,
+    {
+        self.decompress_bytes_with_optional_dictionary(buffer, dictionary).map(KvReaderU16::new)
+    }
+
+    /// Same as [`Self::decompress_with_optional_dictionary`] but returns the raw obkv bytes.
+    pub fn decompress_bytes_with_optional_dictionary<'b>(
+        &self,
+        buffer: &'b mut Vec<u8>,
+        dictionary: Option<&DecoderDictionary>,
+    ) -> io::Result<&'b [u8]>
+    where
+        'a: 'b,
+    {
+        match dictionary {
+            Some(dictionary) => {
+                let mut decoder = Decoder::with_prepared_dictionary(self.0, dictionary)?;
+                buffer.clear();
+                decoder.read_to_end(buffer)?;
+                Ok(buffer)
+            }
+            None => Ok(self.0),
+        }
+    }
+
+    /// Returns the document either borrowed from the database or decompressed in memory.
+    pub fn into_document(
+        self,
+        dictionary: Option<&DecoderDictionary>,
+    ) -> io::Result<ObkvDocument<'a>> {
+        match dictionary {
+            Some(_) => {
+                let mut buffer = Vec::new();
+                self.decompress_bytes_with_optional_dictionary(&mut buffer, dictionary)?;
+                Ok(ObkvDocument(Cow::Owned(buffer)))
+            }
+            None => Ok(ObkvDocument(Cow::Borrowed(self.0))),
+        }
+    }
+
+    /// Returns the bytes as they are stored in the database.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+}
+
+/// A document obkv that is either borrowed from the database, when the documents
+/// are not compressed, or decompressed into an owned buffer.
+#[derive(Debug, Clone)]
+pub struct ObkvDocument<'a>(Cow<'a, [u8]>);
+
+impl ObkvDocument<'_> {
+    pub fn as_obkv(&self) -> KvReaderU16<'_> {
+        KvReaderU16::new(&self.0)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::convert::TryInto;
 use std::fs::File;
 use std::path::Path;
+use std::sync::{Arc, RwLock};
 
 use charabia::{Language, Script};
 use heed::types::*;
@@ -11,26 +12,31 @@ use roaring::RoaringBitmap;
 use rstar::RTree;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
+use zstd::dict::DecoderDictionary;
 
 use crate::documents::PrimaryKey;
 use crate::error::{InternalError, UserError};
 use crate::fields_ids_map::FieldsIdsMap;
+use crate::geojson::Geometry;
 use crate::heed_codec::facet::{
     FacetGroupKeyCodec, FacetGroupValueCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec,
     FieldIdCodec, OrderedF64Codec,
 };
 use crate::heed_codec::{
-    BEU16StrCodec, FstSetCodec, ScriptLanguageCodec, StrBEU16Codec, StrRefCodec,
+    BEU16StrCodec, CompressedObkvCodec, CompressedObkvU16, FstSetCodec, ScriptLanguageCodec,
+    StrBEU16Codec, StrRefCodec,
 };
+use crate::localized_attributes_rules::LocalizedAttributesRule;
+use crate::merchandising_rules::MerchandisingRules;
 use crate::order_by_map::OrderByMap;
 use crate::proximity::ProximityPrecision;
 use crate::vector::parsed_vectors::RESERVED_VECTORS_FIELD_NAME;
-use crate::vector::{Embedding, EmbeddingConfig};
+use crate::vector::{ArroyWrapper, Embedding, EmbeddingConfig};
 use crate::{
     default_criteria, CboRoaringBitmapCodec, Criterion, DocumentId, ExternalDocumentsIds,
     FacetDistribution, FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldIdWordCountCodec,
-    FieldidsWeightsMap, GeoPoint, ObkvCodec, Result, RoaringBitmapCodec, RoaringBitmapLenCodec,
-    Search, U8StrStrCodec, Weight, BEU16, BEU32, BEU64,
+    FieldidsWeightsMap, GeoJsonShape, GeoPoint, ObkvDocument, Result, RoaringBitmapCodec,
+    RoaringBitmapLenCodec, Search, U8StrStrCodec, Weight, BEU16, BEU32, BEU64,
 };
 
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
@@ -49,6 +55,7 @@ pub mod main_key {
     pub const FIELDIDS_WEIGHTS_MAP_KEY: &str = "fieldids-weights-map";
     pub const GEO_FACETED_DOCUMENTS_IDS_KEY: &str = "geo-faceted-documents-ids";
     pub const GEO_RTREE_KEY: &str = "geo-rtree";
+    pub const GEOJSON_RTREE_KEY: &str = "geojson-rtree";
     pub const PRIMARY_KEY_KEY: &str = "primary-key";
     pub const SEARCHABLE_FIELDS_KEY: &str = "searchable-fields";
     pub const USER_DEFINED_SEARCHABLE_FIELDS_KEY: &str = "user-defined-searchable-fields";
@@ -73,6 +80,12 @@ pub mod main_key {
     pub const PROXIMITY_PRECISION: &str = "proximity-precision";
     pub const EMBEDDING_CONFIGS: &str = "embedding_configs";
     pub const SEARCH_CUTOFF: &str = "search_cutoff";
+    pub const LOCALIZED_ATTRIBUTES_RULES: &str = "localized_attributes_rules";
+    pub const MERCHANDISING_RULES: &str = "merchandising-rules";
+    pub const ALLOW_RANKING_RULES_OVERRIDE: &str = "allow-ranking-rules-override";
+    pub const DOCUMENT_COMPRESSION_DICTIONARY: &str = "document-compression-dictionary";
+    pub const DOCUMENTS_MODIFIED_SINCE_DICTIONARY: &str = "documents-modified-since-dictionary";
+    pub const VECTOR_STORE_VERSION: &str = "vector-store-version";
 }
 
 pub mod db_name {
@@ -101,6 +114,7 @@ pub mod db_name {
     pub const VECTOR_EMBEDDER_CATEGORY_ID: &str = "vector-embedder-category-id";
     pub const VECTOR_ARROY: &str = "vector-arroy";
     pub const DOCUMENTS: &str = "documents";
+    pub const GEOJSON_GEOMETRIES: &str = "geojson-geometries";
     pub const SCRIPT_LANGUAGE_DOCIDS: &str = "script_language_docids";
 }
 
@@ -169,12 +183,23 @@ pub struct Index {
     /// Maps an embedder name to its id in the arroy store.
     pub embedder_category_id: Database<Str, U8>,
     /// Vector store based on arroy™.
-    pub vector_arroy: arroy::Database<arroy::distances::Angular>,
+    pub vector_arroy: arroy::Database<arroy::distances::Cosine>,
 
-    /// Maps the document id to the document as an obkv store.
-    pub(crate) documents: Database<BEU32, ObkvCodec>,
+    /// Maps the document id to the document as an obkv store, compressed
+    /// with the document compression dictionary if there is one.
+    pub(crate) documents: Database<BEU32, CompressedObkvCodec>,
+
+    /// Maps the document id to its `_geojson` geometry, the geojson rtree only stores their envelopes.
+    pub geojson_geometries: Database<BEU32, SerdeBincode<Geometry>>,
+
+    /// The dictionary to decompress the documents, prepared once and shared by the clones
+    /// of the index, along with the raw dictionary it was prepared from.
+    document_decompression_dictionary: Arc<RwLock<Option<PreparedDictionary>>>,
 }
 
+/// A raw zstd dictionary along with its prepared version to decompress the documents.
+type PreparedDictionary = (Vec<u8>, Arc<DecoderDictionary<'static>>);
+
 impl Index {
     pub fn new_with_creation_dates<P: AsRef<Path>>(
         mut options: heed::EnvOpenOptions,
@@ -184,7 +209,7 @@ impl Index {
     ) -> Result<Index> {
         use db_name::*;
 
-        options.max_dbs(25);
+        options.max_dbs(26);
 
         let env = unsafe { options.open(path) }?;
         let mut wtxn = env.write_txn()?;
@@ -230,6 +255,20 @@ impl Index {
         let vector_arroy = env.create_database(&mut wtxn, Some(VECTOR_ARROY))?;
 
         let documents = env.create_database(&mut wtxn, Some(DOCUMENTS))?;
+        let geojson_geometries = env.create_database(&mut wtxn, Some(GEOJSON_GEOMETRIES))?;
+
+        // The vector stores written by arroy 0.4 can't be read anymore, they must be upgraded
+        // with meilitool first. The indexes without any vector are already up to date.
+        let vector_store_version = main.remap_types::<Str, Str>();
+        match vector_store_version.get(&wtxn, main_key::VECTOR_STORE_VERSION)? {
+            Some(version) if version == crate::vector::VECTOR_STORE_VERSION => (),
+            None if vector_arroy.is_empty(&wtxn)? => vector_store_version.put(
+                &mut wtxn,
+                main_key::VECTOR_STORE_VERSION,
+                crate::vector::VECTOR_STORE_VERSION,
+            )?,
+            _ => return Err(InternalError::OutdatedVectorStore.into()),
+        }
         wtxn.commit()?;
 
         Index::set_creation_dates(&env, main, created_at, updated_at)?;
@@ -261,6 +300,8 @@ impl Index {
             vector_arroy,
             embedder_category_id,
             documents,
+            geojson_geometries,
+            document_decompression_dictionary: Arc::default(),
         })
     }
 
@@ -326,6 +367,13 @@ impl Index {
         self.env.info().map_size
     }
 
+    /// Returns the id of the last transaction committed to the underlying environment.
+    ///
+    /// Any write to the index increases it, but the compacted copies of the index start over.
+    pub fn last_txn_id(&self) -> usize {
+        self.env.info().last_txn_id
+    }
+
     pub fn copy_to_file<P: AsRef<Path>>(&self, path: P, option: CompactionOption) -> Result<File> {
         self.env.copy_to_file(path, option).map_err(Into::into)
     }
@@ -515,6 +563,38 @@ impl Index {
         }
     }
 
+    /* geojson rtree */
+
+    /// Writes the provided `rtree` which associates the envelopes of the `_geojson` geometries to documents ids.
+    pub(crate) fn put_geojson_rtree(
+        &self,
+        wtxn: &mut RwTxn,
+        rtree: &RTree<GeoJsonShape>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<RTree<GeoJsonShape>>>().put(
+            wtxn,
+            main_key::GEOJSON_RTREE_KEY,
+            rtree,
+        )
+    }
+
+    /// Delete the `rtree` which associates the envelopes of the `_geojson` geometries to documents ids.
+    pub(crate) fn delete_geojson_rtree(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::GEOJSON_RTREE_KEY)
+    }
+
+    /// Returns the `rtree` which associates the envelopes of the `_geojson` geometries to documents ids.
+    pub fn geojson_rtree(&self, rtxn: &RoTxn) -> Result<Option<RTree<GeoJsonShape>>> {
+        match self
+            .main
+            .remap_types::<Str, SerdeBincode<RTree<GeoJsonShape>>>()
+            .get(rtxn, main_key::GEOJSON_RTREE_KEY)?
+        {
+            Some(rtree) => Ok(Some(rtree)),
+            None => Ok(None),
+        }
+    }
+
     /* geo faceted */
 
     /// Writes the documents ids that are faceted with a _geo field.
@@ -1246,20 +1326,115 @@ impl Index {
         self.word_docids.remap_data_type::<RoaringBitmapLenCodec>().get(rtxn, word)
     }
 
+    /* document compression */
+
+    /// Writes the zstd dictionary used to compress the documents.
+    pub(crate) fn put_document_compression_dictionary(
+        &self,
+        wtxn: &mut RwTxn,
+        dictionary: &[u8],
+    ) -> heed::Result<()> {
+        *self.document_decompression_dictionary.write().unwrap() = None;
+        self.main.remap_types::<Str, Bytes>().put(
+            wtxn,
+            main_key::DOCUMENT_COMPRESSION_DICTIONARY,
+            dictionary,
+        )
+    }
+
+    /// Deletes the zstd dictionary used to compress the documents,
+    /// and the number of documents modified since it was trained.
+    pub(crate) fn delete_document_compression_dictionary(
+        &self,
+        wtxn: &mut RwTxn,
+    ) -> heed::Result<bool> {
+        *self.document_decompression_dictionary.write().unwrap() = None;
+        self.main
+            .remap_key_type::<Str>()
+            .delete(wtxn, main_key::DOCUMENTS_MODIFIED_SINCE_DICTIONARY)?;
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::DOCUMENT_COMPRESSION_DICTIONARY)
+    }
+
+    /// Returns the zstd dictionary used to compress the documents, if any.
+    pub fn document_compression_dictionary<'t>(
+        &self,
+        rtxn: &'t RoTxn,
+    ) -> heed::Result<Option<&'t [u8]>> {
+        self.main.remap_types::<Str, Bytes>().get(rtxn, main_key::DOCUMENT_COMPRESSION_DICTIONARY)
+    }
+
+    /// Returns the prepared dictionary to decompress the documents, if they are compressed.
+    ///
+    /// The dictionary is only prepared again when it differs from the cached one, as transactions
+    /// opened before or after a new dictionary is trained don't see the same dictionary.
+    pub fn document_decompression_dictionary(
+        &self,
+        rtxn: &RoTxn,
+    ) -> heed::Result<Option<Arc<DecoderDictionary<'static>>>> {
+        let Some(dictionary) = self.document_compression_dictionary(rtxn)? else {
+            return Ok(None);
+        };
+
+        if let Some((raw, prepared)) = &*self.document_decompression_dictionary.read().unwrap() {
+            if raw.as_slice() == dictionary {
+                return Ok(Some(prepared.clone()));
+            }
+        }
+
+        let prepared = Arc::new(DecoderDictionary::copy(dictionary));
+        *self.document_decompression_dictionary.write().unwrap() =
+            Some((dictionary.to_vec(), prepared.clone()));
+        Ok(Some(prepared))
+    }
+
+    pub(crate) fn put_documents_modified_since_dictionary(
+        &self,
+        wtxn: &mut RwTxn,
+        count: u64,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, BEU64>().put(
+            wtxn,
+            main_key::DOCUMENTS_MODIFIED_SINCE_DICTIONARY,
+            &count,
+        )
+    }
+
+    /// Returns the number of documents that were added, updated or deleted since
+    /// the document compression dictionary was trained.
+    pub fn documents_modified_since_dictionary(&self, rtxn: &RoTxn) -> heed::Result<u64> {
+        Ok(self
+            .main
+            .remap_types::<Str, BEU64>()
+            .get(rtxn, main_key::DOCUMENTS_MODIFIED_SINCE_DICTIONARY)?
+            .unwrap_or(0))
+    }
+
     /* documents */
 
+    /// Returns the requested document as it is stored in the database, possibly compressed.
+    pub fn compressed_document<'t>(
+        &self,
+        rtxn: &'t RoTxn,
+        id: DocumentId,
+    ) -> Result<Option<CompressedObkvU16<'t>>> {
+        Ok(self.documents.get(rtxn, &id)?)
+    }
+
     /// Returns an iterator over the requested documents. The next item will be an error if a document is missing.
+    ///
+    /// The documents are transparently decompressed if the index has a compression dictionary.
     pub fn iter_documents<'a, 't: 'a>(
         &'a self,
         rtxn: &'t RoTxn,
         ids: impl IntoIterator<Item = DocumentId> + 'a,
-    ) -> Result<impl Iterator<Item = Result<(DocumentId, obkv::KvReaderU16<'t>)>> + 'a> {
+    ) -> Result<impl Iterator<Item = Result<(DocumentId, ObkvDocument<'t>)>> + 'a> {
+        let dictionary = self.document_decompression_dictionary(rtxn)?;
         Ok(ids.into_iter().map(move |id| {
-            let kv = self
+            let compressed = self
                 .documents
                 .get(rtxn, &id)?
                 .ok_or(UserError::UnknownInternalDocumentId { document_id: id })?;
-            Ok((id, kv))
+            Ok((id, compressed.into_document(dictionary.as_deref())?))
         }))
     }
 
@@ -1268,7 +1443,7 @@ impl Index {
         &self,
         rtxn: &'t RoTxn,
         ids: impl IntoIterator<Item = DocumentId>,
-    ) -> Result<Vec<(DocumentId, obkv::KvReaderU16<'t>)>> {
+    ) -> Result<Vec<(DocumentId, ObkvDocument<'t>)>> {
         self.iter_documents(rtxn, ids)?.collect()
     }
 
@@ -1276,7 +1451,7 @@ impl Index {
     pub fn all_documents<'a, 't: 'a>(
         &'a self,
         rtxn: &'t RoTxn,
-    ) -> Result<impl Iterator<Item = Result<(DocumentId, obkv::KvReaderU16<'t>)>> + 'a> {
+    ) -> Result<impl Iterator<Item = Result<(DocumentId, ObkvDocument<'t>)>> + 'a> {
         self.iter_documents(rtxn, self.documents_ids(rtxn)?)
     }
 
@@ -1300,7 +1475,7 @@ impl Index {
         })?;
         Ok(self.iter_documents(rtxn, ids)?.map(move |entry| -> Result<_> {
             let (_docid, obkv) = entry?;
-            match primary_key.document_id(&obkv, &fields)? {
+            match primary_key.document_id(&obkv.as_obkv(), &fields)? {
                 Ok(document_id) => Ok(document_id),
                 Err(_) => Err(InternalError::DocumentsError(
                     crate::documents::Error::InvalidDocumentFormat,
@@ -1601,22 +1776,6 @@ impl Index {
             .unwrap_or_default())
     }
 
-    pub fn arroy_readers<'a>(
-        &'a self,
-        rtxn: &'a RoTxn<'a>,
-        embedder_id: u8,
-    ) -> impl Iterator<Item = Result<arroy::Reader<arroy::distances::Angular>>> + 'a {
-        crate::vector::arroy_db_range_for_embedder(embedder_id).map_while(move |k| {
-            arroy::Reader::open(rtxn, k, self.vector_arroy)
-                .map(Some)
-                .or_else(|e| match e {
-                    arroy::Error::MissingMetadata(_) => Ok(None),
-                    e => Err(e.into()),
-                })
-                .transpose()
-        })
-    }
-
     pub(crate) fn put_search_cutoff(&self, wtxn: &mut RwTxn<'_>, cutoff: u64) -> heed::Result<()> {
         self.main.remap_types::<Str, BEU64>().put(wtxn, main_key::SEARCH_CUTOFF, &cutoff)
     }
@@ -1629,37 +1788,110 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(wtxn, main_key::SEARCH_CUTOFF)
     }
 
+    /// Whether the ranking rules of the index can be overridden at search time.
+    pub fn allow_ranking_rules_override(&self, rtxn: &RoTxn<'_>) -> heed::Result<bool> {
+        // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead. We
+        // identify 0 as being false, and anything else as true. The absence of a value is false,
+        // because by default, the ranking rules cannot be overridden.
+        match self
+            .main
+            .remap_types::<Str, U8>()
+            .get(rtxn, main_key::ALLOW_RANKING_RULES_OVERRIDE)?
+        {
+            Some(0) | None => Ok(false),
+            Some(_) => Ok(true),
+        }
+    }
+
+    pub(crate) fn put_allow_ranking_rules_override(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        flag: bool,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, U8>().put(
+            wtxn,
+            main_key::ALLOW_RANKING_RULES_OVERRIDE,
+            &(flag as u8),
+        )
+    }
+
+    pub(crate) fn delete_allow_ranking_rules_override(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+    ) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::ALLOW_RANKING_RULES_OVERRIDE)
+    }
+
+    pub fn localized_attributes_rules(
+        &self,
+        rtxn: &RoTxn<'_>,
+    ) -> heed::Result<Option<Vec<LocalizedAttributesRule>>> {
+        self.main
+            .remap_types::<Str, SerdeJson<Vec<LocalizedAttributesRule>>>()
+            .get(rtxn, main_key::LOCALIZED_ATTRIBUTES_RULES)
+    }
+
+    pub(crate) fn put_localized_attributes_rules(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: Vec<LocalizedAttributesRule>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<Vec<LocalizedAttributesRule>>>().put(
+            txn,
+            main_key::LOCALIZED_ATTRIBUTES_RULES,
+            &val,
+        )
+    }
+
+    pub(crate) fn delete_localized_attributes_rules(
+        &self,
+        txn: &mut RwTxn<'_>,
+    ) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::LOCALIZED_ATTRIBUTES_RULES)
+    }
+
+    /* merchandising rules */
+
+    /// Returns the merchandising rules of the index, identified by their uid.
+    pub fn merchandising_rules(&self, rtxn: &RoTxn<'_>) -> heed::Result<MerchandisingRules> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<MerchandisingRules>>()
+            .get(rtxn, main_key::MERCHANDISING_RULES)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_merchandising_rules(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        rules: &MerchandisingRules,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<MerchandisingRules>>().put(
+            wtxn,
+            main_key::MERCHANDISING_RULES,
+            rules,
+        )
+    }
+
+    pub(crate) fn delete_merchandising_rules(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::MERCHANDISING_RULES)
+    }
+
     pub fn embeddings(
         &self,
         rtxn: &RoTxn<'_>,
         docid: DocumentId,
     ) -> Result<BTreeMap<String, Vec<Embedding>>> {
         let mut res = BTreeMap::new();
+        let embedding_configs = self.embedding_configs(rtxn)?;
         for row in self.embedder_category_id.iter(rtxn)? {
             let (embedder_name, embedder_id) = row?;
-            let embedder_id = (embedder_id as u16) << 8;
-            let mut embeddings = Vec::new();
-            'vectors: for i in 0..=u8::MAX {
-                let reader = arroy::Reader::open(rtxn, embedder_id | (i as u16), self.vector_arroy)
-                    .map(Some)
-                    .or_else(|e| match e {
-                        arroy::Error::MissingMetadata(_) => Ok(None),
-                        e => Err(e),
-                    })
-                    .transpose();
-
-                let Some(reader) = reader else {
-                    break 'vectors;
-                };
-
-                let embedding = reader?.item_vector(rtxn, docid)?;
-                if let Some(embedding) = embedding {
-                    embeddings.push(embedding)
-                } else {
-                    break 'vectors;
-                }
-            }
-
+            let quantized = embedding_configs
+                .iter()
+                .find(|config| config.name == embedder_name)
+                .map_or(false, |config| config.config.quantized());
+            let embeddings = ArroyWrapper::new(self.vector_arroy, embedder_id, quantized)
+                .item_vectors(rtxn, docid)?;
             if !embeddings.is_empty() {
                 res.insert(embedder_name.to_owned(), embeddings);
             }
@@ -2410,8 +2642,9 @@ pub(crate) mod tests {
         "###);
 
         let rtxn = index.read_txn().unwrap();
-        let (_docid, obkv) = index.documents(&rtxn, [0]).unwrap()[0];
-        let json = obkv_to_json(&[0, 1, 2], &index.fields_ids_map(&rtxn).unwrap(), obkv).unwrap();
+        let (_docid, obkv) = index.documents(&rtxn, [0]).unwrap().remove(0);
+        let json = obkv_to_json(&[0, 1, 2], &index.fields_ids_map(&rtxn).unwrap(), obkv.as_obkv())
+            .unwrap();
         insta::assert_debug_snapshot!(json, @r###"
         {
             "primary_key": Number(30),
@@ -2419,8 +2652,9 @@ pub(crate) mod tests {
         "###);
 
         // Furthermore, when we retrieve document 34, it is not the result of merging 35 with 34
-        let (_docid, obkv) = index.documents(&rtxn, [2]).unwrap()[0];
-        let json = obkv_to_json(&[0, 1, 2], &index.fields_ids_map(&rtxn).unwrap(), obkv).unwrap();
+        let (_docid, obkv) = index.documents(&rtxn, [2]).unwrap().remove(0);
+        let json = obkv_to_json(&[0, 1, 2], &index.fields_ids_map(&rtxn).unwrap(), obkv.as_obkv())
+            .unwrap();
         insta::assert_debug_snapshot!(json, @r###"
         {
             "primary_key": Number(34),
@@ -2629,7 +2863,7 @@ pub(crate) mod tests {
         let docs = index.documents(&rtxn, documents_ids).unwrap();
         let mut all_ids = HashSet::new();
         for (_docid, obkv) in docs {
-            let id = obkv.get(primary_key_id).unwrap();
+            let id = obkv.as_obkv().get(primary_key_id).unwrap().to_vec();
             assert!(all_ids.insert(id));
         }
     }
@@ -2889,4 +3123,53 @@ pub(crate) mod tests {
             .unwrap();
         assert!(results.candidates.is_empty());
     }
+
+    #[test]
+    fn refuse_to_open_an_index_with_vectors_stored_by_arroy_0_4() {
+        use heed::types::{Bytes, Str, U8};
+        use heed::Database;
+        use roaring::RoaringBitmap;
+
+        use crate::index::db_name::{VECTOR_ARROY, VECTOR_EMBEDDER_CATEGORY_ID};
+
+        let tempdir = TempDir::new_in(".").unwrap();
+        let vectors: [[f32; 2]; 3] = [[1.0, 0.0], [0.0, 1.0], [0.7, 0.7]];
+
+        // Write the vectors of the default embedder in the format of arroy 0.4 and its angular distance
+        let mut options = EnvOpenOptions::new();
+        options.map_size(4096 * 2000).max_dbs(2);
+        let env = unsafe { options.open(tempdir.path()) }.unwrap();
+        let mut wtxn = env.write_txn().unwrap();
+        let categories: Database<Str, U8> =
+            env.create_database(&mut wtxn, Some(VECTOR_EMBEDDER_CATEGORY_ID)).unwrap();
+        categories.put(&mut wtxn, "default", &0).unwrap();
+        let arroy: Database<Bytes, Bytes> =
+            env.create_database(&mut wtxn, Some(VECTOR_ARROY)).unwrap();
+        for (item, vector) in vectors.iter().enumerate() {
+            let [a, b, c, d] = (item as u32).to_be_bytes();
+            let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+            let mut leaf = vec![0];
+            leaf.extend_from_slice(&norm.to_ne_bytes());
+            leaf.extend(vector.iter().flat_map(|x| x.to_ne_bytes()));
+            arroy.put(&mut wtxn, &[0, 0, 0, a, b, c, d, 0], &leaf).unwrap();
+        }
+        let items = RoaringBitmap::from_sorted_iter(0..3).unwrap();
+        let mut descendants = vec![1];
+        items.serialize_into(&mut descendants).unwrap();
+        arroy.put(&mut wtxn, &[0, 0, 1, 0, 0, 0, 0, 0], &descendants).unwrap();
+        let mut metadata = b"angular\0".to_vec();
+        metadata.extend_from_slice(&2u32.to_be_bytes());
+        metadata.extend_from_slice(&(items.serialized_size() as u32).to_be_bytes());
+        items.serialize_into(&mut metadata).unwrap();
+        metadata.extend_from_slice(&0u32.to_ne_bytes());
+        arroy.put(&mut wtxn, &[0, 0, 2, 0, 0, 0, 0, 0], &metadata).unwrap();
+        wtxn.commit().unwrap();
+        env.prepare_for_closing().wait();
+
+        // The index can't be opened until its vector stores are upgraded
+        let mut options = EnvOpenOptions::new();
+        options.map_size(4096 * 2000);
+        let error = Index::new(options, tempdir.path()).unwrap_err();
+        assert!(matches!(error, Error::InternalError(InternalError::OutdatedVectorStore)));
+    }
 }
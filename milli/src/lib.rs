@@ -14,8 +14,11 @@ mod error;
 mod external_documents_ids;
 pub mod facet;
 mod fields_ids_map;
+pub mod geojson;
 pub mod heed_codec;
 pub mod index;
+pub mod localized_attributes_rules;
+pub mod merchandising_rules;
 pub mod order_by_map;
 pub mod prompt;
 pub mod proximity;
@@ -45,9 +48,9 @@ pub use search::new::{
 };
 use serde_json::Value;
 pub use thread_pool_no_abort::{PanicCatched, ThreadPoolNoAbort, ThreadPoolNoAbortBuilder};
-pub use {charabia as tokenizer, heed};
+pub use {arroy, charabia as tokenizer, heed};
 
-pub use self::asc_desc::{AscDesc, AscDescError, Member, SortError};
+pub use self::asc_desc::{AscDesc, AscDescError, DecayFunction, GeoDecay, Member, SortError};
 pub use self::criterion::{default_criteria, Criterion, CriterionError};
 pub use self::error::{
     Error, FieldIdMapMissingEntry, InternalError, SerializationError, UserError,
@@ -57,16 +60,19 @@ pub use self::fieldids_weights_map::FieldidsWeightsMap;
 pub use self::fields_ids_map::FieldsIdsMap;
 pub use self::heed_codec::{
     BEU16StrCodec, BEU32StrCodec, BoRoaringBitmapCodec, BoRoaringBitmapLenCodec,
-    CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, FieldIdWordCountCodec, ObkvCodec,
-    RoaringBitmapCodec, RoaringBitmapLenCodec, StrBEU32Codec, U8StrStrCodec,
-    UncheckedU8StrStrCodec,
+    CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, CompressedObkvCodec, CompressedObkvU16,
+    FieldIdWordCountCodec, ObkvCodec, ObkvDocument, RoaringBitmapCodec, RoaringBitmapLenCodec,
+    StrBEU32Codec, U8StrStrCodec, UncheckedU8StrStrCodec,
 };
 pub use self::index::Index;
+pub use self::localized_attributes_rules::LocalizedAttributesRule;
+pub use self::merchandising_rules::{MerchandisingRule, MerchandisingRules};
 pub use self::search::facet::{FacetValueHit, SearchForFacetValues};
 pub use self::search::similar::Similar;
 pub use self::search::{
-    FacetDistribution, Filter, FormatOptions, MatchBounds, MatcherBuilder, MatchingWords, OrderBy,
-    Search, SearchResult, SemanticSearch, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    Boost, FacetAggregation, FacetDistribution, Filter, FormatOptions, HistogramBucket,
+    HistogramBuckets, MatchBounds, MatcherBuilder, MatchingWords, OrderBy, Search, SearchResult,
+    SemanticSearch, Suggest, Suggestion, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
 };
 
 pub type Result<T> = std::result::Result<T, error::Error>;
@@ -94,6 +100,12 @@ pub type SmallVec8<T> = smallvec::SmallVec<[T; 8]>;
 /// expressed in term of latitude and longitude.
 pub type GeoPoint = rstar::primitives::GeomWithData<[f64; 3], (DocumentId, [f64; 2])>;
 
+/// A GeoJsonShape is the envelope of a `_geojson` geometry expressed in term of latitude and
+/// longitude. Its metadata is the DocumentId of the associated document, the geometry itself
+/// is stored in the `geojson_geometries` database.
+pub type GeoJsonShape =
+    rstar::primitives::GeomWithData<rstar::primitives::Rectangle<[f64; 2]>, DocumentId>;
+
 /// The maximum length a LMDB key can be.
 ///
 /// Note that the actual allowed length is a little bit higher, but
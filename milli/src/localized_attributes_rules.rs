@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+
+use charabia::{Language, Script};
+use serde::{Deserialize, Serialize};
+
+use crate::fields_ids_map::FieldsIdsMap;
+use crate::FieldId;
+
+/// A rule that defines which locales are supported for a given attribute.
+///
+/// The rule is a list of attribute patterns and a list of locales.
+/// The attribute patterns are matched against the attribute name.
+/// The pattern `*` matches any attribute name.
+/// The pattern `attribute*` matches any attribute name that starts with `attribute`.
+/// The pattern `*attribute` matches any attribute name that ends with `attribute`.
+/// The pattern `*attribute*` matches any attribute name that contains `attribute`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedAttributesRule {
+    pub attribute_patterns: Vec<String>,
+    #[serde(with = "languages_codes")]
+    pub locales: Vec<Language>,
+}
+
+impl LocalizedAttributesRule {
+    pub fn new(attribute_patterns: Vec<String>, locales: Vec<Language>) -> Self {
+        Self { attribute_patterns, locales }
+    }
+
+    /// Returns `true` if the given attribute name is matched by one of the patterns of the rule.
+    pub fn match_str(&self, str: &str) -> bool {
+        self.attribute_patterns.iter().any(|pattern| match_pattern(pattern.as_str(), str))
+    }
+
+    pub fn locales(&self) -> &[Language] {
+        &self.locales
+    }
+}
+
+fn match_pattern(pattern: &str, str: &str) -> bool {
+    if pattern == "*" {
+        true
+    } else if pattern.starts_with('*') && pattern.ends_with('*') && pattern.len() > 1 {
+        str.contains(&pattern[1..pattern.len() - 1])
+    } else if let Some(pattern) = pattern.strip_prefix('*') {
+        str.ends_with(pattern)
+    } else if let Some(pattern) = pattern.strip_suffix('*') {
+        str.starts_with(pattern)
+    } else {
+        pattern == str
+    }
+}
+
+/// The locales associated with each field id,
+/// resolved from the `localizedAttributes` rules of an index.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LocalizedFieldIds {
+    field_id_to_locales: HashMap<FieldId, Vec<Language>>,
+}
+
+impl LocalizedFieldIds {
+    /// Associates each of the given field ids to the locales of the first rule matching its name.
+    ///
+    /// The fields that are not matched by any rule, or matched by a rule without locales,
+    /// are not localized.
+    pub fn new<I: Iterator<Item = FieldId>>(
+        rules: &Option<Vec<LocalizedAttributesRule>>,
+        fields_ids_map: &FieldsIdsMap,
+        fields_ids: I,
+    ) -> Self {
+        let mut field_id_to_locales = HashMap::new();
+
+        if let Some(rules) = rules {
+            for field_id in fields_ids {
+                let Some(field_name) = fields_ids_map.name(field_id) else { continue };
+                let locales = rules
+                    .iter()
+                    .find(|rule| rule.match_str(field_name))
+                    .map(|rule| rule.locales.clone())
+                    .unwrap_or_default();
+
+                if !locales.is_empty() {
+                    field_id_to_locales.insert(field_id, locales);
+                }
+            }
+        }
+
+        Self { field_id_to_locales }
+    }
+
+    pub fn locales(&self, fields_id: FieldId) -> Option<&[Language]> {
+        self.field_id_to_locales.get(&fields_id).map(Vec::as_slice)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.field_id_to_locales.is_empty()
+    }
+
+    /// Returns the localized field ids with their locales.
+    pub fn iter(&self) -> impl Iterator<Item = (FieldId, &[Language])> {
+        self.field_id_to_locales.iter().map(|(field_id, locales)| (*field_id, locales.as_slice()))
+    }
+}
+
+/// Builds the tokenizer allow list restricting the language detection to the given locales.
+///
+/// The allow list of the tokenizer is grouped by script,
+/// a locale without any known script is ignored.
+pub fn locales_allow_list(locales: &[Language]) -> HashMap<Script, Vec<Language>> {
+    let mut allow_list: HashMap<Script, Vec<Language>> = HashMap::new();
+    for &language in locales {
+        if let Some(script) = language_script(language) {
+            let languages = allow_list.entry(script).or_default();
+            if !languages.contains(&language) {
+                languages.push(language);
+            }
+        }
+    }
+    allow_list
+}
+
+/// The script in which a language detected by the tokenizer is written.
+fn language_script(language: Language) -> Option<Script> {
+    use Language::*;
+
+    let script = match language {
+        Epo | Eng | Spa | Por | Ita | Fra | Deu | Pol | Jav | Nob | Dan | Swe | Fin | Tur | Nld
+        | Hun | Ces | Ron | Slv | Hrv | Lit | Lav | Est | Vie | Uzb | Aze | Ind | Tuk | Aka
+        | Zul | Sna | Afr | Lat | Slk | Cat | Tgl => Script::Latin,
+        Rus | Ukr | Bul | Bel | Srp | Mkd => Script::Cyrillic,
+        Cmn | Jpn => Script::Cj,
+        Ara | Urd | Pes => Script::Arabic,
+        Hin | Mar | Nep => Script::Devanagari,
+        Heb | Yid => Script::Hebrew,
+        Ben => Script::Bengali,
+        Kat => Script::Georgian,
+        Amh => Script::Ethiopic,
+        Kor => Script::Hangul,
+        Ell => Script::Greek,
+        Kan => Script::Kannada,
+        Tam => Script::Tamil,
+        Tha => Script::Thai,
+        Guj => Script::Gujarati,
+        Pan => Script::Gurmukhi,
+        Tel => Script::Telugu,
+        Mal => Script::Malayalam,
+        Ori => Script::Oriya,
+        Mya => Script::Myanmar,
+        Sin => Script::Sinhala,
+        Khm => Script::Khmer,
+        Hye => Script::Armenian,
+        _ => return None,
+    };
+
+    Some(script)
+}
+
+/// (De)serializes the languages using their ISO-639-3 codes.
+mod languages_codes {
+    use charabia::Language;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        languages: &[Language],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let codes: Vec<_> = languages.iter().map(Language::name).collect();
+        codes.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Language>, D::Error> {
+        let codes = Vec::<String>::deserialize(deserializer)?;
+        Ok(codes.iter().map(Language::from_name).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_attribute_patterns() {
+        let rule = LocalizedAttributesRule::new(
+            vec!["title_*".to_string(), "*_ja".to_string(), "*desc*".to_string()],
+            vec![Language::Jpn],
+        );
+
+        assert!(rule.match_str("title_main"));
+        assert!(rule.match_str("name_ja"));
+        assert!(rule.match_str("short_description"));
+        assert!(!rule.match_str("title"));
+        assert!(!rule.match_str("name_jp"));
+
+        let rule = LocalizedAttributesRule::new(vec!["*".to_string()], vec![Language::Jpn]);
+        assert!(rule.match_str("anything"));
+
+        let rule = LocalizedAttributesRule::new(vec!["title".to_string()], vec![Language::Jpn]);
+        assert!(rule.match_str("title"));
+        assert!(!rule.match_str("title.en"));
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let mut fields_ids_map = FieldsIdsMap::new();
+        let title = fields_ids_map.insert("title").unwrap();
+        let title_ja = fields_ids_map.insert("title_ja").unwrap();
+        let description = fields_ids_map.insert("description").unwrap();
+
+        let rules = Some(vec![
+            LocalizedAttributesRule::new(vec!["*_ja".to_string()], vec![Language::Jpn]),
+            LocalizedAttributesRule::new(
+                vec!["title*".to_string()],
+                vec![Language::Eng, Language::Fra],
+            ),
+        ]);
+
+        let localized = LocalizedFieldIds::new(&rules, &fields_ids_map, fields_ids_map.ids());
+        assert_eq!(localized.locales(title), Some(&[Language::Eng, Language::Fra][..]));
+        assert_eq!(localized.locales(title_ja), Some(&[Language::Jpn][..]));
+        assert_eq!(localized.locales(description), None);
+
+        let allow_list = locales_allow_list(&[Language::Jpn, Language::Eng, Language::Jpn]);
+        assert_eq!(allow_list.get(&Script::Cj), Some(&vec![Language::Jpn]));
+        assert_eq!(allow_list.get(&Script::Latin), Some(&vec![Language::Eng]));
+    }
+}
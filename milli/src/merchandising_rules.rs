@@ -0,0 +1,60 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The merchandising rules of an index, identified by their uid.
+pub type MerchandisingRules = BTreeMap<String, MerchandisingRule>;
+
+/// A rule that pins, hides or filters documents for the searches matching its condition.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MerchandisingRule {
+    pub condition: RuleCondition,
+    pub actions: RuleActions,
+}
+
+/// The searches a merchandising rule applies to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleCondition {
+    /// The pattern matched against the query of the search.
+    pub query: String,
+    /// How the pattern is matched against the query of the search.
+    #[serde(rename = "match")]
+    pub query_match: QueryMatch,
+    /// The filter expressions that the filter of the search must contain for the rule to apply.
+    pub filter: Option<Value>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QueryMatch {
+    /// The query is equal to the pattern.
+    #[default]
+    Exact,
+    /// The query contains the pattern.
+    Contains,
+    /// The query is matched by the pattern, interpreted as a regular expression.
+    Regex,
+}
+
+/// What a merchandising rule does to the searches it applies to.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleActions {
+    /// The documents to place at a fixed position in the results.
+    pub pin: Vec<PinnedDocument>,
+    /// The external ids of the documents to remove from the results.
+    pub hide: Vec<String>,
+    /// A filter applied on top of the filter of the search.
+    pub filter: Option<Value>,
+}
+
+/// A document pinned at a fixed position, starting at zero, of the results.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinnedDocument {
+    pub id: String,
+    pub position: usize,
+}
@@ -3,7 +3,7 @@ use std::cmp::Ordering;
 use itertools::Itertools;
 use serde::Serialize;
 
-use crate::distance_between_two_points;
+use crate::{distance_between_two_points, GeoDecay};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ScoreDetails {
@@ -12,11 +12,14 @@ pub enum ScoreDetails {
     Proximity(Rank),
     Fid(Rank),
     Position(Rank),
+    AttributeRank(Rank),
+    WordPosition(Rank),
     ExactAttribute(ExactAttribute),
     ExactWords(ExactWords),
     Sort(Sort),
     Vector(Vector),
     GeoSort(GeoSort),
+    Boost(Boost),
 
     /// Returned when we don't have the time to finish applying all the subsequent ranking-rules
     Skipped,
@@ -48,10 +51,13 @@ impl ScoreDetails {
             ScoreDetails::Proximity(details) => Some(*details),
             ScoreDetails::Fid(details) => Some(*details),
             ScoreDetails::Position(details) => Some(*details),
+            ScoreDetails::AttributeRank(details) => Some(*details),
+            ScoreDetails::WordPosition(details) => Some(*details),
             ScoreDetails::ExactAttribute(details) => Some(details.rank()),
             ScoreDetails::ExactWords(details) => Some(details.rank()),
             ScoreDetails::Sort(_) => None,
-            ScoreDetails::GeoSort(_) => None,
+            ScoreDetails::GeoSort(details) => details.decay_rank(),
+            ScoreDetails::Boost(details) => Some(details.rank()),
             ScoreDetails::Vector(_) => None,
             ScoreDetails::Skipped => Some(Rank { rank: 0, max_rank: 1 }),
         }
@@ -94,10 +100,17 @@ impl ScoreDetails {
             ScoreDetails::Proximity(p) => RankOrValue::Rank(*p),
             ScoreDetails::Fid(f) => RankOrValue::Rank(*f),
             ScoreDetails::Position(p) => RankOrValue::Rank(*p),
+            ScoreDetails::AttributeRank(a) => RankOrValue::Rank(*a),
+            ScoreDetails::WordPosition(w) => RankOrValue::Rank(*w),
             ScoreDetails::ExactAttribute(e) => RankOrValue::Rank(e.rank()),
             ScoreDetails::ExactWords(e) => RankOrValue::Rank(e.rank()),
             ScoreDetails::Sort(sort) => RankOrValue::Sort(sort),
-            ScoreDetails::GeoSort(geosort) => RankOrValue::GeoSort(geosort),
+            ScoreDetails::Boost(boost) => RankOrValue::Rank(boost.rank()),
+            ScoreDetails::GeoSort(geosort) => match geosort.decay_rank() {
+                // a decayed geo sort has a normalized score that can be merged with the others
+                Some(rank) => RankOrValue::Rank(rank),
+                None => RankOrValue::GeoSort(geosort),
+            },
             ScoreDetails::Vector(vector) => {
                 RankOrValue::Score(vector.similarity.as_ref().map(|s| *s as f64).unwrap_or(0.0f64))
             }
@@ -175,6 +188,22 @@ impl ScoreDetails {
 
                     // do not update the order since this was already done by fid
                 }
+                ScoreDetails::AttributeRank(attribute_rank) => {
+                    let attribute_rank_details = serde_json::json!({
+                        "order": order,
+                        "score": attribute_rank.local_score(),
+                    });
+                    details_map.insert("attributeRank".into(), attribute_rank_details);
+                    order += 1;
+                }
+                ScoreDetails::WordPosition(word_position) => {
+                    let word_position_details = serde_json::json!({
+                        "order": order,
+                        "score": word_position.local_score(),
+                    });
+                    details_map.insert("wordPosition".into(), word_position_details);
+                    order += 1;
+                }
                 ScoreDetails::ExactAttribute(exact_attribute) => {
                     let exactness_details = serde_json::json!({
                         "order": order,
@@ -229,25 +258,44 @@ impl ScoreDetails {
                     order += 1;
                 }
                 ScoreDetails::GeoSort(details) => {
-                    let sort = format!(
-                        "_geoPoint({}, {}):{}",
-                        details.target_point[0],
-                        details.target_point[1],
-                        if details.ascending { "asc" } else { "desc" }
-                    );
+                    let sort = match details.decay {
+                        Some(decay) => format!(
+                            "_geoPoint({}, {}):{}",
+                            details.target_point[0], details.target_point[1], decay
+                        ),
+                        None => format!(
+                            "_geoPoint({}, {}):{}",
+                            details.target_point[0],
+                            details.target_point[1],
+                            if details.ascending { "asc" } else { "desc" }
+                        ),
+                    };
                     let point = if let Some(value) = details.value {
                         serde_json::json!({ "lat": value[0], "lng": value[1]})
                     } else {
                         serde_json::Value::Null
                     };
-                    let sort_details = serde_json::json!({
+                    let mut sort_details = serde_json::json!({
                         "order": order,
                         "value": point,
                         "distance": details.distance(),
                     });
+                    if let Some(rank) = details.decay_rank() {
+                        sort_details["score"] = rank.local_score().into();
+                    }
                     details_map.insert(sort, sort_details);
                     order += 1;
                 }
+                ScoreDetails::Boost(boost) => {
+                    let boost_details = serde_json::json!({
+                        "order": order,
+                        "weight": boost.weight,
+                        "maxWeight": boost.max_weight,
+                        "score": boost.rank().local_score(),
+                    });
+                    details_map.insert("boost".into(), boost_details);
+                    order += 1;
+                }
                 ScoreDetails::Vector(s) => {
                     let similarity = s.similarity.as_ref();
 
@@ -462,6 +510,8 @@ pub struct GeoSort {
     pub target_point: [f64; 2],
     pub ascending: bool,
     pub value: Option<[f64; 2]>,
+    /// The decay applied to the distance, the documents are then bucketed by their score.
+    pub decay: Option<GeoDecay>,
 }
 
 impl PartialOrd for GeoSort {
@@ -469,9 +519,12 @@ impl PartialOrd for GeoSort {
         if self.target_point != other.target_point {
             return None;
         }
-        if self.ascending != other.ascending {
+        if self.ascending != other.ascending || self.decay != other.decay {
             return None;
         }
+        if let (Some(left), Some(right)) = (self.decay_rank(), other.decay_rank()) {
+            return left.rank.partial_cmp(&right.rank);
+        }
         Some(match (self.distance(), other.distance()) {
             (None, None) => Ordering::Equal,
             (None, Some(_)) => Ordering::Less,
@@ -494,8 +547,45 @@ pub struct Vector {
     pub similarity: Option<f32>,
 }
 
+/// The number of buckets the decayed geo scores are rounded to.
+const GEO_DECAY_PRECISION: u32 = 100;
+
 impl GeoSort {
     pub fn distance(&self) -> Option<f64> {
         self.value.map(|value| distance_between_two_points(&self.target_point, &value))
     }
+
+    /// The rank of the decayed score, the documents without any geo point get the lowest rank.
+    pub fn decay_rank(&self) -> Option<Rank> {
+        let decay = self.decay?;
+        let rank = match self.distance() {
+            Some(distance) => {
+                2 + (decay.score(distance) * GEO_DECAY_PRECISION as f64).round() as u32
+            }
+            None => 1,
+        };
+        Some(Rank { rank, max_rank: GEO_DECAY_PRECISION + 2 })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Boost {
+    /// The sum of the weights of the boost filters matched by the document.
+    pub weight: f64,
+    /// The sum of the weights of all the boost filters of the query.
+    pub max_weight: f64,
+}
+
+/// The number of buckets the boost scores are rounded to.
+const BOOST_PRECISION: u32 = 100;
+
+impl Boost {
+    /// The rank of the boost, the documents that match none of the boost filters get the lowest rank.
+    pub fn rank(&self) -> Rank {
+        let score = if self.max_weight > 0.0 { self.weight / self.max_weight } else { 0.0 };
+        Rank {
+            rank: 1 + (score * BOOST_PRECISION as f64).round() as u32,
+            max_rank: BOOST_PRECISION + 1,
+        }
+    }
 }
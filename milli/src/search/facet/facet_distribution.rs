@@ -27,6 +27,9 @@ pub const DEFAULT_VALUES_PER_FACET: usize = 100;
 /// the system to choose between one algorithm or another.
 const CANDIDATES_THRESHOLD: u64 = 3000;
 
+/// The maximum number of buckets a fixed-width histogram can contain.
+pub const MAX_HISTOGRAM_BUCKETS: u64 = 1000;
+
 /// How should we fetch the facets?
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OrderBy {
@@ -37,11 +40,39 @@ pub enum OrderBy {
     Count,
 }
 
+/// How the values of a numeric facet are grouped into the buckets of a histogram.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HistogramBuckets {
+    /// Buckets of the given width, aligned on the multiples of the width.
+    Interval(f64),
+    /// Explicit buckets, each one including its lower bound and excluding its upper bound.
+    Ranges(Vec<(f64, f64)>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HistogramBucket {
+    pub from: f64,
+    pub to: f64,
+    /// The number of facet values of the candidates that are in the bucket,
+    /// a document with several values in the bucket is counted once per value.
+    pub count: u64,
+}
+
+/// The aggregations computed over the values of a numeric facet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetAggregation {
+    pub sum: f64,
+    pub avg: f64,
+    pub histogram: Option<Vec<HistogramBucket>>,
+}
+
 pub struct FacetDistribution<'a> {
     facets: Option<HashMap<String, OrderBy>>,
     candidates: Option<RoaringBitmap>,
     max_values_per_facet: usize,
     default_order_by: OrderBy,
+    histograms: HashMap<String, HistogramBuckets>,
+    aggregate_all_facets: bool,
     rtxn: &'a heed::RoTxn<'a>,
     index: &'a Index,
 }
@@ -53,6 +84,8 @@ impl<'a> FacetDistribution<'a> {
             candidates: None,
             max_values_per_facet: DEFAULT_VALUES_PER_FACET,
             default_order_by: OrderBy::default(),
+            histograms: HashMap::new(),
+            aggregate_all_facets: false,
             rtxn,
             index,
         }
@@ -86,6 +119,19 @@ impl<'a> FacetDistribution<'a> {
         self
     }
 
+    /// The histograms to compute along the aggregations, by facet name.
+    pub fn histograms(&mut self, histograms: HashMap<String, HistogramBuckets>) -> &mut Self {
+        self.histograms = histograms;
+        self
+    }
+
+    /// Whether the aggregations are computed for all the facets or only for the ones
+    /// with a histogram, computing them requires iterating over all the facet values.
+    pub fn aggregate_all_facets(&mut self, aggregate_all_facets: bool) -> &mut Self {
+        self.aggregate_all_facets = aggregate_all_facets;
+        self
+    }
+
     /// There is a small amount of candidates OR we ask for facet string values so we
     /// decide to iterate over the facet values of each one of them, one by one.
     fn facet_distribution_from_documents(
@@ -332,6 +378,90 @@ impl<'a> FacetDistribution<'a> {
         Ok(distribution)
     }
 
+    /// Computes the sum, the average and the requested histograms of the values
+    /// of the numeric facets among the candidates.
+    ///
+    /// Only the facets with a histogram are aggregated unless all the facets were requested.
+    pub fn compute_aggregations(&self) -> Result<BTreeMap<String, FacetAggregation>> {
+        if !self.aggregate_all_facets && self.histograms.is_empty() {
+            return Ok(Default::default());
+        }
+
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+        let candidates = if let Some(candidates) = self.candidates.clone() {
+            candidates
+        } else {
+            return Ok(Default::default());
+        };
+
+        let fields = match &self.facets {
+            Some(facets) => {
+                let invalid_fields: HashSet<_> = facets
+                    .iter()
+                    .map(|(name, _)| name)
+                    .filter(|facet| !crate::is_faceted(facet, &filterable_fields))
+                    .collect();
+                if !invalid_fields.is_empty() {
+                    return Err(UserError::InvalidFacetsDistribution {
+                        invalid_facets_name: invalid_fields.into_iter().cloned().collect(),
+                        valid_facets_name: filterable_fields.into_iter().collect(),
+                    }
+                    .into());
+                } else {
+                    facets.iter().map(|(name, _)| name).cloned().collect()
+                }
+            }
+            None => filterable_fields,
+        };
+
+        let db =
+            self.index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+        let mut aggregations = BTreeMap::new();
+        for (fid, name) in fields_ids_map.iter() {
+            if !crate::is_faceted(name, &fields)
+                || !(self.aggregate_all_facets || self.histograms.contains_key(name))
+            {
+                continue;
+            }
+
+            // The facet values are returned in increasing order, along with the number of
+            // candidates containing them. The level tree lets us skip the groups of values
+            // that do not contain any candidate.
+            let mut values = Vec::new();
+            lexicographically_iterate_over_facet_distribution(
+                self.rtxn,
+                db,
+                fid,
+                &candidates,
+                |facet_key, count, _| {
+                    let value = OrderedF64Codec::bytes_decode(facet_key).unwrap();
+                    values.push((value, count));
+                    Ok(ControlFlow::Continue(()))
+                },
+            )?;
+
+            if values.is_empty() {
+                continue;
+            }
+
+            let (sum, total) = values.iter().fold((0.0, 0), |(sum, total), (value, count)| {
+                (sum + value * *count as f64, total + count)
+            });
+            let histogram = match self.histograms.get(name) {
+                Some(buckets) => Some(compute_histogram(name, buckets, &values)?),
+                None => None,
+            };
+
+            aggregations.insert(
+                name.to_string(),
+                FacetAggregation { sum, avg: sum / total as f64, histogram },
+            );
+        }
+
+        Ok(aggregations)
+    }
+
     pub fn execute(&self) -> Result<BTreeMap<String, IndexMap<String, u64>>> {
         let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
         let filterable_fields = self.index.filterable_fields(self.rtxn)?;
@@ -373,6 +503,68 @@ impl<'a> FacetDistribution<'a> {
     }
 }
 
+/// Counts the facet values falling in each bucket, the values must be sorted in increasing order.
+fn compute_histogram(
+    facet_name: &str,
+    buckets: &HistogramBuckets,
+    values: &[(f64, u64)],
+) -> Result<Vec<HistogramBucket>> {
+    match buckets {
+        HistogramBuckets::Interval(interval) => {
+            let (Some((min, _)), Some((max, _))) = (values.first(), values.last()) else {
+                return Ok(Vec::new());
+            };
+            let first_bucket = (min / interval).floor();
+            let last_bucket = (max / interval).floor();
+            // a tiny interval can make the number of buckets overflow an integer,
+            // it must be checked before the conversion.
+            let number_of_buckets = last_bucket - first_bucket + 1.0;
+            if !(number_of_buckets <= MAX_HISTOGRAM_BUCKETS as f64) {
+                return Err(UserError::TooManyHistogramBuckets {
+                    facet_name: facet_name.to_string(),
+                    // saturates on overflow
+                    buckets: number_of_buckets as u64,
+                    max_buckets: MAX_HISTOGRAM_BUCKETS,
+                }
+                .into());
+            }
+            let number_of_buckets = number_of_buckets as u64;
+
+            let mut histogram: Vec<_> = (0..number_of_buckets)
+                .map(|i| {
+                    let from = (first_bucket + i as f64) * interval;
+                    HistogramBucket { from, to: from + interval, count: 0 }
+                })
+                .collect();
+            for (value, count) in values {
+                let bucket = ((value / interval).floor() - first_bucket) as usize;
+                histogram[bucket].count += count;
+            }
+            Ok(histogram)
+        }
+        HistogramBuckets::Ranges(ranges) => {
+            if ranges.len() as u64 > MAX_HISTOGRAM_BUCKETS {
+                return Err(UserError::TooManyHistogramBuckets {
+                    facet_name: facet_name.to_string(),
+                    buckets: ranges.len() as u64,
+                    max_buckets: MAX_HISTOGRAM_BUCKETS,
+                }
+                .into());
+            }
+            Ok(ranges
+                .iter()
+                .map(|&(from, to)| {
+                    // the values are sorted, we can binary search the bounds of the range
+                    let start = values.partition_point(|(value, _)| *value < from);
+                    let end = values.partition_point(|(value, _)| *value < to);
+                    let count = values[start..end.max(start)].iter().map(|(_, count)| count).sum();
+                    HistogramBucket { from, to, count }
+                })
+                .collect())
+        }
+    }
+}
+
 impl fmt::Debug for FacetDistribution<'_> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let FacetDistribution {
@@ -380,6 +572,8 @@ impl fmt::Debug for FacetDistribution<'_> {
             candidates,
             max_values_per_facet,
             default_order_by,
+            histograms,
+            aggregate_all_facets,
             rtxn: _,
             index: _,
         } = self;
@@ -389,6 +583,8 @@ impl fmt::Debug for FacetDistribution<'_> {
             .field("candidates", candidates)
             .field("max_values_per_facet", max_values_per_facet)
             .field("default_order_by", default_order_by)
+            .field("histograms", histograms)
+            .field("aggregate_all_facets", aggregate_all_facets)
             .finish()
     }
 }
@@ -398,8 +594,9 @@ mod tests {
     use std::iter;
 
     use big_s::S;
-    use maplit::hashset;
+    use maplit::{hashmap, hashset};
 
+    use super::{HistogramBucket, HistogramBuckets};
     use crate::documents::documents_batch_reader_from_objects;
     use crate::index::tests::TempIndex;
     use crate::{milli_snap, FacetDistribution, OrderBy};
@@ -677,6 +874,125 @@ mod tests {
         milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": (217.0, 776.0)}"###);
     }
 
+    #[test]
+    fn facet_aggregations() {
+        let mut index = TempIndex::new_with_map_size(4096 * 10_000);
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| settings.set_filterable_fields(hashset! { S("colour") }))
+            .unwrap();
+
+        let documents = documents_batch_reader_from_objects(
+            (0..1000).map(|i| serde_json::json!({ "colour": i }).as_object().unwrap().clone()),
+        );
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("colour", OrderBy::default())))
+            .compute_aggregations()
+            .unwrap();
+        assert!(map.is_empty());
+
+        // without histogram, the facets are only aggregated when requested
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("colour", OrderBy::default())))
+            .candidates((0..1000).collect())
+            .compute_aggregations()
+            .unwrap();
+        assert!(map.is_empty());
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("colour", OrderBy::default())))
+            .candidates((0..1000).collect())
+            .aggregate_all_facets(true)
+            .compute_aggregations()
+            .unwrap();
+        assert_eq!(map["colour"].sum, 499_500.0);
+        assert!(map["colour"].histogram.is_none());
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("colour", OrderBy::default())))
+            .candidates((0..1000).collect())
+            .histograms(hashmap! { S("colour") => HistogramBuckets::Interval(300.0) })
+            .compute_aggregations()
+            .unwrap();
+        let aggregation = &map["colour"];
+        assert_eq!(aggregation.sum, 499_500.0);
+        assert_eq!(aggregation.avg, 499.5);
+        let histogram: Vec<_> = aggregation
+            .histogram
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|HistogramBucket { from, to, count }| (*from, *to, *count))
+            .collect();
+        assert_eq!(
+            histogram,
+            vec![(0.0, 300.0, 300), (300.0, 600.0, 300), (600.0, 900.0, 300), (900.0, 1200.0, 100)]
+        );
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("colour", OrderBy::default())))
+            .candidates((217..777).collect())
+            .histograms(hashmap! {
+                S("colour") => HistogramBuckets::Ranges(vec![(0.0, 250.0), (250.0, 500.0), (1000.0, 2000.0)])
+            })
+            .compute_aggregations()
+            .unwrap();
+        let aggregation = &map["colour"];
+        assert_eq!(aggregation.sum, (217..777).sum::<u32>() as f64);
+        assert_eq!(aggregation.avg, 496.5);
+        let counts: Vec<_> =
+            aggregation.histogram.as_ref().unwrap().iter().map(|bucket| bucket.count).collect();
+        assert_eq!(counts, vec![33, 250, 0]);
+
+        let error = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("colour", OrderBy::default())))
+            .candidates((0..1000).collect())
+            .histograms(hashmap! { S("colour") => HistogramBuckets::Interval(0.5) })
+            .compute_aggregations()
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            crate::Error::UserError(crate::UserError::TooManyHistogramBuckets {
+                buckets: 1999,
+                ..
+            })
+        ));
+
+        let error = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("colour", OrderBy::default())))
+            .candidates((0..1000).collect())
+            .histograms(hashmap! { S("colour") => HistogramBuckets::Interval(1e-300) })
+            .compute_aggregations()
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            crate::Error::UserError(crate::UserError::TooManyHistogramBuckets {
+                buckets: u64::MAX,
+                ..
+            })
+        ));
+
+        let ranges = (0..1001).map(|i| (i as f64, i as f64 + 1.0)).collect();
+        let error = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("colour", OrderBy::default())))
+            .candidates((0..1000).collect())
+            .histograms(hashmap! { S("colour") => HistogramBuckets::Ranges(ranges) })
+            .compute_aggregations()
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            crate::Error::UserError(crate::UserError::TooManyHistogramBuckets {
+                buckets: 1001,
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn facet_stats_array() {
         let mut index = TempIndex::new_with_map_size(4096 * 10_000);
@@ -5,10 +5,13 @@ use std::ops::Bound::{self, Excluded, Included};
 use either::Either;
 pub use filter_parser::{Condition, Error as FPError, FilterCondition, Token};
 use roaring::{MultiOps, RoaringBitmap};
+use rstar::AABB;
 use serde_json::Value;
 
 use super::facet_range_search;
+use super::geo_polygon::GeoPolygon;
 use crate::error::{Error, UserError};
+use crate::geojson::{circle_envelope, wrapped_envelopes, Geometry, RESERVED_GEOJSON_FIELD_NAME};
 use crate::heed_codec::facet::{
     FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, OrderedF64Codec,
 };
@@ -55,6 +58,7 @@ impl Display for BadGeoError {
 enum FilterError<'a> {
     AttributeNotFilterable { attribute: &'a str, filterable_fields: HashSet<String> },
     ParseGeoError(BadGeoError),
+    AmbiguousGeoPolygon,
     TooDeep,
 }
 impl<'a> std::error::Error for FilterError<'a> {}
@@ -96,6 +100,10 @@ impl<'a> Display for FilterError<'a> {
                 MAX_FILTER_DEPTH
             ),
             Self::ParseGeoError(error) => write!(f, "{}", error),
+            Self::AmbiguousGeoPolygon => write!(
+                f,
+                "The `_geoPolygon` filter is ambiguous: its edges must span less than 180 degrees of longitude and it must not go around a pole. Add intermediate vertices to split the longest edges.",
+            ),
         }
     }
 }
@@ -295,6 +303,30 @@ impl<'a> Filter<'a> {
                 let all_ids = index.documents_ids(rtxn)?;
                 return Ok(all_ids - docids);
             }
+            Condition::StartsWith(val) => {
+                let prefix = crate::normalize_facet(val.value());
+                let prefix_key = FacetGroupKey { field_id, level: 0, left_bound: prefix.as_str() };
+                let docids = strings_db
+                    .prefix_iter(rtxn, &prefix_key)?
+                    .map(|result| result.map(|(_, value)| value.bitmap))
+                    .union()?;
+                return Ok(docids);
+            }
+            Condition::Contains(val) => {
+                let substring = crate::normalize_facet(val.value());
+                // The facet values are only indexed by prefix, so we must go through all the
+                // values of the field, without decoding the bitmaps of the ones that don't match.
+                let field_key = FacetGroupKey { field_id, level: 0, left_bound: "" };
+                let mut docids = RoaringBitmap::new();
+                for result in strings_db.prefix_iter(rtxn, &field_key)?.lazily_decode_data() {
+                    let (FacetGroupKey { left_bound, .. }, lazy_value) = result?;
+                    if left_bound.contains(substring.as_str()) {
+                        let value = lazy_value.decode().map_err(heed::Error::Decoding)?;
+                        docids |= value.bitmap;
+                    }
+                }
+                return Ok(docids);
+            }
         };
 
         let mut output = RoaringBitmap::new();
@@ -433,7 +465,9 @@ impl<'a> Filter<'a> {
                 }
             }
             FilterCondition::GeoLowerThan { point, radius } => {
-                if filterable_fields.contains("_geo") {
+                let geo_filterable = filterable_fields.contains("_geo");
+                let geojson_filterable = filterable_fields.contains(RESERVED_GEOJSON_FIELD_NAME);
+                if geo_filterable || geojson_filterable {
                     let base_point: [f64; 2] =
                         [point[0].parse_finite_float()?, point[1].parse_finite_float()?];
                     if !(-90.0..=90.0).contains(&base_point[0]) {
@@ -443,21 +477,36 @@ impl<'a> Filter<'a> {
                         return Err(point[1].as_external_error(BadGeoError::Lng(base_point[1])))?;
                     }
                     let radius = radius.parse_finite_float()?;
-                    let rtree = match index.geo_rtree(rtxn)? {
-                        Some(rtree) => rtree,
-                        None => return Ok(RoaringBitmap::new()),
-                    };
-
-                    let xyz_base_point = lat_lng_to_xyz(&base_point);
+                    let mut result = RoaringBitmap::new();
+
+                    let geo_rtree = if geo_filterable { index.geo_rtree(rtxn)? } else { None };
+                    if let Some(rtree) = geo_rtree {
+                        let xyz_base_point = lat_lng_to_xyz(&base_point);
+
+                        result |= rtree
+                            .nearest_neighbor_iter(&xyz_base_point)
+                            .take_while(|point| {
+                                distance_between_two_points(&base_point, &point.data.1)
+                                    <= radius + f64::EPSILON
+                            })
+                            .map(|point| point.data.0)
+                            .collect::<RoaringBitmap>();
+                    }
 
-                    let result = rtree
-                        .nearest_neighbor_iter(&xyz_base_point)
-                        .take_while(|point| {
-                            distance_between_two_points(&base_point, &point.data.1)
-                                <= radius + f64::EPSILON
-                        })
-                        .map(|point| point.data.0)
-                        .collect();
+                    let geojson_rtree =
+                        if geojson_filterable { index.geojson_rtree(rtxn)? } else { None };
+                    if let Some(rtree) = geojson_rtree {
+                        // We first select the geometries intersecting the envelope of the circle
+                        // with the rtree, and then only keep the ones that really intersect it.
+                        let candidates = wrapped_envelopes(circle_envelope(&base_point, radius))
+                            .iter()
+                            .flat_map(|envelope| rtree.locate_in_envelope_intersecting(envelope))
+                            .map(|shape| shape.data)
+                            .collect();
+                        result |= filter_geojson_geometries(rtxn, index, candidates, |geometry| {
+                            geometry.intersects_circle(&base_point, radius)
+                        })?;
+                    }
 
                     Ok(result)
                 } else {
@@ -601,8 +650,87 @@ impl<'a> Filter<'a> {
                     ))?
                 }
             }
+            FilterCondition::GeoPolygon { points } => {
+                let geo_filterable = filterable_fields.contains("_geo");
+                let geojson_filterable = filterable_fields.contains(RESERVED_GEOJSON_FIELD_NAME);
+                if geo_filterable || geojson_filterable {
+                    let mut vertices = Vec::with_capacity(points.len());
+                    for [lat, lng] in points {
+                        let vertex = [lat.parse_finite_float()?, lng.parse_finite_float()?];
+                        if !(-90.0..=90.0).contains(&vertex[0]) {
+                            return Err(lat.as_external_error(BadGeoError::Lat(vertex[0])))?;
+                        }
+                        if !(-180.0..=180.0).contains(&vertex[1]) {
+                            return Err(lng.as_external_error(BadGeoError::Lng(vertex[1])))?;
+                        }
+                        vertices.push(vertex);
+                    }
+
+                    let polygon = match GeoPolygon::new(vertices) {
+                        Some(polygon) => polygon,
+                        None => {
+                            return Err(
+                                points[0][0].as_external_error(FilterError::AmbiguousGeoPolygon)
+                            )?
+                        }
+                    };
+                    let mut result = RoaringBitmap::new();
+
+                    let geo_rtree = if geo_filterable { index.geo_rtree(rtxn)? } else { None };
+                    if let Some(rtree) = geo_rtree {
+                        // We first select the points contained in the envelopes of the polygon
+                        // with the rtree, and then only keep the ones that are really inside of it.
+                        result |= polygon
+                            .xyz_envelopes()
+                            .iter()
+                            .flat_map(|envelope| rtree.locate_in_envelope(envelope))
+                            .filter(|point| polygon.contains(&point.data.1))
+                            .map(|point| point.data.0)
+                            .collect::<RoaringBitmap>();
+                    }
+
+                    let geojson_rtree =
+                        if geojson_filterable { index.geojson_rtree(rtxn)? } else { None };
+                    if let Some(rtree) = geojson_rtree {
+                        let envelope = AABB::from_points(polygon.vertices());
+                        let candidates = wrapped_envelopes(envelope)
+                            .iter()
+                            .flat_map(|envelope| rtree.locate_in_envelope_intersecting(envelope))
+                            .map(|shape| shape.data)
+                            .collect();
+                        result |= filter_geojson_geometries(rtxn, index, candidates, |geometry| {
+                            geometry.intersects_polygon(polygon.vertices())
+                        })?;
+                    }
+
+                    Ok(result)
+                } else {
+                    Err(points[0][0].as_external_error(FilterError::AttributeNotFilterable {
+                        attribute: "_geo",
+                        filterable_fields: filterable_fields.clone(),
+                    }))?
+                }
+            }
+        }
+    }
+}
+
+/// Returns the candidates whose `_geojson` geometry matches the predicate.
+fn filter_geojson_geometries(
+    rtxn: &heed::RoTxn,
+    index: &Index,
+    candidates: RoaringBitmap,
+    predicate: impl Fn(&Geometry) -> bool,
+) -> Result<RoaringBitmap> {
+    let mut result = RoaringBitmap::new();
+    for docid in candidates {
+        if let Some(geometry) = index.geojson_geometries.get(rtxn, &docid)? {
+            if predicate(&geometry) {
+                result.insert(docid);
+            }
         }
     }
+    Ok(result)
 }
 
 impl<'a> From<FilterCondition<'a>> for Filter<'a> {
@@ -853,6 +981,165 @@ mod tests {
         assert_eq!(documents_ids, vec![0]);
     }
 
+    #[test]
+    fn geo_polygon() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("_geo") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+              { "id": 0, "_geo": { "lat": 5.0, "lng": 5.0 } },
+              { "id": 1, "_geo": { "lat": 5.0, "lng": 15.0 } },
+              { "id": 2, "_geo": { "lat": 20.0, "lng": 5.0 } },
+              { "id": 3, "_geo": { "lat": 0.0, "lng": 175.0 } },
+              { "id": 4, "_geo": { "lat": 0.0, "lng": -175.0 } },
+              { "id": 5, "_geo": { "lat": 0.0, "lng": -160.0 } },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        // a triangle that contains the document 0 but not the document 1
+        let filter = Filter::from_str("_geoPolygon([0, 0], [0, 12], [12, 0])").unwrap().unwrap();
+        let bitmap = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(bitmap, RoaringBitmap::from_iter([0]));
+
+        // a concave polygon whose bounding box contains the document 2
+        let filter =
+            Filter::from_str("_geoPolygon([0, 0], [0, 30], [30, 30], [30, 20], [10, 20], [10, 0])")
+                .unwrap()
+                .unwrap();
+        let bitmap = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(bitmap, RoaringBitmap::from_iter([0, 1]));
+
+        // a polygon crossing the antimeridian
+        let filter =
+            Filter::from_str("_geoPolygon([-10, 170], [-10, -170], [10, -170], [10, 170])")
+                .unwrap()
+                .unwrap();
+        let bitmap = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(bitmap, RoaringBitmap::from_iter([3, 4]));
+
+        let filter =
+            Filter::from_str("NOT _geoPolygon([-10, 170], [-10, -170], [10, -170], [10, 170])")
+                .unwrap()
+                .unwrap();
+        let bitmap = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(bitmap, RoaringBitmap::from_iter([0, 1, 2, 5]));
+    }
+
+    #[test]
+    fn geo_polygon_error() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("_geo"), S("price") });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        let filter =
+            Filter::from_str("_geoPolygon([0, 0], [-90.0000001, 10], [10, 10])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(
+            error.to_string().starts_with(
+                "Bad latitude `-90.0000001`. Latitude must be contained between -90 and 90 degrees."
+            ),
+            "{}",
+            error.to_string()
+        );
+
+        let filter =
+            Filter::from_str("_geoPolygon([0, 0], [10, 180.000001], [10, 10])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().contains(
+            "Bad longitude `180.000001`. Longitude must be contained between -180 and 180 degrees."
+        ));
+
+        let filter = Filter::from_str("_geoPolygon([0, -90], [0, 90], [10, 90])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with("The `_geoPolygon` filter is ambiguous"), "{error}");
+    }
+
+    #[test]
+    fn geojson() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("_geojson") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+              { "id": 0, "_geojson": { "type": "Point", "coordinates": [5.0, 5.0] } },
+              { "id": 1, "_geojson": { "type": "LineString", "coordinates": [[-5.0, 8.0], [20.0, 8.0]] } },
+              { "id": 2, "_geojson": { "type": "Polygon", "coordinates": [[[-50.0, -50.0], [50.0, -50.0], [50.0, 50.0], [-50.0, 50.0], [-50.0, -50.0]]] } },
+              { "id": 3, "_geojson": { "type": "MultiPoint", "coordinates": [[30.0, 30.0], [100.0, 60.0]] } },
+              { "id": 4, "_geojson": { "type": "Polygon", "coordinates": [[[170.0, -10.0], [-170.0, -10.0], [-170.0, 10.0], [170.0, 10.0], [170.0, -10.0]]] } },
+              { "id": 5, "_geojson": null },
+              { "id": 6 },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        // the point is inside, the line crosses the triangle and the large polygon contains it
+        let filter = Filter::from_str("_geoPolygon([0, 0], [0, 12], [12, 0])").unwrap().unwrap();
+        let bitmap = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(bitmap, RoaringBitmap::from_iter([0, 1, 2]));
+
+        let filter =
+            Filter::from_str("_geoPolygon([55, 95], [55, 105], [65, 100])").unwrap().unwrap();
+        let bitmap = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(bitmap, RoaringBitmap::from_iter([3]));
+
+        // a polygon on the other side of the antimeridian
+        let filter =
+            Filter::from_str("_geoPolygon([-1, -179], [-1, -178], [1, -178])").unwrap().unwrap();
+        let bitmap = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(bitmap, RoaringBitmap::from_iter([4]));
+
+        // one degree of latitude is about 111km, the line is 3 degrees north of the center
+        let filter = Filter::from_str("_geoRadius(5, 10, 300000)").unwrap().unwrap();
+        let bitmap = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(bitmap, RoaringBitmap::from_iter([2]));
+        let filter = Filter::from_str("_geoRadius(5, 10, 400000)").unwrap().unwrap();
+        let bitmap = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(bitmap, RoaringBitmap::from_iter([1, 2]));
+
+        let filter = Filter::from_str("_geoRadius(0, 180, 100000)").unwrap().unwrap();
+        let bitmap = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(bitmap, RoaringBitmap::from_iter([4]));
+        drop(rtxn);
+
+        // the geometries are removed with their documents
+        index.delete_documents(vec![S("1"), S("4")]);
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("_geoRadius(5, 10, 400000)").unwrap().unwrap();
+        let bitmap = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(bitmap, RoaringBitmap::from_iter([2]));
+        drop(rtxn);
+
+        let error = index
+            .add_documents(documents!([
+              { "id": 7, "_geojson": { "type": "Polygon", "coordinates": [[[0.0, 0.0], [1.0, 1.0], [0.0, 0.0]]] } },
+            ]))
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The `_geojson` field in the document with the id: `7` is not a valid GeoJSON geometry. A polygon ring must contain at least four positions and be closed but instead got `[[0.0,0.0],[1.0,1.0],[0.0,0.0]]`."
+        );
+    }
+
     #[test]
     fn geo_radius_error() {
         let index = TempIndex::new();
@@ -1149,4 +1436,53 @@ mod tests {
         let result = filter.evaluate(&rtxn, &index).unwrap();
         assert_eq!(result, RoaringBitmap::from_iter((0..100).filter(|x| x % 10 != 0)));
     }
+
+    #[test]
+    fn filter_string_contains_and_starts_with() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(hashset! { S("url"), S("sku") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "url": "https://meilisearch.com", "sku": "SHIRT-XL-BLUE" },
+                { "id": 1, "url": "http://example.com", "sku": ["PANTS-S", "SHIRT-M-RED"] },
+                { "id": 2, "url": "HTTPS://Example.org", "sku": "HAT" },
+                { "id": 3, "sku": 42 },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            Filter::from_str(filter).unwrap().unwrap().evaluate(&rtxn, &index).unwrap()
+        };
+
+        assert_eq!(evaluate("url STARTS WITH https"), RoaringBitmap::from_iter([0, 2]));
+        assert_eq!(evaluate("url STARTS WITH 'http'"), RoaringBitmap::from_iter([0, 1, 2]));
+        assert_eq!(evaluate("url STARTS WITH ftp"), RoaringBitmap::new());
+        assert_eq!(evaluate("url NOT STARTS WITH https"), RoaringBitmap::from_iter([1, 3]));
+
+        assert_eq!(evaluate("sku CONTAINS shirt"), RoaringBitmap::from_iter([0, 1]));
+        assert_eq!(evaluate("sku CONTAINS '-S'"), RoaringBitmap::from_iter([1]));
+        assert_eq!(evaluate("sku CONTAINS 'xl-blue'"), RoaringBitmap::from_iter([0]));
+        assert_eq!(evaluate("sku CONTAINS 42"), RoaringBitmap::new());
+        assert_eq!(evaluate("sku NOT CONTAINS red"), RoaringBitmap::from_iter([0, 2, 3]));
+        assert_eq!(
+            evaluate("url CONTAINS example AND sku CONTAINS hat"),
+            RoaringBitmap::from_iter([2])
+        );
+
+        // The field being used must be filterable
+        let error = Filter::from_str("title CONTAINS shirt")
+            .unwrap()
+            .unwrap()
+            .evaluate(&rtxn, &index)
+            .unwrap_err();
+        assert!(error.to_string().starts_with("Attribute `title` is not filterable."));
+    }
 }
@@ -0,0 +1,263 @@
+use rstar::AABB;
+
+use crate::geojson::ring_contains;
+
+/// A polygon drawn on the surface of the earth, its edges are straight lines in the
+/// latitude/longitude plane, the same way the sides of a `_geoBoundingBox` are.
+///
+/// The longitudes of the vertices are unwrapped so that two consecutive vertices are never
+/// more than 180 degrees apart, which means that an edge always takes the shortest way
+/// around the earth, and that polygons crossing the antimeridian are supported.
+/// An edge spanning more than 180 degrees of longitude must therefore be split by
+/// intermediate vertices, e.g. `[0, -100], [0, 100]` crosses the antimeridian while
+/// `[0, -100], [0, 0], [0, 100]` crosses the prime meridian.
+///
+/// The polygons whose direction is ambiguous, because one of their edges spans exactly
+/// 180 degrees of longitude, and the polygons going around a pole are not supported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoPolygon {
+    vertices: Vec<[f64; 2]>,
+}
+
+impl GeoPolygon {
+    /// Creates a polygon from a list of `[latitude, longitude]` vertices expressed in degrees.
+    ///
+    /// Returns `None` if the polygon is ambiguous or goes around a pole.
+    pub fn new(points: impl IntoIterator<Item = [f64; 2]>) -> Option<GeoPolygon> {
+        let mut vertices: Vec<[f64; 2]> = Vec::new();
+        for [lat, lng] in points {
+            let lng = match vertices.last() {
+                Some(&[_, previous_lng]) => unwrap_longitude(previous_lng, lng)?,
+                None => lng,
+            };
+            vertices.push([lat, lng]);
+        }
+
+        // The closing edge must bring us back to the first vertex, and not to
+        // the same vertex one turn around the earth further.
+        if let (Some(&[_, first_lng]), Some(&[_, last_lng])) = (vertices.first(), vertices.last()) {
+            let closing_lng = unwrap_longitude(last_lng, first_lng)?;
+            if (closing_lng - first_lng).abs() > 180.0 {
+                return None;
+            }
+        }
+
+        Some(GeoPolygon { vertices })
+    }
+
+    /// Returns `true` if the `[latitude, longitude]` point is inside the polygon.
+    pub fn contains(&self, point: &[f64; 2]) -> bool {
+        let [lat, lng] = *point;
+        // the unwrapped vertices can go beyond the antimeridian, we must check
+        // the point on both sides of it.
+        [lng, lng - 360.0, lng + 360.0]
+            .iter()
+            .any(|&lng| ring_contains(&self.vertices, &[lat, lng]))
+    }
+
+    /// The unwrapped `[latitude, longitude]` vertices of the polygon.
+    pub fn vertices(&self) -> &[[f64; 2]] {
+        &self.vertices
+    }
+
+    /// Returns the envelopes, in the cartesian coordinates used by the geo rtree,
+    /// that contain every point of the polygon.
+    ///
+    /// A polygon crossing the antimeridian is covered by two envelopes, one on each side of it.
+    pub fn xyz_envelopes(&self) -> Vec<AABB<[f64; 3]>> {
+        let mut min_lat = f64::INFINITY;
+        let mut max_lat = f64::NEG_INFINITY;
+        let mut min_lng = f64::INFINITY;
+        let mut max_lng = f64::NEG_INFINITY;
+        for &[lat, lng] in &self.vertices {
+            min_lat = min_lat.min(lat);
+            max_lat = max_lat.max(lat);
+            min_lng = min_lng.min(lng);
+            max_lng = max_lng.max(lng);
+        }
+
+        if min_lat > max_lat {
+            return Vec::new();
+        }
+
+        if max_lng - min_lng >= 360.0 {
+            return vec![xyz_envelope([min_lat, max_lat], [-180.0, 180.0])];
+        }
+
+        // bring the longitudes back between -180 and 180
+        let shift = ((min_lng + 180.0) / 360.0).floor() * 360.0;
+        let (min_lng, max_lng) = (min_lng - shift, max_lng - shift);
+        if max_lng > 180.0 {
+            vec![
+                xyz_envelope([min_lat, max_lat], [min_lng, 180.0]),
+                xyz_envelope([min_lat, max_lat], [-180.0, max_lng - 360.0]),
+            ]
+        } else {
+            vec![xyz_envelope([min_lat, max_lat], [min_lng, max_lng])]
+        }
+    }
+}
+
+/// Returns the longitude equivalent to `lng` that is the closest to `previous_lng`,
+/// or `None` if both directions are as close.
+fn unwrap_longitude(previous_lng: f64, mut lng: f64) -> Option<f64> {
+    while lng - previous_lng > 180.0 {
+        lng -= 360.0;
+    }
+    while lng - previous_lng < -180.0 {
+        lng += 360.0;
+    }
+    if (lng - previous_lng).abs() == 180.0 {
+        None
+    } else {
+        Some(lng)
+    }
+}
+
+/// Computes the cartesian envelope of the area delimited by the given latitudes
+/// and longitudes, the longitudes must be contained between -180 and 180 degrees.
+fn xyz_envelope([min_lat, max_lat]: [f64; 2], [min_lng, max_lng]: [f64; 2]) -> AABB<[f64; 3]> {
+    // Accounts for the rounding errors of the conversion to cartesian coordinates.
+    const EPSILON: f64 = 1e-9;
+
+    let (min_lat, max_lat) = (min_lat.to_radians(), max_lat.to_radians());
+    let (min_lng, max_lng) = (min_lng.to_radians(), max_lng.to_radians());
+
+    // the distance to the polar axis
+    let min_radius = min_lat.cos().min(max_lat.cos());
+    let max_radius =
+        if min_lat <= 0.0 && 0.0 <= max_lat { 1.0 } else { min_lat.cos().max(max_lat.cos()) };
+
+    // the cosine reaches its maximum at 0, its minimum at ±180 which are always bounds
+    let min_cos = min_lng.cos().min(max_lng.cos());
+    let max_cos =
+        if min_lng <= 0.0 && 0.0 <= max_lng { 1.0 } else { min_lng.cos().max(max_lng.cos()) };
+
+    // the sine reaches its maximum at 90 and its minimum at -90
+    let half_pi = std::f64::consts::FRAC_PI_2;
+    let min_sin = if min_lng <= -half_pi && -half_pi <= max_lng {
+        -1.0
+    } else {
+        min_lng.sin().min(max_lng.sin())
+    };
+    let max_sin = if min_lng <= half_pi && half_pi <= max_lng {
+        1.0
+    } else {
+        min_lng.sin().max(max_lng.sin())
+    };
+
+    // the radius is positive, the farthest point from the origin is
+    // the one with the largest radius when the factor is negative.
+    let scale = |factor: f64, lower: bool| {
+        if (factor < 0.0) == lower {
+            factor * max_radius
+        } else {
+            factor * min_radius
+        }
+    };
+
+    let lower = [scale(min_cos, true), scale(min_sin, true), min_lat.sin()];
+    let upper = [scale(max_cos, false), scale(max_sin, false), max_lat.sin()];
+
+    AABB::from_corners(lower.map(|c| c - EPSILON), upper.map(|c| c + EPSILON))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstar::Envelope;
+
+    use super::*;
+    use crate::lat_lng_to_xyz;
+
+    #[test]
+    fn contains() {
+        let polygon =
+            GeoPolygon::new([[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]]).unwrap();
+        assert!(polygon.contains(&[5.0, 5.0]));
+        assert!(!polygon.contains(&[15.0, 5.0]));
+        assert!(!polygon.contains(&[5.0, -5.0]));
+
+        // a concave polygon, shaped as a `U`
+        let polygon = GeoPolygon::new([
+            [0.0, 0.0],
+            [0.0, 30.0],
+            [30.0, 30.0],
+            [30.0, 20.0],
+            [10.0, 20.0],
+            [10.0, 10.0],
+            [30.0, 10.0],
+            [30.0, 0.0],
+        ])
+        .unwrap();
+        assert!(polygon.contains(&[20.0, 5.0]));
+        assert!(polygon.contains(&[20.0, 25.0]));
+        assert!(polygon.contains(&[5.0, 15.0]));
+        assert!(!polygon.contains(&[20.0, 15.0]));
+    }
+
+    #[test]
+    fn contains_across_the_antimeridian() {
+        let polygon =
+            GeoPolygon::new([[-10.0, 170.0], [-10.0, -170.0], [10.0, -170.0], [10.0, 170.0]])
+                .unwrap();
+        assert!(polygon.contains(&[0.0, 175.0]));
+        assert!(polygon.contains(&[0.0, -175.0]));
+        assert!(polygon.contains(&[0.0, 180.0]));
+        assert!(!polygon.contains(&[0.0, 0.0]));
+        assert!(!polygon.contains(&[0.0, 160.0]));
+        assert!(!polygon.contains(&[0.0, -160.0]));
+
+        let envelopes = polygon.xyz_envelopes();
+        assert_eq!(envelopes.len(), 2);
+        for point in [[0.0, 175.0], [0.0, -175.0], [9.0, 171.0], [-9.0, -171.0]] {
+            let xyz = lat_lng_to_xyz(&point);
+            assert!(envelopes.iter().any(|envelope| envelope.contains_point(&xyz)), "{point:?}");
+        }
+    }
+
+    #[test]
+    fn edges_take_the_shortest_way() {
+        // the edges between -100 and 100 cross the antimeridian
+        let polygon =
+            GeoPolygon::new([[-45.0, -100.0], [-45.0, 100.0], [60.0, 100.0], [60.0, -100.0]])
+                .unwrap();
+        assert!(polygon.contains(&[0.0, 180.0]));
+        assert!(polygon.contains(&[0.0, -150.0]));
+        assert!(!polygon.contains(&[0.0, 0.0]));
+        assert_eq!(polygon.xyz_envelopes().len(), 2);
+
+        // an edge spanning exactly 180 degrees could go both ways
+        assert_eq!(GeoPolygon::new([[0.0, -90.0], [0.0, 90.0], [10.0, 90.0]]), None);
+        // going around the north pole
+        assert_eq!(
+            GeoPolygon::new([[80.0, 0.0], [80.0, 120.0], [80.0, -120.0], [80.0, -1.0]]),
+            None
+        );
+    }
+
+    #[test]
+    fn envelopes_contain_the_polygon() {
+        // the intermediate vertices make the edges cross the prime meridian
+        let polygon = GeoPolygon::new([
+            [-45.0, -100.0],
+            [-45.0, 0.0],
+            [-45.0, 100.0],
+            [60.0, 100.0],
+            [60.0, 0.0],
+            [60.0, -100.0],
+        ])
+        .unwrap();
+        let envelopes = polygon.xyz_envelopes();
+        assert_eq!(envelopes.len(), 1);
+
+        for lat in (-45..=60).step_by(5) {
+            for lng in (-100..=100).step_by(5) {
+                let xyz = lat_lng_to_xyz(&[lat as f64, lng as f64]);
+                assert!(envelopes[0].contains_point(&xyz), "{lat}, {lng}");
+            }
+        }
+
+        let xyz = lat_lng_to_xyz(&[0.0, 180.0]);
+        assert!(!envelopes[0].contains_point(&xyz));
+    }
+}
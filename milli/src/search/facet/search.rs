@@ -1,3 +1,4 @@
+use std::cell::Cell;
 use std::cmp::{Ordering, Reverse};
 use std::collections::BinaryHeap;
 use std::ops::ControlFlow;
@@ -23,6 +24,9 @@ pub struct SearchForFacetValues<'a> {
     search_query: Search<'a>,
     max_values: usize,
     is_hybrid: bool,
+    exhaustive_facet_count: bool,
+    /// Set by [`Self::execute`] when the search ran out of time.
+    degraded: Cell<bool>,
 }
 
 impl<'a> SearchForFacetValues<'a> {
@@ -37,6 +41,8 @@ impl<'a> SearchForFacetValues<'a> {
             search_query,
             max_values: DEFAULT_MAX_NUMBER_OF_VALUES_PER_FACET,
             is_hybrid,
+            exhaustive_facet_count: false,
+            degraded: Cell::new(false),
         }
     }
 
@@ -50,6 +56,61 @@ impl<'a> SearchForFacetValues<'a> {
         self
     }
 
+    /// Forces the facet counts to be computed against the exact set of documents
+    /// matching the search, as long as the search cutoff is not reached.
+    ///
+    /// Without it, the candidates are only deduplicated by the distinct attribute and
+    /// filtered by the ranking score threshold up to the search limit.
+    pub fn exhaustive_facet_count(&mut self, exhaustive: bool) -> &mut Self {
+        self.exhaustive_facet_count = exhaustive;
+        if exhaustive {
+            self.search_query.exhaustive_number_hits(true);
+            // The ranking score threshold is only applied to the documents that are ranked,
+            // every document must therefore go through the ranking rules.
+            if self.search_query.ranking_score_threshold.is_some() {
+                self.search_query.offset(0).limit(u32::MAX as usize);
+            }
+        }
+        self
+    }
+
+    /// Returns `true` if the counts returned by [`Self::execute`] are exact.
+    ///
+    /// Must be called after [`Self::execute`]: the counts are never exact
+    /// when the search ran out of time.
+    pub fn is_exhaustive(&self) -> Result<bool> {
+        if self.degraded.get() {
+            return Ok(false);
+        }
+
+        let index = self.search_query.index;
+        let rtxn = self.search_query.rtxn;
+
+        let has_distinct = match self.search_query.distinct.as_deref() {
+            Some(_) => true,
+            None => index.distinct_field(rtxn)?.is_some(),
+        };
+
+        // The vector search candidates are the filtered universe, on which
+        // the distinct attribute is never applied.
+        if self.has_vector_search() {
+            return Ok(!has_distinct);
+        }
+
+        Ok(self.exhaustive_facet_count
+            || (!has_distinct && self.search_query.ranking_score_threshold.is_none()))
+    }
+
+    fn has_vector_search(&self) -> bool {
+        self.is_hybrid
+            || self
+                .search_query
+                .semantic
+                .as_ref()
+                .and_then(|semantic| semantic.vector.as_ref())
+                .is_some()
+    }
+
     fn one_original_value_of(
         &self,
         field_id: FieldId,
@@ -92,15 +153,9 @@ impl<'a> SearchForFacetValues<'a> {
             None => return Ok(Vec::new()),
         };
 
-        let search_candidates = self.search_query.execute_for_candidates(
-            self.is_hybrid
-                || self
-                    .search_query
-                    .semantic
-                    .as_ref()
-                    .and_then(|semantic| semantic.vector.as_ref())
-                    .is_some(),
-        )?;
+        let (search_candidates, degraded) =
+            self.search_query.execute_for_candidates(self.has_vector_search())?;
+        self.degraded.set(degraded);
 
         let mut results = match index.sort_facet_values_by(rtxn)?.get(&self.facet) {
             OrderBy::Lexicographic => ValuesCollection::by_lexicographic(self.max_values),
@@ -174,6 +174,10 @@ impl<'a> Search<'a> {
             semantic: self.semantic.clone(),
             time_budget: self.time_budget.clone(),
             ranking_score_threshold: self.ranking_score_threshold,
+            locales: self.locales.clone(),
+            ranking_rules: self.ranking_rules.clone(),
+            boosts: self.boosts.clone(),
+            excluded_documents: self.excluded_documents.clone(),
         };
 
         let semantic = search.semantic.take();
@@ -1,18 +1,23 @@
 use std::fmt;
 use std::sync::Arc;
 
+use charabia::Language;
 use levenshtein_automata::{LevenshteinAutomatonBuilder as LevBuilder, DFA};
 use once_cell::sync::Lazy;
 use roaring::bitmap::RoaringBitmap;
 
-pub use self::facet::{FacetDistribution, Filter, OrderBy, DEFAULT_VALUES_PER_FACET};
+pub use self::facet::{
+    FacetAggregation, FacetDistribution, Filter, HistogramBucket, HistogramBuckets, OrderBy,
+    DEFAULT_VALUES_PER_FACET,
+};
 pub use self::new::matches::{FormatOptions, MatchBounds, MatcherBuilder, MatchingWords};
 use self::new::{execute_vector_search, PartialSearchResult};
+pub use self::new::{Boost, Suggest, Suggestion};
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::vector::Embedder;
 use crate::{
-    execute_search, filtered_universe, AscDesc, DefaultSearchLogger, DocumentId, Error, Index,
-    Result, SearchContext, TimeBudget, UserError,
+    execute_search, filtered_universe, AscDesc, Criterion, DefaultSearchLogger, DocumentId, Error,
+    Index, Result, SearchContext, TimeBudget, UserError,
 };
 
 // Building these factories is not free.
@@ -40,7 +45,7 @@ pub struct Search<'a> {
     offset: usize,
     limit: usize,
     sort_criteria: Option<Vec<AscDesc>>,
-    distinct: Option<String>,
+    distinct: Option<Vec<String>>,
     searchable_attributes: Option<&'a [String]>,
     geo_strategy: new::GeoSortStrategy,
     terms_matching_strategy: TermsMatchingStrategy,
@@ -52,6 +57,10 @@ pub struct Search<'a> {
     semantic: Option<SemanticSearch>,
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
+    locales: Option<Vec<Language>>,
+    ranking_rules: Option<Vec<Criterion>>,
+    boosts: Vec<Boost<'a>>,
+    excluded_documents: RoaringBitmap,
 }
 
 impl<'a> Search<'a> {
@@ -74,6 +83,10 @@ impl<'a> Search<'a> {
             semantic: None,
             time_budget: TimeBudget::max(),
             ranking_score_threshold: None,
+            locales: None,
+            ranking_rules: None,
+            boosts: Vec::new(),
+            excluded_documents: RoaringBitmap::new(),
         }
     }
 
@@ -108,6 +121,12 @@ impl<'a> Search<'a> {
     }
 
     pub fn distinct(&mut self, distinct: String) -> &mut Search<'a> {
+        self.distinct = Some(vec![distinct]);
+        self
+    }
+
+    /// Makes the documents distinct on the tuple of values of the given fields.
+    pub fn distinct_fields(&mut self, distinct: Vec<String>) -> &mut Search<'a> {
         self.distinct = Some(distinct);
         self
     }
@@ -160,12 +179,40 @@ impl<'a> Search<'a> {
         self
     }
 
-    pub fn execute_for_candidates(&self, has_vector_search: bool) -> Result<RoaringBitmap> {
+    /// Forces the tokenizer to only detect the given locales in the query.
+    pub fn locales(&mut self, locales: Vec<Language>) -> &mut Search<'a> {
+        self.locales = Some(locales);
+        self
+    }
+
+    /// Overrides the ranking rules of the index for this search only.
+    pub fn ranking_rules(&mut self, ranking_rules: Vec<Criterion>) -> &mut Search<'a> {
+        self.ranking_rules = Some(ranking_rules);
+        self
+    }
+
+    /// Boosts the documents matching the given filters, where the `boost` ranking rule is placed.
+    pub fn boosts(&mut self, boosts: Vec<Boost<'a>>) -> &mut Search<'a> {
+        self.boosts = boosts;
+        self
+    }
+
+    /// Removes the given documents from the candidates of the search, as if they were filtered out.
+    pub fn excluded_documents(&mut self, excluded_documents: RoaringBitmap) -> &mut Search<'a> {
+        self.excluded_documents = excluded_documents;
+        self
+    }
+
+    /// Returns the candidates of the search and whether the search was degraded
+    /// because it ran out of time.
+    pub fn execute_for_candidates(&self, has_vector_search: bool) -> Result<(RoaringBitmap, bool)> {
         if has_vector_search {
             let ctx = SearchContext::new(self.index, self.rtxn)?;
-            filtered_universe(ctx.index, ctx.txn, &self.filter)
+            let universe = filtered_universe(ctx.index, ctx.txn, &self.filter)?;
+            Ok((universe - &self.excluded_documents, false))
         } else {
-            Ok(self.execute()?.candidates)
+            let SearchResult { candidates, degraded, .. } = self.execute()?;
+            Ok((candidates, degraded))
         }
     }
 
@@ -176,20 +223,29 @@ impl<'a> Search<'a> {
             ctx.attributes_to_search_on(searchable_attributes)?;
         }
 
+        if let Some(ranking_rules) = &self.ranking_rules {
+            ctx.ranking_rules(ranking_rules.clone())?;
+        }
+
+        ctx.boosts(&self.boosts)?;
+
         if let Some(distinct) = &self.distinct {
             let filterable_fields = ctx.index.filterable_fields(ctx.txn)?;
-            if !crate::is_faceted(distinct, &filterable_fields) {
+            if let Some(field) =
+                distinct.iter().find(|field| !crate::is_faceted(field, &filterable_fields))
+            {
                 let (valid_fields, hidden_fields) =
                     ctx.index.remove_hidden_fields(ctx.txn, filterable_fields)?;
                 return Err(Error::UserError(UserError::InvalidDistinctAttribute {
-                    field: distinct.clone(),
+                    field: field.clone(),
                     valid_fields,
                     hidden_fields,
                 }));
             }
         }
 
-        let universe = filtered_universe(ctx.index, ctx.txn, &self.filter)?;
+        let mut universe = filtered_universe(ctx.index, ctx.txn, &self.filter)?;
+        universe -= &self.excluded_documents;
         let PartialSearchResult {
             located_query_terms,
             candidates,
@@ -232,6 +288,7 @@ impl<'a> Search<'a> {
                 &mut DefaultSearchLogger,
                 self.time_budget.clone(),
                 self.ranking_score_threshold,
+                self.locales.as_deref(),
             )?,
         };
 
@@ -250,6 +307,45 @@ impl<'a> Search<'a> {
             used_negative_operator,
         })
     }
+
+    /// Returns, for each of the given documents, the other documents sharing its distinct key.
+    ///
+    /// The members of a group must match the filter and the query of the search. A semantic
+    /// search considers every document, so its groups only need to match the filter.
+    /// When there is no distinct attribute, the groups are empty.
+    pub fn distinct_groups(&self, documents_ids: &[DocumentId]) -> Result<Vec<RoaringBitmap>> {
+        let distinct_fids = new::distinct_fids(self.index, self.rtxn, self.distinct.as_deref())?;
+        if distinct_fids.is_empty() {
+            return Ok(vec![RoaringBitmap::new(); documents_ids.len()]);
+        }
+
+        let mut universe = filtered_universe(self.index, self.rtxn, &self.filter)?;
+        universe -= &self.excluded_documents;
+        if self.semantic.is_none() {
+            let mut ctx = SearchContext::new(self.index, self.rtxn)?;
+            if let Some(searchable_attributes) = self.searchable_attributes {
+                ctx.attributes_to_search_on(searchable_attributes)?;
+            }
+            universe = new::resolve_query_candidates(
+                &mut ctx,
+                self.query.as_deref(),
+                self.terms_matching_strategy,
+                Some(self.words_limit),
+                self.locales.as_deref(),
+                universe,
+            )?;
+        }
+        documents_ids
+            .iter()
+            .map(|&docid| {
+                let mut group = new::distinct_group(self.index, self.rtxn, &distinct_fids, docid)?
+                    .unwrap_or_default();
+                group &= &universe;
+                group.remove(docid);
+                Ok(group)
+            })
+            .collect()
+    }
 }
 
 impl fmt::Debug for Search<'_> {
@@ -272,6 +368,10 @@ impl fmt::Debug for Search<'_> {
             semantic,
             time_budget,
             ranking_score_threshold,
+            locales,
+            ranking_rules,
+            boosts,
+            excluded_documents,
         } = self;
         f.debug_struct("Search")
             .field("query", query)
@@ -292,6 +392,10 @@ impl fmt::Debug for Search<'_> {
             )
             .field("time_budget", time_budget)
             .field("ranking_score_threshold", ranking_score_threshold)
+            .field("locales", locales)
+            .field("ranking_rules", ranking_rules)
+            .field("boosts", boosts)
+            .field("excluded_documents", excluded_documents)
             .finish()
     }
 }
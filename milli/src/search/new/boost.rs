@@ -0,0 +1,113 @@
+use roaring::RoaringBitmap;
+
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::score_details::{self, ScoreDetails};
+use crate::{Filter, Result};
+
+/// A filter that boosts the documents it matches by the given weight.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Boost<'a> {
+    pub filter: Filter<'a>,
+    pub weight: f64,
+}
+
+/// The documents matched by a boost filter, along with the weight of the filter.
+#[derive(Debug, Clone)]
+pub struct BoostDocids {
+    pub docids: RoaringBitmap,
+    pub weight: f64,
+}
+
+/// A ranking rule that partitions its universe by the sum of the weights of
+/// the boost filters matched by each document, the heaviest buckets first.
+pub struct BoostRule<Query> {
+    boosts: Vec<BoostDocids>,
+    max_weight: f64,
+    /// The buckets that are still to be returned, sorted by increasing weight.
+    buckets: Vec<(f64, RoaringBitmap)>,
+    query: Option<Query>,
+}
+
+impl<Query> BoostRule<Query> {
+    pub fn new(boosts: Vec<BoostDocids>) -> Self {
+        let max_weight = boosts.iter().map(|boost| boost.weight).sum();
+        Self { boosts, max_weight, buckets: Vec::new(), query: None }
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for BoostRule<Query> {
+    fn id(&self) -> String {
+        "boost".to_owned()
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::boost")]
+    fn start_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+        query: &Query,
+    ) -> Result<()> {
+        // Split the universe in the disjoint sets of documents matching the same boost filters.
+        let mut buckets = vec![(0.0, universe.clone())];
+        for BoostDocids { docids, weight } in &self.boosts {
+            buckets = buckets
+                .into_iter()
+                .flat_map(|(bucket_weight, bucket)| {
+                    let matched = &bucket & docids;
+                    let unmatched = bucket - &matched;
+                    [(bucket_weight + weight, matched), (bucket_weight, unmatched)]
+                })
+                .filter(|(_, bucket)| !bucket.is_empty())
+                .collect();
+        }
+
+        // Merge the sets that end up with the same weight.
+        buckets.sort_by(|(left, _), (right, _)| left.total_cmp(right));
+        buckets.dedup_by(|(weight, bucket), (kept_weight, kept_bucket)| {
+            let same_weight = weight == kept_weight;
+            if same_weight {
+                *kept_bucket |= &*bucket;
+            }
+            same_weight
+        });
+
+        self.buckets = buckets;
+        self.query = Some(query.clone());
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::boost")]
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.query.as_ref().unwrap().clone();
+        while let Some((weight, mut candidates)) = self.buckets.pop() {
+            candidates &= universe;
+            if candidates.is_empty() {
+                continue;
+            }
+            let score = score_details::Boost { weight, max_weight: self.max_weight };
+            return Ok(Some(RankingRuleOutput {
+                query,
+                candidates,
+                score: ScoreDetails::Boost(score),
+            }));
+        }
+        Ok(None)
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::boost")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.buckets = Vec::new();
+        self.query = None;
+    }
+}
@@ -4,7 +4,9 @@ use super::logger::SearchLogger;
 use super::ranking_rules::{BoxRankingRule, RankingRuleQueryTrait};
 use super::SearchContext;
 use crate::score_details::{ScoreDetails, ScoringStrategy};
-use crate::search::new::distinct::{apply_distinct_rule, distinct_single_docid, DistinctOutput};
+use crate::search::new::distinct::{
+    apply_distinct_rule, distinct_fids, distinct_single_docid, DistinctOutput,
+};
 use crate::{Result, TimeBudget};
 
 pub struct BucketSortOutput {
@@ -22,7 +24,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     ctx: &mut SearchContext<'ctx>,
     mut ranking_rules: Vec<BoxRankingRule<'ctx, Q>>,
     query: &Q,
-    distinct: Option<&str>,
+    distinct: Option<&[String]>,
     universe: &RoaringBitmap,
     from: usize,
     length: usize,
@@ -35,16 +37,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     logger.ranking_rules(&ranking_rules);
     logger.initial_universe(universe);
 
-    let distinct_field = match distinct {
-        Some(distinct) => Some(distinct),
-        None => ctx.index.distinct_field(ctx.txn)?,
-    };
-
-    let distinct_fid = if let Some(field) = distinct_field {
-        ctx.index.fields_ids_map(ctx.txn)?.id(field)
-    } else {
-        None
-    };
+    let distinct_fids = distinct_fids(ctx.index, ctx.txn, distinct)?;
 
     if universe.len() < from as u64 {
         return Ok(BucketSortOutput {
@@ -55,7 +48,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
         });
     }
     if ranking_rules.is_empty() {
-        if let Some(distinct_fid) = distinct_fid {
+        if !distinct_fids.is_empty() {
             let mut excluded = RoaringBitmap::new();
             let mut results = vec![];
             for docid in universe.iter() {
@@ -66,7 +59,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                     continue;
                 }
 
-                distinct_single_docid(ctx.index, ctx.txn, distinct_fid, docid, &mut excluded)?;
+                distinct_single_docid(ctx.index, ctx.txn, &distinct_fids, docid, &mut excluded)?;
                 results.push(docid);
             }
 
@@ -159,7 +152,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 &mut ranking_rules,
                 cur_ranking_rule_index,
                 &mut cur_offset,
-                distinct_fid,
+                &distinct_fids,
                 &ranking_rule_scores,
                 $candidates,
             )?;
@@ -302,14 +295,14 @@ fn maybe_add_to_results<'ctx, Q: RankingRuleQueryTrait>(
 
     cur_offset: &mut usize,
 
-    distinct_fid: Option<u16>,
+    distinct_fids: &[u16],
     ranking_rule_scores: &[ScoreDetails],
     candidates: RoaringBitmap,
 ) -> Result<()> {
     // First apply the distinct rule on the candidates, reducing the universes if necessary
-    let candidates = if let Some(distinct_fid) = distinct_fid {
+    let candidates = if !distinct_fids.is_empty() {
         let DistinctOutput { remaining, excluded } =
-            apply_distinct_rule(ctx, distinct_fid, &candidates)?;
+            apply_distinct_rule(ctx, distinct_fids, &candidates)?;
         for universe in ranking_rule_universes.iter_mut() {
             *universe -= &excluded;
             *all_candidates -= &excluded;
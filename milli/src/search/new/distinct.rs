@@ -18,13 +18,13 @@ pub struct DistinctOutput {
 
 /// Return a [`DistinctOutput`] containing:
 /// - `remaining`: a set of docids built such that exactly one element from `candidates`
-/// is kept for each distinct value inside the given field. If the field does not exist, it
-/// is considered unique.
-/// - `excluded`: the set of document ids that contain a value for the given field that occurs
-/// in the given candidates.
+/// is kept for each distinct key made of the values of the given fields. If a document has
+/// no value for any of the fields, it is considered unique.
+/// - `excluded`: the set of document ids that share the distinct key of a document
+/// of the given candidates.
 pub fn apply_distinct_rule(
     ctx: &mut SearchContext,
-    field_id: u16,
+    field_ids: &[u16],
     candidates: &RoaringBitmap,
 ) -> Result<DistinctOutput> {
     let mut excluded = RoaringBitmap::new();
@@ -33,7 +33,7 @@ pub fn apply_distinct_rule(
         if excluded.contains(docid) {
             continue;
         }
-        distinct_single_docid(ctx.index, ctx.txn, field_id, docid, &mut excluded)?;
+        distinct_single_docid(ctx.index, ctx.txn, field_ids, docid, &mut excluded)?;
         remaining.push(docid);
     }
     Ok(DistinctOutput { remaining, excluded })
@@ -43,10 +43,71 @@ pub fn apply_distinct_rule(
 pub fn distinct_single_docid(
     index: &Index,
     txn: &RoTxn,
-    field_id: u16,
+    field_ids: &[u16],
     docid: u32,
     excluded: &mut RoaringBitmap,
 ) -> Result<()> {
+    if let Some(group) = distinct_group(index, txn, field_ids, docid)? {
+        *excluded |= group;
+    }
+    Ok(())
+}
+
+/// Return the ids of the distinct fields, either the given ones or the distinct attribute
+/// of the index. The fields that do not exist in the index are ignored.
+pub fn distinct_fids(index: &Index, txn: &RoTxn, distinct: Option<&[String]>) -> Result<Vec<u16>> {
+    let fields_ids_map = index.fields_ids_map(txn)?;
+    let fids = match distinct {
+        Some(fields) => fields.iter().filter_map(|field| fields_ids_map.id(field)).collect(),
+        None => index
+            .distinct_field(txn)?
+            .and_then(|field| fields_ids_map.id(field))
+            .into_iter()
+            .collect(),
+    };
+    Ok(fids)
+}
+
+/// Return all the docids sharing the distinct key of the given document, itself included.
+///
+/// Two documents share a key when, for each of the given fields, they have a value in common
+/// or both have no value. If the document has no value for any of the fields, it is considered
+/// unique and `None` is returned.
+pub fn distinct_group(
+    index: &Index,
+    txn: &RoTxn,
+    field_ids: &[u16],
+    docid: u32,
+) -> Result<Option<RoaringBitmap>> {
+    let mut fields_docids = Vec::with_capacity(field_ids.len());
+    for &field_id in field_ids {
+        fields_docids.push(field_value_docids(index, txn, field_id, docid)?);
+    }
+    if fields_docids.iter().all(RoaringBitmap::is_empty) {
+        return Ok(None);
+    }
+
+    let mut group: Option<RoaringBitmap> = None;
+    for (&field_id, mut docids) in field_ids.iter().zip(fields_docids) {
+        if docids.is_empty() {
+            docids = documents_without_value(index, txn, field_id)?;
+        }
+        group = Some(match group {
+            Some(group) => group & docids,
+            None => docids,
+        });
+    }
+    Ok(group)
+}
+
+/// Return all the docids sharing at least one value with the given document in the given field.
+fn field_value_docids(
+    index: &Index,
+    txn: &RoTxn,
+    field_id: u16,
+    docid: u32,
+) -> Result<RoaringBitmap> {
+    let mut docids = RoaringBitmap::new();
     for item in facet_string_values(docid, field_id, index, txn)? {
         let ((_, _, facet_value), _) = item?;
         if let Some(facet_docids) = facet_value_docids(
@@ -55,7 +116,7 @@ pub fn distinct_single_docid(
             field_id,
             facet_value,
         )? {
-            *excluded |= facet_docids;
+            docids |= facet_docids;
         }
     }
     for item in facet_number_values(docid, field_id, index, txn)? {
@@ -63,10 +124,20 @@ pub fn distinct_single_docid(
         if let Some(facet_docids) =
             facet_value_docids(index.facet_id_f64_docids.remap_types(), txn, field_id, facet_value)?
         {
-            *excluded |= facet_docids;
+            docids |= facet_docids;
         }
     }
-    Ok(())
+    Ok(docids)
+}
+
+/// Return all the docids that have no value in the given field,
+/// the field being either missing, `null` or empty.
+fn documents_without_value(index: &Index, txn: &RoTxn, field_id: u16) -> Result<RoaringBitmap> {
+    let mut docids =
+        index.documents_ids(txn)? - index.exists_faceted_documents_ids(txn, field_id)?;
+    docids |= index.null_faceted_documents_ids(txn, field_id)?;
+    docids |= index.empty_faceted_documents_ids(txn, field_id)?;
+    Ok(docids)
 }
 
 /// Return all the docids containing the given value in the given field
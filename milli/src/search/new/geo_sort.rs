@@ -11,7 +11,7 @@ use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait
 use crate::heed_codec::facet::{FieldDocIdFacetCodec, OrderedF64Codec};
 use crate::score_details::{self, ScoreDetails};
 use crate::{
-    distance_between_two_points, lat_lng_to_xyz, GeoPoint, Index, Result, SearchContext,
+    distance_between_two_points, lat_lng_to_xyz, GeoDecay, GeoPoint, Index, Result, SearchContext,
     SearchLogger,
 };
 
@@ -79,6 +79,7 @@ pub struct GeoSort<Q: RankingRuleQueryTrait> {
     strategy: Strategy,
     ascending: bool,
     point: [f64; 2],
+    decay: Option<GeoDecay>,
     field_ids: Option<[u16; 2]>,
     rtree: Option<RTree<GeoPoint>>,
 
@@ -92,12 +93,14 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
         geo_faceted_docids: RoaringBitmap,
         point: [f64; 2],
         ascending: bool,
+        decay: Option<GeoDecay>,
     ) -> Result<Self> {
         Ok(Self {
             query: None,
             strategy,
             ascending,
             point,
+            decay,
             geo_candidates: geo_faceted_docids,
             field_ids: None,
             rtree: None,
@@ -171,6 +174,56 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
 
         Ok(())
     }
+
+    /// Returns the next bucket of the closest documents sharing the same decayed score.
+    fn next_decayed_bucket(
+        &mut self,
+        ctx: &mut SearchContext,
+        query: Q,
+        mut geo_candidates: RoaringBitmap,
+    ) -> Result<RankingRuleOutput<Q>> {
+        debug_assert!(self.ascending, "the decayed geo sort always starts with the closest");
+        let (target_point, ascending, decay) = (self.point, self.ascending, self.decay);
+        let details =
+            |point| score_details::GeoSort { target_point, ascending, value: Some(point), decay };
+
+        let mut bucket = RoaringBitmap::new();
+        let mut bucket_details: Option<score_details::GeoSort> = None;
+        while !geo_candidates.is_empty() {
+            while let Some((id, point)) = self.cached_sorted_docids.pop_front() {
+                if !geo_candidates.contains(id) {
+                    continue;
+                }
+                let point_details = details(point);
+                if let Some(bucket_details) = bucket_details {
+                    if bucket_details.decay_rank() != point_details.decay_rank() {
+                        // this document belongs to the next bucket
+                        self.cached_sorted_docids.push_front((id, point));
+                        return Ok(RankingRuleOutput {
+                            query,
+                            candidates: bucket,
+                            score: ScoreDetails::GeoSort(bucket_details),
+                        });
+                    }
+                } else {
+                    bucket_details = Some(point_details);
+                }
+                bucket.insert(id);
+                geo_candidates.remove(id);
+            }
+
+            // we exhausted our cache, we refill it with the remaining candidates.
+            if !geo_candidates.is_empty() {
+                self.fill_buffer(ctx, &geo_candidates)?;
+            }
+        }
+
+        Ok(RankingRuleOutput {
+            query,
+            candidates: bucket,
+            score: ScoreDetails::GeoSort(bucket_details.expect("a decayed bucket cannot be empty")),
+        })
+    }
 }
 
 /// Extracts the lat and long values from a single document.
@@ -255,10 +308,15 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
                     target_point: self.point,
                     ascending: self.ascending,
                     value: None,
+                    decay: self.decay,
                 }),
             }));
         }
 
+        if self.decay.is_some() {
+            return self.next_decayed_bucket(ctx, query, geo_candidates).map(Some);
+        }
+
         let ascending = self.ascending;
         let next = |cache: &mut VecDeque<_>| {
             if ascending {
@@ -276,6 +334,7 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
                         target_point: self.point,
                         ascending: self.ascending,
                         value: Some(point),
+                        decay: None,
                     }),
                 }));
             }
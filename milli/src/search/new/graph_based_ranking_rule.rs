@@ -50,7 +50,7 @@ use super::ranking_rule_graph::{
 };
 use super::small_bitmap::SmallBitmap;
 use super::{QueryGraph, RankingRule, RankingRuleOutput, SearchContext};
-use crate::score_details::Rank;
+use crate::score_details::{Rank, ScoreDetails};
 use crate::search::new::query_term::LocatedQueryTermSubset;
 use crate::search::new::ranking_rule_graph::PathVisitor;
 use crate::{Result, TermsMatchingStrategy};
@@ -72,12 +72,28 @@ impl GraphBasedRankingRule<FidGraph> {
     pub fn new(terms_matching_strategy: Option<TermsMatchingStrategy>) -> Self {
         Self::new_with_id("fid".to_owned(), terms_matching_strategy)
     }
+
+    /// Creates the standalone `attributeRank` ranking rule, whose score is not merged with the one of [`Position`].
+    pub fn attribute_rank(terms_matching_strategy: Option<TermsMatchingStrategy>) -> Self {
+        Self {
+            rank_to_score: ScoreDetails::AttributeRank,
+            ..Self::new_with_id("attributeRank".to_owned(), terms_matching_strategy)
+        }
+    }
 }
 pub type Position = GraphBasedRankingRule<PositionGraph>;
 impl GraphBasedRankingRule<PositionGraph> {
     pub fn new(terms_matching_strategy: Option<TermsMatchingStrategy>) -> Self {
         Self::new_with_id("position".to_owned(), terms_matching_strategy)
     }
+
+    /// Creates the standalone `wordPosition` ranking rule, whose score is not merged with the one of [`Fid`].
+    pub fn word_position(terms_matching_strategy: Option<TermsMatchingStrategy>) -> Self {
+        Self {
+            rank_to_score: ScoreDetails::WordPosition,
+            ..Self::new_with_id("wordPosition".to_owned(), terms_matching_strategy)
+        }
+    }
 }
 pub type Typo = GraphBasedRankingRule<TypoGraph>;
 impl GraphBasedRankingRule<TypoGraph> {
@@ -96,6 +112,8 @@ impl GraphBasedRankingRule<ExactnessGraph> {
 pub struct GraphBasedRankingRule<G: RankingRuleGraphTrait> {
     id: String,
     terms_matching_strategy: Option<TermsMatchingStrategy>,
+    /// Converts the rank of a bucket into its score details
+    rank_to_score: fn(Rank) -> ScoreDetails,
     // When the ranking rule is not iterating over its buckets,
     // its state is `None`.
     state: Option<GraphBasedRankingRuleState<G>>,
@@ -103,7 +121,7 @@ pub struct GraphBasedRankingRule<G: RankingRuleGraphTrait> {
 impl<G: RankingRuleGraphTrait> GraphBasedRankingRule<G> {
     /// Creates the ranking rule with the given identifier
     pub fn new_with_id(id: String, terms_matching_strategy: Option<TermsMatchingStrategy>) -> Self {
-        Self { id, terms_matching_strategy, state: None }
+        Self { id, terms_matching_strategy, rank_to_score: G::rank_to_score, state: None }
     }
 }
 
@@ -242,7 +260,8 @@ impl<'ctx, G: RankingRuleGraphTrait> RankingRule<'ctx, QueryGraph> for GraphBase
         } = &mut state;
 
         let rank = *next_max_cost - cost;
-        let score = G::rank_to_score(Rank { rank: rank as u32, max_rank: *next_max_cost as u32 });
+        let score =
+            (self.rank_to_score)(Rank { rank: rank as u32, max_rank: *next_max_cost as u32 });
 
         let mut universe = universe.clone();
 
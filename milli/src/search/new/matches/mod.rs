@@ -525,6 +525,7 @@ mod tests {
                 &mut crate::DefaultSearchLogger,
                 TimeBudget::max(),
                 None,
+                None,
             )
             .unwrap();
 
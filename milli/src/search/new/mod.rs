@@ -1,3 +1,4 @@
+mod boost;
 mod bucket_sort;
 mod db_cache;
 mod distinct;
@@ -13,6 +14,7 @@ mod ranking_rule_graph;
 mod ranking_rules;
 mod resolve_query_graph;
 mod small_bitmap;
+mod suggest;
 
 mod exact_attribute;
 mod sort;
@@ -23,8 +25,10 @@ mod tests;
 
 use std::collections::HashSet;
 
+pub use boost::Boost;
+use boost::{BoostDocids, BoostRule};
 use bucket_sort::{bucket_sort, BucketSortOutput};
-use charabia::TokenizerBuilder;
+use charabia::{Language, TokenizerBuilder};
 use db_cache::DatabaseCache;
 use exact_attribute::ExactAttribute;
 use graph_based_ranking_rule::{Exactness, Fid, Position, Proximity, Typo};
@@ -44,17 +48,20 @@ use roaring::RoaringBitmap;
 use sort::Sort;
 
 use self::distinct::facet_string_values;
+pub(crate) use self::distinct::{distinct_fids, distinct_group};
 use self::geo_sort::GeoSort;
 pub use self::geo_sort::Strategy as GeoSortStrategy;
 use self::graph_based_ranking_rule::Words;
 use self::interner::Interned;
+pub use self::suggest::{Suggest, Suggestion};
 use self::vector_sort::VectorSort;
+use crate::localized_attributes_rules::{locales_allow_list, LocalizedFieldIds};
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::search::new::distinct::apply_distinct_rule;
 use crate::vector::Embedder;
 use crate::{
-    AscDesc, DocumentId, FieldId, Filter, Index, Member, Result, TermsMatchingStrategy, TimeBudget,
-    UserError, Weight,
+    AscDesc, Criterion, DocumentId, FieldId, Filter, Index, Member, Result, TermsMatchingStrategy,
+    TimeBudget, UserError, Weight,
 };
 
 /// A structure used throughout the execution of a search query.
@@ -67,6 +74,8 @@ pub struct SearchContext<'ctx> {
     pub term_interner: Interner<QueryTerm>,
     pub phrase_docids: PhraseDocIdsCache,
     pub restricted_fids: Option<RestrictedFids>,
+    pub ranking_rules: Option<Vec<Criterion>>,
+    pub boosts: Vec<BoostDocids>,
 }
 
 impl<'ctx> SearchContext<'ctx> {
@@ -93,6 +102,8 @@ impl<'ctx> SearchContext<'ctx> {
             term_interner: <_>::default(),
             phrase_docids: <_>::default(),
             restricted_fids: None,
+            ranking_rules: None,
+            boosts: Vec::new(),
         })
     }
 
@@ -152,6 +163,68 @@ impl<'ctx> SearchContext<'ctx> {
 
         Ok(())
     }
+
+    /// Overrides the ranking rules of the index for this search.
+    ///
+    /// The index must allow it and the `asc`/`desc` ranking rules must be on sortable fields.
+    pub fn ranking_rules(&mut self, ranking_rules: Vec<Criterion>) -> Result<()> {
+        if !self.index.allow_ranking_rules_override(self.txn)? {
+            return Err(UserError::RankingRulesOverrideNotAllowed.into());
+        }
+
+        let sortable_fields = self.index.sortable_fields(self.txn)?;
+        for criterion in &ranking_rules {
+            if let Criterion::Asc(field) | Criterion::Desc(field) = criterion {
+                if !crate::is_faceted(field, &sortable_fields) {
+                    let (valid_fields, hidden_fields) =
+                        self.index.remove_hidden_fields(self.txn, sortable_fields)?;
+
+                    return Err(UserError::InvalidRankingRuleSortableAttribute {
+                        field: field.to_string(),
+                        valid_fields,
+                        hidden_fields,
+                    }
+                    .into());
+                }
+            }
+        }
+
+        self.ranking_rules = Some(ranking_rules);
+
+        Ok(())
+    }
+
+    /// Evaluates the boost filters that the `boost` ranking rule will use.
+    pub fn boosts(&mut self, boosts: &[Boost]) -> Result<()> {
+        if boosts.is_empty() {
+            return Ok(());
+        }
+
+        // We check that the boost ranking rule exists and throw an
+        // error if we try to use it and that it doesn't.
+        if !self.criteria()?.contains(&Criterion::Boost) {
+            return Err(UserError::BoostRankingRuleMissing.into());
+        }
+
+        self.boosts = boosts
+            .iter()
+            .map(|Boost { filter, weight }| {
+                let docids = filter.evaluate(self.txn, self.index)?;
+                Ok(BoostDocids { docids, weight: *weight })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(())
+    }
+
+    /// The ranking rules used by this search, the ones given with the search
+    /// take precedence over the ones of the index.
+    pub fn criteria(&self) -> Result<Vec<Criterion>> {
+        match &self.ranking_rules {
+            Some(ranking_rules) => Ok(ranking_rules.clone()),
+            None => Ok(self.index.criteria(self.txn)?),
+        }
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Eq)]
@@ -181,6 +254,41 @@ impl RestrictedFids {
     }
 }
 
+/// Returns the locales of the attributes the query is searched on,
+/// only if all of them are localized by the `localizedAttributes` setting.
+fn searched_attributes_locales(ctx: &SearchContext) -> Result<Option<Vec<Language>>> {
+    let Some(rules) = ctx.index.localized_attributes_rules(ctx.txn)? else { return Ok(None) };
+
+    let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
+    let searched_fids: Vec<_> = match &ctx.restricted_fids {
+        Some(restricted_fids) => restricted_fids
+            .tolerant
+            .iter()
+            .chain(&restricted_fids.exact)
+            .map(|(fid, _)| *fid)
+            .collect(),
+        None => ctx.index.searchable_fields_ids(ctx.txn)?,
+    };
+    let localized_fids =
+        LocalizedFieldIds::new(&Some(rules), &fields_ids_map, searched_fids.iter().cloned());
+
+    let mut locales = Vec::new();
+    for fid in searched_fids {
+        match localized_fids.locales(fid) {
+            Some(field_locales) => {
+                for locale in field_locales {
+                    if !locales.contains(locale) {
+                        locales.push(*locale);
+                    }
+                }
+            }
+            None => return Ok(None),
+        }
+    }
+
+    Ok((!locales.is_empty()).then_some(locales))
+}
+
 /// Apply the [`TermsMatchingStrategy`] to the query graph and resolve it.
 fn resolve_maximally_reduced_query_graph(
     ctx: &mut SearchContext,
@@ -265,16 +373,19 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
     geo_strategy: geo_sort::Strategy,
 ) -> Result<Vec<BoxRankingRule<'ctx, PlaceholderQuery>>> {
     let mut sort = false;
+    let mut boost = false;
     let mut sorted_fields = HashSet::new();
     let mut geo_sorted = false;
     let mut ranking_rules: Vec<BoxRankingRule<PlaceholderQuery>> = vec![];
-    let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
+    let settings_ranking_rules = ctx.criteria()?;
     for rr in settings_ranking_rules {
         match rr {
             // These rules need a query to have an effect; ignore them in placeholder search
             crate::Criterion::Words
             | crate::Criterion::Typo
             | crate::Criterion::Attribute
+            | crate::Criterion::AttributeRank
+            | crate::Criterion::WordPosition
             | crate::Criterion::Proximity
             | crate::Criterion::Exactness => continue,
             crate::Criterion::Sort => {
@@ -291,6 +402,13 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
                 )?;
                 sort = true;
             }
+            crate::Criterion::Boost => {
+                if boost || ctx.boosts.is_empty() {
+                    continue;
+                }
+                boost = true;
+                ranking_rules.push(Box::new(BoostRule::new(ctx.boosts.clone())));
+            }
             crate::Criterion::Asc(field_name) => {
                 if sorted_fields.contains(&field_name) {
                     continue;
@@ -322,19 +440,22 @@ fn get_ranking_rules_for_vector<'ctx>(
     // query graph search
 
     let mut sort = false;
+    let mut boost = false;
     let mut sorted_fields = HashSet::new();
     let mut geo_sorted = false;
 
     let mut vector = false;
     let mut ranking_rules: Vec<BoxRankingRule<PlaceholderQuery>> = vec![];
 
-    let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
+    let settings_ranking_rules = ctx.criteria()?;
     for rr in settings_ranking_rules {
         match rr {
             crate::Criterion::Words
             | crate::Criterion::Typo
             | crate::Criterion::Proximity
             | crate::Criterion::Attribute
+            | crate::Criterion::AttributeRank
+            | crate::Criterion::WordPosition
             | crate::Criterion::Exactness => {
                 if !vector {
                     let vector_candidates = ctx.index.documents_ids(ctx.txn)?;
@@ -364,6 +485,13 @@ fn get_ranking_rules_for_vector<'ctx>(
                 )?;
                 sort = true;
             }
+            crate::Criterion::Boost => {
+                if boost || ctx.boosts.is_empty() {
+                    continue;
+                }
+                boost = true;
+                ranking_rules.push(Box::new(BoostRule::new(ctx.boosts.clone())));
+            }
             crate::Criterion::Asc(field_name) => {
                 if sorted_fields.contains(&field_name) {
                     continue;
@@ -396,7 +524,9 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
     let mut typo = false;
     let mut proximity = false;
     let mut sort = false;
-    let mut attribute = false;
+    let mut boost = false;
+    let mut attribute_rank = false;
+    let mut word_position = false;
     let mut exactness = false;
     let mut sorted_fields = HashSet::new();
     let mut geo_sorted = false;
@@ -407,12 +537,14 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
     }
 
     let mut ranking_rules: Vec<BoxRankingRule<QueryGraph>> = vec![];
-    let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
+    let settings_ranking_rules = ctx.criteria()?;
     for rr in settings_ranking_rules {
         // Add Words before any of: typo, proximity, attribute
         match rr {
             crate::Criterion::Typo
             | crate::Criterion::Attribute
+            | crate::Criterion::AttributeRank
+            | crate::Criterion::WordPosition
             | crate::Criterion::Proximity
             | crate::Criterion::Exactness => {
                 if !words {
@@ -445,12 +577,31 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                 ranking_rules.push(Box::new(Proximity::new(None)));
             }
             crate::Criterion::Attribute => {
-                if attribute {
+                match (attribute_rank, word_position) {
+                    (true, true) => continue,
+                    (false, false) => {
+                        ranking_rules.push(Box::new(Fid::new(None)));
+                        ranking_rules.push(Box::new(Position::new(None)));
+                    }
+                    (false, true) => ranking_rules.push(Box::new(Fid::attribute_rank(None))),
+                    (true, false) => ranking_rules.push(Box::new(Position::word_position(None))),
+                }
+                attribute_rank = true;
+                word_position = true;
+            }
+            crate::Criterion::AttributeRank => {
+                if attribute_rank {
                     continue;
                 }
-                attribute = true;
-                ranking_rules.push(Box::new(Fid::new(None)));
-                ranking_rules.push(Box::new(Position::new(None)));
+                attribute_rank = true;
+                ranking_rules.push(Box::new(Fid::attribute_rank(None)));
+            }
+            crate::Criterion::WordPosition => {
+                if word_position {
+                    continue;
+                }
+                word_position = true;
+                ranking_rules.push(Box::new(Position::word_position(None)));
             }
             crate::Criterion::Sort => {
                 if sort {
@@ -474,6 +625,13 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                 ranking_rules.push(Box::new(Exactness::new()));
                 exactness = true;
             }
+            crate::Criterion::Boost => {
+                if boost || ctx.boosts.is_empty() {
+                    continue;
+                }
+                boost = true;
+                ranking_rules.push(Box::new(BoostRule::new(ctx.boosts.clone())));
+            }
             crate::Criterion::Asc(field_name) => {
                 if sorted_fields.contains(&field_name) {
                     continue;
@@ -529,6 +687,7 @@ fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
                     geo_faceted_docids,
                     point,
                     true,
+                    None,
                 )?));
             }
             AscDesc::Desc(Member::Geo(point)) => {
@@ -541,6 +700,22 @@ fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
                     geo_faceted_docids,
                     point,
                     false,
+                    None,
+                )?));
+            }
+            AscDesc::Asc(Member::GeoDecay(point, decay))
+            | AscDesc::Desc(Member::GeoDecay(point, decay)) => {
+                if *geo_sorted {
+                    continue;
+                }
+                // the closest documents have the highest scores, the sort is thus ascending
+                let geo_faceted_docids = ctx.index.geo_faceted_documents_ids(ctx.txn)?;
+                ranking_rules.push(Box::new(GeoSort::new(
+                    geo_strategy,
+                    geo_faceted_docids,
+                    point,
+                    true,
+                    Some(decay),
                 )?));
             }
         };
@@ -568,7 +743,7 @@ pub fn execute_vector_search(
     scoring_strategy: ScoringStrategy,
     universe: RoaringBitmap,
     sort_criteria: &Option<Vec<AscDesc>>,
-    distinct: &Option<String>,
+    distinct: &Option<Vec<String>>,
     geo_strategy: geo_sort::Strategy,
     from: usize,
     length: usize,
@@ -619,6 +794,96 @@ pub fn execute_vector_search(
     })
 }
 
+/// Tokenizes the query into the terms to search for and removes the documents containing
+/// its negative words or phrases from the universe.
+///
+/// Also returns whether the query used the negative operator.
+fn tokenize_query(
+    ctx: &mut SearchContext,
+    query: &str,
+    words_limit: Option<usize>,
+    locales: Option<&[Language]>,
+    universe: &mut RoaringBitmap,
+) -> Result<(Vec<LocatedQueryTerm>, bool)> {
+    let span = tracing::trace_span!(target: "search::tokens", "tokenizer_builder");
+    let entered = span.enter();
+
+    // We make sure that the analyzer is aware of the stop words
+    // this ensures that the query builder is able to properly remove them.
+    let mut tokbuilder = TokenizerBuilder::new();
+    let stop_words = ctx.index.stop_words(ctx.txn)?;
+    if let Some(ref stop_words) = stop_words {
+        tokbuilder.stop_words(stop_words);
+    }
+
+    let separators = ctx.index.allowed_separators(ctx.txn)?;
+    let separators: Option<Vec<_>> =
+        separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+    if let Some(ref separators) = separators {
+        tokbuilder.separators(separators);
+    }
+
+    let dictionary = ctx.index.dictionary(ctx.txn)?;
+    let dictionary: Option<Vec<_>> =
+        dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+    if let Some(ref dictionary) = dictionary {
+        tokbuilder.words_dict(dictionary);
+    }
+
+    // The locales given with the query take precedence over the locales of the searched
+    // attributes, which themselves take precedence over the languages detected in the documents.
+    let locales = match locales {
+        Some(locales) => Some(locales.to_vec()),
+        None => searched_attributes_locales(ctx)?,
+    };
+    let script_lang_map = match locales {
+        Some(locales) if !locales.is_empty() => locales_allow_list(&locales),
+        _ => ctx.index.script_language(ctx.txn)?,
+    };
+    if !script_lang_map.is_empty() {
+        tokbuilder.allow_list(&script_lang_map);
+    }
+
+    let tokenizer = tokbuilder.build();
+    drop(entered);
+
+    let span = tracing::trace_span!(target: "search::tokens", "tokenize");
+    let entered = span.enter();
+    let tokens = tokenizer.tokenize(query);
+    drop(entered);
+
+    let ExtractedTokens { query_terms, negative_words, negative_phrases } =
+        located_query_terms_from_tokens(ctx, tokens, words_limit)?;
+    let used_negative_operator = !negative_words.is_empty() || !negative_phrases.is_empty();
+
+    let ignored_documents = resolve_negative_words(ctx, &negative_words)?;
+    let ignored_phrases = resolve_negative_phrases(ctx, &negative_phrases)?;
+
+    *universe -= ignored_documents;
+    *universe -= ignored_phrases;
+
+    Ok((query_terms, used_negative_operator))
+}
+
+/// Returns the documents of the universe matching the query, before any distinct rule is applied.
+pub fn resolve_query_candidates(
+    ctx: &mut SearchContext,
+    query: Option<&str>,
+    terms_matching_strategy: TermsMatchingStrategy,
+    words_limit: Option<usize>,
+    locales: Option<&[Language]>,
+    mut universe: RoaringBitmap,
+) -> Result<RoaringBitmap> {
+    let Some(query) = query else { return Ok(universe) };
+    let (query_terms, _) = tokenize_query(ctx, query, words_limit, locales, &mut universe)?;
+    if query_terms.is_empty() {
+        return Ok(universe);
+    }
+
+    let (graph, _) = QueryGraph::from_query(ctx, &query_terms)?;
+    resolve_universe(ctx, &universe, &graph, terms_matching_strategy, &mut DefaultSearchLogger)
+}
+
 #[allow(clippy::too_many_arguments)]
 #[tracing::instrument(level = "trace", skip_all, target = "search::main")]
 pub fn execute_search(
@@ -629,7 +894,7 @@ pub fn execute_search(
     exhaustive_number_hits: bool,
     mut universe: RoaringBitmap,
     sort_criteria: &Option<Vec<AscDesc>>,
-    distinct: &Option<String>,
+    distinct: &Option<Vec<String>>,
     geo_strategy: geo_sort::Strategy,
     from: usize,
     length: usize,
@@ -638,59 +903,16 @@ pub fn execute_search(
     query_graph_logger: &mut dyn SearchLogger<QueryGraph>,
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
+    locales: Option<&[Language]>,
 ) -> Result<PartialSearchResult> {
     check_sort_criteria(ctx, sort_criteria.as_ref())?;
 
     let mut used_negative_operator = false;
     let mut located_query_terms = None;
     let query_terms = if let Some(query) = query {
-        let span = tracing::trace_span!(target: "search::tokens", "tokenizer_builder");
-        let entered = span.enter();
-
-        // We make sure that the analyzer is aware of the stop words
-        // this ensures that the query builder is able to properly remove them.
-        let mut tokbuilder = TokenizerBuilder::new();
-        let stop_words = ctx.index.stop_words(ctx.txn)?;
-        if let Some(ref stop_words) = stop_words {
-            tokbuilder.stop_words(stop_words);
-        }
-
-        let separators = ctx.index.allowed_separators(ctx.txn)?;
-        let separators: Option<Vec<_>> =
-            separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
-        if let Some(ref separators) = separators {
-            tokbuilder.separators(separators);
-        }
-
-        let dictionary = ctx.index.dictionary(ctx.txn)?;
-        let dictionary: Option<Vec<_>> =
-            dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
-        if let Some(ref dictionary) = dictionary {
-            tokbuilder.words_dict(dictionary);
-        }
-
-        let script_lang_map = ctx.index.script_language(ctx.txn)?;
-        if !script_lang_map.is_empty() {
-            tokbuilder.allow_list(&script_lang_map);
-        }
-
-        let tokenizer = tokbuilder.build();
-        drop(entered);
-
-        let span = tracing::trace_span!(target: "search::tokens", "tokenize");
-        let entered = span.enter();
-        let tokens = tokenizer.tokenize(query);
-        drop(entered);
-
-        let ExtractedTokens { query_terms, negative_words, negative_phrases } =
-            located_query_terms_from_tokens(ctx, tokens, words_limit)?;
-        used_negative_operator = !negative_words.is_empty() || !negative_phrases.is_empty();
-
-        let ignored_documents = resolve_negative_words(ctx, &negative_words)?;
-        let ignored_phrases = resolve_negative_phrases(ctx, &negative_phrases)?;
-
-        universe -= ignored_documents;
-        universe -= ignored_phrases;
+        let (query_terms, negative_operator) =
+            tokenize_query(ctx, query, words_limit, locales, &mut universe)?;
+        used_negative_operator = negative_operator;
 
         if query_terms.is_empty() {
             // Do a placeholder search instead
@@ -748,20 +970,13 @@ pub fn execute_search(
     };
 
     let BucketSortOutput { docids, scores, mut all_candidates, degraded } = bucket_sort_output;
-    let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
 
     // The candidates is the universe unless the exhaustive number of hits
     // is requested and a distinct attribute is set.
     if exhaustive_number_hits {
-        let distinct_field = match distinct.as_deref() {
-            Some(distinct) => Some(distinct),
-            None => ctx.index.distinct_field(ctx.txn)?,
-        };
-
-        if let Some(f) = distinct_field {
-            if let Some(distinct_fid) = fields_ids_map.id(f) {
-                all_candidates = apply_distinct_rule(ctx, distinct_fid, &all_candidates)?.remaining;
-            }
+        let distinct_fids = distinct_fids(ctx.index, ctx.txn, distinct.as_deref())?;
+        if !distinct_fids.is_empty() {
+            all_candidates = apply_distinct_rule(ctx, &distinct_fids, &all_candidates)?.remaining;
         }
     }
 
@@ -788,7 +1003,7 @@ fn check_sort_criteria(ctx: &SearchContext, sort_criteria: Option<&Vec<AscDesc>>
 
     // We check that the sort ranking rule exists and throw an
     // error if we try to use it and that it doesn't.
-    let sort_ranking_rule_missing = !ctx.index.criteria(ctx.txn)?.contains(&crate::Criterion::Sort);
+    let sort_ranking_rule_missing = !ctx.criteria()?.contains(&crate::Criterion::Sort);
     if sort_ranking_rule_missing {
         return Err(UserError::SortRankingRuleMissing.into());
     }
@@ -809,7 +1024,7 @@ fn check_sort_criteria(ctx: &SearchContext, sort_criteria: Option<&Vec<AscDesc>>
                 }
                 .into());
             }
-            Member::Geo(_) if !sortable_fields.contains("_geo") => {
+            Member::Geo(_) | Member::GeoDecay(..) if !sortable_fields.contains("_geo") => {
                 let (valid_fields, hidden_fields) =
                     ctx.index.remove_hidden_fields(ctx.txn, sortable_fields)?;
 
@@ -444,6 +444,32 @@ impl QueryTerm {
     }
 }
 
+/// Return the words of the index whose beginning is one or two typos away from the given prefix,
+/// along with their number of typos.
+///
+/// The derivations are the ones of a prefix query term, computed with the same limits.
+pub fn prefix_typo_derivations(
+    ctx: &mut SearchContext,
+    prefix: &str,
+    max_typo: u8,
+) -> Result<Vec<(Interned<String>, u8)>> {
+    let term = compute_derivations::partially_initialized_term_from_word(
+        ctx, prefix, max_typo, true, false,
+    )?;
+    let term = ctx.term_interner.push(term);
+    term.compute_fully_if_needed(ctx)?;
+
+    let QueryTerm { one_typo, two_typo, .. } = ctx.term_interner.get(term);
+    let mut derivations = Vec::new();
+    if let Lazy::Init(OneTypoTerm { one_typo, split_words: _ }) = one_typo {
+        derivations.extend(one_typo.iter().map(|&word| (word, 1)));
+    }
+    if let Lazy::Init(TwoTypoTerm { two_typos }) = two_typo {
+        derivations.extend(two_typos.iter().map(|&word| (word, 2)));
+    }
+    Ok(derivations)
+}
+
 impl Interned<QueryTerm> {
     /// Return the original word from the given query term
     fn original_single_word(self, ctx: &SearchContext) -> Option<Interned<String>> {
@@ -0,0 +1,198 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use std::ops::Bound;
+
+use charabia::{TokenKind, TokenizerBuilder};
+use fst::automaton::{Automaton, Str};
+use fst::{IntoStreamer, Streamer};
+use heed::RoTxn;
+
+use super::query_term::{number_of_typos_allowed, prefix_typo_derivations};
+use super::SearchContext;
+use crate::{CboRoaringBitmapLenCodec, Index, Result, MAX_WORD_LENGTH};
+
+/// The default maximum number of suggestions returned for a query.
+const DEFAULT_SUGGESTION_LIMIT: usize = 10;
+/// The maximum number of words of the index starting with the last word of the query
+/// that are ranked, the logged words starting with it are ranked in any case.
+const MAX_RANKED_COMPLETIONS: usize = 10_000;
+
+/// A completion of the last word of a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    /// The query in which the last word is replaced by its completion.
+    pub text: String,
+    /// The word of the index completing the last word of the query.
+    pub word: String,
+    /// The number of typos between the last word of the query and the beginning of the word.
+    pub typos: u8,
+    /// The number of documents containing the word.
+    pub document_count: u64,
+    /// The number of times the word was part of a logged query.
+    pub query_count: u64,
+}
+
+/// Suggests completions for the last word of a query, taken from the words of the index.
+///
+/// The completions are ranked by increasing number of typos, then by decreasing number of times
+/// they were searched according to the query log, and then by decreasing number of documents
+/// containing them.
+pub struct Suggest<'a> {
+    query: String,
+    limit: usize,
+    query_word_counts: Option<&'a BTreeMap<String, u64>>,
+    index: &'a Index,
+    rtxn: &'a RoTxn<'a>,
+}
+
+impl<'a> Suggest<'a> {
+    pub fn new(query: impl Into<String>, index: &'a Index, rtxn: &'a RoTxn<'a>) -> Self {
+        Self {
+            query: query.into(),
+            limit: DEFAULT_SUGGESTION_LIMIT,
+            query_word_counts: None,
+            index,
+            rtxn,
+        }
+    }
+
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = limit;
+        self
+    }
+
+    /// The number of times each word was part of a previous successful query made on the index.
+    pub fn query_word_counts(&mut self, query_word_counts: &'a BTreeMap<String, u64>) -> &mut Self {
+        self.query_word_counts = Some(query_word_counts);
+        self
+    }
+
+    pub fn execute(&self) -> Result<Vec<Suggestion>> {
+        let separators = self.index.allowed_separators(self.rtxn)?;
+        let separators: Option<Vec<_>> =
+            separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        let dictionary = self.index.dictionary(self.rtxn)?;
+        let dictionary: Option<Vec<_>> =
+            dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+
+        let mut tokbuilder = TokenizerBuilder::<Vec<u8>>::new();
+        if let Some(ref separators) = separators {
+            tokbuilder.separators(separators);
+        }
+        if let Some(ref dictionary) = dictionary {
+            tokbuilder.words_dict(dictionary);
+        }
+        let tokenizer = tokbuilder.build();
+
+        // We only complete the last word of the query, when the query
+        // ends with a separator the user is done typing the last word.
+        let last_token = tokenizer.tokenize(&self.query).last();
+        let (prefix, start) = match last_token {
+            Some(token) if matches!(token.kind, TokenKind::Word) && !token.lemma().is_empty() => {
+                (token.lemma().to_string(), token.byte_start)
+            }
+            _ => return Ok(Vec::new()),
+        };
+        if prefix.len() > MAX_WORD_LENGTH || self.limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut ctx = SearchContext::new(self.index, self.rtxn)?;
+
+        let empty_counts = BTreeMap::new();
+        let query_word_counts = self.query_word_counts.unwrap_or(&empty_counts);
+
+        // The words starting with a typo of the prefix, the ones starting
+        // with the prefix itself are all streamed from the words FST below.
+        let max_typo = number_of_typos_allowed(&ctx)?(&prefix);
+        let mut typo_completions = HashMap::new();
+        for (word, typos) in prefix_typo_derivations(&mut ctx, &prefix, max_typo)? {
+            let word = ctx.word_interner.get(word);
+            if !word.starts_with(&prefix) {
+                typo_completions.entry(word.clone()).or_insert(typos);
+            }
+        }
+
+        // We rank every completion but only keep the best ones, the worst kept completion
+        // is at the top of the heap and is popped as soon as a better one is found.
+        let mut best = BinaryHeap::new();
+        let mut push = |word: String, typos: u8| -> Result<()> {
+            let document_count = self.document_count(&word)?;
+            let query_count = query_word_counts.get(&word).copied().unwrap_or_default();
+            best.push((typos, Reverse(query_count), Reverse(document_count), word));
+            if best.len() > self.limit {
+                best.pop();
+            }
+            Ok(())
+        };
+
+        // The words are streamed in lexicographic order, when the stream is cut the logged words
+        // not streamed yet are ranked too, so that the popular completions are never missed.
+        let fst = self.index.words_fst(self.rtxn)?;
+        let mut stream = fst.search(Str::new(&prefix).starts_with()).into_stream();
+        let mut last_streamed = None;
+        for _ in 0..MAX_RANKED_COMPLETIONS {
+            let Some(word) = stream.next() else {
+                last_streamed = None;
+                break;
+            };
+            let word = std::str::from_utf8(word)?.to_string();
+            last_streamed = Some(word.clone());
+            push(word, 0)?;
+        }
+        if let Some(last_streamed) = last_streamed {
+            let logged_words = query_word_counts
+                .range::<str, _>((Bound::Excluded(last_streamed.as_str()), Bound::Unbounded))
+                .map(|(word, _)| word)
+                .take_while(|word| word.starts_with(&prefix));
+            for word in logged_words {
+                if fst.contains(word) {
+                    push(word.clone(), 0)?;
+                }
+            }
+        }
+        for (word, typos) in typo_completions {
+            push(word, typos)?;
+        }
+
+        let suggestions = best
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(typos, Reverse(query_count), Reverse(document_count), word)| Suggestion {
+                text: format!("{}{word}", &self.query[..start]),
+                word,
+                typos,
+                document_count,
+                query_count,
+            })
+            .collect();
+
+        Ok(suggestions)
+    }
+
+    /// The number of documents containing the word, in any searchable attribute.
+    fn document_count(&self, word: &str) -> Result<u64> {
+        let exact_count = self
+            .index
+            .exact_word_docids
+            .remap_data_type::<CboRoaringBitmapLenCodec>()
+            .get(self.rtxn, word)?;
+
+        // Only decode the bitmaps when the word is also in the exact attributes,
+        // as the same document may contain the word in both kinds of attributes.
+        match exact_count {
+            None => Ok(self
+                .index
+                .word_docids
+                .remap_data_type::<CboRoaringBitmapLenCodec>()
+                .get(self.rtxn, word)?
+                .unwrap_or_default()),
+            Some(_) => {
+                let docids = self.index.word_docids.get(self.rtxn, word)?.unwrap_or_default();
+                let exact_docids =
+                    self.index.exact_word_docids.get(self.rtxn, word)?.unwrap_or_default();
+                Ok((docids | exact_docids).len())
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+/*!
+This module tests the `attributeRank` and `wordPosition` ranking rules, which are the two
+halves of the `attribute` ranking rule:
+
+1. `attributeRank` only looks at the importance of the attribute in which the query words are found
+2. `wordPosition` only looks at the position of the query words in their attribute
+3. `attribute` is equivalent to `attributeRank` followed by `wordPosition`
+4. each of them has its own entry in the score details
+*/
+
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::{Criterion, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned(), "description".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "title": "the story of the little brown dog and the fox",
+                "description": "",
+            },
+            {
+                "id": 1,
+                "title": "",
+                "description": "fox are quick animals",
+            },
+            {
+                "id": 2,
+                "title": "fox",
+                "description": "",
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search_with_criteria(index: &TempIndex, criteria: Vec<Criterion>) -> SearchResult {
+    index.update_settings(|s| s.set_criteria(criteria.clone())).unwrap();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, index);
+    s.query("fox");
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    s.execute().unwrap()
+}
+
+#[test]
+fn attribute_rank() {
+    let index = create_index();
+
+    let SearchResult { documents_ids, document_scores, .. } =
+        search_with_criteria(&index, vec![Criterion::AttributeRank]);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2, 1]");
+
+    let details = ScoreDetails::to_json_map(document_scores[0].iter());
+    assert!(details.contains_key("attributeRank"));
+    assert!(!details.contains_key("attribute"));
+    assert!(!details.contains_key("wordPosition"));
+}
+
+#[test]
+fn word_position() {
+    let index = create_index();
+
+    let SearchResult { documents_ids, document_scores, .. } =
+        search_with_criteria(&index, vec![Criterion::WordPosition]);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 2, 0]");
+
+    let details = ScoreDetails::to_json_map(document_scores[0].iter());
+    assert!(details.contains_key("wordPosition"));
+    assert!(!details.contains_key("attribute"));
+    assert!(!details.contains_key("attributeRank"));
+}
+
+#[test]
+fn word_position_before_attribute_rank() {
+    let index = create_index();
+
+    let SearchResult { documents_ids, document_scores, .. } =
+        search_with_criteria(&index, vec![Criterion::WordPosition, Criterion::AttributeRank]);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 1, 0]");
+
+    let details = ScoreDetails::to_json_map(document_scores[0].iter());
+    assert!(details["wordPosition"]["order"].as_u64() < details["attributeRank"]["order"].as_u64());
+}
+
+#[test]
+fn attribute_is_an_alias() {
+    let index = create_index();
+
+    let SearchResult { documents_ids: alias_ids, document_scores, .. } =
+        search_with_criteria(&index, vec![Criterion::Attribute]);
+    insta::assert_snapshot!(format!("{alias_ids:?}"), @"[2, 0, 1]");
+
+    // the score details of the alias are merged in a single entry
+    let details = ScoreDetails::to_json_map(document_scores[0].iter());
+    assert!(details.contains_key("attribute"));
+    assert!(!details.contains_key("attributeRank"));
+    assert!(!details.contains_key("wordPosition"));
+
+    let SearchResult { documents_ids, .. } =
+        search_with_criteria(&index, vec![Criterion::AttributeRank, Criterion::WordPosition]);
+    assert_eq!(documents_ids, alias_ids);
+
+    // `attribute` only completes the rules that are not already present
+    let SearchResult { documents_ids, document_scores, .. } = search_with_criteria(
+        &index,
+        vec![Criterion::WordPosition, Criterion::Attribute, Criterion::AttributeRank],
+    );
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 1, 0]");
+    let details = ScoreDetails::to_json_map(document_scores[0].iter());
+    assert!(details["wordPosition"]["order"].as_u64() < details["attributeRank"]["order"].as_u64());
+}
@@ -0,0 +1,94 @@
+/*!
+This module tests the `boost` ranking rule:
+
+1. the documents are sorted by the sum of the weights of the boost filters they match
+2. the ranking rule does nothing when no boost filter is given
+3. an error is returned if boost filters are given but the `boost` ranking rule is missing
+4. the contribution of the boost filters is reported in the score details
+*/
+
+use big_s::S;
+use maplit::hashset;
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{self, ScoreDetails, ScoringStrategy};
+use crate::{Boost, Criterion, Filter, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_filterable_fields(hashset! { S("in_stock"), S("brand") });
+            s.set_sortable_fields(hashset! { S("price") });
+            s.set_criteria(vec![Criterion::Boost, Criterion::Asc(S("price"))]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "in_stock": false, "brand": "A", "price": 10 },
+            { "id": 1, "in_stock": true, "brand": "B", "price": 20 },
+            { "id": 2, "in_stock": true, "brand": "A", "price": 30 },
+            { "id": 3, "in_stock": false, "brand": "B", "price": 40 },
+        ]))
+        .unwrap();
+    index
+}
+
+fn boost(filter: &str, weight: f64) -> Boost<'_> {
+    Boost { filter: Filter::from_str(filter).unwrap().unwrap(), weight }
+}
+
+#[test]
+fn no_boost() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let s = Search::new(&txn, &index);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2, 3]");
+}
+
+#[test]
+fn boost_filters() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.boosts(vec![boost("in_stock = true", 2.0)]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 2, 0, 3]");
+
+    let mut s = Search::new(&txn, &index);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    s.boosts(vec![boost("in_stock = true", 2.0), boost("brand = A", 1.0)]);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 1, 0, 3]");
+
+    let boost_weights: Vec<_> = document_scores
+        .iter()
+        .map(|scores| match scores.first() {
+            Some(ScoreDetails::Boost(score_details::Boost { weight, max_weight })) => {
+                assert_eq!(*max_weight, 3.0);
+                *weight
+            }
+            otherwise => panic!("unexpected score details: {:?}", otherwise),
+        })
+        .collect();
+    insta::assert_snapshot!(format!("{boost_weights:?}"), @"[3.0, 2.0, 1.0, 0.0]");
+}
+
+#[test]
+fn boost_without_boost_ranking_rule() {
+    let index = create_index();
+    index.update_settings(|s| s.set_criteria(vec![Criterion::Asc(S("price"))])).unwrap();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.boosts(vec![boost("in_stock = true", 2.0)]);
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error.to_string(), @"You must specify where `boost` is listed in the rankingRules setting to use the boost parameter at search time.");
+}
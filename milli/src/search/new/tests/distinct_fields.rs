@@ -0,0 +1,137 @@
+/*!
+This module tests the distinct rule applied on several attributes at once,
+and the distinct groups returned along with the hits:
+
+1. two documents are duplicates when they share a value for each of the distinct attributes,
+a missing value being shared by all the documents missing it
+2. a document without a value for any of the distinct attributes is unique
+3. the group of a hit contains the other documents of its distinct key matching the filter
+and the query
+*/
+
+use big_s::S;
+use maplit::hashset;
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, Filter, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_filterable_fields(hashset! { S("brand"), S("color") });
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "brand": "A", "color": "red", "title": "shirt" },
+            { "id": 1, "brand": "A", "color": "blue", "title": "shirt" },
+            { "id": 2, "brand": "A", "color": "red", "title": "shirt" },
+            { "id": 3, "brand": "B", "color": "red", "title": "shirt" },
+            { "id": 4, "brand": "B", "title": "shirt" },
+            { "id": 5, "brand": "B", "title": "shirt" },
+            { "id": 6, "title": "shirt" },
+            { "id": 7, "title": "shirt" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn format_groups(s: &Search, documents_ids: &[u32]) -> String {
+    let groups = s.distinct_groups(documents_ids).unwrap();
+    let groups: Vec<Vec<u32>> = groups.iter().map(|group| group.iter().collect()).collect();
+    format!("{groups:?}")
+}
+
+#[test]
+fn distinct_on_several_fields() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.distinct_fields(vec![S("brand"), S("color")]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 3, 4, 6, 7]");
+    insta::assert_snapshot!(format_groups(&s, &documents_ids), @"[[2], [], [], [5], [], []]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("shirt");
+    s.distinct_fields(vec![S("brand"), S("color")]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 3, 4, 6, 7]");
+}
+
+#[test]
+fn distinct_on_one_field() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.distinct_fields(vec![S("brand")]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 3, 6, 7]");
+    insta::assert_snapshot!(format_groups(&s, &documents_ids), @"[[1, 2], [4, 5], [], []]");
+}
+
+#[test]
+fn distinct_groups_with_filter() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.distinct_fields(vec![S("brand")]);
+    s.filter(Filter::from_str("color = red").unwrap().unwrap());
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 3]");
+    insta::assert_snapshot!(format_groups(&s, &documents_ids), @"[[2], []]");
+}
+
+#[test]
+fn distinct_groups_with_query() {
+    let index = create_index();
+    index
+        .add_documents(documents!([{ "id": 8, "brand": "A", "color": "red", "title": "pants" }]))
+        .unwrap();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("shirt");
+    s.distinct_fields(vec![S("brand")]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 3, 6, 7]");
+    insta::assert_snapshot!(format_groups(&s, &documents_ids), @"[[1, 2], [4, 5], [], []]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("pants");
+    s.distinct_fields(vec![S("brand")]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[8]");
+    insta::assert_snapshot!(format_groups(&s, &documents_ids), @"[[]]");
+}
+
+#[test]
+fn distinct_groups_without_distinct_attribute() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let s = Search::new(&txn, &index);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2, 3, 4, 5, 6, 7]");
+    insta::assert_snapshot!(format_groups(&s, &documents_ids), @"[[], [], [], [], [], [], [], []]");
+}
+
+#[test]
+fn distinct_on_a_field_that_is_not_filterable() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.distinct_fields(vec![S("brand"), S("title")]);
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error.to_string(), @"Attribute `title` is not filterable and thus, cannot be used as distinct attribute. Available filterable attributes are: `brand, color`.");
+}
@@ -0,0 +1,70 @@
+/*!
+This module tests the `excluded_documents` search parameter:
+
+1. the excluded documents are never returned, whatever their ranking
+2. the excluded documents are not counted in the candidates
+3. the excluded documents are removed from the filtered universe
+*/
+
+use std::iter::FromIterator;
+
+use big_s::S;
+use maplit::hashset;
+use meili_snap::insta;
+use roaring::RoaringBitmap;
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, Filter, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_filterable_fields(hashset! { S("in_stock") });
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "in_stock": true, "title": "phone" },
+            { "id": 1, "in_stock": false, "title": "phone case" },
+            { "id": 2, "in_stock": true, "title": "phone charger" },
+            { "id": 3, "in_stock": true, "title": "laptop" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn excluded_documents() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("phone");
+    let SearchResult { documents_ids, candidates, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2]");
+    insta::assert_snapshot!(format!("{candidates:?}"), @"RoaringBitmap<[0, 1, 2]>");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("phone");
+    s.excluded_documents(RoaringBitmap::from_iter([0, 3]));
+    let SearchResult { documents_ids, candidates, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 2]");
+    insta::assert_snapshot!(format!("{candidates:?}"), @"RoaringBitmap<[1, 2]>");
+}
+
+#[test]
+fn excluded_documents_with_filter() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.filter(Filter::from_str("in_stock = true").unwrap().unwrap());
+    s.excluded_documents(RoaringBitmap::from_iter([2]));
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 3]");
+}
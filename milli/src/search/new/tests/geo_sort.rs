@@ -221,6 +221,7 @@ fn geo_sort_mixed_with_words() {
                             0.0,
                         ],
                     ),
+                    decay: None,
                 },
             ),
         ],
@@ -244,6 +245,7 @@ fn geo_sort_mixed_with_words() {
                             -179.0,
                         ],
                     ),
+                    decay: None,
                 },
             ),
         ],
@@ -275,6 +277,7 @@ fn geo_sort_mixed_with_words() {
                             0.0,
                         ],
                     ),
+                    decay: None,
                 },
             ),
         ],
@@ -307,3 +310,47 @@ fn geo_sort_without_any_geo_faceted_documents() {
     insta::assert_snapshot!(format!("{ids:?}"), @"[0, 2, 3]");
     insta::assert_snapshot!(format!("{scores:#?}"));
 }
+
+#[test]
+fn geo_sort_with_decay() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_sortable_fields(hashset! { S("_geo") });
+            s.set_criteria(vec![Criterion::Sort, Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "name": "pizza", "_geo": { "lat": 0, "lng": 0.001 } },
+            { "id": 1, "name": "pizza margherita", "_geo": { "lat": 0, "lng": 0.005 } },
+            { "id": 2, "name": "pizza margherita", "_geo": { "lat": 0, "lng": 0.05 } },
+            { "id": 3, "name": "pizza margherita", "_geo": { "lat": 0, "lng": 0.5 } },
+            { "id": 4, "name": "pizza margherita" },
+        ]))
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&rtxn, &index);
+    s.scoring_strategy(crate::score_details::ScoringStrategy::Detailed);
+    s.query("pizza margherita");
+    s.sort_criteria(vec!["_geoPoint(0, 0):decay(scale=10km, offset=1km, fn=gauss)"
+        .parse::<AscDesc>()
+        .unwrap()]);
+
+    // the documents 0 and 1 are both under the offset, the words ranking rule decides
+    let (ids, scores) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[1, 0, 2, 3, 4]");
+
+    let ranks: Vec<_> = scores.iter().map(|score| score[0].rank().unwrap().rank).collect();
+    insta::assert_snapshot!(format!("{ranks:?}"), @"[102, 102, 89, 2, 1]");
+
+    // the decayed score is normalized and merged with the other ranking rules
+    let global_scores: Vec<_> =
+        scores.iter().map(|score| ScoreDetails::global_score(score.iter())).collect();
+    assert!(global_scores.windows(2).all(|w| w[0] > w[1]), "{global_scores:?}");
+}
@@ -1,8 +1,12 @@
 pub mod attribute_fid;
 pub mod attribute_position;
+pub mod attribute_rank_word_position;
+pub mod boost;
 pub mod cutoff;
 pub mod distinct;
+pub mod distinct_fields;
 pub mod exactness;
+pub mod excluded_documents;
 pub mod geo_sort;
 pub mod integration;
 #[cfg(feature = "all-tokenizations")]
@@ -10,8 +14,10 @@ pub mod language;
 pub mod ngram_split_words;
 pub mod proximity;
 pub mod proximity_typo;
+pub mod ranking_rules;
 pub mod sort;
 pub mod stop_words;
+pub mod suggest;
 pub mod typo;
 pub mod typo_proximity;
 pub mod words_tms;
@@ -25,7 +31,7 @@ fn collect_field_values(
     let mut values = vec![];
     let fid = index.fields_ids_map(txn).unwrap().id(fid).unwrap();
     for doc in index.documents(txn, docids.iter().copied()).unwrap() {
-        if let Some(v) = doc.1.get(fid) {
+        if let Some(v) = doc.1.as_obkv().get(fid) {
             let v: serde_json::Value = serde_json::from_slice(v).unwrap();
             let v = v.to_string();
             values.push(v);
@@ -0,0 +1,109 @@
+/*!
+This module tests the ranking rules given at search time:
+
+1. an error is returned if the index does not allow the ranking rules to be overridden
+2. the ranking rules given at search time replace the ones of the index
+3. an error is returned if an `asc`/`desc` ranking rule is not on a sortable field
+4. the `sort` search parameter requires the `sort` ranking rule in the overriding ranking rules
+*/
+
+use big_s::S;
+use maplit::hashset;
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::{AscDesc, Criterion, Member, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_sortable_fields(hashset! { S("rank"), S("price") });
+            s.set_criteria(vec![Criterion::Sort, Criterion::Asc(S("rank"))]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "rank": 2, "price": 10, "letter": "A" },
+            { "id": 1, "rank": 0, "price": 30, "letter": "B" },
+            { "id": 2, "rank": 1, "price": 20, "letter": "C" },
+            { "id": 3, "rank": 0, "price": 10, "letter": "D" },
+            { "id": 4, "rank": 2, "price": 0, "letter": "E" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn override_not_allowed() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.ranking_rules(vec![Criterion::Desc(S("price"))]);
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error.to_string(), @"The ranking rules of this index cannot be overridden at search time. Set the `allowRankingRulesOverride` setting to `true` to use the `rankingRules` search parameter.");
+}
+
+#[test]
+fn override_ranking_rules() {
+    let index = create_index();
+    index.update_settings(|s| s.set_allow_ranking_rules_override(true)).unwrap();
+    let txn = index.read_txn().unwrap();
+
+    let s = Search::new(&txn, &index);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 3, 2, 0, 4]");
+
+    let mut s = Search::new(&txn, &index);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    s.ranking_rules(vec![Criterion::Desc(S("price"))]);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 2, 0, 3, 4]");
+
+    // the score details are the ones of the overriding ranking rules
+    for scores in document_scores {
+        match scores.as_slice() {
+            [ScoreDetails::Sort(sort)] => {
+                assert_eq!(sort.field_name, "price");
+                assert!(!sort.ascending);
+            }
+            otherwise => panic!("unexpected score details: {:?}", otherwise),
+        }
+    }
+}
+
+#[test]
+fn override_with_unsortable_field() {
+    let index = create_index();
+    index.update_settings(|s| s.set_allow_ranking_rules_override(true)).unwrap();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.ranking_rules(vec![Criterion::Asc(S("letter"))]);
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error.to_string(), @"Attribute `letter` is not sortable and thus, cannot be used in the `rankingRules` search parameter. Available sortable attributes are: `price, rank`.");
+}
+
+#[test]
+fn override_without_sort_ranking_rule() {
+    let index = create_index();
+    index.update_settings(|s| s.set_allow_ranking_rules_override(true)).unwrap();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.ranking_rules(vec![Criterion::Asc(S("rank"))]);
+    s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("price")))]);
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error.to_string(), @"You must specify where `sort` is listed in the rankingRules setting to use the sort parameter at search time.");
+
+    let mut s = Search::new(&txn, &index);
+    s.ranking_rules(vec![Criterion::Asc(S("rank")), Criterion::Sort]);
+    s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("price")))]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 3, 2, 0, 4]");
+}
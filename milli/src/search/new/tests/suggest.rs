@@ -0,0 +1,138 @@
+/*!
+This module tests the suggestions of completions for the last word of a query:
+
+1. the completions are the words of the index starting with the last word of the query
+2. they are ranked by number of documents containing them
+3. the words starting with a typo of the last word are suggested after the exact completions
+4. the words of the logged queries are ranked before the others
+5. nothing is suggested when the query ends with a separator
+6. the completions are ranked among many words starting with the last word of the query
+*/
+
+use std::collections::BTreeMap;
+
+use meili_snap::insta;
+use serde_json::{json, Value};
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, Suggest, Suggestion};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "red shirt" },
+            { "id": 1, "title": "blue shirt" },
+            { "id": 2, "title": "shiny shoes" },
+            { "id": 3, "title": "shirt and shoes" },
+            { "id": 4, "title": "short skirt" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn format_suggestions(suggestions: &[Suggestion]) -> String {
+    let suggestions: Vec<_> = suggestions
+        .iter()
+        .map(|s| {
+            format!(
+                "{} (typos: {}, documents: {}, queries: {})",
+                s.text, s.typos, s.document_count, s.query_count
+            )
+        })
+        .collect();
+    suggestions.join("\n")
+}
+
+#[test]
+fn suggest_completions() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let suggestions = Suggest::new("sh", &index, &txn).execute().unwrap();
+    insta::assert_snapshot!(format_suggestions(&suggestions), @r###"
+    shirt (typos: 0, documents: 3, queries: 0)
+    shoes (typos: 0, documents: 2, queries: 0)
+    shiny (typos: 0, documents: 1, queries: 0)
+    short (typos: 0, documents: 1, queries: 0)
+    "###);
+
+    let suggestions = Suggest::new("sh", &index, &txn).limit(2).execute().unwrap();
+    insta::assert_snapshot!(format_suggestions(&suggestions), @r###"
+    shirt (typos: 0, documents: 3, queries: 0)
+    shoes (typos: 0, documents: 2, queries: 0)
+    "###);
+}
+
+#[test]
+fn suggest_completions_of_the_last_word() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let suggestions = Suggest::new("Red SHI", &index, &txn).execute().unwrap();
+    insta::assert_snapshot!(format_suggestions(&suggestions), @r###"
+    Red shirt (typos: 0, documents: 3, queries: 0)
+    Red shiny (typos: 0, documents: 1, queries: 0)
+    "###);
+
+    let suggestions = Suggest::new("red ", &index, &txn).execute().unwrap();
+    insta::assert_snapshot!(format_suggestions(&suggestions), @"");
+}
+
+#[test]
+fn suggest_completions_with_typos() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let suggestions = Suggest::new("skurt", &index, &txn).execute().unwrap();
+    insta::assert_snapshot!(format_suggestions(&suggestions), @"skirt (typos: 1, documents: 1, queries: 0)");
+
+    // the word is too short to allow typos
+    let suggestions = Suggest::new("sku", &index, &txn).execute().unwrap();
+    insta::assert_snapshot!(format_suggestions(&suggestions), @"");
+}
+
+#[test]
+fn suggest_completions_with_query_log() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let query_word_counts = BTreeMap::from([
+        (String::from("shiny"), 5),
+        (String::from("shoes"), 5),
+        (String::from("short"), 1),
+    ]);
+    let suggestions =
+        Suggest::new("sh", &index, &txn).query_word_counts(&query_word_counts).execute().unwrap();
+    insta::assert_snapshot!(format_suggestions(&suggestions), @r###"
+    shoes (typos: 0, documents: 2, queries: 5)
+    shiny (typos: 0, documents: 1, queries: 5)
+    short (typos: 0, documents: 1, queries: 1)
+    shirt (typos: 0, documents: 3, queries: 0)
+    "###);
+}
+
+#[test]
+fn suggest_the_best_completions_among_many_words() {
+    let index = create_index();
+
+    // many rare words sorted before the words of the other documents
+    let documents: Vec<Value> =
+        (0..2000).map(|i| json!({ "id": 100 + i, "title": format!("sha{i:04}") })).collect();
+    index.add_documents(documents!(documents)).unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let suggestions = Suggest::new("sh", &index, &txn).limit(2).execute().unwrap();
+    insta::assert_snapshot!(format_suggestions(&suggestions), @r###"
+    shirt (typos: 0, documents: 3, queries: 0)
+    shoes (typos: 0, documents: 2, queries: 0)
+    "###);
+}
@@ -1,11 +1,10 @@
 use std::iter::FromIterator;
 
-use ordered_float::OrderedFloat;
 use roaring::RoaringBitmap;
 
 use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
 use crate::score_details::{self, ScoreDetails};
-use crate::vector::{DistributionShift, Embedder};
+use crate::vector::{ArroyWrapper, DistributionShift, Embedder};
 use crate::{DocumentId, Result, SearchContext, SearchLogger};
 
 pub struct VectorSort<Q: RankingRuleQueryTrait> {
@@ -16,6 +15,7 @@ pub struct VectorSort<Q: RankingRuleQueryTrait> {
     limit: usize,
     distribution_shift: Option<DistributionShift>,
     embedder_index: u8,
+    quantized: bool,
 }
 
 impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
@@ -32,6 +32,12 @@ impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
             .embedder_category_id
             .get(ctx.txn, embedder_name)?
             .ok_or_else(|| crate::UserError::InvalidEmbedder(embedder_name.to_owned()))?;
+        let quantized = ctx
+            .index
+            .embedding_configs(ctx.txn)?
+            .into_iter()
+            .find(|config| config.name == embedder_name)
+            .map_or(false, |config| config.config.quantized());
 
         Ok(Self {
             query: None,
@@ -41,6 +47,7 @@ impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
             limit,
             distribution_shift: embedder.distribution(),
             embedder_index,
+            quantized,
         })
     }
 
@@ -49,19 +56,9 @@ impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
         ctx: &mut SearchContext<'_>,
         vector_candidates: &RoaringBitmap,
     ) -> Result<()> {
-        let readers: std::result::Result<Vec<_>, _> =
-            ctx.index.arroy_readers(ctx.txn, self.embedder_index).collect();
-        let readers = readers?;
-
-        let target = &self.target;
-        let mut results = Vec::new();
-
-        for reader in readers.iter() {
-            let nns_by_vector =
-                reader.nns_by_vector(ctx.txn, target, self.limit, None, Some(vector_candidates))?;
-            results.extend(nns_by_vector.into_iter());
-        }
-        results.sort_unstable_by_key(|(_, distance)| OrderedFloat(*distance));
+        let arroy = ArroyWrapper::new(ctx.index.vector_arroy, self.embedder_index, self.quantized);
+        let results =
+            arroy.nns_by_vector(ctx.txn, &self.target, self.limit, Some(vector_candidates))?;
         self.cached_sorted_docids = results.into_iter();
 
         Ok(())
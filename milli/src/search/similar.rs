@@ -1,10 +1,9 @@
 use std::sync::Arc;
 
-use ordered_float::OrderedFloat;
 use roaring::RoaringBitmap;
 
 use crate::score_details::{self, ScoreDetails};
-use crate::vector::Embedder;
+use crate::vector::{ArroyWrapper, Embedder};
 use crate::{filtered_universe, DocumentId, Filter, Index, Result, SearchResult};
 
 pub struct Similar<'a> {
@@ -67,29 +66,16 @@ impl<'a> Similar<'a> {
                 .get(self.rtxn, &self.embedder_name)?
                 .ok_or_else(|| crate::UserError::InvalidEmbedder(self.embedder_name.to_owned()))?;
 
-        let readers: std::result::Result<Vec<_>, _> =
-            self.index.arroy_readers(self.rtxn, embedder_index).collect();
-
-        let readers = readers?;
-
-        let mut results = Vec::new();
-
-        for reader in readers.iter() {
-            let nns_by_item = reader.nns_by_item(
-                self.rtxn,
-                self.id,
-                self.limit + self.offset + 1,
-                None,
-                Some(&universe),
-            )?;
-            if let Some(mut nns_by_item) = nns_by_item {
-                results.append(&mut nns_by_item);
-            } else {
-                break;
-            }
-        }
+        let quantized = self
+            .index
+            .embedding_configs(self.rtxn)?
+            .into_iter()
+            .find(|config| config.name == self.embedder_name)
+            .map_or(false, |config| config.config.quantized());
 
-        results.sort_unstable_by_key(|(_, distance)| OrderedFloat(*distance));
+        let arroy = ArroyWrapper::new(self.index.vector_arroy, embedder_index, quantized);
+        let results =
+            arroy.nns_by_item(self.rtxn, self.id, self.limit + self.offset + 1, Some(&universe))?;
 
         let mut documents_ids = Vec::with_capacity(self.limit);
         let mut document_scores = Vec::with_capacity(self.limit);
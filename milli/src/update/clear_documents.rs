@@ -49,6 +49,7 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
             vector_arroy,
             embedder_category_id: _,
             documents,
+            geojson_geometries,
         } = self.index;
 
         let empty_roaring = RoaringBitmap::default();
@@ -62,7 +63,10 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         self.index.put_documents_ids(self.wtxn, &empty_roaring)?;
         self.index.put_field_distribution(self.wtxn, &FieldDistribution::default())?;
         self.index.delete_geo_rtree(self.wtxn)?;
+        self.index.delete_geojson_rtree(self.wtxn)?;
         self.index.delete_geo_faceted_documents_ids(self.wtxn)?;
+        // The documents are no longer compressed once they are all removed.
+        self.index.delete_document_compression_dictionary(self.wtxn)?;
 
         // Remove all user-provided bits from the configs
         let mut configs = self.index.embedding_configs(self.wtxn)?;
@@ -93,6 +97,7 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         facet_id_string_docids.clear(self.wtxn)?;
         field_id_docid_facet_f64s.clear(self.wtxn)?;
         field_id_docid_facet_strings.clear(self.wtxn)?;
+        geojson_geometries.clear(self.wtxn)?;
         // vector
         vector_arroy.clear(self.wtxn)?;
 
@@ -136,6 +141,8 @@ mod tests {
         assert!(index.documents_ids(&rtxn).unwrap().is_empty());
         assert!(index.field_distribution(&rtxn).unwrap().is_empty());
         assert!(index.geo_rtree(&rtxn).unwrap().is_none());
+        assert!(index.geojson_rtree(&rtxn).unwrap().is_none());
+        assert!(index.geojson_geometries.is_empty(&rtxn).unwrap());
         assert!(index.geo_faceted_documents_ids(&rtxn).unwrap().is_empty());
 
         assert!(index.word_docids.is_empty(&rtxn).unwrap());
@@ -0,0 +1,162 @@
+use heed::RwTxn;
+use zstd::bulk::Compressor;
+
+use crate::{Index, Result};
+
+/// The zstd level used to compress the documents with the dictionary.
+const COMPRESSION_LEVEL: i32 = 3;
+/// The minimum number of documents an index must contain to train a dictionary.
+const MIN_DOCUMENTS_TO_TRAIN: u64 = 1_000;
+/// The maximum number of documents used as samples to train a dictionary.
+const MAX_SAMPLES: u64 = 10_000;
+/// The maximum size of the trained dictionary.
+const DICTIONARY_SIZE: usize = 64 * 1024; // 64 KiB
+/// The fraction of the documents that must be modified since the
+/// last training to train a new dictionary.
+const RETRAIN_MODIFIED_RATIO: f64 = 0.5;
+
+/// Returns a compressor to compress the documents before writing them
+/// in the documents database, if the index has a compression dictionary.
+pub(crate) fn document_compressor(
+    index: &Index,
+    rtxn: &heed::RoTxn,
+) -> Result<Option<Compressor<'static>>> {
+    match index.document_compression_dictionary(rtxn)? {
+        Some(dictionary) => Ok(Some(Compressor::with_dictionary(COMPRESSION_LEVEL, dictionary)?)),
+        None => Ok(None),
+    }
+}
+
+/// Trains a new compression dictionary from a sample of the documents and recompresses
+/// all the documents with it when the index has no dictionary yet and contains enough
+/// documents, or when a large fraction of the documents changed since the last training.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::documents")]
+pub(crate) fn train_document_compression_dictionary_if_needed(
+    index: &Index,
+    wtxn: &mut RwTxn,
+) -> Result<()> {
+    let documents_ids = index.documents_ids(wtxn)?;
+    let number_of_documents = documents_ids.len();
+    if number_of_documents < MIN_DOCUMENTS_TO_TRAIN {
+        return Ok(());
+    }
+
+    let decompression_dictionary = index.document_decompression_dictionary(wtxn)?;
+    if decompression_dictionary.is_some() {
+        let modified = index.documents_modified_since_dictionary(wtxn)?;
+        if (modified as f64) < number_of_documents as f64 * RETRAIN_MODIFIED_RATIO {
+            return Ok(());
+        }
+    }
+
+    // We pick documents evenly spread over the whole index to train the dictionary.
+    let step = (number_of_documents / MAX_SAMPLES).max(1) as usize;
+    let mut buffer = Vec::new();
+    let mut samples = Vec::new();
+    for docid in documents_ids.iter().step_by(step) {
+        let Some(compressed) = index.compressed_document(wtxn, docid)? else { continue };
+        let obkv = compressed.decompress_bytes_with_optional_dictionary(
+            &mut buffer,
+            decompression_dictionary.as_deref(),
+        )?;
+        samples.push(obkv.to_vec());
+    }
+
+    let dictionary = match zstd::dict::from_samples(&samples, DICTIONARY_SIZE) {
+        Ok(dictionary) => dictionary,
+        Err(error) => {
+            // A failed training must not make the whole indexing fail,
+            // we keep the documents as they are and retry on the next update.
+            tracing::warn!(%error, "Could not train the document compression dictionary");
+            return Ok(());
+        }
+    };
+    drop(samples);
+
+    let mut compressor = Compressor::with_dictionary(COMPRESSION_LEVEL, &dictionary)?;
+    for docid in documents_ids {
+        let Some(compressed) = index.compressed_document(wtxn, docid)? else { continue };
+        let obkv = compressed.decompress_bytes_with_optional_dictionary(
+            &mut buffer,
+            decompression_dictionary.as_deref(),
+        )?;
+        let compressed = compressor.compress(obkv)?;
+        index.documents.put(wtxn, &docid, &compressed)?;
+    }
+
+    index.put_document_compression_dictionary(wtxn, &dictionary)?;
+    index.put_documents_modified_since_dictionary(wtxn, 0)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::documents::documents_batch_reader_from_objects;
+    use crate::index::tests::TempIndex;
+
+    #[test]
+    fn compress_documents_with_trained_dictionary() {
+        let mut index = TempIndex::new_with_map_size(4096 * 100_000);
+        index.indexer_config.document_compression = true;
+
+        let documents = |range: std::ops::Range<u32>, color: &str| {
+            documents_batch_reader_from_objects(range.map(|i| {
+                let description = format!(
+                    "The product number {i} is a very nice product sold by the shop {}, \
+                     it comes in the color {} and costs around {} euros.",
+                    i % 13,
+                    color,
+                    i * 7 % 1000,
+                );
+                let name = format!("product {i}");
+                let json = serde_json::json!({ "id": i, "name": name, "description": description });
+                json.as_object().unwrap().clone()
+            }))
+        };
+
+        // Not enough documents to train a dictionary.
+        index.add_documents(documents(0..10, "red")).unwrap();
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.document_compression_dictionary(&rtxn).unwrap().is_none());
+        drop(rtxn);
+
+        index.add_documents(documents(10..2000, "red")).unwrap();
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.document_compression_dictionary(&rtxn).unwrap().is_some());
+        assert_eq!(index.documents_modified_since_dictionary(&rtxn).unwrap(), 0);
+
+        // The documents are stored compressed but transparently decompressed.
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let name = fields_ids_map.id("name").unwrap();
+        let external_ids = index.external_documents_ids();
+        let docid = external_ids.get(&rtxn, "42").unwrap().unwrap();
+        let (_, document) = index.documents(&rtxn, Some(docid)).unwrap().remove(0);
+        assert_eq!(document.as_obkv().get(name), Some(&br#""product 42""#[..]));
+        let compressed = index.compressed_document(&rtxn, docid).unwrap().unwrap();
+        assert_ne!(compressed.as_bytes(), document.as_bytes());
+        assert_eq!(index.all_documents(&rtxn).unwrap().count(), 2000);
+
+        // The dictionary is only prepared once.
+        let dictionary = index.document_decompression_dictionary(&rtxn).unwrap().unwrap();
+        let cached = index.document_decompression_dictionary(&rtxn).unwrap().unwrap();
+        assert!(std::sync::Arc::ptr_eq(&dictionary, &cached));
+        drop(rtxn);
+
+        // The updated documents are counted to know when to train a new dictionary.
+        index.add_documents(documents(0..100, "blue")).unwrap();
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.documents_modified_since_dictionary(&rtxn).unwrap(), 100);
+        let docid = external_ids.get(&rtxn, "42").unwrap().unwrap();
+        let (_, document) = index.documents(&rtxn, Some(docid)).unwrap().remove(0);
+        assert_eq!(document.as_obkv().get(name), Some(&br#""product 42""#[..]));
+        drop(rtxn);
+
+        // Clearing the documents removes the dictionary.
+        let mut wtxn = index.write_txn().unwrap();
+        crate::update::ClearDocuments::new(&mut wtxn, &index).execute().unwrap();
+        assert!(index.document_compression_dictionary(&wtxn).unwrap().is_none());
+        assert!(index.document_decompression_dictionary(&wtxn).unwrap().is_none());
+        wtxn.commit().unwrap();
+    }
+}
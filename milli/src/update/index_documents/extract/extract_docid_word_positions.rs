@@ -11,6 +11,7 @@ use serde_json::Value;
 
 use super::helpers::{create_sorter, keep_latest_obkv, sorter_into_reader, GrenadParameters};
 use crate::error::{InternalError, SerializationError};
+use crate::localized_attributes_rules::locales_allow_list;
 use crate::update::del_add::{del_add_from_two_obkvs, DelAdd, KvReaderDelAdd};
 use crate::update::settings::{InnerIndexSettings, InnerIndexSettingsDiff};
 use crate::{FieldId, Result, MAX_POSITION_PER_ATTRIBUTE, MAX_WORD_LENGTH};
@@ -68,6 +69,14 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
         None,
     );
     let del_tokenizer = del_builder.build();
+    let del_allow_lists = localized_allow_lists(&settings_diff.old);
+    let mut del_localized_builders = localized_tokenizer_builders(
+        old_stop_words,
+        old_separators.as_deref(),
+        old_dictionary.as_deref(),
+        &del_allow_lists,
+    );
+    let del_localized_tokenizers = build_localized_tokenizers(&mut del_localized_builders);
 
     let new_stop_words = settings_diff.new.stop_words.as_ref();
     let new_separators: Option<Vec<_>> = settings_diff
@@ -84,6 +93,14 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
         None,
     );
     let add_tokenizer = add_builder.build();
+    let add_allow_lists = localized_allow_lists(&settings_diff.new);
+    let mut add_localized_builders = localized_tokenizer_builders(
+        new_stop_words,
+        new_separators.as_deref(),
+        new_dictionary.as_deref(),
+        &add_allow_lists,
+    );
+    let add_localized_tokenizers = build_localized_tokenizers(&mut add_localized_builders);
 
     // iterate over documents.
     let mut cursor = obkv_documents.into_cursor()?;
@@ -113,6 +130,7 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
                     &obkv,
                     &settings_diff.old,
                     &del_tokenizer,
+                    &del_localized_tokenizers,
                     max_positions_per_attributes,
                     DelAdd::Deletion,
                     &mut del_buffers,
@@ -124,6 +142,7 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
                     &obkv,
                     &settings_diff.new,
                     &add_tokenizer,
+                    &add_localized_tokenizers,
                     max_positions_per_attributes,
                     DelAdd::Addition,
                     &mut add_buffers,
@@ -225,12 +244,49 @@ fn tokenizer_builder<'a>(
     tokenizer_builder
 }
 
+/// Builds the tokenizer allow list of each searchable field having locales
+/// defined by the `localizedAttributes` setting.
+fn localized_allow_lists(
+    settings: &InnerIndexSettings,
+) -> HashMap<FieldId, HashMap<Script, Vec<Language>>> {
+    settings
+        .localized_searchable_fields_ids
+        .iter()
+        .map(|(field_id, locales)| (field_id, locales_allow_list(locales)))
+        .filter(|(_, allow_list)| !allow_list.is_empty())
+        .collect()
+}
+
+/// Factorize the building of the tokenizers dedicated to the localized fields.
+fn localized_tokenizer_builders<'a>(
+    stop_words: Option<&'a fst::Set<Vec<u8>>>,
+    allowed_separators: Option<&'a [&str]>,
+    dictionary: Option<&'a [&str]>,
+    allow_lists: &'a HashMap<FieldId, HashMap<Script, Vec<Language>>>,
+) -> HashMap<FieldId, TokenizerBuilder<'a, Vec<u8>>> {
+    allow_lists
+        .iter()
+        .map(|(field_id, allow_list)| {
+            let builder =
+                tokenizer_builder(stop_words, allowed_separators, dictionary, Some(allow_list));
+            (*field_id, builder)
+        })
+        .collect()
+}
+
+fn build_localized_tokenizers<'a>(
+    builders: &'a mut HashMap<FieldId, TokenizerBuilder<'_, Vec<u8>>>,
+) -> HashMap<FieldId, Tokenizer<'a>> {
+    builders.iter_mut().map(|(field_id, builder)| (*field_id, builder.build())).collect()
+}
+
 /// Extract words mapped with their positions of a document,
 /// ensuring no Language detection mistakes was made.
 fn lang_safe_tokens_from_document<'a>(
     obkv: &KvReader<FieldId>,
     settings: &InnerIndexSettings,
     tokenizer: &Tokenizer,
+    localized_tokenizers: &HashMap<FieldId, Tokenizer>,
     max_positions_per_attributes: u32,
     del_add: DelAdd,
     buffers: &'a mut Buffers,
@@ -241,6 +297,7 @@ fn lang_safe_tokens_from_document<'a>(
         obkv,
         &settings.searchable_fields_ids,
         tokenizer,
+        localized_tokenizers,
         max_positions_per_attributes,
         del_add,
         buffers,
@@ -281,6 +338,7 @@ fn lang_safe_tokens_from_document<'a>(
                 obkv,
                 &settings.searchable_fields_ids,
                 &tokenizer,
+                localized_tokenizers,
                 max_positions_per_attributes,
                 del_add,
                 buffers,
@@ -294,10 +352,14 @@ fn lang_safe_tokens_from_document<'a>(
 }
 
 /// Extract words mapped with their positions of a document.
+///
+/// The fields having locales defined by the `localizedAttributes` setting
+/// are tokenized with their dedicated tokenizer.
 fn tokens_from_document<'a>(
     obkv: &KvReader<FieldId>,
     searchable_fields: &[FieldId],
     tokenizer: &Tokenizer,
+    localized_tokenizers: &HashMap<FieldId, Tokenizer>,
     max_positions_per_attributes: u32,
     del_add: DelAdd,
     buffers: &'a mut Buffers,
@@ -321,8 +383,14 @@ fn tokens_from_document<'a>(
                 // convert json into a unique string.
                 buffers.field_buffer.clear();
                 if let Some(field) = json_to_string(&value, &mut buffers.field_buffer) {
+                    // use the tokenizer restricted to the locales of the field, if any.
+                    let tokens = match localized_tokenizers.get(&field_id) {
+                        Some(localized_tokenizer) => localized_tokenizer.tokenize(field),
+                        None => tokenizer.tokenize(field),
+                    };
+
                     // create an iterator of token with their positions.
-                    let tokens = process_tokens(tokenizer.tokenize(field))
+                    let tokens = process_tokens(tokens)
                         .take_while(|(p, _)| (*p as u32) < max_positions_per_attributes);
 
                     for (index, token) in tokens {
@@ -0,0 +1,90 @@
+use std::fs::File;
+use std::io::{self, BufReader};
+
+use serde_json::Value;
+
+use super::helpers::{create_writer, writer_into_reader, GrenadParameters};
+use crate::error::GeoError;
+use crate::geojson::Geometry;
+use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
+use crate::update::settings::{InnerIndexSettings, InnerIndexSettingsDiff};
+use crate::{FieldId, InternalError, Result};
+
+/// Extracts the geometries contained in each document under the `_geojson` field.
+///
+/// Returns the generated grenad reader containing the docid as key associated to the
+/// serialized geometry.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::extract")]
+pub fn extract_geojson<R: io::Read + io::Seek>(
+    obkv_documents: grenad::Reader<R>,
+    indexer: GrenadParameters,
+    primary_key_id: FieldId,
+    settings_diff: &InnerIndexSettingsDiff,
+) -> Result<grenad::Reader<BufReader<File>>> {
+    let mut writer = create_writer(
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        tempfile::tempfile()?,
+    );
+
+    let mut cursor = obkv_documents.into_cursor()?;
+    while let Some((docid_bytes, value)) = cursor.move_on_next()? {
+        let obkv = obkv::KvReader::new(value);
+        // since we only need the primary key when we throw an error
+        // we create this getter to lazily get it when needed
+        let document_id = || -> Value {
+            let reader = KvReaderDelAdd::new(obkv.get(primary_key_id).unwrap());
+            let document_id =
+                reader.get(DelAdd::Deletion).or(reader.get(DelAdd::Addition)).unwrap();
+            serde_json::from_slice(document_id).unwrap()
+        };
+
+        // extract old version
+        let del_geometry =
+            extract_geometry(&obkv, &settings_diff.old, DelAdd::Deletion, document_id)?;
+        // extract new version
+        let add_geometry =
+            extract_geometry(&obkv, &settings_diff.new, DelAdd::Addition, document_id)?;
+
+        if del_geometry != add_geometry {
+            let mut obkv = KvWriterDelAdd::memory();
+            if let Some(geometry) = del_geometry {
+                let bytes = serde_json::to_vec(&geometry).map_err(InternalError::SerdeJson)?;
+                obkv.insert(DelAdd::Deletion, bytes)?;
+            }
+            if let Some(geometry) = add_geometry {
+                let bytes = serde_json::to_vec(&geometry).map_err(InternalError::SerdeJson)?;
+                obkv.insert(DelAdd::Addition, bytes)?;
+            }
+            let bytes = obkv.into_inner()?;
+            writer.insert(docid_bytes, bytes)?;
+        }
+    }
+
+    writer_into_reader(writer)
+}
+
+/// Extract the geometry of the `_geojson` field, a `null` value is considered as missing.
+fn extract_geometry(
+    document: &obkv::KvReader<FieldId>,
+    settings: &InnerIndexSettings,
+    deladd: DelAdd,
+    document_id: impl Fn() -> Value,
+) -> Result<Option<Geometry>> {
+    let value = match settings.geojson_fid {
+        Some(fid) => document.get(fid).map(KvReaderDelAdd::new).and_then(|r| r.get(deladd)),
+        None => None,
+    };
+    let value: Value = match value {
+        Some(value) => serde_json::from_slice(value).map_err(InternalError::SerdeJson)?,
+        None => return Ok(None),
+    };
+    if value.is_null() {
+        return Ok(None);
+    }
+
+    match Geometry::from_geojson(&value) {
+        Ok(geometry) => Ok(Some(geometry)),
+        Err(error) => Err(GeoError::BadGeoJson { document_id: document_id(), error }.into()),
+    }
+}
@@ -16,6 +16,7 @@ use grenad::{Merger, MergerBuilder};
 use heed::types::Str;
 use heed::Database;
 use rand::SeedableRng;
+use rhai::{Dynamic, Engine, OptimizationLevel, Scope, AST};
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 use slice_group_by::GroupBy;
@@ -32,15 +33,17 @@ pub use self::helpers::{
 };
 use self::helpers::{grenad_obkv_into_chunks, GrenadParameters};
 pub use self::transform::{Transform, TransformOutput};
-use crate::documents::{obkv_to_object, DocumentsBatchReader};
+use crate::documents::{obkv_to_object, DocumentsBatchBuilder, DocumentsBatchReader};
 use crate::error::{Error, InternalError, UserError};
+use crate::index::{db_name, main_key};
 use crate::thread_pool_no_abort::ThreadPoolNoAbortBuilder;
+use crate::update::document_compression::train_document_compression_dictionary_if_needed;
 pub use crate::update::index_documents::helpers::CursorClonableMmap;
 use crate::update::{
     IndexerConfig, UpdateIndexingStep, WordPrefixDocids, WordPrefixIntegerDocids, WordsPrefixesFst,
 };
-use crate::vector::EmbeddingConfigs;
-use crate::{CboRoaringBitmapCodec, Index, Result};
+use crate::vector::{ArroyWrapper, EmbeddingConfigs};
+use crate::{obkv_to_json, CboRoaringBitmapCodec, Index, Object, Result};
 
 static MERGED_DATABASE_COUNT: usize = 7;
 static PREFIX_DATABASE_COUNT: usize = 4;
@@ -236,6 +239,88 @@ where
         Ok((self, deleted_documents))
     }
 
+    /// Edits the given documents by running a Rhai function on each of them.
+    ///
+    /// The function finds the document in the `doc` variable and the optional `context` object
+    /// in the `context` constant. It can modify `doc` in place or set it to `()` to delete it.
+    /// The edited documents go through the same transform as a documents replacement.
+    ///
+    /// Returns the number of deleted and edited documents.
+    #[tracing::instrument(level = "trace", skip_all, target = "indexing::documents")]
+    pub fn edit_documents(
+        self,
+        documents: &RoaringBitmap,
+        context: Option<Object>,
+        code: &str,
+    ) -> Result<(Self, StdResult<(u64, u64), UserError>)> {
+        let engine = document_edition_engine();
+        let ast = match engine.compile(code) {
+            Ok(ast) => ast,
+            Err(error) => {
+                return Ok((self, Err(UserError::DocumentEditionCompilationError(error))))
+            }
+        };
+        let context = match context.map(rhai::serde::to_dynamic).transpose() {
+            Ok(context) => context,
+            Err(error) => return Ok((self, Err(UserError::DocumentEditionRuntimeError(error)))),
+        };
+
+        // Early return when there is no document to edit, once the function is known to compile
+        if documents.is_empty() {
+            return Ok((self, Ok((0, 0))));
+        }
+
+        let fields_ids_map = self.index.fields_ids_map(self.wtxn)?;
+        let all_fields: Vec<_> = fields_ids_map.ids().collect();
+        let primary_key = self
+            .index
+            .primary_key(self.wtxn)?
+            .ok_or(InternalError::DatabaseMissingEntry {
+                db_name: db_name::MAIN,
+                key: Some(main_key::PRIMARY_KEY_KEY),
+            })?
+            .to_string();
+
+        let mut documents_batch_builder = tempfile::tempfile().map(DocumentsBatchBuilder::new)?;
+        let mut documents_to_remove = RoaringBitmap::new();
+        let mut user_error = None;
+
+        for result in self.index.iter_documents(self.wtxn, documents)? {
+            if (self.should_abort)() {
+                return Err(Error::InternalError(InternalError::AbortedIndexation));
+            }
+
+            let (docid, obkv) = result?;
+            let document = obkv_to_json(&all_fields, &fields_ids_map, obkv.as_obkv())?;
+            match edit_document(&engine, &ast, context.as_ref(), document, &primary_key) {
+                Ok(DocumentEdition::Deleted) => {
+                    documents_to_remove.insert(docid);
+                }
+                Ok(DocumentEdition::Edited(new_document)) => {
+                    documents_batch_builder.append_json_object(&new_document)?;
+                }
+                Ok(DocumentEdition::Unchanged) => (),
+                Err(error) => {
+                    user_error = Some(error);
+                    break;
+                }
+            }
+        }
+
+        if let Some(error) = user_error {
+            return Ok((self, Err(error)));
+        }
+
+        let file = documents_batch_builder.into_inner()?;
+        let reader = DocumentsBatchReader::from_reader(file)?;
+
+        let (this, deleted_documents) =
+            self.remove_documents_from_db_no_batch(&documents_to_remove)?;
+        let (this, result) = this.add_documents(reader)?;
+
+        Ok((this, result.map(|edited_documents| (deleted_documents, edited_documents))))
+    }
+
     #[tracing::instrument(
         level = "trace"
         skip_all,
@@ -365,6 +450,7 @@ where
         let mut exact_word_docids = None;
         let mut chunk_accumulator = ChunkAccumulator::default();
         let mut dimension = HashMap::new();
+        let mut embedded_documents: HashMap<String, usize> = HashMap::new();
 
         let current_span = tracing::Span::current();
 
@@ -427,8 +513,18 @@ where
                 match lmdb_writer_rx.clone().recv_timeout(std::time::Duration::from_millis(500)) {
                     Err(status) => {
                         if let Some(typed_chunks) = chunk_accumulator.pop_longest() {
+                            let embedded = embedded_documents_count(&typed_chunks);
                             let (docids, is_merged_database) =
                                 write_typed_chunk_into_index(self.wtxn, self.index, &settings_diff, typed_chunks)?;
+                            if let Some((embedder_name, count)) = embedded {
+                                let documents_seen = embedded_documents.entry(embedder_name.clone()).or_default();
+                                *documents_seen += count;
+                                (self.progress)(UpdateIndexingStep::EmbedDocuments {
+                                    embedder_name,
+                                    documents_seen: *documents_seen,
+                                    total_documents: documents_count,
+                                });
+                            }
                             if !docids.is_empty() {
                                 final_documents_ids |= docids;
                                 let documents_seen_count = final_documents_ids.len();
@@ -535,29 +631,32 @@ where
         self.index.put_primary_key(self.wtxn, &primary_key)?;
         let number_of_documents = self.index.number_of_documents(self.wtxn)?;
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let embedding_configs = self.index.embedding_configs(self.wtxn)?;
 
         for (embedder_name, dimension) in dimension {
             let wtxn = &mut *self.wtxn;
             let vector_arroy = self.index.vector_arroy;
+            let binary_quantized = embedding_configs
+                .iter()
+                .find(|config| config.name == embedder_name)
+                .map_or(false, |config| config.config.quantized());
 
             let embedder_index = self.index.embedder_category_id.get(wtxn, &embedder_name)?.ok_or(
                 InternalError::DatabaseMissingEntry { db_name: "embedder_category_id", key: None },
             )?;
 
             pool.install(|| {
-                for k in crate::vector::arroy_db_range_for_embedder(embedder_index) {
-                    let writer = arroy::Writer::new(vector_arroy, k, dimension);
-                    if writer.need_build(wtxn)? {
-                        writer.build(wtxn, &mut rng, None)?;
-                    } else if writer.is_empty(wtxn)? {
-                        break;
-                    }
-                }
+                let writer = ArroyWrapper::new(vector_arroy, embedder_index, binary_quantized);
+                writer.build(wtxn, &mut rng, dimension)?;
                 Result::Ok(())
             })
             .map_err(InternalError::from)??;
         }
 
+        if self.indexer_config.document_compression {
+            train_document_compression_dictionary_if_needed(self.index, self.wtxn)?;
+        }
+
         self.execute_prefix_databases(
             word_docids.map(MergerBuilder::build),
             exact_word_docids.map(MergerBuilder::build),
@@ -749,6 +848,84 @@ where
     }
 }
 
+/// The outcome of running the edition function on a single document.
+enum DocumentEdition {
+    Deleted,
+    Edited(Object),
+    Unchanged,
+}
+
+/// Creates a Rhai engine restricted enough to safely run the functions sent by the users.
+fn document_edition_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_optimization_level(OptimizationLevel::Full);
+    engine.set_max_call_levels(1000);
+    engine.set_max_operations(1_000_000);
+    engine.set_max_variables(1000);
+    engine.set_max_functions(30);
+    engine.set_max_expr_depths(100, 1000);
+    engine.set_max_string_size(1024 * 1024 * 1024); // 1 GiB
+    engine.set_max_array_size(10_000);
+    engine.set_max_map_size(10_000);
+    engine
+}
+
+/// Runs the edition function on a single document and tells what must be done with it.
+fn edit_document(
+    engine: &Engine,
+    ast: &AST,
+    context: Option<&Dynamic>,
+    document: Object,
+    primary_key: &str,
+) -> StdResult<DocumentEdition, UserError> {
+    let document_id = match document.get(primary_key) {
+        Some(serde_json::Value::String(id)) => id.clone(),
+        Some(id) => id.to_string(),
+        None => String::new(),
+    };
+
+    let mut scope = Scope::new();
+    if let Some(context) = context {
+        scope.push_constant_dynamic("context", context.clone());
+    }
+    let doc = rhai::serde::to_dynamic(&document).map_err(UserError::DocumentEditionRuntimeError)?;
+    scope.push_dynamic("doc", doc);
+
+    engine.run_ast_with_scope(&mut scope, ast).map_err(UserError::DocumentEditionRuntimeError)?;
+
+    let new_document = match scope.remove::<Dynamic>("doc") {
+        // Setting `doc` to `()` means that the document must be deleted.
+        Some(doc) if doc.is_unit() => return Ok(DocumentEdition::Deleted),
+        Some(doc) => rhai::serde::from_dynamic::<Object>(&doc).map_err(|_| {
+            UserError::DocumentEditionDocumentMustBeObject { document_id: document_id.clone() }
+        })?,
+        None => return Err(UserError::DocumentEditionDocumentMustBeObject { document_id }),
+    };
+
+    if new_document == document {
+        Ok(DocumentEdition::Unchanged)
+    } else if new_document.get(primary_key) != document.get(primary_key) {
+        Err(UserError::DocumentEditionCannotModifyPrimaryKey { document_id })
+    } else {
+        Ok(DocumentEdition::Edited(new_document))
+    }
+}
+
+/// Returns the name of the embedder and the number of documents it generated embeddings for,
+/// if the typed chunks are vector points.
+fn embedded_documents_count(typed_chunks: &[TypedChunk]) -> Option<(String, usize)> {
+    let mut embedder = None;
+    let mut count = 0;
+    for typed_chunk in typed_chunks {
+        let TypedChunk::VectorPoints { embedder_name, embeddings, .. } = typed_chunk else {
+            return None;
+        };
+        embedder.get_or_insert_with(|| embedder_name.clone());
+        count += embeddings.as_ref().map_or(0, |embeddings| embeddings.len() as usize);
+    }
+    embedder.map(|embedder_name| (embedder_name, count))
+}
+
 /// Run the word prefix docids update operation.
 #[allow(clippy::too_many_arguments)]
 #[tracing::instrument(
@@ -863,8 +1040,9 @@ mod tests {
         // Check that we get only one document from the database.
         let docs = index.documents(&rtxn, Some(0)).unwrap();
         assert_eq!(docs.len(), 1);
-        let (id, doc) = docs[0];
-        assert_eq!(id, 0);
+        let (id, doc) = &docs[0];
+        assert_eq!(*id, 0);
+        let doc = doc.as_obkv();
 
         // Check that this document is equal to the last one sent.
         let mut doc_iter = doc.iter();
@@ -884,8 +1062,9 @@ mod tests {
         // Check that we get only one document from the database.
         let docs = index.documents(&rtxn, Some(0)).unwrap();
         assert_eq!(docs.len(), 1);
-        let (id, doc) = docs[0];
-        assert_eq!(id, 0);
+        let (id, doc) = &docs[0];
+        assert_eq!(*id, 0);
+        let doc = doc.as_obkv();
 
         // Check that this document is equal to the last one sent.
         let mut doc_iter = doc.iter();
@@ -933,8 +1112,9 @@ mod tests {
         assert_eq!(count, 3);
 
         let docs = index.documents(&rtxn, vec![0, 1, 2]).unwrap();
-        let (_id, obkv) = docs.iter().find(|(_id, kv)| kv.get(0) == Some(br#""kevin""#)).unwrap();
-        let kevin_uuid: String = serde_json::from_slice(obkv.get(1).unwrap()).unwrap();
+        let (_id, obkv) =
+            docs.iter().find(|(_id, kv)| kv.as_obkv().get(0) == Some(br#""kevin""#)).unwrap();
+        let kevin_uuid: String = serde_json::from_slice(obkv.as_obkv().get(1).unwrap()).unwrap();
         drop(rtxn);
 
         // Second we send 1 document with the generated uuid, to erase the previous ones.
@@ -947,10 +1127,13 @@ mod tests {
 
         // the document 0 has been deleted and reinserted with the id 3
         let docs = index.documents(&rtxn, vec![1, 2, 0]).unwrap();
-        let kevin_position =
-            docs.iter().position(|(_, d)| d.get(0).unwrap() == br#""updated kevin""#).unwrap();
+        let kevin_position = docs
+            .iter()
+            .position(|(_, d)| d.as_obkv().get(0).unwrap() == br#""updated kevin""#)
+            .unwrap();
         assert_eq!(kevin_position, 2);
-        let (_, doc) = docs[kevin_position];
+        let (_, doc) = &docs[kevin_position];
+        let doc = doc.as_obkv();
 
         // Check that this document is equal to the last
         // one sent and that an UUID has been generated.
@@ -2604,6 +2787,7 @@ mod tests {
                         embedding_object: Setting::NotSet,
                         input_type: Setting::NotSet,
                         distribution: Setting::NotSet,
+                        binary_quantized: Setting::NotSet,
                     }),
                 );
                 settings.set_embedder_settings(embedders);
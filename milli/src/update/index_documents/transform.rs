@@ -4,6 +4,7 @@ use std::collections::hash_map::Entry as HEntry;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
 use std::io::{Read, Seek};
+use std::sync::Arc;
 
 use fxhash::FxHashMap;
 use itertools::Itertools;
@@ -11,6 +12,7 @@ use obkv::{KvReader, KvReaderU16, KvWriter};
 use roaring::RoaringBitmap;
 use serde_json::Value;
 use smartstring::SmartString;
+use zstd::dict::DecoderDictionary;
 
 use super::helpers::{
     create_sorter, create_writer, keep_first, obkvs_keep_last_addition_merge_deletions,
@@ -28,7 +30,8 @@ use crate::update::index_documents::GrenadParameters;
 use crate::update::settings::{InnerIndexSettings, InnerIndexSettingsDiff};
 use crate::update::{AvailableDocumentsIds, UpdateIndexingStep};
 use crate::vector::parsed_vectors::{ExplicitVectors, VectorOrArrayOfVectors};
-use crate::vector::settings::{EmbedderAction, WriteBackToDocuments};
+use crate::vector::settings::WriteBackToDocuments;
+use crate::vector::ArroyWrapper;
 use crate::{
     is_faceted_by, FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldsIdsMap, Index, Result,
 };
@@ -67,6 +70,8 @@ pub struct Transform<'a, 'i> {
     // To increase the cache locality and decrease the heap usage we use compact smartstring.
     new_external_documents_ids_builder: FxHashMap<SmartString<smartstring::Compact>, u64>,
     documents_count: usize,
+    // The dictionary to decompress the documents of the index, if they are compressed.
+    document_decompression_dictionary: Option<Arc<DecoderDictionary<'static>>>,
 }
 
 /// This enum is specific to the grenad sorter stored in the transform.
@@ -135,6 +140,7 @@ impl<'a, 'i> Transform<'a, 'i> {
             indexer_settings.max_memory.map(|mem| mem / 2),
         );
         let documents_ids = index.documents_ids(wtxn)?;
+        let document_decompression_dictionary = index.document_decompression_dictionary(wtxn)?;
 
         Ok(Transform {
             index,
@@ -148,6 +154,7 @@ impl<'a, 'i> Transform<'a, 'i> {
             new_documents_ids: RoaringBitmap::new(),
             new_external_documents_ids_builder: FxHashMap::default(),
             documents_count: 0,
+            document_decompression_dictionary,
         })
     }
 
@@ -173,6 +180,7 @@ impl<'a, 'i> Transform<'a, 'i> {
             self.fields_ids_map.insert(&primary_key).ok_or(UserError::AttributeLimitReached)?;
 
         let mut obkv_buffer = Vec::new();
+        let mut document_decompression_buffer = Vec::new();
         let mut document_sorter_value_buffer = Vec::new();
         let mut document_sorter_key_buffer = Vec::new();
         let mut documents_count = 0;
@@ -250,12 +258,15 @@ impl<'a, 'i> Transform<'a, 'i> {
                 let base_obkv = self
                     .index
                     .documents
-                    .remap_data_type::<heed::types::Bytes>()
                     .get(wtxn, &original_key)?
                     .ok_or(InternalError::DatabaseMissingEntry {
                         db_name: db_name::DOCUMENTS,
                         key: None,
-                    })?;
+                    })?
+                    .decompress_bytes_with_optional_dictionary(
+                        &mut document_decompression_buffer,
+                        self.document_decompression_dictionary.as_deref(),
+                    )?;
 
                 // we check if the two documents are exactly equal. If it's the case we can skip this document entirely
                 if base_obkv == obkv_buffer {
@@ -501,15 +512,16 @@ impl<'a, 'i> Transform<'a, 'i> {
 
         // fetch the obkv document
         let original_key = internal_docid;
+        let mut document_decompression_buffer = Vec::new();
         let base_obkv = self
             .index
             .documents
-            .remap_data_type::<heed::types::Bytes>()
             .get(txn, &original_key)?
-            .ok_or(InternalError::DatabaseMissingEntry {
-                db_name: db_name::DOCUMENTS,
-                key: None,
-            })?;
+            .ok_or(InternalError::DatabaseMissingEntry { db_name: db_name::DOCUMENTS, key: None })?
+            .decompress_bytes_with_optional_dictionary(
+                &mut document_decompression_buffer,
+                self.document_decompression_dictionary.as_deref(),
+            )?;
 
         // Key is the concatenation of the internal docid and the external one.
         document_sorter_key_buffer.clear();
@@ -989,29 +1001,30 @@ impl<'a, 'i> Transform<'a, 'i> {
             None
         };
 
-        let readers: Result<
-            BTreeMap<&str, (Vec<arroy::Reader<arroy::distances::Angular>>, &RoaringBitmap)>,
-        > = settings_diff
-            .embedding_config_updates
-            .iter()
-            .filter_map(|(name, action)| {
-                if let EmbedderAction::WriteBackToDocuments(WriteBackToDocuments {
-                    embedder_id,
-                    user_provided,
-                }) = action
-                {
-                    let readers: Result<Vec<_>> =
-                        self.index.arroy_readers(wtxn, *embedder_id).collect();
-                    match readers {
-                        Ok(readers) => Some(Ok((name.as_str(), (readers, user_provided)))),
-                        Err(error) => Some(Err(error)),
-                    }
-                } else {
-                    None
-                }
-            })
-            .collect();
-        let readers = readers?;
+        // The user-provided vectors of the embedders that are removed or being quantized must
+        // be written back into the documents, so that they are not lost.
+        let embedding_configs = self.index.embedding_configs(wtxn)?;
+        let mut readers: BTreeMap<&str, (ArroyWrapper, Cow<RoaringBitmap>, bool)> = BTreeMap::new();
+        for (name, action) in settings_diff.embedding_config_updates.iter() {
+            if let Some(WriteBackToDocuments { embedder_id, user_provided }) = action.write_back() {
+                let reader =
+                    ArroyWrapper::new(self.index.vector_arroy, *embedder_id, action.was_quantized);
+                readers.insert(name.as_str(), (reader, Cow::Borrowed(user_provided), true));
+            } else if action.is_being_quantized {
+                let Some(embedder_id) = self.index.embedder_category_id.get(wtxn, name)? else {
+                    continue;
+                };
+                let Some(config) = embedding_configs.iter().find(|config| &config.name == name)
+                else {
+                    continue;
+                };
+                let reader = ArroyWrapper::new(self.index.vector_arroy, embedder_id, false);
+                readers.insert(
+                    name.as_str(),
+                    (reader, Cow::Owned(config.user_provided.clone()), false),
+                );
+            }
+        }
 
         let old_vectors_fid = settings_diff
             .old
@@ -1037,50 +1050,44 @@ impl<'a, 'i> Transform<'a, 'i> {
             let modified_faceted_fields = settings_diff.modified_faceted_fields();
             let mut original_obkv_buffer = Vec::new();
             let mut flattened_obkv_buffer = Vec::new();
+            let mut document_decompression_buffer = Vec::new();
             let mut document_sorter_key_buffer = Vec::new();
             for result in self.index.external_documents_ids().iter(wtxn)? {
                 let (external_id, docid) = result?;
-                let old_obkv = self.index.documents.get(wtxn, &docid)?.ok_or(
-                    InternalError::DatabaseMissingEntry { db_name: db_name::DOCUMENTS, key: None },
-                )?;
+                let old_obkv = self
+                    .index
+                    .documents
+                    .get(wtxn, &docid)?
+                    .ok_or(InternalError::DatabaseMissingEntry {
+                        db_name: db_name::DOCUMENTS,
+                        key: None,
+                    })?
+                    .decompress_with_optional_dictionary(
+                        &mut document_decompression_buffer,
+                        self.document_decompression_dictionary.as_deref(),
+                    )?;
 
-                let injected_vectors: std::result::Result<
-                    serde_json::Map<String, serde_json::Value>,
-                    arroy::Error,
-                > = readers
-                    .iter()
-                    .filter_map(|(name, (readers, user_provided))| {
-                        if !user_provided.contains(docid) {
-                            return None;
-                        }
-                        let mut vectors = Vec::new();
-                        for reader in readers {
-                            let Some(vector) = reader.item_vector(wtxn, docid).transpose() else {
-                                break;
-                            };
-
-                            match vector {
-                                Ok(vector) => vectors.push(vector),
-                                Err(error) => return Some(Err(error)),
-                            }
-                        }
-                        if vectors.is_empty() {
-                            return None;
-                        }
-                        Some(Ok((
-                            name.to_string(),
-                            serde_json::to_value(ExplicitVectors {
-                                embeddings: Some(VectorOrArrayOfVectors::from_array_of_vectors(
-                                    vectors,
-                                )),
-                                regenerate: false,
-                            })
-                            .unwrap(),
-                        )))
-                    })
-                    .collect();
-
-                let injected_vectors = injected_vectors?;
+                let mut injected_vectors = serde_json::Map::new();
+                for (name, (reader, user_provided, _)) in &readers {
+                    // The vectors of a quantized embedder are already kept in the documents.
+                    if reader.quantized() || !user_provided.contains(docid) {
+                        continue;
+                    }
+                    let vectors = reader.item_vectors(wtxn, docid)?;
+                    if vectors.is_empty() {
+                        continue;
+                    }
+                    injected_vectors.insert(
+                        name.to_string(),
+                        serde_json::to_value(ExplicitVectors {
+                            embeddings: Some(VectorOrArrayOfVectors::from_array_of_vectors(
+                                vectors,
+                            )),
+                            regenerate: false,
+                        })
+                        .unwrap(),
+                    );
+                }
 
                 Self::rebind_existing_document(
                     old_obkv,
@@ -1104,21 +1111,14 @@ impl<'a, 'i> Transform<'a, 'i> {
             }
         }
 
-        let mut writers = Vec::new();
-
         // delete all vectors from the embedders that need removal
-        for (_, (readers, _)) in readers {
-            for reader in readers {
-                let dimensions = reader.dimensions();
-                let arroy_index = reader.index();
-                drop(reader);
-                let writer = arroy::Writer::new(self.index.vector_arroy, arroy_index, dimensions);
-                writers.push(writer);
+        for (_, (reader, _, clear)) in readers {
+            if !clear {
+                continue;
+            }
+            if let Some(dimensions) = reader.dimensions(wtxn)? {
+                reader.clear(wtxn, dimensions)?;
             }
-        }
-
-        for writer in writers {
-            writer.clear(wtxn)?;
         }
 
         let grenad_params = GrenadParameters {
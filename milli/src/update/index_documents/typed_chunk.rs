@@ -8,6 +8,7 @@ use charabia::{Language, Script};
 use grenad::{Merger, MergerBuilder};
 use heed::types::Bytes;
 use heed::{BytesDecode, RwTxn};
+use itertools::{Either, Itertools};
 use obkv::{KvReader, KvWriter};
 use roaring::RoaringBitmap;
 
@@ -19,15 +20,18 @@ use super::helpers::{
 use super::MergeFn;
 use crate::external_documents_ids::{DocumentOperation, DocumentOperationKind};
 use crate::facet::FacetType;
+use crate::geojson::Geometry;
 use crate::index::db_name::DOCUMENTS;
 use crate::index::IndexEmbeddingConfig;
 use crate::proximity::MAX_DISTANCE;
 use crate::update::del_add::{deladd_serialize_add_side, DelAdd, KvReaderDelAdd};
+use crate::update::document_compression::document_compressor;
 use crate::update::facet::FacetsUpdate;
 use crate::update::index_documents::helpers::{
     as_cloneable_grenad, keep_latest_obkv, try_split_array_at,
 };
 use crate::update::settings::InnerIndexSettingsDiff;
+use crate::vector::ArroyWrapper;
 use crate::{
     lat_lng_to_xyz, CboRoaringBitmapCodec, DocumentId, FieldId, GeoPoint, Index, InternalError,
     Result, SerializationError, U8StrStrCodec,
@@ -85,6 +89,7 @@ pub(crate) enum TypedChunk {
     FieldIdFacetIsNullDocids(grenad::Reader<BufReader<File>>),
     FieldIdFacetIsEmptyDocids(grenad::Reader<BufReader<File>>),
     GeoPoints(grenad::Reader<BufReader<File>>),
+    GeoJson(grenad::Reader<BufReader<File>>),
     VectorPoints {
         remove_vectors: grenad::Reader<BufReader<File>>,
         embeddings: Option<grenad::Reader<BufReader<File>>>,
@@ -114,6 +119,7 @@ impl TypedChunk {
             | (FieldIdFacetIsNullDocids(_), FieldIdFacetIsNullDocids(_))
             | (FieldIdFacetIsEmptyDocids(_), FieldIdFacetIsEmptyDocids(_))
             | (GeoPoints(_), GeoPoints(_))
+            | (GeoJson(_), GeoJson(_))
             | (ScriptLanguageDocids(_), ScriptLanguageDocids(_)) => true,
             (
                 VectorPoints { embedder_name: left, expected_dimension: left_dim, .. },
@@ -157,12 +163,28 @@ pub(crate) fn write_typed_chunk_into_index(
             let mut docids = index.documents_ids(wtxn)?;
             let mut iter = merger.into_stream_merger_iter()?;
 
-            let embedders: BTreeSet<_> = index
-                .embedding_configs(wtxn)?
-                .into_iter()
-                .map(|IndexEmbeddingConfig { name, .. }| name)
-                .collect();
+            // The user-provided vectors of the quantized embedders are kept in the documents
+            // because the quantized vectors cannot be converted back.
+            let (quantized_embedders, embedders): (BTreeSet<_>, BTreeSet<_>) =
+                index
+                    .embedding_configs(wtxn)?
+                    .into_iter()
+                    .map(|IndexEmbeddingConfig { name, config, .. }| {
+                        let is_being_quantized = settings_diff
+                            .embedding_config_updates
+                            .get(&name)
+                            .map_or(false, |action| action.is_being_quantized);
+                        (name, config.quantized() || is_being_quantized)
+                    })
+                    .partition_map(|(name, quantized)| {
+                        if quantized {
+                            Either::Left(name)
+                        } else {
+                            Either::Right(name)
+                        }
+                    });
             let mut vectors_buffer = Vec::new();
+            let mut compressor = document_compressor(index, wtxn)?;
             while let Some((key, reader)) = iter.next()? {
                 let mut writer: KvWriter<_, FieldId> = KvWriter::memory();
                 let reader: KvReader<FieldId> = KvReader::new(reader);
@@ -188,6 +210,7 @@ pub(crate) fn write_typed_chunk_into_index(
                                     break 'vectors Some(addition);
                                 };
                                 vectors.retain_not_embedded_vectors(&embedders);
+                                vectors.retain_user_provided_vectors(&quantized_embedders);
                                 let crate::vector::parsed_vectors::ParsedVectors(vectors) = vectors;
                                 if vectors.is_empty() {
                                     // skip writing empty `_vectors` map
@@ -208,10 +231,14 @@ pub(crate) fn write_typed_chunk_into_index(
                     }
                 }
 
-                let db = index.documents.remap_data_type::<Bytes>();
+                let db = index.documents;
 
                 if !writer.is_empty() {
-                    db.put(wtxn, &docid, &writer.into_inner().unwrap())?;
+                    let obkv = writer.into_inner().unwrap();
+                    match compressor.as_mut() {
+                        Some(compressor) => db.put(wtxn, &docid, &compressor.compress(&obkv)?)?,
+                        None => db.put(wtxn, &docid, &obkv)?,
+                    }
                     operations.push(DocumentOperation {
                         external_id: external_id.to_string(),
                         internal_id: docid,
@@ -228,6 +255,13 @@ pub(crate) fn write_typed_chunk_into_index(
                     docids.remove(docid);
                 }
             }
+            if compressor.is_some() {
+                let modified = index.documents_modified_since_dictionary(wtxn)?;
+                index.put_documents_modified_since_dictionary(
+                    wtxn,
+                    modified + operations.len() as u64,
+                )?;
+            }
             let external_documents_docids = index.external_documents_ids();
             external_documents_docids.apply(wtxn, operations)?;
             index.put_documents_ids(wtxn, &docids)?;
@@ -618,6 +652,43 @@ pub(crate) fn write_typed_chunk_into_index(
             index.put_geo_rtree(wtxn, &rtree)?;
             index.put_geo_faceted_documents_ids(wtxn, &geo_faceted_docids)?;
         }
+        TypedChunk::GeoJson(_) => {
+            let span = tracing::trace_span!(target: "indexing::write_db", "geojson");
+            let _entered = span.enter();
+
+            let mut builder = MergerBuilder::new(keep_first as MergeFn);
+            for typed_chunk in typed_chunks {
+                let TypedChunk::GeoJson(chunk) = typed_chunk else {
+                    unreachable!();
+                };
+
+                builder.push(chunk.into_cursor()?);
+            }
+            let merger = builder.build();
+
+            let mut rtree = index.geojson_rtree(wtxn)?.unwrap_or_default();
+
+            let mut iter = merger.into_stream_merger_iter()?;
+            while let Some((key, value)) = iter.next()? {
+                // convert the key back to a u32 (4 bytes)
+                let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
+
+                let deladd_obkv = KvReaderDelAdd::new(value);
+                if let Some(value) = deladd_obkv.get(DelAdd::Deletion) {
+                    let geometry: Geometry =
+                        serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+                    rtree.remove(&geometry.shape(docid));
+                    index.geojson_geometries.delete(wtxn, &docid)?;
+                }
+                if let Some(value) = deladd_obkv.get(DelAdd::Addition) {
+                    let geometry: Geometry =
+                        serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+                    rtree.insert(geometry.shape(docid));
+                    index.geojson_geometries.put(wtxn, &docid, &geometry)?;
+                }
+            }
+            index.put_geojson_rtree(wtxn, &rtree)?;
+        }
         TypedChunk::VectorPoints { .. } => {
             let span = tracing::trace_span!(target: "indexing::write_db", "vector_points");
             let _entered = span.enter();
@@ -663,6 +734,7 @@ pub(crate) fn write_typed_chunk_into_index(
                 .unwrap();
             index_embedder_config.user_provided -= remove_from_user_provided;
             index_embedder_config.user_provided |= add_to_user_provided;
+            let binary_quantized = index_embedder_config.config.quantized();
 
             index.put_embedding_configs(wtxn, embedding_configs)?;
 
@@ -670,22 +742,14 @@ pub(crate) fn write_typed_chunk_into_index(
                 InternalError::DatabaseMissingEntry { db_name: "embedder_category_id", key: None },
             )?;
             // FIXME: allow customizing distance
-            let writers: Vec<_> = crate::vector::arroy_db_range_for_embedder(embedder_index)
-                .map(|k| arroy::Writer::new(index.vector_arroy, k, expected_dimension))
-                .collect();
+            let writer = ArroyWrapper::new(index.vector_arroy, embedder_index, binary_quantized);
 
             // remove vectors for docids we want them removed
             let merger = remove_vectors_builder.build();
             let mut iter = merger.into_stream_merger_iter()?;
             while let Some((key, _)) = iter.next()? {
                 let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
-
-                for writer in &writers {
-                    // Uses invariant: vectors are packed in the first writers.
-                    if !writer.del_item(wtxn, docid)? {
-                        break;
-                    }
-                }
+                writer.del_items(wtxn, expected_dimension, docid)?;
             }
 
             // add generated embeddings
@@ -713,9 +777,7 @@ pub(crate) fn write_typed_chunk_into_index(
                         embeddings.embedding_count(),
                     )));
                 }
-                for (embedding, writer) in embeddings.iter().zip(&writers) {
-                    writer.add_item(wtxn, docid, embedding)?;
-                }
+                writer.add_items(wtxn, docid, &embeddings)?;
             }
 
             // perform the manual diff
@@ -729,47 +791,13 @@ pub(crate) fn write_typed_chunk_into_index(
                 let vector_deladd_obkv = KvReaderDelAdd::new(value);
                 if let Some(value) = vector_deladd_obkv.get(DelAdd::Deletion) {
                     let vector: Vec<f32> = pod_collect_to_vec(value);
-
-                    let mut deleted_index = None;
-                    for (index, writer) in writers.iter().enumerate() {
-                        let Some(candidate) = writer.item_vector(wtxn, docid)? else {
-                            // uses invariant: vectors are packed in the first writers.
-                            break;
-                        };
-                        if candidate == vector {
-                            writer.del_item(wtxn, docid)?;
-                            deleted_index = Some(index);
-                        }
-                    }
-
-                    // 🥲 enforce invariant: vectors are packed in the first writers.
-                    if let Some(deleted_index) = deleted_index {
-                        let mut last_index_with_a_vector = None;
-                        for (index, writer) in writers.iter().enumerate().skip(deleted_index) {
-                            let Some(candidate) = writer.item_vector(wtxn, docid)? else {
-                                break;
-                            };
-                            last_index_with_a_vector = Some((index, candidate));
-                        }
-                        if let Some((last_index, vector)) = last_index_with_a_vector {
-                            // unwrap: computed the index from the list of writers
-                            let writer = writers.get(last_index).unwrap();
-                            writer.del_item(wtxn, docid)?;
-                            writers.get(deleted_index).unwrap().add_item(wtxn, docid, &vector)?;
-                        }
-                    }
+                    writer.del_item(wtxn, docid, &vector)?;
                 }
 
                 if let Some(value) = vector_deladd_obkv.get(DelAdd::Addition) {
-                    let vector = pod_collect_to_vec(value);
-
+                    let vector: Vec<f32> = pod_collect_to_vec(value);
                     // overflow was detected during vector extraction.
-                    for writer in &writers {
-                        if !writer.contains_item(wtxn, docid)? {
-                            writer.add_item(wtxn, docid, &vector)?;
-                            break;
-                        }
-                    }
+                    writer.add_item(wtxn, docid, &vector)?;
                 }
             }
 
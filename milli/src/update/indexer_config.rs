@@ -13,6 +13,8 @@ pub struct IndexerConfig {
     pub thread_pool: Option<ThreadPoolNoAbort>,
     pub max_positions_per_attributes: Option<u32>,
     pub skip_index_budget: bool,
+    /// Compress the documents with a zstd dictionary trained on a sample of the index.
+    pub document_compression: bool,
 }
 
 impl Default for IndexerConfig {
@@ -27,6 +29,7 @@ impl Default for IndexerConfig {
             thread_pool: None,
             max_positions_per_attributes: None,
             skip_index_budget: false,
+            document_compression: false,
         }
     }
 }
@@ -16,6 +16,7 @@ pub use self::words_prefixes_fst::WordsPrefixesFst;
 mod available_documents_ids;
 mod clear_documents;
 pub(crate) mod del_add;
+pub(crate) mod document_compression;
 pub(crate) mod facet;
 mod index_documents;
 mod indexer_config;
@@ -6,6 +6,7 @@ use std::sync::Arc;
 use charabia::{Normalize, Tokenizer, TokenizerBuilder};
 use deserr::{DeserializeError, Deserr};
 use itertools::{EitherOrBoth, Itertools};
+use rand::SeedableRng;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use time::OffsetDateTime;
@@ -15,9 +16,12 @@ use super::index_documents::{IndexDocumentsConfig, Transform};
 use super::IndexerConfig;
 use crate::criterion::Criterion;
 use crate::error::UserError;
+use crate::geojson::RESERVED_GEOJSON_FIELD_NAME;
 use crate::index::{
     IndexEmbeddingConfig, DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS,
 };
+use crate::localized_attributes_rules::{LocalizedAttributesRule, LocalizedFieldIds};
+use crate::merchandising_rules::MerchandisingRule;
 use crate::order_by_map::OrderByMap;
 use crate::proximity::ProximityPrecision;
 use crate::update::index_documents::IndexDocumentsMethod;
@@ -27,7 +31,7 @@ use crate::vector::settings::{
     check_set, check_unset, EmbedderAction, EmbedderSource, EmbeddingSettings, ReindexAction,
     WriteBackToDocuments,
 };
-use crate::vector::{Embedder, EmbeddingConfig, EmbeddingConfigs};
+use crate::vector::{ArroyWrapper, Embedder, EmbeddingConfig, EmbeddingConfigs};
 use crate::{FieldId, FieldsIdsMap, Index, Result};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
@@ -159,6 +163,12 @@ pub struct Settings<'a, 't, 'i> {
     proximity_precision: Setting<ProximityPrecision>,
     embedder_settings: Setting<BTreeMap<String, Setting<EmbeddingSettings>>>,
     search_cutoff: Setting<u64>,
+    localized_attributes_rules: Setting<Vec<LocalizedAttributesRule>>,
+    allow_ranking_rules_override: Setting<bool>,
+    /// The rules to create, replace or delete, the other rules of the index are kept.
+    merchandising_rules: Setting<BTreeMap<String, Setting<MerchandisingRule>>>,
+    /// Whether all the rules of the index are deleted before applying `merchandising_rules`.
+    clear_merchandising_rules: bool,
 }
 
 impl<'a, 't, 'i> Settings<'a, 't, 'i> {
@@ -193,6 +203,10 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             proximity_precision: Setting::NotSet,
             embedder_settings: Setting::NotSet,
             search_cutoff: Setting::NotSet,
+            localized_attributes_rules: Setting::NotSet,
+            allow_ranking_rules_override: Setting::NotSet,
+            merchandising_rules: Setting::NotSet,
+            clear_merchandising_rules: false,
             indexer_config,
         }
     }
@@ -391,6 +405,37 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.search_cutoff = Setting::Reset;
     }
 
+    pub fn set_localized_attributes_rules(&mut self, value: Vec<LocalizedAttributesRule>) {
+        self.localized_attributes_rules = Setting::Set(value);
+    }
+
+    pub fn reset_localized_attributes_rules(&mut self) {
+        self.localized_attributes_rules = Setting::Reset;
+    }
+
+    pub fn set_allow_ranking_rules_override(&mut self, value: bool) {
+        self.allow_ranking_rules_override = Setting::Set(value);
+    }
+
+    pub fn reset_allow_ranking_rules_override(&mut self) {
+        self.allow_ranking_rules_override = Setting::Reset;
+    }
+
+    /// Creates or replaces the rules set to `Setting::Set` and deletes the ones set to `Setting::Reset`.
+    ///
+    /// Successive calls are merged together, the last update of a rule wins.
+    pub fn set_merchandising_rules(&mut self, rules: BTreeMap<String, Setting<MerchandisingRule>>) {
+        match &mut self.merchandising_rules {
+            Setting::Set(previous) => previous.extend(rules),
+            _ => self.merchandising_rules = Setting::Set(rules),
+        }
+    }
+
+    pub fn reset_merchandising_rules(&mut self) {
+        self.merchandising_rules = Setting::Reset;
+        self.clear_merchandising_rules = true;
+    }
+
     #[tracing::instrument(
         level = "trace"
         skip(self, progress_callback, should_abort, settings_diff),
@@ -935,7 +980,7 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                 let old_configs = self.index.embedding_configs(self.wtxn)?;
                 let remove_all: Result<BTreeMap<String, EmbedderAction>> = old_configs
                     .into_iter()
-                    .map(|IndexEmbeddingConfig { name, config: _, user_provided }| -> Result<_> {
+                    .map(|IndexEmbeddingConfig { name, config, user_provided }| -> Result<_> {
                         let embedder_id =
                             self.index.embedder_category_id.get(self.wtxn, &name)?.ok_or(
                                 crate::InternalError::DatabaseMissingEntry {
@@ -945,10 +990,10 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                             )?;
                         Ok((
                             name,
-                            EmbedderAction::WriteBackToDocuments(WriteBackToDocuments {
-                                embedder_id,
-                                user_provided,
-                            }),
+                            EmbedderAction::with_write_back(
+                                WriteBackToDocuments { embedder_id, user_provided },
+                                config.quantized(),
+                            ),
                         ))
                     })
                     .collect();
@@ -985,7 +1030,8 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             match joined {
                 // updated config
                 EitherOrBoth::Both((name, (old, user_provided)), (_, new)) => {
-                    let settings_diff = SettingsDiff::from_settings(old, new);
+                    let was_quantized = old.binary_quantized.set().unwrap_or_default();
+                    let settings_diff = SettingsDiff::from_settings(&name, old, new)?;
                     match settings_diff {
                         SettingsDiff::Remove => {
                             tracing::debug!(
@@ -1004,30 +1050,41 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                             self.index.embedder_category_id.delete(self.wtxn, &name)?;
                             embedder_actions.insert(
                                 name,
-                                EmbedderAction::WriteBackToDocuments(WriteBackToDocuments {
-                                    embedder_id,
-                                    user_provided,
-                                }),
+                                EmbedderAction::with_write_back(
+                                    WriteBackToDocuments { embedder_id, user_provided },
+                                    was_quantized,
+                                ),
                             );
                         }
-                        SettingsDiff::Reindex { action, updated_settings } => {
+                        SettingsDiff::Reindex { action, updated_settings, quantize } => {
                             tracing::debug!(
                                 embedder = name,
                                 user_provided = user_provided.len(),
                                 ?action,
                                 "reindex embedder"
                             );
-                            embedder_actions.insert(name.clone(), EmbedderAction::Reindex(action));
+                            embedder_actions.insert(
+                                name.clone(),
+                                EmbedderAction::with_reindex(action, was_quantized)
+                                    .with_is_being_quantized(quantize),
+                            );
                             let new =
                                 validate_embedding_settings(Setting::Set(updated_settings), &name)?;
                             updated_configs.insert(name, (new, user_provided));
                         }
-                        SettingsDiff::UpdateWithoutReindex { updated_settings } => {
+                        SettingsDiff::UpdateWithoutReindex { updated_settings, quantize } => {
                             tracing::debug!(
                                 embedder = name,
                                 user_provided = user_provided.len(),
+                                quantize,
                                 "update without reindex embedder"
                             );
+                            if quantize {
+                                embedder_actions.insert(
+                                    name.clone(),
+                                    EmbedderAction::default().with_is_being_quantized(true),
+                                );
+                            }
                             let new =
                                 validate_embedding_settings(Setting::Set(updated_settings), &name)?;
                             updated_configs.insert(name, (new, user_provided));
@@ -1048,8 +1105,11 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                         &mut setting,
                     );
                     let setting = validate_embedding_settings(setting, &name)?;
-                    embedder_actions
-                        .insert(name.clone(), EmbedderAction::Reindex(ReindexAction::FullReindex));
+                    // a new embedder has no vectors yet, so they can directly be stored quantized
+                    embedder_actions.insert(
+                        name.clone(),
+                        EmbedderAction::with_reindex(ReindexAction::FullReindex, false),
+                    );
                     updated_configs.insert(name, (setting, RoaringBitmap::new()));
                 }
             }
@@ -1063,11 +1123,11 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         let mut find_free_index =
             move || free_indices.find(|(_, free)| **free).map(|(index, _)| index as u8);
         for (name, action) in embedder_actions.iter() {
-            match action {
-                EmbedderAction::Reindex(ReindexAction::RegeneratePrompts) => {
+            match action.reindex() {
+                Some(ReindexAction::RegeneratePrompts) => {
                     /* cannot be a new embedder, so has to have an id already */
                 }
-                EmbedderAction::Reindex(ReindexAction::FullReindex) => {
+                Some(ReindexAction::FullReindex) => {
                     if self.index.embedder_category_id.get(self.wtxn, name)?.is_none() {
                         let id = find_free_index()
                             .ok_or(UserError::TooManyEmbedders(updated_configs.len()))?;
@@ -1075,14 +1135,21 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                         self.index.embedder_category_id.put(self.wtxn, name, &id)?;
                     }
                 }
-                EmbedderAction::WriteBackToDocuments(_) => { /* already removed */ }
+                None => { /* already removed or only being quantized */ }
             }
         }
         let updated_configs: Vec<IndexEmbeddingConfig> = updated_configs
             .into_iter()
             .filter_map(|(name, (config, user_provided))| match config {
                 Setting::Set(config) => {
-                    Some(IndexEmbeddingConfig { name, config: config.into(), user_provided })
+                    let mut config: EmbeddingConfig = config.into();
+                    // the vectors are quantized at the end of the settings update,
+                    // until then they are still stored in their original form.
+                    if embedder_actions.get(&name).map_or(false, |action| action.is_being_quantized)
+                    {
+                        config.quantized = Some(false);
+                    }
+                    Some(IndexEmbeddingConfig { name, config, user_provided })
                 }
                 Setting::Reset => None,
                 Setting::NotSet => Some(IndexEmbeddingConfig {
@@ -1118,6 +1185,74 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(changed)
     }
 
+    fn update_allow_ranking_rules_override(&mut self) -> Result<bool> {
+        let changed = match self.allow_ranking_rules_override {
+            Setting::Set(new) => {
+                let old = self.index.allow_ranking_rules_override(self.wtxn)?;
+                if old == new {
+                    false
+                } else {
+                    self.index.put_allow_ranking_rules_override(self.wtxn, new)?;
+                    true
+                }
+            }
+            Setting::Reset => self.index.delete_allow_ranking_rules_override(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        Ok(changed)
+    }
+
+    fn update_merchandising_rules(&mut self) -> Result<bool> {
+        let updates = match &self.merchandising_rules {
+            Setting::Set(updates) => Some(updates),
+            Setting::Reset => None,
+            Setting::NotSet => return Ok(false),
+        };
+
+        let old = self.index.merchandising_rules(self.wtxn)?;
+        let mut rules =
+            if self.clear_merchandising_rules { Default::default() } else { old.clone() };
+        for (uid, rule) in updates.into_iter().flatten() {
+            match rule {
+                Setting::Set(rule) => {
+                    rules.insert(uid.clone(), rule.clone());
+                }
+                Setting::Reset => {
+                    rules.remove(uid);
+                }
+                Setting::NotSet => (),
+            }
+        }
+
+        if rules == old {
+            Ok(false)
+        } else if rules.is_empty() {
+            Ok(self.index.delete_merchandising_rules(self.wtxn)?)
+        } else {
+            self.index.put_merchandising_rules(self.wtxn, &rules)?;
+            Ok(true)
+        }
+    }
+
+    fn update_localized_attributes_rules(&mut self) -> Result<bool> {
+        let changed = match self.localized_attributes_rules {
+            Setting::Set(ref new) => {
+                let old = self.index.localized_attributes_rules(self.wtxn)?;
+                if old.as_ref() == Some(new) {
+                    false
+                } else {
+                    self.index.put_localized_attributes_rules(self.wtxn, new.clone())?;
+                    true
+                }
+            }
+            Setting::Reset => self.index.delete_localized_attributes_rules(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        Ok(changed)
+    }
+
     pub fn execute<FP, FA>(mut self, progress_callback: FP, should_abort: FA) -> Result<()>
     where
         FP: Fn(UpdateIndexingStep) + Sync,
@@ -1139,6 +1274,8 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_sort_facet_values_by()?;
         self.update_pagination_max_total_hits()?;
         self.update_search_cutoff()?;
+        self.update_allow_ranking_rules_override()?;
+        self.update_merchandising_rules()?;
 
         // could trigger re-indexing
         self.update_filterable()?;
@@ -1151,8 +1288,14 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_searchable()?;
         self.update_exact_attributes()?;
         self.update_proximity_precision()?;
+        self.update_localized_attributes_rules()?;
 
         let embedding_config_updates = self.update_embedding_configs()?;
+        let embedders_to_quantize: Vec<String> = embedding_config_updates
+            .iter()
+            .filter(|(_, action)| action.is_being_quantized)
+            .map(|(name, _)| name.clone())
+            .collect();
 
         let mut new_inner_settings = InnerIndexSettings::from_index(self.index, self.wtxn)?;
         new_inner_settings.recompute_facets(self.wtxn, self.index)?;
@@ -1174,6 +1317,39 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             self.reindex(&progress_callback, &should_abort, inner_settings_diff)?;
         }
 
+        // The vectors are quantized once the documents have been reindexed, so that the user
+        // provided vectors have been written back into the documents.
+        self.quantize_embedders(embedders_to_quantize)?;
+
+        Ok(())
+    }
+
+    fn quantize_embedders(&mut self, embedders: Vec<String>) -> Result<()> {
+        if embedders.is_empty() {
+            return Ok(());
+        }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let mut embedding_configs = self.index.embedding_configs(self.wtxn)?;
+        for name in embedders {
+            let embedder_id = self.index.embedder_category_id.get(self.wtxn, &name)?.ok_or(
+                crate::InternalError::DatabaseMissingEntry {
+                    db_name: crate::index::db_name::VECTOR_EMBEDDER_CATEGORY_ID,
+                    key: None,
+                },
+            )?;
+            let mut arroy = ArroyWrapper::new(self.index.vector_arroy, embedder_id, false);
+            if let Some(dimensions) = arroy.dimensions(self.wtxn)? {
+                tracing::debug!(embedder = name, dimensions, "quantizing embedder");
+                arroy.quantize(self.wtxn, dimensions)?;
+                arroy.build(self.wtxn, &mut rng, dimensions)?;
+            }
+            if let Some(config) = embedding_configs.iter_mut().find(|config| config.name == name) {
+                config.config.quantized = Some(true);
+            }
+        }
+        self.index.put_embedding_configs(self.wtxn, embedding_configs)?;
+
         Ok(())
     }
 }
@@ -1189,7 +1365,7 @@ pub struct InnerIndexSettingsDiff {
     pub(crate) only_additional_fields: Option<HashSet<String>>,
 
     // Cache the check to see if all the stop_words, allowed_separators, dictionary,
-    // exact_attributes, proximity_precision are different.
+    // exact_attributes, proximity_precision, localized_searchable_fields_ids are different.
     pub(crate) cache_reindex_searchable_without_user_defined: bool,
     // Cache the check to see if the user_defined_searchables are different.
     pub(crate) cache_user_defined_searchables: bool,
@@ -1229,6 +1405,8 @@ impl InnerIndexSettingsDiff {
                 || old_settings.allowed_separators != new_settings.allowed_separators
                 || old_settings.dictionary != new_settings.dictionary
                 || old_settings.proximity_precision != new_settings.proximity_precision
+                || old_settings.localized_searchable_fields_ids
+                    != new_settings.localized_searchable_fields_ids
         };
 
         let cache_exact_attributes = old_settings.exact_attributes != new_settings.exact_attributes;
@@ -1319,6 +1497,11 @@ impl InnerIndexSettingsDiff {
             || (!self.settings_update_only && self.new.geo_fields_ids.is_some())
     }
 
+    pub fn run_geojson_indexing(&self) -> bool {
+        self.old.geojson_fid != self.new.geojson_fid
+            || (!self.settings_update_only && self.new.geojson_fid.is_some())
+    }
+
     pub fn modified_faceted_fields(&self) -> HashSet<String> {
         &self.old.user_defined_faceted_fields ^ &self.new.user_defined_faceted_fields
     }
@@ -1339,8 +1522,10 @@ pub(crate) struct InnerIndexSettings {
     pub embedding_configs: EmbeddingConfigs,
     pub existing_fields: HashSet<String>,
     pub geo_fields_ids: Option<(FieldId, FieldId)>,
+    pub geojson_fid: Option<FieldId>,
     pub non_searchable_fields_ids: Vec<FieldId>,
     pub non_faceted_fields_ids: Vec<FieldId>,
+    pub localized_searchable_fields_ids: LocalizedFieldIds,
 }
 
 impl InnerIndexSettings {
@@ -1382,11 +1567,23 @@ impl InnerIndexSettings {
             }
             None => None,
         };
+        // the `_geojson` geometries are only indexed when the field is filterable
+        let geojson_fid = match fields_ids_map.id(RESERVED_GEOJSON_FIELD_NAME) {
+            Some(fid) if index.filterable_fields_ids(rtxn)?.contains(&fid) => Some(fid),
+            _ => None,
+        };
 
         let vectors_fids = fields_ids_map.nested_ids(RESERVED_VECTORS_FIELD_NAME);
         searchable_fields_ids.retain(|id| !vectors_fids.contains(id));
         faceted_fields_ids.retain(|id| !vectors_fids.contains(id));
 
+        let localized_attributes_rules = index.localized_attributes_rules(rtxn)?;
+        let localized_searchable_fields_ids = LocalizedFieldIds::new(
+            &localized_attributes_rules,
+            &fields_ids_map,
+            searchable_fields_ids.iter().cloned(),
+        );
+
         Ok(Self {
             stop_words,
             allowed_separators,
@@ -1401,8 +1598,10 @@ impl InnerIndexSettings {
             embedding_configs,
             existing_fields,
             geo_fields_ids,
+            geojson_fid,
             non_searchable_fields_ids: vectors_fids.clone(),
             non_faceted_fields_ids: vectors_fids.clone(),
+            localized_searchable_fields_ids,
         })
     }
 
@@ -1440,6 +1639,14 @@ impl InnerIndexSettings {
         let searchable_fields_ids = index.searchable_fields_ids(wtxn)?;
         self.searchable_fields_ids = searchable_fields_ids;
 
+        // the newly introduced searchable fields may be matched by the localized attributes rules.
+        let localized_attributes_rules = index.localized_attributes_rules(wtxn)?;
+        self.localized_searchable_fields_ids = LocalizedFieldIds::new(
+            &localized_attributes_rules,
+            &self.fields_ids_map,
+            self.searchable_fields_ids.iter().cloned(),
+        );
+
         Ok(())
     }
 }
@@ -1450,7 +1657,7 @@ fn embedders(embedding_configs: Vec<IndexEmbeddingConfig>) -> Result<EmbeddingCo
         .map(
             |IndexEmbeddingConfig {
                  name,
-                 config: EmbeddingConfig { embedder_options, prompt },
+                 config: EmbeddingConfig { embedder_options, prompt, quantized: _ },
                  ..
              }| {
                 let prompt = Arc::new(prompt.try_into().map_err(crate::Error::from)?);
@@ -1486,6 +1693,7 @@ fn validate_prompt(
             embedding_object,
             input_type,
             distribution,
+            binary_quantized,
         }) => {
             // validate
             let template = crate::prompt::Prompt::new(template)
@@ -1506,6 +1714,7 @@ fn validate_prompt(
                 embedding_object,
                 input_type,
                 distribution,
+                binary_quantized,
             }))
         }
         new => Ok(new),
@@ -1532,6 +1741,7 @@ pub fn validate_embedding_settings(
         embedding_object,
         input_type,
         distribution,
+        binary_quantized,
     } = settings;
 
     if let Some(0) = dimensions.set() {
@@ -1564,6 +1774,7 @@ pub fn validate_embedding_settings(
             embedding_object,
             input_type,
             distribution,
+            binary_quantized,
         }));
     };
     match inferred_source {
@@ -1709,6 +1920,7 @@ pub fn validate_embedding_settings(
         embedding_object,
         input_type,
         distribution,
+        binary_quantized,
     }))
 }
 
@@ -1776,7 +1988,10 @@ mod tests {
         let documents = index.documents(&rtxn, result.documents_ids).unwrap();
         let fid_map = index.fields_ids_map(&rtxn).unwrap();
         assert_eq!(documents.len(), 1);
-        assert_eq!(documents[0].1.get(fid_map.id("name").unwrap()), Some(&br#""kevin""#[..]));
+        assert_eq!(
+            documents[0].1.as_obkv().get(fid_map.id("name").unwrap()),
+            Some(&br#""kevin""#[..])
+        );
         drop(rtxn);
 
         // We change the searchable fields to be the "name" field only.
@@ -1810,7 +2025,10 @@ mod tests {
         let result = index.search(&rtxn).query("23").execute().unwrap();
         assert_eq!(result.documents_ids.len(), 1);
         let documents = index.documents(&rtxn, result.documents_ids).unwrap();
-        assert_eq!(documents[0].1.get(fid_map.id("name").unwrap()), Some(&br#""kevin""#[..]));
+        assert_eq!(
+            documents[0].1.as_obkv().get(fid_map.id("name").unwrap()),
+            Some(&br#""kevin""#[..])
+        );
     }
 
     #[test]
@@ -1952,8 +2170,12 @@ mod tests {
         let fidmap = index.fields_ids_map(&rtxn).unwrap();
         for document in index.all_documents(&rtxn).unwrap() {
             let document = document.unwrap();
-            let json = crate::obkv_to_json(&fidmap.ids().collect::<Vec<_>>(), &fidmap, document.1)
-                .unwrap();
+            let json = crate::obkv_to_json(
+                &fidmap.ids().collect::<Vec<_>>(),
+                &fidmap,
+                document.1.as_obkv(),
+            )
+            .unwrap();
             println!("json: {:?}", json);
         }
         let count = index
@@ -2080,7 +2302,7 @@ mod tests {
         // Fetch the documents "age" field in the ordre in which the documents appear.
         let age_field_id = index.fields_ids_map(&rtxn).unwrap().id("age").unwrap();
         let iter = documents.into_iter().map(|(_, doc)| {
-            let bytes = doc.get(age_field_id).unwrap();
+            let bytes = doc.as_obkv().get(age_field_id).unwrap();
             let string = std::str::from_utf8(bytes).unwrap();
             string.parse::<u32>().unwrap()
         });
@@ -2512,7 +2734,7 @@ mod tests {
         let (_, content) = documents.iter().find(|(id, _)| *id == first_id).unwrap();
 
         let fid = index.fields_ids_map(&rtxn).unwrap().id("title").unwrap();
-        let line = std::str::from_utf8(content.get(fid).unwrap()).unwrap();
+        let line = std::str::from_utf8(content.as_obkv().get(fid).unwrap()).unwrap();
         assert_eq!(line, r#""Star Wars""#);
     }
 
@@ -2626,6 +2848,10 @@ mod tests {
                     proximity_precision,
                     embedder_settings,
                     search_cutoff,
+                    localized_attributes_rules,
+                    allow_ranking_rules_override,
+                    merchandising_rules,
+                    clear_merchandising_rules,
                 } = settings;
                 assert!(matches!(searchable_fields, Setting::NotSet));
                 assert!(matches!(displayed_fields, Setting::NotSet));
@@ -2650,8 +2876,59 @@ mod tests {
                 assert!(matches!(proximity_precision, Setting::NotSet));
                 assert!(matches!(embedder_settings, Setting::NotSet));
                 assert!(matches!(search_cutoff, Setting::NotSet));
+                assert!(matches!(localized_attributes_rules, Setting::NotSet));
+                assert!(matches!(allow_ranking_rules_override, Setting::NotSet));
+                assert!(matches!(merchandising_rules, Setting::NotSet));
+                assert!(!*clear_merchandising_rules);
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn merchandising_rules_updates_are_merged() {
+        use crate::merchandising_rules::{QueryMatch, RuleActions, RuleCondition};
+
+        let rule = |query: &str| MerchandisingRule {
+            condition: RuleCondition {
+                query: query.to_string(),
+                query_match: QueryMatch::Exact,
+                filter: None,
+            },
+            actions: RuleActions { hide: vec![S("1")], ..Default::default() },
+        };
+
+        let index = TempIndex::new();
+        index
+            .update_settings(|settings| {
+                settings.set_merchandising_rules(btreemap! {
+                    S("a") => Setting::Set(rule("a")),
+                    S("b") => Setting::Set(rule("b")),
+                });
             })
             .unwrap();
+
+        // the other rules are kept
+        index
+            .update_settings(|settings| {
+                settings.set_merchandising_rules(btreemap! { S("a") => Setting::Reset });
+                settings.set_merchandising_rules(btreemap! { S("c") => Setting::Set(rule("c")) });
+            })
+            .unwrap();
+        let rtxn = index.read_txn().unwrap();
+        let rules = index.merchandising_rules(&rtxn).unwrap();
+        assert_eq!(rules.keys().collect::<Vec<_>>(), vec!["b", "c"]);
+        drop(rtxn);
+
+        // the rules set after a reset are kept
+        index
+            .update_settings(|settings| {
+                settings.reset_merchandising_rules();
+                settings.set_merchandising_rules(btreemap! { S("d") => Setting::Set(rule("d")) });
+            })
+            .unwrap();
+        let rtxn = index.read_txn().unwrap();
+        let rules = index.merchandising_rules(&rtxn).unwrap();
+        assert_eq!(rules, btreemap! { S("d") => rule("d") });
     }
 
     #[test]
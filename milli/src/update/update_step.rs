@@ -1,6 +1,6 @@
 use UpdateIndexingStep::*;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum UpdateIndexingStep {
     /// Remap document addition fields the one present in the database, adding new fields in to the
     /// schema on the go.
@@ -17,6 +17,10 @@ pub enum UpdateIndexingStep {
     /// Merge the previously extracted data (words and facets) into the final LMDB database.
     /// These extracted data are split into multiple databases.
     MergeDataIntoFinalDatabase { databases_seen: usize, total_databases: usize },
+
+    /// Store the embeddings generated by an embedder into the vector database.
+    /// This step runs along with the `IndexDocuments` one, once for each embedder.
+    EmbedDocuments { embedder_name: String, documents_seen: usize, total_documents: usize },
 }
 
 impl UpdateIndexingStep {
@@ -24,7 +28,7 @@ impl UpdateIndexingStep {
         match self {
             RemapDocumentAddition { .. } => 0,
             ComputeIdsAndMergeDocuments { .. } => 1,
-            IndexDocuments { .. } => 2,
+            IndexDocuments { .. } | EmbedDocuments { .. } => 2,
             MergeDataIntoFinalDatabase { .. } => 3,
         }
     }
@@ -1,8 +1,14 @@
 use std::collections::HashMap;
+use std::convert::TryInto;
 use std::sync::Arc;
 
+use arroy::distances::{BinaryQuantizedCosine, Cosine};
+use arroy::{Distance, ItemId};
 use deserr::{DeserializeError, Deserr};
+use heed::{RoTxn, RwTxn};
 use ordered_float::OrderedFloat;
+use rand::{Rng, SeedableRng};
+use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 
 use self::error::{EmbedError, NewEmbedderError};
@@ -123,9 +129,18 @@ pub struct EmbeddingConfig {
     pub embedder_options: EmbedderOptions,
     /// Document template
     pub prompt: PromptData,
+    /// Whether the vectors are stored in their binary quantized form
+    #[serde(default)]
+    pub quantized: Option<bool>,
     // TODO: add metrics and anything needed
 }
 
+impl EmbeddingConfig {
+    pub fn quantized(&self) -> bool {
+        self.quantized.unwrap_or_default()
+    }
+}
+
 /// Map of embedder configurations.
 ///
 /// Each configuration is mapped to a name.
@@ -452,3 +467,406 @@ pub fn arroy_db_range_for_embedder(embedder_id: u8) -> impl Iterator<Item = u16>
 
     (0..=u8::MAX).map(move |k| embedder_id | (k as u16))
 }
+
+/// The distance used to store the binary quantized vectors.
+type QuantizedDistance = BinaryQuantizedCosine;
+
+/// The version of arroy that wrote the vector stores of an index.
+///
+/// The indexes with vector stores written by an older version must be upgraded
+/// with `meilitool upgrade-vector-stores` before being opened.
+pub const VECTOR_STORE_VERSION: &str = "0.5";
+
+/// The arroy stores of an embedder.
+///
+/// An embedder can store several vectors per document, the n-th vector of a document is stored
+/// in the n-th store of the embedder and the vectors are always packed in the first stores.
+/// The vectors are stored with their full precision or, when the `binaryQuantized` setting
+/// is enabled, in their binary quantized form.
+pub struct ArroyWrapper {
+    quantized: bool,
+    embedder_index: u8,
+    database: arroy::Database<Cosine>,
+}
+
+impl ArroyWrapper {
+    pub fn new(database: arroy::Database<Cosine>, embedder_index: u8, quantized: bool) -> Self {
+        Self { quantized, embedder_index, database }
+    }
+
+    pub fn embedder_index(&self) -> u8 {
+        self.embedder_index
+    }
+
+    pub fn quantized(&self) -> bool {
+        self.quantized
+    }
+
+    fn quantized_db(&self) -> arroy::Database<QuantizedDistance> {
+        self.database.remap_data_type()
+    }
+
+    fn readers<'t, D: Distance>(
+        &self,
+        rtxn: &'t RoTxn<'t>,
+        db: arroy::Database<D>,
+    ) -> impl Iterator<Item = Result<arroy::Reader<'t, D>, arroy::Error>> + 't {
+        arroy_db_range_for_embedder(self.embedder_index).map_while(move |index| {
+            match arroy::Reader::open(rtxn, index, db) {
+                Ok(reader) => Some(Ok(reader)),
+                Err(arroy::Error::MissingMetadata(_)) => None,
+                Err(e) => Some(Err(e)),
+            }
+        })
+    }
+
+    fn writers<D: Distance>(
+        &self,
+        db: arroy::Database<D>,
+        dimension: usize,
+    ) -> impl Iterator<Item = arroy::Writer<D>> {
+        arroy_db_range_for_embedder(self.embedder_index)
+            .map(move |index| arroy::Writer::new(db, index, dimension))
+    }
+
+    /// Returns the dimensions of the vectors, if any vector was stored.
+    pub fn dimensions(&self, rtxn: &RoTxn) -> Result<Option<usize>, arroy::Error> {
+        if self.quantized {
+            self.readers(rtxn, self.quantized_db())
+                .next()
+                .transpose()
+                .map(|r| r.map(|r| r.dimensions()))
+        } else {
+            self.readers(rtxn, self.database).next().transpose().map(|r| r.map(|r| r.dimensions()))
+        }
+    }
+
+    /// Builds the trees of the stores that were modified.
+    pub fn build<R: Rng + SeedableRng>(
+        &self,
+        wtxn: &mut RwTxn,
+        rng: &mut R,
+        dimension: usize,
+    ) -> Result<(), arroy::Error> {
+        fn build<D: Distance, R: Rng + SeedableRng>(
+            writers: impl Iterator<Item = arroy::Writer<D>>,
+            wtxn: &mut RwTxn,
+            rng: &mut R,
+        ) -> Result<(), arroy::Error> {
+            for writer in writers {
+                if writer.need_build(wtxn)? {
+                    writer.builder(rng).build(wtxn)?;
+                } else if writer.is_empty(wtxn)? {
+                    break;
+                }
+            }
+            Ok(())
+        }
+
+        if self.quantized {
+            build(self.writers(self.quantized_db(), dimension), wtxn, rng)
+        } else {
+            build(self.writers(self.database, dimension), wtxn, rng)
+        }
+    }
+
+    /// Converts all the vectors of the embedder to their binary quantized form.
+    ///
+    /// This operation cannot be reverted, the trees must be built again afterward.
+    pub fn quantize(&mut self, wtxn: &mut RwTxn, dimension: usize) -> Result<(), arroy::Error> {
+        if self.quantized {
+            return Ok(());
+        }
+
+        for writer in self.writers(self.database, dimension) {
+            if writer.is_empty(wtxn)? {
+                break;
+            }
+            writer.prepare_changing_distance::<QuantizedDistance>(wtxn)?;
+        }
+
+        self.quantized = true;
+        Ok(())
+    }
+
+    /// Adds the embeddings of an item, one vector per store.
+    pub fn add_items(
+        &self,
+        wtxn: &mut RwTxn,
+        item_id: ItemId,
+        embeddings: &Embeddings<f32>,
+    ) -> Result<(), arroy::Error> {
+        fn add_items<D: Distance>(
+            writers: impl Iterator<Item = arroy::Writer<D>>,
+            wtxn: &mut RwTxn,
+            item_id: ItemId,
+            embeddings: &Embeddings<f32>,
+        ) -> Result<(), arroy::Error> {
+            for (vector, writer) in embeddings.iter().zip(writers) {
+                writer.add_item(wtxn, item_id, vector)?;
+            }
+            Ok(())
+        }
+
+        let dimension = embeddings.dimension();
+        if self.quantized {
+            add_items(self.writers(self.quantized_db(), dimension), wtxn, item_id, embeddings)
+        } else {
+            add_items(self.writers(self.database, dimension), wtxn, item_id, embeddings)
+        }
+    }
+
+    /// Adds a vector to an item, in the first store that does not contain this item yet.
+    pub fn add_item(
+        &self,
+        wtxn: &mut RwTxn,
+        item_id: ItemId,
+        vector: &[f32],
+    ) -> Result<(), arroy::Error> {
+        fn add_item<D: Distance>(
+            writers: impl Iterator<Item = arroy::Writer<D>>,
+            wtxn: &mut RwTxn,
+            item_id: ItemId,
+            vector: &[f32],
+        ) -> Result<(), arroy::Error> {
+            for writer in writers {
+                if !writer.contains_item(wtxn, item_id)? {
+                    writer.add_item(wtxn, item_id, vector)?;
+                    break;
+                }
+            }
+            Ok(())
+        }
+
+        let dimension = vector.len();
+        if self.quantized {
+            add_item(self.writers(self.quantized_db(), dimension), wtxn, item_id, vector)
+        } else {
+            add_item(self.writers(self.database, dimension), wtxn, item_id, vector)
+        }
+    }
+
+    /// Deletes all the vectors of an item.
+    pub fn del_items(
+        &self,
+        wtxn: &mut RwTxn,
+        dimension: usize,
+        item_id: ItemId,
+    ) -> Result<(), arroy::Error> {
+        fn del_items<D: Distance>(
+            writers: impl Iterator<Item = arroy::Writer<D>>,
+            wtxn: &mut RwTxn,
+            item_id: ItemId,
+        ) -> Result<(), arroy::Error> {
+            for writer in writers {
+                // uses invariant: vectors are packed in the first writers.
+                if !writer.del_item(wtxn, item_id)? {
+                    break;
+                }
+            }
+            Ok(())
+        }
+
+        if self.quantized {
+            del_items(self.writers(self.quantized_db(), dimension), wtxn, item_id)
+        } else {
+            del_items(self.writers(self.database, dimension), wtxn, item_id)
+        }
+    }
+
+    /// Deletes one vector of an item, returns `true` if the vector was found.
+    ///
+    /// The vector of the last store containing the item is moved to keep the vectors packed.
+    /// When the vectors are binary quantized, the first vector with the same quantized form is deleted.
+    pub fn del_item(
+        &self,
+        wtxn: &mut RwTxn,
+        item_id: ItemId,
+        vector: &[f32],
+    ) -> Result<bool, arroy::Error> {
+        fn del_item<D: Distance>(
+            writers: Vec<arroy::Writer<D>>,
+            wtxn: &mut RwTxn,
+            item_id: ItemId,
+            matches: impl Fn(&[f32]) -> bool,
+        ) -> Result<bool, arroy::Error> {
+            let mut deleted_index = None;
+            for (index, writer) in writers.iter().enumerate() {
+                let Some(candidate) = writer.item_vector(wtxn, item_id)? else {
+                    // uses invariant: vectors are packed in the first writers.
+                    break;
+                };
+                if matches(&candidate) {
+                    writer.del_item(wtxn, item_id)?;
+                    deleted_index = Some(index);
+                    break;
+                }
+            }
+
+            // 🥲 enforce invariant: vectors are packed in the first writers.
+            let Some(deleted_index) = deleted_index else { return Ok(false) };
+            let mut last_index_with_a_vector = None;
+            for (index, writer) in writers.iter().enumerate().skip(deleted_index) {
+                let Some(candidate) = writer.item_vector(wtxn, item_id)? else {
+                    break;
+                };
+                last_index_with_a_vector = Some((index, candidate));
+            }
+            if let Some((last_index, vector)) = last_index_with_a_vector {
+                writers[last_index].del_item(wtxn, item_id)?;
+                writers[deleted_index].add_item(wtxn, item_id, &vector)?;
+            }
+            Ok(true)
+        }
+
+        let dimension = vector.len();
+        if self.quantized {
+            let writers = self.writers(self.quantized_db(), dimension).collect();
+            // only the sign of each dimension is kept in the binary quantized form
+            let matches = |candidate: &[f32]| {
+                candidate.iter().zip(vector).all(|(c, v)| (*c > 0.0) == (*v > 0.0))
+            };
+            del_item(writers, wtxn, item_id, matches)
+        } else {
+            let writers = self.writers(self.database, dimension).collect();
+            del_item(writers, wtxn, item_id, |candidate| candidate == vector)
+        }
+    }
+
+    /// Removes all the vectors of the embedder.
+    pub fn clear(&self, wtxn: &mut RwTxn, dimension: usize) -> Result<(), arroy::Error> {
+        fn clear<D: Distance>(
+            writers: impl Iterator<Item = arroy::Writer<D>>,
+            wtxn: &mut RwTxn,
+        ) -> Result<(), arroy::Error> {
+            for writer in writers {
+                if writer.is_empty(wtxn)? {
+                    break;
+                }
+                writer.clear(wtxn)?;
+            }
+            Ok(())
+        }
+
+        if self.quantized {
+            clear(self.writers(self.quantized_db(), dimension), wtxn)
+        } else {
+            clear(self.writers(self.database, dimension), wtxn)
+        }
+    }
+
+    /// Returns `true` if the embedder does not contain any vector.
+    pub fn is_empty(&self, rtxn: &RoTxn, dimension: usize) -> Result<bool, arroy::Error> {
+        if self.quantized {
+            self.writers(self.quantized_db(), dimension).next().unwrap().is_empty(rtxn)
+        } else {
+            self.writers(self.database, dimension).next().unwrap().is_empty(rtxn)
+        }
+    }
+
+    /// Returns the items that are the nearest to the given item, sorted by increasing distance.
+    pub fn nns_by_item(
+        &self,
+        rtxn: &RoTxn,
+        item: ItemId,
+        limit: usize,
+        filter: Option<&RoaringBitmap>,
+    ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
+        fn nns_by_item<'a, D: Distance>(
+            readers: impl Iterator<Item = Result<arroy::Reader<'a, D>, arroy::Error>>,
+            rtxn: &RoTxn,
+            item: ItemId,
+            limit: usize,
+            filter: Option<&RoaringBitmap>,
+        ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
+            let mut results = Vec::new();
+            for reader in readers {
+                let reader = reader?;
+                let mut searcher = reader.nns(limit);
+                if let Some(filter) = filter {
+                    searcher.candidates(filter);
+                }
+                match searcher.by_item(rtxn, item)? {
+                    Some(mut nns) => results.append(&mut nns),
+                    None => break,
+                }
+            }
+            Ok(results)
+        }
+
+        let mut results = if self.quantized {
+            nns_by_item(self.readers(rtxn, self.quantized_db()), rtxn, item, limit, filter)?
+        } else {
+            nns_by_item(self.readers(rtxn, self.database), rtxn, item, limit, filter)?
+        };
+        results.sort_unstable_by_key(|(_, distance)| OrderedFloat(*distance));
+        Ok(results)
+    }
+
+    /// Returns the items that are the nearest to the given vector, sorted by increasing distance.
+    pub fn nns_by_vector(
+        &self,
+        rtxn: &RoTxn,
+        vector: &[f32],
+        limit: usize,
+        filter: Option<&RoaringBitmap>,
+    ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
+        fn nns_by_vector<'a, D: Distance>(
+            readers: impl Iterator<Item = Result<arroy::Reader<'a, D>, arroy::Error>>,
+            rtxn: &RoTxn,
+            vector: &[f32],
+            limit: usize,
+            filter: Option<&RoaringBitmap>,
+        ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
+            let mut results = Vec::new();
+            for reader in readers {
+                let reader = reader?;
+                let mut searcher = reader.nns(limit);
+                if let Some(filter) = filter {
+                    searcher.candidates(filter);
+                }
+                results.append(&mut searcher.by_vector(rtxn, vector)?);
+            }
+            Ok(results)
+        }
+
+        let mut results = if self.quantized {
+            nns_by_vector(self.readers(rtxn, self.quantized_db()), rtxn, vector, limit, filter)?
+        } else {
+            nns_by_vector(self.readers(rtxn, self.database), rtxn, vector, limit, filter)?
+        };
+        results.sort_unstable_by_key(|(_, distance)| OrderedFloat(*distance));
+        Ok(results)
+    }
+
+    /// Returns all the vectors of an item.
+    ///
+    /// The vectors are returned in their binary quantized form when the embedder is quantized.
+    pub fn item_vectors(
+        &self,
+        rtxn: &RoTxn,
+        item_id: ItemId,
+    ) -> Result<Vec<Vec<f32>>, arroy::Error> {
+        fn item_vectors<'a, D: Distance>(
+            readers: impl Iterator<Item = Result<arroy::Reader<'a, D>, arroy::Error>>,
+            rtxn: &RoTxn,
+            item_id: ItemId,
+        ) -> Result<Vec<Vec<f32>>, arroy::Error> {
+            let mut vectors = Vec::new();
+            for reader in readers {
+                match reader?.item_vector(rtxn, item_id)? {
+                    Some(vector) => vectors.push(vector),
+                    // uses invariant: vectors are packed in the first stores.
+                    None => break,
+                }
+            }
+            Ok(vectors)
+        }
+
+        if self.quantized {
+            item_vectors(self.readers(rtxn, self.quantized_db()), rtxn, item_id)
+        } else {
+            item_vectors(self.readers(rtxn, self.database), rtxn, item_id)
+        }
+    }
+}
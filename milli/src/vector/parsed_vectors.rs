@@ -160,6 +160,8 @@ impl ParsedVectorsDiff {
             VectorState::Manual => VectorState::Manual,
             // generated is still generated
             VectorState::Generated => VectorState::Generated,
+            // the user-provided vectors of a quantized embedder are kept inline in the documents
+            VectorState::Inline(ref v) if !v.must_regenerate() => VectorState::Manual,
             // weird case that shouldn't happen were the previous docs version is inline,
             // but it was removed in the new version
             // Since it is not in the new version, we switch to generated
@@ -202,6 +204,26 @@ impl ParsedVectors {
     pub fn retain_not_embedded_vectors(&mut self, embedders: &BTreeSet<String>) {
         self.0.retain(|k, _v| !embedders.contains(k))
     }
+
+    /// Keeps only the user-provided vectors of the given embedders, in their explicit form.
+    pub fn retain_user_provided_vectors(&mut self, embedders: &BTreeSet<String>) {
+        let vectors = std::mem::take(&mut self.0);
+        self.0 = vectors
+            .into_iter()
+            .filter_map(|(name, vectors)| {
+                if !embedders.contains(&name) {
+                    return Some((name, vectors));
+                }
+                if vectors.must_regenerate() {
+                    return None;
+                }
+                let embeddings = vectors
+                    .into_array_of_vectors()
+                    .map(VectorOrArrayOfVectors::from_array_of_vectors);
+                Some((name, Vectors::Explicit(ExplicitVectors { embeddings, regenerate: false })))
+            })
+            .collect();
+    }
 }
 
 pub enum Error {
@@ -52,6 +52,9 @@ pub struct EmbeddingSettings {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
     pub distribution: Setting<DistributionShift>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub binary_quantized: Setting<bool>,
 }
 
 pub fn check_unset<T>(
@@ -86,13 +89,42 @@ pub enum ReindexAction {
 
 pub enum SettingsDiff {
     Remove,
-    Reindex { action: ReindexAction, updated_settings: EmbeddingSettings },
-    UpdateWithoutReindex { updated_settings: EmbeddingSettings },
+    Reindex { action: ReindexAction, updated_settings: EmbeddingSettings, quantize: bool },
+    UpdateWithoutReindex { updated_settings: EmbeddingSettings, quantize: bool },
+}
+
+/// Indicates what must be done with the vectors of an embedder after its settings changed.
+#[derive(Default)]
+pub struct EmbedderAction {
+    /// Whether the vectors of the embedder were stored in their binary quantized form before the update.
+    pub was_quantized: bool,
+    /// Whether the vectors of the embedder must be converted to their binary quantized form.
+    pub is_being_quantized: bool,
+    pub write_back: Option<WriteBackToDocuments>,
+    pub reindex: Option<ReindexAction>,
 }
 
-pub enum EmbedderAction {
-    WriteBackToDocuments(WriteBackToDocuments),
-    Reindex(ReindexAction),
+impl EmbedderAction {
+    pub fn with_write_back(write_back: WriteBackToDocuments, was_quantized: bool) -> Self {
+        Self { was_quantized, write_back: Some(write_back), ..Default::default() }
+    }
+
+    pub fn with_reindex(reindex: ReindexAction, was_quantized: bool) -> Self {
+        Self { was_quantized, reindex: Some(reindex), ..Default::default() }
+    }
+
+    pub fn with_is_being_quantized(mut self, quantize: bool) -> Self {
+        self.is_being_quantized = quantize;
+        self
+    }
+
+    pub fn write_back(&self) -> Option<&WriteBackToDocuments> {
+        self.write_back.as_ref()
+    }
+
+    pub fn reindex(&self) -> Option<&ReindexAction> {
+        self.reindex.as_ref()
+    }
 }
 
 pub struct WriteBackToDocuments {
@@ -101,8 +133,12 @@ pub struct WriteBackToDocuments {
 }
 
 impl SettingsDiff {
-    pub fn from_settings(old: EmbeddingSettings, new: Setting<EmbeddingSettings>) -> Self {
-        match new {
+    pub fn from_settings(
+        embedder_name: &str,
+        old: EmbeddingSettings,
+        new: Setting<EmbeddingSettings>,
+    ) -> Result<Self, UserError> {
+        let ret = match new {
             Setting::Set(new) => {
                 let EmbeddingSettings {
                     mut source,
@@ -118,6 +154,7 @@ impl SettingsDiff {
                     mut embedding_object,
                     mut input_type,
                     mut distribution,
+                    mut binary_quantized,
                 } = old;
 
                 let EmbeddingSettings {
@@ -134,10 +171,23 @@ impl SettingsDiff {
                     embedding_object: new_embedding_object,
                     input_type: new_input_type,
                     distribution: new_distribution,
+                    binary_quantized: new_binary_quantized,
                 } = new;
 
                 let mut reindex_action = None;
 
+                // the binary quantization is one-way: the full vectors are lost once quantized
+                let was_quantized = binary_quantized.set().unwrap_or_default();
+                if was_quantized
+                    && matches!(new_binary_quantized, Setting::Set(false) | Setting::Reset)
+                {
+                    return Err(UserError::InvalidDisableBinaryQuantization {
+                        embedder_name: embedder_name.to_owned(),
+                    });
+                }
+                binary_quantized.apply(new_binary_quantized);
+                let quantize = !was_quantized && binary_quantized.set().unwrap_or_default();
+
                 // **Warning**: do not use short-circuiting || here, we want all these operations applied
                 if source.apply(new_source) {
                     ReindexAction::push_action(&mut reindex_action, ReindexAction::FullReindex);
@@ -207,16 +257,21 @@ impl SettingsDiff {
                     embedding_object,
                     input_type,
                     distribution,
+                    binary_quantized,
                 };
 
                 match reindex_action {
-                    Some(action) => Self::Reindex { action, updated_settings },
-                    None => Self::UpdateWithoutReindex { updated_settings },
+                    Some(action) => Self::Reindex { action, updated_settings, quantize },
+                    None => Self::UpdateWithoutReindex { updated_settings, quantize },
                 }
             }
             Setting::Reset => Self::Remove,
-            Setting::NotSet => Self::UpdateWithoutReindex { updated_settings: old },
-        }
+            Setting::NotSet => {
+                Self::UpdateWithoutReindex { updated_settings: old, quantize: false }
+            }
+        };
+
+        Ok(ret)
     }
 }
 
@@ -339,6 +394,8 @@ impl EmbeddingSettings {
 
     pub const DISTRIBUTION: &'static str = "distribution";
 
+    pub const BINARY_QUANTIZED: &'static str = "binaryQuantized";
+
     pub fn allowed_sources_for_field(field: &'static str) -> &'static [EmbedderSource] {
         match field {
             Self::SOURCE => &[
@@ -377,6 +434,13 @@ impl EmbeddingSettings {
                 EmbedderSource::Rest,
                 EmbedderSource::UserProvided,
             ],
+            Self::BINARY_QUANTIZED => &[
+                EmbedderSource::HuggingFace,
+                EmbedderSource::Ollama,
+                EmbedderSource::OpenAi,
+                EmbedderSource::Rest,
+                EmbedderSource::UserProvided,
+            ],
             _other => unreachable!("unknown field"),
         }
     }
@@ -390,6 +454,7 @@ impl EmbeddingSettings {
                 Self::DOCUMENT_TEMPLATE,
                 Self::DIMENSIONS,
                 Self::DISTRIBUTION,
+                Self::BINARY_QUANTIZED,
             ],
             EmbedderSource::HuggingFace => &[
                 Self::SOURCE,
@@ -397,6 +462,7 @@ impl EmbeddingSettings {
                 Self::REVISION,
                 Self::DOCUMENT_TEMPLATE,
                 Self::DISTRIBUTION,
+                Self::BINARY_QUANTIZED,
             ],
             EmbedderSource::Ollama => &[
                 Self::SOURCE,
@@ -405,8 +471,11 @@ impl EmbeddingSettings {
                 Self::URL,
                 Self::API_KEY,
                 Self::DISTRIBUTION,
+                Self::BINARY_QUANTIZED,
             ],
-            EmbedderSource::UserProvided => &[Self::SOURCE, Self::DIMENSIONS, Self::DISTRIBUTION],
+            EmbedderSource::UserProvided => {
+                &[Self::SOURCE, Self::DIMENSIONS, Self::DISTRIBUTION, Self::BINARY_QUANTIZED]
+            }
             EmbedderSource::Rest => &[
                 Self::SOURCE,
                 Self::API_KEY,
@@ -419,6 +488,7 @@ impl EmbeddingSettings {
                 Self::EMBEDDING_OBJECT,
                 Self::INPUT_TYPE,
                 Self::DISTRIBUTION,
+                Self::BINARY_QUANTIZED,
             ],
         }
     }
@@ -472,7 +542,8 @@ impl std::fmt::Display for EmbedderSource {
 
 impl From<EmbeddingConfig> for EmbeddingSettings {
     fn from(value: EmbeddingConfig) -> Self {
-        let EmbeddingConfig { embedder_options, prompt } = value;
+        let EmbeddingConfig { embedder_options, prompt, quantized } = value;
+        let binary_quantized = quantized.map(Setting::Set).unwrap_or_default();
         match embedder_options {
             super::EmbedderOptions::HuggingFace(super::hf::EmbedderOptions {
                 model,
@@ -492,6 +563,7 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 embedding_object: Setting::NotSet,
                 input_type: Setting::NotSet,
                 distribution: distribution.map(Setting::Set).unwrap_or_default(),
+                binary_quantized,
             },
             super::EmbedderOptions::OpenAi(super::openai::EmbedderOptions {
                 api_key,
@@ -512,6 +584,7 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 embedding_object: Setting::NotSet,
                 input_type: Setting::NotSet,
                 distribution: distribution.map(Setting::Set).unwrap_or_default(),
+                binary_quantized,
             },
             super::EmbedderOptions::Ollama(super::ollama::EmbedderOptions {
                 embedding_model,
@@ -532,6 +605,7 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 embedding_object: Setting::NotSet,
                 input_type: Setting::NotSet,
                 distribution: distribution.map(Setting::Set).unwrap_or_default(),
+                binary_quantized,
             },
             super::EmbedderOptions::UserProvided(super::manual::EmbedderOptions {
                 dimensions,
@@ -550,6 +624,7 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 embedding_object: Setting::NotSet,
                 input_type: Setting::NotSet,
                 distribution: distribution.map(Setting::Set).unwrap_or_default(),
+                binary_quantized,
             },
             super::EmbedderOptions::Rest(super::rest::EmbedderOptions {
                 api_key,
@@ -575,6 +650,7 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 embedding_object: Setting::Set(embedding_object),
                 input_type: Setting::Set(input_type),
                 distribution: distribution.map(Setting::Set).unwrap_or_default(),
+                binary_quantized,
             },
         }
     }
@@ -597,8 +673,11 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
             embedding_object,
             input_type,
             distribution,
+            binary_quantized,
         } = value;
 
+        this.quantized = binary_quantized.set();
+
         if let Some(source) = source.set() {
             match source {
                 EmbedderSource::OpenAi => {
@@ -109,7 +109,7 @@ pub fn expected_order(
         let mut new_groups = Vec::new();
         for group in groups.iter_mut() {
             match criterion {
-                Criterion::Attribute => {
+                Criterion::Attribute | Criterion::AttributeRank => {
                     group.sort_by_key(|d| d.attribute_rank);
                     new_groups
                         .extend(group.linear_group_by_key(|d| d.attribute_rank).map(Vec::from));
@@ -149,9 +149,11 @@ pub fn expected_order(
                     new_groups
                         .extend(group.linear_group_by_key(|d| d.asc_desc_rank).map(Vec::from));
                 }
-                Criterion::Asc(_) | Criterion::Desc(_) | Criterion::Sort => {
-                    new_groups.push(group.clone())
-                }
+                Criterion::Asc(_)
+                | Criterion::Desc(_)
+                | Criterion::Sort
+                | Criterion::WordPosition
+                | Criterion::Boost => new_groups.push(group.clone()),
             }
         }
         groups = std::mem::take(&mut new_groups);
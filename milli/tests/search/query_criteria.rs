@@ -337,17 +337,17 @@ fn criteria_ascdesc() {
 
         let expected_document_ids = match criterion {
             Asc(field_name) if field_name == "name" => {
-                documents.iter().sorted_by_key(|(_, obkv)| obkv.get(0).unwrap())
-            }
-            Desc(field_name) if field_name == "name" => {
-                documents.iter().sorted_by_key(|(_, obkv)| Reverse(obkv.get(0).unwrap()))
+                documents.iter().sorted_by_key(|(_, obkv)| obkv.as_obkv().get(0).unwrap().to_vec())
             }
+            Desc(field_name) if field_name == "name" => documents
+                .iter()
+                .sorted_by_key(|(_, obkv)| Reverse(obkv.as_obkv().get(0).unwrap().to_vec())),
             Asc(field_name) if field_name == "name" => {
-                documents.iter().sorted_by_key(|(_, obkv)| obkv.get(1).unwrap())
-            }
-            Desc(field_name) if field_name == "name" => {
-                documents.iter().sorted_by_key(|(_, obkv)| Reverse(obkv.get(1).unwrap()))
+                documents.iter().sorted_by_key(|(_, obkv)| obkv.as_obkv().get(1).unwrap().to_vec())
             }
+            Desc(field_name) if field_name == "name" => documents
+                .iter()
+                .sorted_by_key(|(_, obkv)| Reverse(obkv.as_obkv().get(1).unwrap().to_vec())),
             _ => continue,
         }
         .map(|(id, _)| *id)